@@ -1,5 +1,6 @@
 use anyhow::{Context, Result};
 use miden_client::EMPTY_WORD;
+use miden_client::account::mnemonic::WalletMnemonic;
 use miden_client::account::{AccountStorageMode, build_wallet_id};
 use miden_client::asset::{Asset, FungibleAsset};
 use miden_client::auth::AuthSecretKey;
@@ -387,6 +388,73 @@ pub async fn test_import_account_by_id(client_config: ClientConfig) -> Result<()
     Ok(())
 }
 
+pub async fn test_recover_wallets_from_mnemonic(client_config: ClientConfig) -> Result<()> {
+    let (mut client_1, keystore_1) = client_config.clone().into_client().await?;
+    let (mut client_2, keystore_2) = ClientConfig::default()
+        .with_rpc_endpoint(client_config.rpc_endpoint())
+        .into_client()
+        .await?;
+    wait_for_node(&mut client_1).await;
+
+    let mnemonic = WalletMnemonic::generate(client_1.rng());
+
+    let (faucet_account_header, ..) =
+        insert_new_fungible_faucet(&mut client_1, AccountStorageMode::Public, &keystore_1).await?;
+    let faucet_account_id = faucet_account_header.id();
+
+    // Create public wallets for indices 0 and 2, leaving a gap at index 1. The wallets only become
+    // visible on the network after their first transaction.
+    let mut wallet_ids = Vec::new();
+    for index in [0, 2] {
+        let secrets = mnemonic.derive_account_secrets(index);
+        let (wallet, ..) = insert_new_wallet_with_secrets(
+            &mut client_1,
+            AccountStorageMode::Public,
+            &keystore_1,
+            secrets.init_seed,
+            secrets.secret_key,
+        )
+        .await?;
+        assert_eq!(wallet.id(), mnemonic.wallet_id(index, AccountStorageMode::Public, false)?);
+
+        let tx_id =
+            mint_and_consume(&mut client_1, wallet.id(), faucet_account_id, NoteType::Public).await;
+        wait_for_tx(&mut client_1, tx_id).await?;
+        wallet_ids.push(wallet.id());
+    }
+
+    // A gap limit of 1 stops the recovery at the gap
+    let restored = WalletMnemonic::from_phrase(&mnemonic.phrase())?;
+    let recovered = client_2.recover_wallets(&restored, 1).await?;
+    assert_eq!(
+        recovered.iter().map(|wallet| wallet.account_id).collect::<Vec<_>>(),
+        wallet_ids[..1]
+    );
+
+    // A larger gap limit finds every wallet, without importing the already recovered ones again
+    let recovered = client_2.recover_wallets(&restored, 2).await?;
+    assert_eq!(recovered.iter().map(|wallet| wallet.account_id).collect::<Vec<_>>(), wallet_ids);
+
+    for wallet in recovered {
+        keystore_2.add_key(&AuthSecretKey::RpoFalcon512(wallet.secrets.secret_key))?;
+
+        let original_account = client_1
+            .get_account(wallet.account_id)
+            .await?
+            .with_context(|| format!("Account {} not found in client_1", wallet.account_id))?;
+        let recovered_account = client_2
+            .get_account(wallet.account_id)
+            .await?
+            .with_context(|| format!("Account {} not found in client_2", wallet.account_id))?;
+        assert_eq!(
+            recovered_account.account().commitment(),
+            original_account.account().commitment()
+        );
+    }
+
+    Ok(())
+}
+
 pub async fn test_incorrect_genesis(client_config: ClientConfig) -> Result<()> {
     let (builder, _) = client_config.into_client_builder().await?;
     let mut client = builder.build().await?;
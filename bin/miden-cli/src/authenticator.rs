@@ -0,0 +1,59 @@
+use miden_client::auth::{SigningInputs, TransactionAuthenticator};
+use miden_client::keystore::{ExternalSigner, SignerTransport};
+use miden_client::{AuthenticationError, Felt, Word};
+
+use crate::CliKeyStore;
+use crate::config::CliConfig;
+use crate::errors::CliError;
+
+// CLI AUTHENTICATOR
+// ================================================================================================
+
+/// The transaction authenticator used by the CLI, which signs either with the keys in the
+/// keystore or through the external signer set in the config.
+#[derive(Debug, Clone)]
+pub enum CliAuthenticator {
+    KeyStore(CliKeyStore),
+    External(ExternalSigner),
+}
+
+impl CliAuthenticator {
+    /// Returns the authenticator selected in the config. The keystore is only used if no external
+    /// signer is set, in which case it isn't unlocked by `load_keystore`.
+    pub fn from_config(cli_config: &CliConfig, keystore: CliKeyStore) -> Result<Self, CliError> {
+        match &cli_config.external_signer {
+            Some(signer_config) => {
+                let transport = SignerTransport::try_from(signer_config)?;
+                Ok(Self::External(ExternalSigner::new(transport)))
+            },
+            None => Ok(Self::KeyStore(keystore)),
+        }
+    }
+
+    /// Returns the external signer, if signatures are requested from one.
+    pub fn external_signer(&self) -> Option<&ExternalSigner> {
+        match self {
+            Self::KeyStore(_) => None,
+            Self::External(signer) => Some(signer),
+        }
+    }
+}
+
+impl From<CliKeyStore> for CliAuthenticator {
+    fn from(keystore: CliKeyStore) -> Self {
+        Self::KeyStore(keystore)
+    }
+}
+
+impl TransactionAuthenticator for CliAuthenticator {
+    async fn get_signature(
+        &self,
+        pub_key: Word,
+        signing_inputs: &SigningInputs,
+    ) -> Result<Vec<Felt>, AuthenticationError> {
+        match self {
+            Self::KeyStore(keystore) => keystore.get_signature(pub_key, signing_inputs).await,
+            Self::External(signer) => signer.get_signature(pub_key, signing_inputs).await,
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//! Reference external signer for the Miden client.
+//!
+//! Signs the requests of an [`ExternalSigner`](miden_client::keystore::ExternalSigner) with the
+//! keys of a [`FilesystemKeyStore`], where the keys it's asked to generate are also stored.
+//! Requests are served over the Unix socket given with `--socket`, or over stdin and stdout
+//! otherwise, so that the client can spawn it as a subprocess. If the keystore is encrypted, its
+//! passphrase is read from the `MIDEN_KEYSTORE_PASSPHRASE` environment variable.
+
+use std::io::{BufReader, stdin, stdout};
+use std::path::PathBuf;
+
+use clap::Parser;
+use miden_client::keystore::FilesystemKeyStore;
+use miden_client::keystore::external_signer::serve_signer;
+
+/// Environment variable from which the keystore passphrase is read.
+const KEYSTORE_PASSPHRASE_ENV: &str = "MIDEN_KEYSTORE_PASSPHRASE";
+
+#[derive(Debug, Parser)]
+#[command(about = "Reference external signer for the Miden client")]
+struct SignerCli {
+    /// Path to the directory that contains the secret key files.
+    #[arg(short, long)]
+    keystore: PathBuf,
+
+    /// Listen on the Unix socket at this path instead of serving requests over stdin and stdout.
+    #[arg(short, long)]
+    socket: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = SignerCli::parse();
+
+    let mut keystore = FilesystemKeyStore::new(cli.keystore)?;
+    if let Ok(passphrase) = std::env::var(KEYSTORE_PASSPHRASE_ENV) {
+        keystore = keystore.with_passphrase(&passphrase)?;
+    }
+
+    match cli.socket {
+        #[cfg(unix)]
+        Some(socket_path) => {
+            use std::os::unix::net::UnixListener;
+
+            // A socket left behind by a previous run would make binding fail
+            if socket_path.exists() {
+                std::fs::remove_file(&socket_path)?;
+            }
+
+            let listener = UnixListener::bind(&socket_path)?;
+            eprintln!("Listening on {}", socket_path.display());
+
+            for stream in listener.incoming() {
+                let stream = stream?;
+                let reader = BufReader::new(stream.try_clone()?);
+                if let Err(err) = serve_signer(&keystore, reader, stream).await {
+                    eprintln!("Error serving connection: {err}");
+                }
+            }
+        },
+        #[cfg(not(unix))]
+        Some(_) => return Err("Unix sockets aren't supported on this platform".into()),
+        None => serve_signer(&keystore, BufReader::new(stdin().lock()), stdout().lock()).await?,
+    }
+
+    Ok(())
+}
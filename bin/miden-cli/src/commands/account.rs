@@ -2,7 +2,7 @@ use clap::Parser;
 use comfy_table::{Cell, ContentArrangement, presets};
 use miden_client::account::{Account, AccountId, AccountType, StorageSlot};
 use miden_client::asset::Asset;
-use miden_client::rpc::{NodeRpcClient, TonicRpcClient};
+use miden_client::rpc::{NodeRpcClient, RetryingRpcClient};
 use miden_client::transaction::{AccountComponentInterface, AccountInterface};
 use miden_client::{Client, ZERO};
 use miden_objects::PrettyPrint;
@@ -135,8 +135,7 @@ pub async fn show_account<AUTH>(
     } else {
         println!("Account {account_id} is not tracked by the client. Fetching from the network...",);
 
-        let rpc_client =
-            TonicRpcClient::new(&cli_config.rpc.endpoint.clone().into(), cli_config.rpc.timeout_ms);
+        let rpc_client = RetryingRpcClient::try_from(&cli_config.rpc)?;
 
         let fetched_account = rpc_client.get_account_details(account_id).await.map_err(|_| {
             CliError::Input(format!(
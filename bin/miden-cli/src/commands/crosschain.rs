@@ -8,7 +8,7 @@ use miden_client::crypto::FeltRng;
 use miden_client::transaction::TransactionRequestBuilder;
 use miden_client::{Client, Felt};
 use miden_objects::StarkField;
-use miden_objects::note::NoteTag;
+use miden_objects::note::{Note, NoteTag};
 use miden_objects::transaction::OutputNote;
 
 use crate::commands::new_transactions::execute_transaction;
@@ -44,6 +44,10 @@ pub struct CrosschainCmd {
 
     #[clap(short, long)]
     tag: Option<u32>,
+
+    /// Print the effects of the transaction as JSON without proving or submitting it.
+    #[clap(long, default_value_t = false)]
+    dry_run: bool,
 }
 
 impl CrosschainCmd {
@@ -54,43 +58,61 @@ impl CrosschainCmd {
         let faucet_id = AccountId::from_hex(&self.asset_faucet_id)
             .map_err(|e| CliError::AccountId(e, "Malformed Faucet account id hex".to_string()))?;
 
-        let evm_dest_address = Address::from_hex(self.dest_addr.as_str())
-            .map_err(|_e| CliError::Input(format!("Non evm address hex {:?}", self.dest_addr)))?;
-
         let sender = get_input_acc_id_by_prefix_or_default(&client, self.sender.clone()).await?;
 
-        let address_felts = [
-            Felt::try_from(&evm_dest_address.0[..8])
-                .map_err(|e| CliError::Internal(Box::new(e)))?,
-            Felt::try_from(&evm_dest_address.0[8..16])
-                .map_err(|e| CliError::Internal(Box::new(e)))?,
-            Felt::from_bytes_with_padding(&evm_dest_address.0[16..20]),
-        ];
-
-        let dest_chain = Felt::from(self.dest_chain);
-
-        let note = new_crosschain_note(
-            client.rng().draw_word(),
-            client.rng().draw_word(),
-            dest_chain,
-            address_felts,
-            None,
+        let note = build_crosschain_note(
+            &mut client,
+            sender,
+            self.dest_chain,
+            &self.dest_addr,
             faucet_id,
             self.asset_amount,
-            sender,
-            self.tag.map(NoteTag::from).unwrap_or(bridge_note_tag()),
-        )
-        .map_err(|e| CliError::Internal(Box::new(e)))?;
+            self.tag,
+        )?;
 
         let tx_request = TransactionRequestBuilder::new()
             .own_output_notes(vec![OutputNote::Full(note)])
             .build()
             .map_err(|e| CliError::Internal(Box::new(e)))?;
 
-        execute_transaction(&mut client, sender, tx_request, false, false)
+        execute_transaction(&mut client, sender, tx_request, false, false, None, self.dry_run)
             .await
             .map_err(|e| CliError::Internal(Box::new(e)))?;
 
         Ok(())
     }
 }
+
+/// Builds a CROSSCHAIN note sent by `sender` that carries `asset_amount` of the faucet's tokens to
+/// the EVM address `dest_addr` on chain `dest_chain`.
+pub(crate) fn build_crosschain_note<AUTH: TransactionAuthenticator>(
+    client: &mut Client<AUTH>,
+    sender: AccountId,
+    dest_chain: u32,
+    dest_addr: &str,
+    faucet_id: AccountId,
+    asset_amount: u64,
+    tag: Option<u32>,
+) -> Result<Note, CliError> {
+    let evm_dest_address = Address::from_hex(dest_addr)
+        .map_err(|_e| CliError::Input(format!("Non evm address hex {dest_addr:?}")))?;
+
+    let address_felts = [
+        Felt::try_from(&evm_dest_address.0[..8]).map_err(|e| CliError::Internal(Box::new(e)))?,
+        Felt::try_from(&evm_dest_address.0[8..16]).map_err(|e| CliError::Internal(Box::new(e)))?,
+        Felt::from_bytes_with_padding(&evm_dest_address.0[16..20]),
+    ];
+
+    new_crosschain_note(
+        client.rng().draw_word(),
+        client.rng().draw_word(),
+        Felt::from(dest_chain),
+        address_felts,
+        None,
+        faucet_id,
+        asset_amount,
+        sender,
+        tag.map(NoteTag::from).unwrap_or(bridge_note_tag()),
+    )
+    .map_err(|e| CliError::Internal(Box::new(e)))
+}
@@ -0,0 +1,37 @@
+use std::path::Path;
+
+use clap::Parser;
+
+use crate::CliKeyStore;
+use crate::config::CliConfig;
+use crate::errors::CliError;
+use crate::utils::{unlock_keystore, update_config};
+
+// ENCRYPT KEYSTORE COMMAND
+// ================================================================================================
+
+#[derive(Debug, Clone, Parser)]
+#[command(
+    about = "Encrypt the plaintext secret keys in the keystore with a passphrase. The passphrase \
+is read from the `MIDEN_KEYSTORE_PASSPHRASE` environment variable or prompted for, and the config \
+is updated so that it is required from then on"
+)]
+pub struct EncryptKeystoreCmd {}
+
+impl EncryptKeystoreCmd {
+    pub fn execute(&self, mut cli_config: CliConfig, config_path: &Path) -> Result<(), CliError> {
+        let keystore = CliKeyStore::new(cli_config.secret_keys_directory.clone())
+            .map_err(CliError::KeyStore)?;
+        let keystore = unlock_keystore(keystore)?;
+
+        let encrypted_keys = keystore.encrypt_plaintext_keys().map_err(CliError::KeyStore)?;
+        println!("Encrypted {encrypted_keys} secret key(s)");
+
+        if !cli_config.encrypt_keystore {
+            cli_config.encrypt_keystore = true;
+            update_config(config_path, &cli_config)?;
+        }
+
+        Ok(())
+    }
+}
@@ -7,6 +7,7 @@ use miden_client::{Client, Felt, Word};
 use miden_objects::vm::AdviceInputs;
 use serde::{Deserialize, Deserializer, Serialize, de};
 
+use crate::commands::request_file::{ForeignAccountEntry, build_foreign_account};
 use crate::errors::CliError;
 use crate::utils::get_input_acc_id_by_prefix_or_default;
 
@@ -47,6 +48,13 @@ pub struct ExecCmd {
     /// Print the output stack grouped into words
     #[arg(long, default_value_t = false)]
     hex_words: bool,
+
+    /// Account whose procedures the program calls through foreign procedure invocation, in the
+    /// `<ID>[:<SLOT>=<KEY>[,<KEY>...]...]` format. For public accounts, each `<SLOT>=<KEYS>` part
+    /// lists the storage map keys to fetch from that slot. Private accounts must be tracked by the
+    /// client. Can be repeated.
+    #[arg(long = "foreign-account", value_name = "ACCOUNT")]
+    foreign_accounts: Vec<ForeignAccountEntry>,
 }
 
 impl ExecCmd {
@@ -85,10 +93,15 @@ impl ExecCmd {
 
         let advice_inputs = AdviceInputs::default().with_map(inputs);
 
+        let mut foreign_accounts = BTreeSet::new();
+        for entry in &self.foreign_accounts {
+            foreign_accounts.insert(build_foreign_account(&client, entry.clone()).await?);
+        }
+
         let tx_script = client.script_builder().compile_tx_script(&program)?;
 
         let result = client
-            .execute_program(account_id, tx_script, advice_inputs, BTreeSet::new())
+            .execute_program(account_id, tx_script, advice_inputs, foreign_accounts)
             .await;
 
         match result {
@@ -138,12 +151,21 @@ impl ExecCmd {
 /// Struct that holds a single key-values pair from the provided file inputs file. These will be
 /// aggregated in the [`CliTxInputs`] struct
 #[derive(Serialize, Deserialize)]
-struct CliTxInput {
+pub(crate) struct CliTxInput {
     key: String,
     #[serde(deserialize_with = "string_to_u64")]
     values: Vec<u64>,
 }
 
+impl CliTxInput {
+    /// Returns the advice map entry described by the input.
+    pub(crate) fn into_advice_entry(self) -> Result<(Word, Vec<Felt>), String> {
+        let word = Word::try_from(self.key).map_err(|err| err.to_string())?;
+        let felts = self.values.into_iter().map(Felt::new).collect();
+        Ok((word, felts))
+    }
+}
+
 /// Struct that holds every key-values pair present in the provided inputs file. This struct can be
 /// iterated on to access the different keys.
 #[derive(Serialize, Deserialize)]
@@ -163,7 +185,7 @@ impl IntoIterator for CliTxInputs {
 /// Since the toml crate has problems parsing u64 values (see
 /// [issue](https://github.com/toml-rs/toml/issues/705), we store the values as Strings. Then, when
 /// deserializing, we turn those Strings to u64 in order to then turn them to Felts.
-fn string_to_u64<'de, D>(deserializer: D) -> Result<Vec<u64>, D::Error>
+pub(crate) fn string_to_u64<'de, D>(deserializer: D) -> Result<Vec<u64>, D::Error>
 where
     D: Deserializer<'de>,
 {
@@ -187,11 +209,7 @@ fn deserialize_tx_inputs(serialized: &str) -> Result<Vec<(Word, Vec<Felt>)>, Cli
     })?;
     cli_inputs
         .into_iter()
-        .map(|input| {
-            let word = Word::try_from(input.key).map_err(|err| err.to_string())?;
-            let felts = input.values.into_iter().map(Felt::new).collect();
-            Ok((word, felts))
-        })
+        .map(CliTxInput::into_advice_entry)
         .collect::<Result<Vec<_>, _>>()
         .map_err(|err| CliError::Exec("error deserializing transaction inputs".into(), err))
 }
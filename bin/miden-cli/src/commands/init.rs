@@ -1,14 +1,17 @@
 use std::fs::{self, File};
-use std::io::Write;
+use std::io::{IsTerminal, Write};
 use std::path::PathBuf;
 
 use clap::Parser;
+use miden_client::account::mnemonic::WalletMnemonic;
 use miden_client::consts::MIXER_DEFAULT_URL;
+use miden_client::sync::SyncCheckpoint;
 use tracing::info;
 
-use crate::CLIENT_CONFIG_FILE_NAME;
 use crate::config::{CliConfig, CliEndpoint, Network};
 use crate::errors::CliError;
+use crate::utils::{read_mnemonic_phrase, unlock_keystore};
+use crate::{CLIENT_CONFIG_FILE_NAME, CliKeyStore};
 
 /// Contains the account component template file generated on build.rs, corresponding to the basic
 /// wallet component.
@@ -54,6 +57,29 @@ pub struct InitCmd {
     /// Maximum number of blocks the client can be behind the network.
     #[clap(long)]
     block_delta: Option<u32>,
+
+    /// Encrypt the secret keys with a passphrase. The passphrase is read from the
+    /// `MIDEN_KEYSTORE_PASSPHRASE` environment variable or prompted for whenever the keystore is
+    /// used.
+    #[clap(long, default_value_t = false)]
+    encrypt_keystore: bool,
+
+    /// Restore the wallet mnemonic from an existing phrase read from stdin, instead of generating
+    /// a new one. When stdin is a terminal, the phrase is prompted for without being echoed.
+    /// Wallets derived from it can then be recovered with the `recover` command.
+    #[clap(long, default_value_t = false)]
+    restore: bool,
+
+    /// Trusted block from which the client starts syncing instead of genesis, in the
+    /// `<BLOCK NUMBER>:<COMMITMENT>` format. Notes and accounts from earlier blocks aren't found
+    /// by syncs, so the block should be older than anything the client will track.
+    #[clap(long, value_name = "CHECKPOINT", conflicts_with = "checkpoint_file")]
+    checkpoint: Option<String>,
+
+    /// File containing the trusted block from which the client starts syncing, in the same
+    /// format as `--checkpoint`.
+    #[clap(long, value_name = "FILE")]
+    checkpoint_file: Option<PathBuf>,
 }
 
 impl InitCmd {
@@ -82,6 +108,12 @@ impl InitCmd {
         };
 
         cli_config.max_block_number_delta = self.block_delta;
+        cli_config.encrypt_keystore = self.encrypt_keystore;
+        cli_config.sync_checkpoint =
+            self.sync_checkpoint()?.map(|checkpoint| checkpoint.to_string());
+
+        // Set up the mnemonic first so that the config file isn't created if it fails
+        self.init_mnemonic(&cli_config)?;
 
         let config_as_toml_string = toml::to_string_pretty(&cli_config).map_err(|err| {
             CliError::Config("failed to serialize config".to_string().into(), err.to_string())
@@ -109,6 +141,61 @@ impl InitCmd {
     }
 }
 
+impl InitCmd {
+    /// Returns the checkpoint set with `--checkpoint` or `--checkpoint-file`, if any.
+    fn sync_checkpoint(&self) -> Result<Option<SyncCheckpoint>, CliError> {
+        let checkpoint = match (&self.checkpoint, &self.checkpoint_file) {
+            (Some(checkpoint), _) => checkpoint.clone(),
+            (None, Some(path)) => fs::read_to_string(path)?,
+            (None, None) => return Ok(None),
+        };
+
+        checkpoint
+            .parse()
+            .map(Some)
+            .map_err(|err| CliError::Input(format!("Invalid sync checkpoint: {err}")))
+    }
+
+    /// Stores the mnemonic from which new wallets are derived in the keystore. A new mnemonic is
+    /// generated unless `--restore` is set, and an existing one is never overwritten.
+    fn init_mnemonic(&self, cli_config: &CliConfig) -> Result<(), CliError> {
+        let mut keystore = CliKeyStore::new(cli_config.secret_keys_directory.clone())
+            .map_err(CliError::KeyStore)?;
+        if cli_config.encrypt_keystore {
+            keystore = unlock_keystore(keystore)?;
+        }
+
+        if keystore.get_mnemonic_phrase().map_err(CliError::KeyStore)?.is_some() {
+            println!("The keystore already contains a mnemonic, keeping it.");
+            return Ok(());
+        }
+
+        let mnemonic = if self.restore {
+            WalletMnemonic::from_phrase(&read_mnemonic_phrase()?)
+                .map_err(|err| CliError::Input(format!("Failed to restore mnemonic: {err}")))?
+        } else {
+            let mnemonic = WalletMnemonic::generate(&mut rand::rng());
+            // The phrase is only shown on a terminal, so that it doesn't end up in logs or files
+            // that stdout is redirected to
+            if std::io::stdout().is_terminal() {
+                println!(
+                    "Wallet mnemonic (write it down and keep it safe, it's the only way to \
+recover your wallets if the keystore is lost):\n\n{}\n",
+                    mnemonic.phrase()
+                );
+            } else {
+                println!(
+                    "A wallet mnemonic was generated and stored in the keystore. It isn't printed \
+because stdout is not a terminal."
+                );
+            }
+            mnemonic
+        };
+
+        keystore.set_mnemonic_phrase(&mnemonic.phrase()).map_err(CliError::KeyStore)
+    }
+}
+
 /// Creates the directory specified by `cli_config.component_template_directory`
 /// and writes the default included component templates.
 fn write_template_files(cli_config: &CliConfig) -> Result<(), CliError> {
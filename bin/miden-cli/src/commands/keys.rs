@@ -0,0 +1,221 @@
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+
+use miden_client::account::{Account, AccountId};
+use miden_client::utils::get_public_keys_from_account;
+use miden_client::{Client, Word};
+
+use crate::errors::CliError;
+use crate::{CliKeyStore, Parser, create_dynamic_table};
+
+#[derive(Default, Debug, Parser, Clone)]
+#[command(about = "View and manage the secret keys in the keystore. Defaults to `list` command")]
+pub struct KeysCmd {
+    /// List all keys in the keystore along with the tracked accounts that use them.
+    #[arg(short, long, group = "action")]
+    list: bool,
+
+    /// Remove the key with the given public key from the keystore.
+    #[arg(short, long, group = "action", value_name = "PUB_KEY")]
+    remove: Option<String>,
+
+    /// Remove the key even if tracked accounts use it, which leaves them unable to sign
+    /// transactions.
+    #[arg(long, requires = "remove")]
+    force: bool,
+
+    /// Export the key with the given public key to a file. The exported key is not encrypted.
+    #[arg(short, long, group = "action", value_name = "PUB_KEY")]
+    export: Option<String>,
+
+    /// Import a key from a file created with `--export`.
+    #[arg(short, long, group = "action", value_name = "FILE")]
+    import: Option<PathBuf>,
+
+    /// Desired filename for the exported key. Defaults to the public key if not provided. An
+    /// existing file is never overwritten.
+    #[arg(short, long, requires = "export")]
+    filename: Option<PathBuf>,
+}
+
+impl KeysCmd {
+    pub async fn execute<AUTH>(
+        &self,
+        client: Client<AUTH>,
+        keystore: CliKeyStore,
+    ) -> Result<(), CliError> {
+        match self {
+            KeysCmd { remove: Some(pub_key), .. } => {
+                remove_key(&client, &keystore, pub_key, self.force).await?;
+            },
+            KeysCmd { export: Some(pub_key), .. } => {
+                export_key(&keystore, pub_key, self.filename.clone())?;
+            },
+            KeysCmd { import: Some(filename), .. } => {
+                import_key(&client, &keystore, filename).await?;
+            },
+            _ => {
+                list_keys(&client, &keystore).await?;
+            },
+        }
+        Ok(())
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+async fn list_keys<AUTH>(client: &Client<AUTH>, keystore: &CliKeyStore) -> Result<(), CliError> {
+    let pub_keys = keystore.list_keys().map_err(CliError::KeyStore)?;
+    let accounts_by_key = accounts_by_pub_key(client).await?;
+
+    let mut table = create_dynamic_table(&["Public Key", "Accounts"]);
+    for pub_key in &pub_keys {
+        let accounts = accounts_by_key
+            .get(pub_key)
+            .map(|account_ids| {
+                account_ids.iter().map(AccountId::to_hex).collect::<Vec<_>>().join("\n")
+            })
+            .unwrap_or_else(|| "-".to_string());
+
+        table.add_row(vec![pub_key.to_hex(), accounts]);
+    }
+
+    println!("\n{table}");
+
+    // Accounts that can't sign transactions because one of their keys is not in the keystore
+    for (pub_key, account_ids) in &accounts_by_key {
+        if pub_keys.contains(pub_key) {
+            continue;
+        }
+
+        for account_id in account_ids {
+            println!(
+                "WARNING: account {} has no local key for public key {}",
+                account_id.to_hex(),
+                pub_key.to_hex()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+async fn remove_key<AUTH>(
+    client: &Client<AUTH>,
+    keystore: &CliKeyStore,
+    pub_key: &str,
+    force: bool,
+) -> Result<(), CliError> {
+    let pub_key = parse_pub_key(pub_key)?;
+
+    let account_ids = accounts_by_pub_key(client).await?.remove(&pub_key).unwrap_or_default();
+    if !account_ids.is_empty() && !force {
+        for account_id in &account_ids {
+            println!("WARNING: account {} uses this key", account_id.to_hex());
+        }
+
+        return Err(CliError::Input(format!(
+            "Key {} is used by tracked accounts, which could no longer sign transactions without \
+             it. Use --force to remove it anyway",
+            pub_key.to_hex()
+        )));
+    }
+
+    if !keystore.remove_key(pub_key).map_err(CliError::KeyStore)? {
+        return Err(CliError::Input(format!("Key {} not found in the keystore", pub_key.to_hex())));
+    }
+
+    println!("Key {} removed", pub_key.to_hex());
+
+    for account_id in account_ids {
+        println!(
+            "WARNING: account {} used this key and can no longer sign transactions",
+            account_id.to_hex()
+        );
+    }
+
+    Ok(())
+}
+
+fn export_key(
+    keystore: &CliKeyStore,
+    pub_key: &str,
+    filename: Option<PathBuf>,
+) -> Result<(), CliError> {
+    let pub_key = parse_pub_key(pub_key)?;
+
+    let key_hex = keystore
+        .export_key(pub_key)
+        .map_err(CliError::KeyStore)?
+        .ok_or(CliError::Export(format!("Key {} not found in the keystore", pub_key.to_hex())))?;
+
+    let file_path = if let Some(filename) = filename {
+        filename
+    } else {
+        let current_dir = std::env::current_dir()?;
+        current_dir.join(format!("{}.key", pub_key.to_hex()))
+    };
+
+    // The exported key is a plaintext secret, so it's only made readable by the owner and an
+    // existing file is never replaced
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = options.open(&file_path).map_err(|err| {
+        CliError::Export(format!("Failed to create key file {}: {err}", file_path.display()))
+    })?;
+    file.write_all(key_hex.as_bytes())?;
+
+    println!("Successfully exported key {} to {}", pub_key.to_hex(), file_path.display());
+    Ok(())
+}
+
+async fn import_key<AUTH>(
+    client: &Client<AUTH>,
+    keystore: &CliKeyStore,
+    filename: &Path,
+) -> Result<(), CliError> {
+    let key_hex = std::fs::read_to_string(filename)?;
+    let pub_key = keystore.import_key(&key_hex).map_err(CliError::KeyStore)?;
+
+    println!("Successfully imported key {}", pub_key.to_hex());
+
+    if let Some(account_ids) = accounts_by_pub_key(client).await?.get(&pub_key) {
+        for account_id in account_ids {
+            println!("Key is used by account {}", account_id.to_hex());
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the IDs of the tracked accounts that use each public key.
+async fn accounts_by_pub_key<AUTH>(
+    client: &Client<AUTH>,
+) -> Result<BTreeMap<Word, Vec<AccountId>>, CliError> {
+    let mut accounts_by_key = BTreeMap::<Word, Vec<AccountId>>::new();
+
+    for (header, _) in client.get_account_headers().await? {
+        let Some(account) = client.get_account(header.id()).await? else {
+            continue;
+        };
+        let account: Account = account.into();
+
+        for pub_key in get_public_keys_from_account(&account) {
+            accounts_by_key.entry(pub_key).or_default().push(account.id());
+        }
+    }
+
+    Ok(accounts_by_key)
+}
+
+pub(crate) fn parse_pub_key(pub_key: &str) -> Result<Word, CliError> {
+    Word::try_from(pub_key)
+        .map_err(|err| CliError::Parse(err.into(), format!("Failed to parse public key {pub_key}")))
+}
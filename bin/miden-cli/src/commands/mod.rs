@@ -1,16 +1,22 @@
 pub mod account;
 pub mod crosschain;
+pub mod encrypt_keystore;
 pub mod exec;
 pub mod export;
 pub mod import;
 pub mod import_public;
 pub mod init;
+pub mod keys;
 pub mod mix;
+pub mod multisig;
 pub mod new_account;
 pub mod new_transactions;
 pub mod notes;
 pub mod recipient;
 pub mod reconstruct;
+pub mod recover;
+pub mod request_file;
+pub mod swaps;
 pub mod sync;
 pub mod tags;
 pub mod transactions;
@@ -0,0 +1,299 @@
+use std::path::{Path, PathBuf};
+
+use clap::Subcommand;
+use miden_client::auth::TransactionAuthenticator;
+use miden_client::transaction::{
+    MultisigSigningRequest,
+    NoteReport,
+    StorageChange,
+    TransactionSummary,
+    VaultChange,
+};
+use miden_client::utils::{Deserializable, Serializable};
+use miden_client::{Client, ClientError};
+
+use crate::authenticator::CliAuthenticator;
+use crate::commands::new_transactions::{
+    format_assets,
+    format_note_script,
+    print_account_changes,
+    submit_executed_transaction,
+};
+use crate::commands::request_file::{ForeignAccountEntry, TransactionRequestFile};
+use crate::errors::CliError;
+use crate::{Parser, create_dynamic_table};
+
+#[derive(Debug, Parser, Clone)]
+#[command(about = "Propose, sign and finalize transactions of multisig accounts")]
+pub struct MultisigCmd {
+    #[command(subcommand)]
+    command: MultisigSubcommand,
+}
+
+/// Commands to collect the signatures of the approvers of a multisig account. A transaction is
+/// proposed by one approver, the resulting signing request is passed around for the others to
+/// sign, and the signed requests are finalized once they have enough signatures.
+#[derive(Debug, Subcommand, Clone)]
+pub enum MultisigSubcommand {
+    /// Execute the transaction described in a TOML request file against a multisig account and
+    /// write the signing request for its approvers to a file. The request is signed with the
+    /// approver keys available to the client.
+    Propose {
+        /// Path to the request file. See the CLI reference for the format of the file.
+        file: PathBuf,
+        /// File where the signing request is written.
+        #[arg(short, long)]
+        output: PathBuf,
+        /// Account read through foreign procedure invocation, in addition to the ones in the
+        /// request file, in the `<ID>[:<SLOT>=<KEY>[,<KEY>...]...]` format. Can be repeated.
+        #[arg(long = "foreign-account", value_name = "ACCOUNT")]
+        foreign_accounts: Vec<ForeignAccountEntry>,
+    },
+    /// Sign a signing request with the approver keys available to the client, updating the file
+    /// in place.
+    Sign {
+        /// File with the signing request.
+        file: PathBuf,
+        /// Flag to sign the transaction without asking for confirmation.
+        #[arg(short, long, default_value_t = false)]
+        force: bool,
+    },
+    /// Combine the signatures of one or more signing requests for the same transaction, and
+    /// execute and submit the transaction if there are enough of them.
+    Finalize {
+        /// Files with the signed requests.
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+        /// Flag to submit the executed transaction without asking for confirmation.
+        #[arg(short, long, default_value_t = false)]
+        force: bool,
+        /// Flag to delegate proving to the remote prover specified in the config file.
+        #[arg(long, default_value_t = false)]
+        delegate_proving: bool,
+        /// Export the executed transaction to this file instead of proving and submitting it.
+        #[arg(long, value_name = "FILE")]
+        export_unproven: Option<PathBuf>,
+        /// Print the effects of the transaction as JSON without proving or submitting it.
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+}
+
+impl MultisigCmd {
+    /// Returns whether the finalized transaction is exported unproven instead of being submitted.
+    pub(crate) fn exports_unproven(&self) -> bool {
+        matches!(&self.command, MultisigSubcommand::Finalize { export_unproven: Some(_), .. })
+    }
+
+    pub async fn execute<AUTH: TransactionAuthenticator + Sync + 'static>(
+        &self,
+        client: Client<AUTH>,
+        authenticator: CliAuthenticator,
+    ) -> Result<(), CliError> {
+        match &self.command {
+            MultisigSubcommand::Propose { file, output, foreign_accounts } => {
+                propose_transaction(client, file, output, foreign_accounts).await
+            },
+            MultisigSubcommand::Sign { file, force } => {
+                sign_transaction(&authenticator, file, *force).await
+            },
+            MultisigSubcommand::Finalize {
+                files,
+                force,
+                delegate_proving,
+                export_unproven,
+                dry_run,
+            } => {
+                finalize_transaction(
+                    client,
+                    files,
+                    *force,
+                    *delegate_proving,
+                    export_unproven.as_deref(),
+                    *dry_run,
+                )
+                .await
+            },
+        }
+    }
+}
+
+// PROPOSE TRANSACTION
+// ================================================================================================
+async fn propose_transaction<AUTH: TransactionAuthenticator + Sync + 'static>(
+    mut client: Client<AUTH>,
+    file: &Path,
+    output: &Path,
+    foreign_accounts: &[ForeignAccountEntry],
+) -> Result<(), CliError> {
+    let request_file =
+        TransactionRequestFile::read(file)?.with_foreign_accounts(foreign_accounts.iter().cloned());
+    let base_dir = file.parent().unwrap_or(Path::new("."));
+    let (account_id, transaction_request) =
+        request_file.into_request(&mut client, base_dir).await?;
+
+    println!("Executing transaction...");
+    let signing_request =
+        client.propose_multisig_transaction(account_id, transaction_request).await?;
+
+    std::fs::write(output, signing_request.to_bytes())?;
+
+    print_signing_request(&signing_request);
+    println!("Signing request written to {}", output.display());
+    Ok(())
+}
+
+// SIGN TRANSACTION
+// ================================================================================================
+async fn sign_transaction(
+    authenticator: &CliAuthenticator,
+    file: &Path,
+    force: bool,
+) -> Result<(), CliError> {
+    let mut signing_request = read_signing_request(file)?;
+    print_signing_request(&signing_request);
+
+    if !force {
+        println!("\nSign the transaction? (y/N)");
+        let mut proceed_str: String = String::new();
+        std::io::stdin().read_line(&mut proceed_str).expect("Should read line");
+
+        if proceed_str.trim().to_lowercase() != "y" {
+            println!("Transaction was cancelled.");
+            return Ok(());
+        }
+    }
+
+    let signed = signing_request.sign(authenticator).await.map_err(ClientError::from)?;
+    if signed.is_empty() {
+        return Err(CliError::Input(
+            "None of the approver keys of the account are available to sign".to_string(),
+        ));
+    }
+
+    std::fs::write(file, signing_request.to_bytes())?;
+
+    for pub_key in signed {
+        println!("Signed with key {}", pub_key.to_hex());
+    }
+    println!(
+        "Signing request has {}/{} signatures",
+        signing_request.signers().count(),
+        signing_request.threshold()
+    );
+    Ok(())
+}
+
+// FINALIZE TRANSACTION
+// ================================================================================================
+async fn finalize_transaction<AUTH: TransactionAuthenticator + Sync + 'static>(
+    mut client: Client<AUTH>,
+    files: &[PathBuf],
+    force: bool,
+    delegate_proving: bool,
+    export_unproven: Option<&Path>,
+    dry_run: bool,
+) -> Result<(), CliError> {
+    let (first, others) = files.split_first().expect("at least one file is required");
+    let mut signing_request = read_signing_request(first)?;
+    for file in others {
+        signing_request.merge(read_signing_request(file)?).map_err(ClientError::from)?;
+    }
+
+    if !dry_run {
+        print_signing_request(&signing_request);
+        println!("Executing transaction...");
+    }
+    let tx_result = client.finalize_multisig_transaction(signing_request).await?;
+
+    submit_executed_transaction(
+        &mut client,
+        tx_result,
+        force,
+        delegate_proving,
+        export_unproven,
+        dry_run,
+    )
+    .await
+}
+
+// HELPERS
+// ================================================================================================
+fn read_signing_request(path: &Path) -> Result<MultisigSigningRequest, CliError> {
+    let contents = std::fs::read(path)?;
+    MultisigSigningRequest::read_from_bytes(&contents)
+        .map_err(|err| CliError::Client(ClientError::DataDeserializationError(err)))
+}
+
+/// Prints the transaction to be signed along with the signatures collected so far. The summary
+/// was checked against the signed message when the request was read, so it shows exactly what
+/// the approvers sign.
+fn print_signing_request(signing_request: &MultisigSigningRequest) {
+    print_transaction_summary(signing_request.tx_summary());
+
+    println!("\nAccount: {}", signing_request.account_id().to_hex());
+    println!("Message: {}", signing_request.message().to_hex());
+
+    let mut table = create_dynamic_table(&["Approver", "Signed"]);
+    for pub_key in signing_request.approvers() {
+        let signed = signing_request.signers().any(|signer| signer == pub_key);
+        table.add_row(vec![pub_key.to_hex(), if signed { "Yes" } else { "No" }.to_string()]);
+    }
+
+    println!("\n{table}");
+    println!(
+        "\n{}/{} required signatures collected",
+        signing_request.signers().count(),
+        signing_request.threshold()
+    );
+}
+
+fn print_transaction_summary(tx_summary: &TransactionSummary) {
+    println!("The transaction will have the following effects:\n");
+
+    // INPUT NOTES
+    if tx_summary.input_notes().is_empty() {
+        println!("No notes will be consumed.");
+    } else {
+        let mut table = create_dynamic_table(&["Nullifier", "Note ID"]);
+        for input_note in tx_summary.input_notes().iter() {
+            let note_id = input_note
+                .header()
+                .map_or_else(|| "-".to_string(), |header| header.id().to_hex());
+            table.add_row(vec![input_note.nullifier().to_hex(), note_id]);
+        }
+
+        println!("The following notes will be consumed:");
+        println!("{table}");
+    }
+    println!();
+
+    // OUTPUT NOTES
+    if tx_summary.output_notes().is_empty() {
+        println!("No notes will be created as a result of this transaction.");
+    } else {
+        let mut table = create_dynamic_table(&["Note ID", "Type", "Script", "Assets"]);
+        for output_note in tx_summary.output_notes().iter() {
+            let note = NoteReport::from_output_note(output_note);
+            table.add_row(vec![
+                note.id.to_hex(),
+                note.note_type.to_string(),
+                format_note_script(&note.script),
+                format_assets(&note.assets),
+            ]);
+        }
+
+        println!("The following notes will be created:");
+        println!("{table}");
+    }
+    println!();
+
+    // ACCOUNT CHANGES
+    let account_delta = tx_summary.account_delta();
+    print_account_changes(
+        account_delta.id(),
+        &StorageChange::from_delta(account_delta.storage()),
+        &VaultChange::from_delta(account_delta.vault()),
+        account_delta.nonce_delta().as_int(),
+    );
+}
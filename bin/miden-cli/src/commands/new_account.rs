@@ -7,12 +7,14 @@ use std::vec;
 use clap::{Parser, ValueEnum};
 use miden_client::Client;
 use miden_client::account::component::COMPONENT_TEMPLATE_EXTENSION;
+use miden_client::account::mnemonic::DerivedAccountSecrets;
 use miden_client::account::{Account, AccountBuilder, AccountStorageMode, AccountType};
 use miden_client::auth::{AuthSecretKey, TransactionAuthenticator};
-use miden_client::crypto::SecretKey;
+use miden_client::crypto::{PublicKey, SecretKey};
+use miden_client::keystore::ExternalSigner;
 use miden_client::transaction::TransactionRequestBuilder;
 use miden_client::utils::Deserializable;
-use miden_lib::account::auth::AuthRpoFalcon512;
+use miden_lib::account::auth::{AuthRpoFalcon512, AuthRpoFalcon512Multisig};
 use miden_objects::account::{
     AccountComponent,
     AccountComponentTemplate,
@@ -23,7 +25,9 @@ use rand::RngCore;
 use tracing::debug;
 
 use crate::commands::account::maybe_set_default_account;
+use crate::commands::keys::parse_pub_key;
 use crate::errors::CliError;
+use crate::utils::load_mnemonic;
 use crate::{CliKeyStore, client_binary_name, load_config_file};
 
 // CLI TYPES
@@ -94,6 +98,13 @@ pub struct NewWalletCmd {
     /// authentication transaction.
     #[arg(long, default_value_t = false)]
     pub deploy: bool,
+    /// Makes the wallet a multisig account that requires this many signatures of its approvers to
+    /// authorize a transaction. The key generated for the wallet is one of the approvers.
+    #[arg(long, requires = "approvers", conflicts_with = "deploy")]
+    pub threshold: Option<u32>,
+    /// Public keys of the other approvers of a multisig wallet.
+    #[arg(long, value_name = "PUB_KEY", requires = "threshold")]
+    pub approvers: Vec<String>,
 }
 
 impl NewWalletCmd {
@@ -101,6 +112,7 @@ impl NewWalletCmd {
         &self,
         mut client: Client<AUTH>,
         keystore: CliKeyStore,
+        signer: Option<&ExternalSigner>,
     ) -> Result<(), CliError> {
         let mut component_template_paths = vec![PathBuf::from("basic-wallet")];
         component_template_paths.extend(self.extra_components.iter().cloned());
@@ -112,6 +124,33 @@ impl NewWalletCmd {
             AccountType::RegularAccountImmutableCode
         };
 
+        let (mut current_config, _) = load_config_file()?;
+
+        // Wallets are derived from the keystore's mnemonic, if there's one, so that they can be
+        // recovered from it. With an external signer, the key is generated by the signer instead.
+        let derived_secrets = match signer {
+            Some(_) => None,
+            None => match load_mnemonic(&keystore)? {
+                Some(mnemonic) => {
+                    let index = client.next_wallet_index(&mnemonic).await?;
+                    Some(mnemonic.derive_account_secrets(index))
+                },
+                None => None,
+            },
+        };
+
+        let multisig = match self.threshold {
+            Some(threshold) => {
+                let approvers = self
+                    .approvers
+                    .iter()
+                    .map(|pub_key| parse_pub_key(pub_key).map(PublicKey::new))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Some((threshold, approvers))
+            },
+            None => None,
+        };
+
         let new_account = create_client_account(
             &mut client,
             &keystore,
@@ -120,11 +159,10 @@ impl NewWalletCmd {
             &component_template_paths,
             self.init_storage_data_path.clone(),
             self.deploy,
+            AccountAuth { derived_secrets, signer, multisig },
         )
         .await?;
 
-        let (mut current_config, _) = load_config_file()?;
-
         println!("Successfully created new wallet.");
         println!(
             "To view account details execute {} account -s {}",
@@ -174,6 +212,7 @@ impl NewAccountCmd {
         &self,
         mut client: Client<AUTH>,
         keystore: CliKeyStore,
+        signer: Option<&ExternalSigner>,
     ) -> Result<(), CliError> {
         let new_account = create_client_account(
             &mut client,
@@ -183,6 +222,7 @@ impl NewAccountCmd {
             &self.component_templates,
             self.init_storage_data_path.clone(),
             self.deploy,
+            AccountAuth { signer, ..AccountAuth::default() },
         )
         .await?;
 
@@ -225,6 +265,20 @@ fn load_component_templates(paths: &[PathBuf]) -> Result<Vec<AccountComponentTem
     Ok(templates)
 }
 
+/// Returns the components of a wallet built like `new-wallet` does: the basic wallet component
+/// followed by the extra components, instantiated with the initialization storage data.
+pub(crate) fn wallet_components(
+    extra_components: &[PathBuf],
+    init_storage_data_path: Option<PathBuf>,
+) -> Result<Vec<AccountComponent>, CliError> {
+    let mut component_template_paths = vec![PathBuf::from("basic-wallet")];
+    component_template_paths.extend(extra_components.iter().cloned());
+
+    let component_templates = load_component_templates(&component_template_paths)?;
+    let init_storage_data = load_init_storage_data(init_storage_data_path)?;
+    process_component_templates(&component_templates, &init_storage_data)
+}
+
 /// Loads the initialization storage data from an optional TOML file.
 /// If None is passed, an empty object is returned.
 fn load_init_storage_data(path: Option<PathBuf>) -> Result<InitStorageData, CliError> {
@@ -237,11 +291,26 @@ fn load_init_storage_data(path: Option<PathBuf>) -> Result<InitStorageData, CliE
     }
 }
 
+/// Options for the auth component of a new account.
+#[derive(Default)]
+struct AccountAuth<'a> {
+    /// Secrets derived from the keystore's mnemonic, used instead of a random init seed and key.
+    derived_secrets: Option<DerivedAccountSecrets>,
+    /// External signer that generates and keeps the auth key, instead of the keystore.
+    signer: Option<&'a ExternalSigner>,
+    /// Threshold and public keys of the other approvers, if the account is a multisig account.
+    multisig: Option<(u32, Vec<PublicKey>)>,
+}
+
 /// Helper function to create the seed, initialize the account builder, add the given components,
 /// and build the account.
 ///
 /// The created account will have a Falcon-based auth component, additional to any specified
-/// component.
+/// component. If derived secrets are provided in `auth`, the init seed and the auth key are taken
+/// from them instead of being randomly generated. If `auth` has an external signer, the auth key
+/// is generated by the signer and only its public key is known to the client. If `auth` has
+/// multisig approvers, the auth key is added to them and the account gets a multisig auth
+/// component instead.
 async fn create_client_account<AUTH: TransactionAuthenticator + Sync + 'static>(
     client: &mut Client<AUTH>,
     keystore: &CliKeyStore,
@@ -250,6 +319,7 @@ async fn create_client_account<AUTH: TransactionAuthenticator + Sync + 'static>(
     component_template_paths: &[PathBuf],
     init_storage_data_path: Option<PathBuf>,
     deploy: bool,
+    auth: AccountAuth<'_>,
 ) -> Result<Account, CliError> {
     if component_template_paths.is_empty() {
         return Err(CliError::InvalidArgument(
@@ -266,14 +336,39 @@ async fn create_client_account<AUTH: TransactionAuthenticator + Sync + 'static>(
     debug!("Loaded initialization storage data");
 
     let mut init_seed = [0u8; 32];
-    client.rng().fill_bytes(&mut init_seed);
-
-    let key_pair = SecretKey::with_rng(client.rng());
+    let (pub_key, secret_key) = match (auth.derived_secrets, auth.signer) {
+        (Some(secrets), _) => {
+            init_seed = secrets.init_seed;
+            (secrets.secret_key.public_key(), Some(secrets.secret_key))
+        },
+        (None, Some(signer)) => {
+            client.rng().fill_bytes(&mut init_seed);
+            let pub_key = signer.new_key().await.map_err(CliError::KeyStore)?;
+            (PublicKey::new(pub_key), None)
+        },
+        (None, None) => {
+            client.rng().fill_bytes(&mut init_seed);
+            let secret_key = SecretKey::with_rng(client.rng());
+            (secret_key.public_key(), Some(secret_key))
+        },
+    };
+
+    let auth_component: AccountComponent = match auth.multisig {
+        Some((threshold, mut approvers)) => {
+            approvers.insert(0, pub_key);
+            AuthRpoFalcon512Multisig::new(threshold, approvers)
+                .map_err(|err| {
+                    CliError::Account(err, "failed to build multisig auth component".into())
+                })?
+                .into()
+        },
+        None => AuthRpoFalcon512::new(pub_key).into(),
+    };
 
     let mut builder = AccountBuilder::new(init_seed)
         .account_type(account_type)
         .storage_mode(storage_mode)
-        .with_auth_component(AuthRpoFalcon512::new(key_pair.public_key()));
+        .with_auth_component(auth_component);
 
     // Process component templates and add them to the account builder.
     let account_components = process_component_templates(&component_templates, &init_storage_data)?;
@@ -285,9 +380,11 @@ async fn create_client_account<AUTH: TransactionAuthenticator + Sync + 'static>(
         .build()
         .map_err(|err| CliError::Account(err, "failed to build account".into()))?;
 
-    keystore
-        .add_key(&AuthSecretKey::RpoFalcon512(key_pair))
-        .map_err(CliError::KeyStore)?;
+    if let Some(secret_key) = secret_key {
+        keystore
+            .add_key(&AuthSecretKey::RpoFalcon512(secret_key))
+            .map_err(CliError::KeyStore)?;
+    }
 
     client.add_account(&account, Some(seed), false).await?;
 
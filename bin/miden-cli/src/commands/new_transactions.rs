@@ -1,9 +1,10 @@
 use std::io;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use clap::{Parser, ValueEnum};
+use miden_bridge::notes::bridge::{bridge, croschain};
 use miden_client::account::AccountId;
-use miden_client::asset::{FungibleAsset, NonFungibleDeltaAction};
 use miden_client::auth::TransactionAuthenticator;
 use miden_client::note::{
     BlockNumber,
@@ -13,18 +14,28 @@ use miden_client::note::{
 };
 use miden_client::store::NoteRecordError;
 use miden_client::transaction::{
-    InputNote,
+    AssetReport,
+    NoteInputsDecoder,
+    NoteScriptKind,
     OutputNote,
     PaymentNoteDescription,
+    StorageChange,
     SwapTransactionData,
+    TransactionFile,
+    TransactionProver,
+    TransactionReport,
     TransactionRequest,
     TransactionRequestBuilder,
     TransactionResult,
+    VaultChange,
 };
+use miden_client::utils::{Serializable, base_units_to_tokens};
 use miden_client::{Client, RemoteTransactionProver};
 use tracing::info;
 
+use crate::commands::request_file::{ForeignAccountEntry, build_foreign_accounts};
 use crate::create_dynamic_table;
+use crate::crosschain::{decode_bridge_inputs, decode_crosschain_inputs};
 use crate::errors::CliError;
 use crate::public_notes::{get_public_bridge_output_note, is_crosschain_note};
 use crate::utils::{
@@ -70,9 +81,28 @@ pub struct MintCmd {
     /// Flag to delegate proving to the remote prover specified in the config file.
     #[arg(long, default_value_t = false)]
     delegate_proving: bool,
+
+    /// Export the executed transaction to this file instead of proving and submitting it. The
+    /// transaction can then be proven with `tx prove` and submitted with `tx submit`.
+    #[arg(long, value_name = "FILE")]
+    export_unproven: Option<PathBuf>,
+
+    /// Print the effects of the transaction as JSON without proving or submitting it.
+    #[arg(long, default_value_t = false)]
+    dry_run: bool,
+
+    /// Account read through foreign procedure invocation, in the
+    /// `<ID>[:<SLOT>=<KEY>[,<KEY>...]...]` format. Can be repeated.
+    #[arg(long = "foreign-account", value_name = "ACCOUNT")]
+    foreign_accounts: Vec<ForeignAccountEntry>,
 }
 
 impl MintCmd {
+    /// Returns whether the transaction is exported unproven instead of being submitted.
+    pub(crate) fn exports_unproven(&self) -> bool {
+        self.export_unproven.is_some()
+    }
+
     pub async fn execute<AUTH: TransactionAuthenticator + Sync + 'static>(
         &self,
         mut client: Client<AUTH>,
@@ -84,7 +114,9 @@ impl MintCmd {
 
         let target_account_id = parse_account_id(&client, self.target_account_id.as_str()).await?;
 
+        let foreign_accounts = build_foreign_accounts(&client, &self.foreign_accounts).await?;
         let transaction_request = TransactionRequestBuilder::new()
+            .foreign_accounts(foreign_accounts)
             .build_mint_fungible_asset(
                 fungible_asset,
                 target_account_id,
@@ -101,6 +133,8 @@ impl MintCmd {
             transaction_request,
             force,
             self.delegate_proving,
+            self.export_unproven.as_deref(),
+            self.dry_run,
         )
         .await
     }
@@ -141,9 +175,28 @@ pub struct SendCmd {
     /// Flag to delegate proving to the remote prover specified in the config file
     #[arg(long, default_value_t = false)]
     delegate_proving: bool,
+
+    /// Export the executed transaction to this file instead of proving and submitting it. The
+    /// transaction can then be proven with `tx prove` and submitted with `tx submit`.
+    #[arg(long, value_name = "FILE")]
+    export_unproven: Option<PathBuf>,
+
+    /// Print the effects of the transaction as JSON without proving or submitting it.
+    #[arg(long, default_value_t = false)]
+    dry_run: bool,
+
+    /// Account read through foreign procedure invocation, in the
+    /// `<ID>[:<SLOT>=<KEY>[,<KEY>...]...]` format. Can be repeated.
+    #[arg(long = "foreign-account", value_name = "ACCOUNT")]
+    foreign_accounts: Vec<ForeignAccountEntry>,
 }
 
 impl SendCmd {
+    /// Returns whether the transaction is exported unproven instead of being submitted.
+    pub(crate) fn exports_unproven(&self) -> bool {
+        self.export_unproven.is_some()
+    }
+
     pub async fn execute<AUTH: TransactionAuthenticator + Sync + 'static>(
         &self,
         mut client: Client<AUTH>,
@@ -175,7 +228,9 @@ impl SendCmd {
                 payment_description.with_timelock_height(BlockNumber::from(timelock_height));
         }
 
+        let foreign_accounts = build_foreign_accounts(&client, &self.foreign_accounts).await?;
         let transaction_request = TransactionRequestBuilder::new()
+            .foreign_accounts(foreign_accounts)
             .build_pay_to_id(payment_description, (&self.note_type).into(), client.rng())
             .map_err(|err| {
                 CliError::Transaction(err.into(), "Failed to build payment transaction".to_string())
@@ -187,6 +242,8 @@ impl SendCmd {
             transaction_request,
             force,
             self.delegate_proving,
+            self.export_unproven.as_deref(),
+            self.dry_run,
         )
         .await
     }
@@ -223,9 +280,28 @@ pub struct SwapCmd {
     /// Flag to delegate proving to the remote prover specified in the config file.
     #[arg(long, default_value_t = false)]
     delegate_proving: bool,
+
+    /// Export the executed transaction to this file instead of proving and submitting it. The
+    /// transaction can then be proven with `tx prove` and submitted with `tx submit`.
+    #[arg(long, value_name = "FILE")]
+    export_unproven: Option<PathBuf>,
+
+    /// Print the effects of the transaction as JSON without proving or submitting it.
+    #[arg(long, default_value_t = false)]
+    dry_run: bool,
+
+    /// Account read through foreign procedure invocation, in the
+    /// `<ID>[:<SLOT>=<KEY>[,<KEY>...]...]` format. Can be repeated.
+    #[arg(long = "foreign-account", value_name = "ACCOUNT")]
+    foreign_accounts: Vec<ForeignAccountEntry>,
 }
 
 impl SwapCmd {
+    /// Returns whether the transaction is exported unproven instead of being submitted.
+    pub(crate) fn exports_unproven(&self) -> bool {
+        self.export_unproven.is_some()
+    }
+
     pub async fn execute<AUTH: TransactionAuthenticator + Sync + 'static>(
         &self,
         mut client: Client<AUTH>,
@@ -249,7 +325,9 @@ impl SwapCmd {
             requested_fungible_asset.into(),
         );
 
+        let foreign_accounts = build_foreign_accounts(&client, &self.foreign_accounts).await?;
         let transaction_request = TransactionRequestBuilder::new()
+            .foreign_accounts(foreign_accounts)
             .build_swap(
                 &swap_transaction,
                 (&self.note_type).into(),
@@ -266,6 +344,8 @@ impl SwapCmd {
             transaction_request,
             force,
             self.delegate_proving,
+            self.export_unproven.as_deref(),
+            self.dry_run,
         )
         .await?;
 
@@ -279,6 +359,7 @@ impl SwapCmd {
         println!(
             "To receive updates about the payback Swap Note run `miden tags add {payback_note_tag}`",
         );
+        println!("Run `miden swaps list` to follow the status of the swap");
 
         Ok(())
     }
@@ -302,9 +383,28 @@ pub struct ConsumeNotesCmd {
     /// Flag to delegate proving to the remote prover specified in the config file.
     #[arg(long, default_value_t = false)]
     delegate_proving: bool,
+
+    /// Export the executed transaction to this file instead of proving and submitting it. The
+    /// transaction can then be proven with `tx prove` and submitted with `tx submit`.
+    #[arg(long, value_name = "FILE")]
+    export_unproven: Option<PathBuf>,
+
+    /// Print the effects of the transaction as JSON without proving or submitting it.
+    #[arg(long, default_value_t = false)]
+    dry_run: bool,
+
+    /// Account read through foreign procedure invocation, in the
+    /// `<ID>[:<SLOT>=<KEY>[,<KEY>...]...]` format. Can be repeated.
+    #[arg(long = "foreign-account", value_name = "ACCOUNT")]
+    foreign_accounts: Vec<ForeignAccountEntry>,
 }
 
 impl ConsumeNotesCmd {
+    /// Returns whether the transaction is exported unproven instead of being submitted.
+    pub(crate) fn exports_unproven(&self) -> bool {
+        self.export_unproven.is_some()
+    }
+
     pub async fn execute<AUTH: TransactionAuthenticator + Sync + 'static>(
         &self,
         mut client: Client<AUTH>,
@@ -358,11 +458,13 @@ impl ConsumeNotesCmd {
             ));
         }
 
+        let foreign_accounts = build_foreign_accounts(&client, &self.foreign_accounts).await?;
         let transaction_request = TransactionRequestBuilder::new()
             .authenticated_input_notes(authenticated_notes.into_iter().map(|id| (id, None)))
             .unauthenticated_input_notes(unauthenticated_notes)
             .own_output_notes(output_notes.clone())
             .with_empty_script(!output_notes.is_empty())
+            .foreign_accounts(foreign_accounts)
             .build()
             .map_err(|err| {
                 CliError::Transaction(
@@ -377,6 +479,8 @@ impl ConsumeNotesCmd {
             transaction_request,
             force,
             self.delegate_proving,
+            self.export_unproven.as_deref(),
+            self.dry_run,
         )
         .await
     }
@@ -391,13 +495,47 @@ pub async fn execute_transaction<AUTH: TransactionAuthenticator + Sync + 'static
     transaction_request: TransactionRequest,
     force: bool,
     delegated_proving: bool,
+    export_unproven: Option<&Path>,
+    dry_run: bool,
 ) -> Result<(), CliError> {
-    println!("Executing transaction...");
+    if !dry_run {
+        println!("Executing transaction...");
+    }
     let transaction_execution_result =
         client.new_transaction(account_id, transaction_request).await?;
 
+    submit_executed_transaction(
+        client,
+        transaction_execution_result,
+        force,
+        delegated_proving,
+        export_unproven,
+        dry_run,
+    )
+    .await
+}
+
+/// Shows the effects of an executed transaction and, once confirmed, either exports it unproven
+/// or proves and submits it. With `dry_run`, the effects are printed as JSON and nothing else is
+/// done.
+///
+/// An exported transaction is tracked as pending, the same way a submitted one is, so the
+/// account and its input notes are updated as if it was submitted.
+pub(crate) async fn submit_executed_transaction<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    transaction_execution_result: TransactionResult,
+    force: bool,
+    delegated_proving: bool,
+    export_unproven: Option<&Path>,
+    dry_run: bool,
+) -> Result<(), CliError> {
+    let report = load_transaction_report(client, &transaction_execution_result).await?;
+    if dry_run {
+        return print_transaction_report_json(&report);
+    }
+
     // Show delta and ask for confirmation
-    print_transaction_details(&transaction_execution_result)?;
+    print_transaction_report(&report);
     if !force {
         println!(
             "\nContinue with proving and submission? Changes will be irreversible once the proof is finalized on the network (y/N)"
@@ -411,6 +549,22 @@ pub async fn execute_transaction<AUTH: TransactionAuthenticator + Sync + 'static
         }
     }
 
+    if let Some(export_path) = export_unproven {
+        // The transaction is tracked as pending right away, so that its input notes can't be
+        // consumed by another transaction while it's proven and submitted elsewhere
+        let submission_height = client.get_sync_height().await?;
+        client
+            .apply_transaction(submission_height, transaction_execution_result.clone())
+            .await?;
+
+        let transaction_id = transaction_execution_result.executed_transaction().id();
+        let tx_file = TransactionFile::Unproven(transaction_execution_result);
+        std::fs::write(export_path, tx_file.to_bytes())?;
+
+        println!("Exported unproven transaction {transaction_id} to {}", export_path.display());
+        return Ok(());
+    }
+
     println!("Proving transaction and then submitting it to node...");
 
     let transaction_id = transaction_execution_result.executed_transaction().id();
@@ -421,15 +575,7 @@ pub async fn execute_transaction<AUTH: TransactionAuthenticator + Sync + 'static
         .collect::<Vec<_>>();
 
     if delegated_proving {
-        let (cli_config, _) = load_config_file()?;
-        let remote_prover_endpoint =
-            cli_config.remote_prover_endpoint.as_ref().ok_or(CliError::Config(
-                "Remote prover endpoint".to_string().into(),
-                "remote prover endpoint is not set in the configuration file".to_string(),
-            ))?;
-
-        let remote_prover =
-            Arc::new(RemoteTransactionProver::new(remote_prover_endpoint.to_string()));
+        let remote_prover = load_remote_prover()?;
         client
             .submit_transaction_with_prover(transaction_execution_result, remote_prover)
             .await?;
@@ -452,102 +598,211 @@ pub async fn execute_transaction<AUTH: TransactionAuthenticator + Sync + 'static
     Ok(())
 }
 
-fn print_transaction_details(transaction_result: &TransactionResult) -> Result<(), CliError> {
+/// Returns the prover used when proving is delegated: the prover pool if one is configured, or
+/// the remote prover endpoint otherwise.
+pub(crate) fn load_remote_prover() -> Result<Arc<dyn TransactionProver>, CliError> {
+    let (cli_config, _) = load_config_file()?;
+
+    if let Some(prover_pool) = cli_config.load_prover_pool() {
+        return Ok(Arc::new(prover_pool));
+    }
+
+    let remote_prover_endpoint =
+        cli_config.remote_prover_endpoint.as_ref().ok_or(CliError::Config(
+            "Remote prover endpoint".to_string().into(),
+            "neither a remote prover endpoint nor a prover pool is set in the configuration file"
+                .to_string(),
+        ))?;
+
+    Ok(Arc::new(RemoteTransactionProver::new(remote_prover_endpoint.to_string())))
+}
+
+/// Returns the report of an executed transaction, with token symbols from the token symbol map
+/// and the inputs of the bridge notes decoded.
+pub(crate) async fn load_transaction_report<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &Client<AUTH>,
+    transaction_result: &TransactionResult,
+) -> Result<TransactionReport, CliError> {
+    let mut report = client.transaction_report(transaction_result).await?;
+    report.set_token_info(&load_faucet_details_map()?.token_info());
+    report.decode_note_scripts([
+        (croschain().root(), "CROSSCHAIN", decode_crosschain_inputs as NoteInputsDecoder),
+        (bridge().root(), "BRIDGE", decode_bridge_inputs),
+    ]);
+
+    Ok(report)
+}
+
+/// Prints the report of a transaction as JSON.
+pub(crate) fn print_transaction_report_json(report: &TransactionReport) -> Result<(), CliError> {
+    let json =
+        serde_json::to_string_pretty(report).map_err(|err| CliError::Internal(Box::new(err)))?;
+    println!("{json}");
+
+    Ok(())
+}
+
+fn print_transaction_report(report: &TransactionReport) {
     println!("The transaction will have the following effects:\n");
 
     // INPUT NOTES
-    let input_note_ids = transaction_result
-        .executed_transaction()
-        .input_notes()
-        .iter()
-        .map(InputNote::id)
-        .collect::<Vec<_>>();
-    if input_note_ids.is_empty() {
+    if report.consumed_notes.is_empty() {
         println!("No notes will be consumed.");
     } else {
-        println!("The following notes will be consumed:");
-        for input_note_id in input_note_ids {
-            println!("\t- {}", input_note_id.to_hex());
+        let mut table = create_dynamic_table(&["Note ID", "Script", "Assets"]);
+        for note in &report.consumed_notes {
+            table.add_row(vec![
+                note.id.to_hex(),
+                format_note_script(&note.script),
+                format_assets(&note.assets),
+            ]);
         }
+
+        println!("The following notes will be consumed:");
+        println!("{table}");
     }
     println!();
 
     // OUTPUT NOTES
-    let output_note_count = transaction_result.executed_transaction().output_notes().iter().count();
-    if output_note_count == 0 {
+    if report.created_notes.is_empty() {
         println!("No notes will be created as a result of this transaction.");
     } else {
-        println!("{output_note_count} notes will be created as a result of this transaction.");
+        let mut table = create_dynamic_table(&["Note ID", "Type", "Script", "Assets"]);
+        for note in &report.created_notes {
+            table.add_row(vec![
+                note.id.to_hex(),
+                note.note_type.to_string(),
+                format_note_script(&note.script),
+                format_assets(&note.assets),
+            ]);
+        }
+
+        println!("The following notes will be created:");
+        println!("{table}");
     }
     println!();
 
     // ACCOUNT CHANGES
+    print_account_changes(
+        report.account_id,
+        &report.storage_changes,
+        &report.vault_changes,
+        report.nonce_delta,
+    );
+    println!("Expires after block: {}.", report.expiration_block_num);
     println!(
-        "The account with ID {} will be modified as follows:",
-        transaction_result.executed_transaction().account_id()
+        "Cycles: {} (prologue {}, notes {}, script {}, epilogue {}).",
+        report.cycles.total,
+        report.cycles.prologue,
+        report.cycles.notes_processing,
+        report.cycles.tx_script_processing,
+        report.cycles.epilogue
     );
+}
 
-    let account_delta = transaction_result.account_delta();
+/// Prints the changes a transaction makes to the storage, vault and nonce of its account.
+pub(crate) fn print_account_changes(
+    account_id: AccountId,
+    storage_changes: &[StorageChange],
+    vault_changes: &[VaultChange],
+    nonce_delta: u64,
+) {
+    println!("The account with ID {account_id} will be modified as follows:");
 
-    let has_storage_changes = !account_delta.storage().is_empty();
-    if has_storage_changes {
+    if storage_changes.is_empty() {
+        println!("Account Storage will not be changed.");
+    } else {
         let mut table = create_dynamic_table(&["Storage Slot", "Effect"]);
 
-        for (updated_item_slot, new_value) in account_delta.storage().values() {
-            table.add_row(vec![
-                updated_item_slot.to_string(),
-                format!("Updated ({})", new_value.to_hex()),
-            ]);
+        for change in storage_changes {
+            match change {
+                StorageChange::Value { slot, value } => {
+                    table.add_row(vec![slot.to_string(), format!("Updated ({})", value.to_hex())]);
+                },
+                StorageChange::MapEntry { slot, key, value } => {
+                    table.add_row(vec![
+                        slot.to_string(),
+                        format!("Map entry {} updated ({})", key.to_hex(), value.to_hex()),
+                    ]);
+                },
+            }
         }
 
         println!("Storage changes:");
         println!("{table}");
-    } else {
-        println!("Account Storage will not be changed.");
     }
 
-    if account_delta.vault().is_empty() {
+    if vault_changes.is_empty() {
         println!("Account Vault will not be changed.");
     } else {
-        let faucet_details_map = load_faucet_details_map()?;
-        let mut table = create_dynamic_table(&["Asset Type", "Faucet ID", "Amount"]);
+        let mut table = create_dynamic_table(&["Asset Type", "Asset", "Effect"]);
+
+        for change in vault_changes {
+            let asset_type = match change.asset {
+                AssetReport::Fungible { .. } => "Fungible Asset",
+                AssetReport::NonFungible { .. } => "Non Fungible Asset",
+            };
+            let effect = if change.added { "Added" } else { "Removed" };
+            table.add_row(vec![asset_type, &format_asset(&change.asset), effect]);
+        }
 
-        for (faucet_id, amount) in account_delta.vault().fungible().iter() {
-            let asset =
-                FungibleAsset::new(*faucet_id, amount.unsigned_abs()).map_err(CliError::Asset)?;
-            let (faucet_fmt, amount_fmt) = faucet_details_map.format_fungible_asset(&asset)?;
+        println!("Vault changes:");
+        println!("{table}");
+    }
 
-            if amount.is_positive() {
-                table.add_row(vec!["Fungible Asset", &faucet_fmt, &format!("+{amount_fmt}")]);
-            } else {
-                table.add_row(vec!["Fungible Asset", &faucet_fmt, &format!("-{amount_fmt}")]);
-            }
-        }
+    println!("Nonce incremented by: {nonce_delta}.");
+}
 
-        for (asset, action) in account_delta.vault().non_fungible().iter() {
-            match action {
-                NonFungibleDeltaAction::Add => {
-                    table.add_row(vec![
-                        "Non Fungible Asset",
-                        &asset.faucet_id_prefix().to_hex(),
-                        "1",
-                    ]);
-                },
-                NonFungibleDeltaAction::Remove => {
-                    table.add_row(vec![
-                        "Non Fungible Asset",
-                        &asset.faucet_id_prefix().to_hex(),
-                        "-1",
-                    ]);
-                },
+pub(crate) fn format_note_script(script: &NoteScriptKind) -> String {
+    match script {
+        NoteScriptKind::P2id { target } => format!("P2ID to {target}"),
+        NoteScriptKind::P2ide { target, reclaim_height, timelock_height } => {
+            let mut description = format!("P2IDE to {target}");
+            if *reclaim_height != 0 {
+                description.push_str(&format!(", reclaimable after block {reclaim_height}"));
             }
-        }
+            if *timelock_height != 0 {
+                description.push_str(&format!(", timelocked until block {timelock_height}"));
+            }
+            description
+        },
+        NoteScriptKind::Swap { requested_asset: Some(asset) } => {
+            format!("SWAP for {}", format_asset(asset))
+        },
+        NoteScriptKind::Swap { requested_asset: None } => "SWAP".to_string(),
+        NoteScriptKind::Named { name, decoded_inputs } if decoded_inputs.is_empty() => name.clone(),
+        NoteScriptKind::Named { name, decoded_inputs } => {
+            let inputs: Vec<String> = decoded_inputs
+                .iter()
+                .map(|(input, value)| format!("{input}: {value}"))
+                .collect();
+            format!("{name} ({})", inputs.join(", "))
+        },
+        NoteScriptKind::Unknown => "-".to_string(),
+    }
+}
 
-        println!("Vault changes:");
-        println!("{table}");
+pub(crate) fn format_assets(assets: &[AssetReport]) -> String {
+    if assets.is_empty() {
+        return "-".to_string();
     }
 
-    println!("Nonce incremented by: {}.", account_delta.nonce_delta());
+    assets.iter().map(format_asset).collect::<Vec<_>>().join(", ")
+}
 
-    Ok(())
+fn format_asset(asset: &AssetReport) -> String {
+    match asset {
+        AssetReport::Fungible {
+            amount,
+            symbol: Some(symbol),
+            decimals: Some(decimals),
+            ..
+        } => format!("{} {symbol}", base_units_to_tokens(*amount, *decimals)),
+        AssetReport::Fungible { faucet_id, amount, .. } => {
+            format!("{amount} {}", faucet_id.to_hex())
+        },
+        AssetReport::NonFungible { faucet_id_prefix, .. } => {
+            format!("NFT {}", faucet_id_prefix.to_hex())
+        },
+    }
 }
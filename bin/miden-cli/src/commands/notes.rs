@@ -201,16 +201,17 @@ async fn show_note<AUTH: TransactionAuthenticator + Sync>(
         },
         ref crosschain_root if crosschain_root == &croschain().root().to_string() => {
             script_root += " (CROSSCHAIN)";
-            let mut bridge_seq_num_felts = output_note_record.clone().unwrap().recipient()
-                .unwrap()
-                .inputs().values()[0..4].to_vec();
+            let mut bridge_seq_num_felts =
+                output_note_record.clone().unwrap().recipient().unwrap().inputs().values()[0..4]
+                    .to_vec();
             bridge_seq_num_felts.reverse();
-            crosschain_bridge_serial_num = Some(
-                Word::try_from(bridge_seq_num_felts.as_slice())
-                    .map_err(|_| CliError::InvalidArgument("Unable to deserialize the CROSSCHAIN bridge serial number"
-                        .to_string()))?
-            );
-        }
+            crosschain_bridge_serial_num =
+                Some(Word::try_from(bridge_seq_num_felts.as_slice()).map_err(|_| {
+                    CliError::InvalidArgument(
+                        "Unable to deserialize the CROSSCHAIN bridge serial number".to_string(),
+                    )
+                })?);
+        },
         _ => {},
     }
 
@@ -219,8 +220,9 @@ async fn show_note<AUTH: TransactionAuthenticator + Sync>(
     table.add_row(vec![Cell::new("Inputs Commitment"), Cell::new(inputs_commitment)]);
     table.add_row(vec![Cell::new("Serial Number"), Cell::new(serial_num)]);
     if crosschain_bridge_serial_num.is_some() {
-        table.add_row(vec![Cell::new("Bridge Serial Number"), Cell::new(
-            crosschain_bridge_serial_num.unwrap().to_hex())
+        table.add_row(vec![
+            Cell::new("Bridge Serial Number"),
+            Cell::new(crosschain_bridge_serial_num.unwrap().to_hex()),
         ]);
     }
     table.add_row(vec![Cell::new("Type"), Cell::new(note_type)]);
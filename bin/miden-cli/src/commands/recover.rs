@@ -0,0 +1,76 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use miden_client::Client;
+use miden_client::auth::AuthSecretKey;
+
+use crate::commands::account::maybe_set_default_account;
+use crate::commands::new_account::wallet_components;
+use crate::errors::CliError;
+use crate::utils::load_mnemonic;
+use crate::{CliKeyStore, load_config_file};
+
+// RECOVER COMMAND
+// ================================================================================================
+
+#[derive(Debug, Clone, Parser)]
+#[command(
+    about = "Recover the public wallets derived from the keystore's mnemonic. Their keys are \
+re-derived and added to the keystore, and the accounts are imported from the network"
+)]
+pub struct RecoverCmd {
+    /// Number of consecutive derivation indices without an account on the network after which
+    /// the recovery stops.
+    #[arg(short, long, default_value_t = 20)]
+    gap_limit: u32,
+
+    /// Extra components the wallets were created with, as passed to `new-wallet`. Only wallets
+    /// with exactly these components are found.
+    #[arg(short, long)]
+    extra_components: Vec<PathBuf>,
+
+    /// File with the initialization storage data of the extra components, as passed to
+    /// `new-wallet`.
+    #[arg(short, long)]
+    init_storage_data_path: Option<PathBuf>,
+}
+
+impl RecoverCmd {
+    pub async fn execute<AUTH>(
+        &self,
+        mut client: Client<AUTH>,
+        keystore: CliKeyStore,
+    ) -> Result<(), CliError> {
+        let mnemonic = load_mnemonic(&keystore)?.ok_or_else(|| {
+            CliError::Input(
+                "The keystore has no mnemonic. Use `init --restore` to restore one".to_string(),
+            )
+        })?;
+
+        let components =
+            wallet_components(&self.extra_components, self.init_storage_data_path.clone())?;
+
+        println!("Recovering wallets...");
+        let recovered = client.recover_accounts(&mnemonic, self.gap_limit, &components).await?;
+
+        for wallet in &recovered {
+            keystore
+                .add_key(&AuthSecretKey::RpoFalcon512(wallet.secrets.secret_key.clone()))
+                .map_err(CliError::KeyStore)?;
+            println!(
+                "Recovered wallet {} (index {})",
+                wallet.account_id.to_hex(),
+                wallet.secrets.index
+            );
+        }
+
+        println!("Recovered {} wallet(s)", recovered.len());
+
+        if let Some(wallet) = recovered.first() {
+            let (mut current_config, _) = load_config_file()?;
+            maybe_set_default_account(&mut current_config, wallet.account_id)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,495 @@
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use miden_client::account::AccountId;
+use miden_client::asset::Asset;
+use miden_client::auth::TransactionAuthenticator;
+use miden_client::crypto::FeltRng;
+use miden_client::note::{
+    Note,
+    NoteAssets,
+    NoteExecutionHint,
+    NoteInputs,
+    NoteMetadata,
+    NoteRecipient,
+    NoteTag,
+    NoteType,
+    get_input_note_with_id_prefix,
+};
+use miden_client::rpc::domain::account::AccountStorageRequirements;
+use miden_client::store::NoteRecordError;
+use miden_client::transaction::{
+    ForeignAccount,
+    OutputNote,
+    TransactionRequest,
+    TransactionRequestBuilder,
+};
+use miden_client::{Client, Felt, Word};
+use miden_lib::note::{create_p2id_note, create_p2ide_note};
+use miden_objects::block::BlockNumber;
+use serde::Deserialize;
+
+use crate::commands::crosschain::build_crosschain_note;
+use crate::commands::exec::{CliTxInput, string_to_u64};
+use crate::errors::CliError;
+use crate::utils::{
+    get_input_acc_id_by_prefix_or_default,
+    load_faucet_details_map,
+    parse_account_id,
+};
+
+// TRANSACTION REQUEST FILE
+// ================================================================================================
+
+/// A transaction request described in a TOML file, as run by `tx run`.
+///
+/// Paths in the file are relative to the directory that contains it. See the CLI reference for
+/// the full format.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TransactionRequestFile {
+    /// Account that executes the transaction. The default account is used if it isn't set.
+    account: Option<String>,
+    /// Number of blocks after the reference block during which the transaction can be included.
+    expiration_delta: Option<u16>,
+    /// Transaction script, which replaces the script that creates the output notes.
+    script: Option<ScriptSource>,
+    /// Argument passed to the transaction script.
+    script_arg: Option<String>,
+    /// Notes consumed by the transaction.
+    #[serde(default)]
+    input_notes: Vec<InputNoteEntry>,
+    /// Notes created by the transaction.
+    #[serde(default)]
+    output_notes: Vec<OutputNoteEntry>,
+    /// Entries added to the advice map, in the same format as the `exec` inputs file.
+    #[serde(default)]
+    advice_map: Vec<CliTxInput>,
+    /// Accounts whose state is read through foreign procedure invocation.
+    #[serde(default)]
+    foreign_accounts: Vec<ForeignAccountEntry>,
+}
+
+/// MASM code of a script, either inline or in a separate file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScriptSource {
+    path: Option<PathBuf>,
+    code: Option<String>,
+}
+
+impl ScriptSource {
+    fn read(&self, base_dir: &Path) -> Result<String, CliError> {
+        match (&self.path, &self.code) {
+            (Some(path), None) => Ok(std::fs::read_to_string(base_dir.join(path))?),
+            (None, Some(code)) => Ok(code.clone()),
+            _ => Err(invalid_file("a script needs exactly one of `path` and `code`")),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct InputNoteEntry {
+    /// ID of a note tracked by the client, or its hex prefix.
+    id: String,
+    /// Whether the note is consumed by proving its inclusion in the chain. By default, notes are
+    /// authenticated when the client has their inclusion proof.
+    authenticated: Option<bool>,
+    /// Note arguments.
+    args: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+enum OutputNoteEntry {
+    P2id {
+        target: String,
+        assets: Vec<String>,
+        note_type: NoteTypeEntry,
+    },
+    P2ide {
+        target: String,
+        assets: Vec<String>,
+        note_type: NoteTypeEntry,
+        reclaim_height: Option<u32>,
+        timelock_height: Option<u32>,
+    },
+    Crosschain {
+        dest_chain: u32,
+        dest_address: String,
+        asset: String,
+        tag: Option<u32>,
+    },
+    Custom {
+        script: ScriptSource,
+        #[serde(default, deserialize_with = "string_to_u64")]
+        inputs: Vec<u64>,
+        #[serde(default)]
+        assets: Vec<String>,
+        note_type: NoteTypeEntry,
+        tag: u32,
+        serial_num: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum NoteTypeEntry {
+    Public,
+    Private,
+}
+
+impl From<NoteTypeEntry> for NoteType {
+    fn from(note_type: NoteTypeEntry) -> Self {
+        match note_type {
+            NoteTypeEntry::Public => NoteType::Public,
+            NoteTypeEntry::Private => NoteType::Private,
+        }
+    }
+}
+
+/// A foreign account read through foreign procedure invocation, either from the request file or
+/// from a `--foreign-account` option.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct ForeignAccountEntry {
+    /// ID of the account.
+    id: String,
+    /// Storage map keys to fetch for public accounts.
+    #[serde(default)]
+    storage_maps: Vec<StorageMapEntry>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StorageMapEntry {
+    slot: u8,
+    keys: Vec<String>,
+}
+
+impl FromStr for ForeignAccountEntry {
+    type Err = String;
+
+    /// Parses a `--foreign-account` option in the `<ID>[:<SLOT>=<KEY>[,<KEY>...]...]` format,
+    /// where each `<SLOT>=<KEY>,...` part lists the storage map keys to fetch from a slot.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut parts = value.split(':');
+        let id = parts.next().unwrap_or_default().to_string();
+        if id.is_empty() {
+            return Err("the foreign account ID is missing".to_string());
+        }
+
+        let storage_maps = parts
+            .map(|part| {
+                let (slot, keys) = part.split_once('=').ok_or_else(|| {
+                    format!("expected `<SLOT>=<KEY>[,<KEY>...]` but found `{part}`")
+                })?;
+                let slot = slot
+                    .parse::<u8>()
+                    .map_err(|err| format!("invalid storage slot index {slot}: {err}"))?;
+                let keys = keys.split(',').map(str::to_string).collect::<Vec<_>>();
+                for key in &keys {
+                    Word::try_from(key.as_str())
+                        .map_err(|err| format!("invalid storage map key {key}: {err}"))?;
+                }
+
+                Ok(StorageMapEntry { slot, keys })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(Self { id, storage_maps })
+    }
+}
+
+impl TransactionRequestFile {
+    /// Reads and parses the request file at `path`.
+    pub fn read(path: &Path) -> Result<Self, CliError> {
+        let contents = std::fs::read_to_string(path)?;
+        toml::from_str(&contents).map_err(|err| {
+            CliError::Parse(err.into(), format!("Failed to parse request file {}", path.display()))
+        })
+    }
+
+    /// Adds foreign accounts to the ones listed in the file, such as the ones passed with
+    /// `--foreign-account`.
+    pub(crate) fn with_foreign_accounts(
+        mut self,
+        foreign_accounts: impl IntoIterator<Item = ForeignAccountEntry>,
+    ) -> Self {
+        self.foreign_accounts.extend(foreign_accounts);
+        self
+    }
+
+    /// Builds the transaction request described by the file and returns it along with the ID of
+    /// the account that executes it. Relative paths are resolved against `base_dir`.
+    pub async fn into_request<AUTH: TransactionAuthenticator + Sync + 'static>(
+        self,
+        client: &mut Client<AUTH>,
+        base_dir: &Path,
+    ) -> Result<(AccountId, TransactionRequest), CliError> {
+        if self.script.is_none() && self.input_notes.is_empty() && self.output_notes.is_empty() {
+            return Err(invalid_file(
+                "the request needs a script, input notes or output notes to do anything",
+            ));
+        }
+
+        let account_id = get_input_acc_id_by_prefix_or_default(client, self.account).await?;
+        let mut builder = TransactionRequestBuilder::new();
+
+        // INPUT NOTES
+        let mut authenticated_notes = Vec::new();
+        let mut unauthenticated_notes = Vec::new();
+        for entry in self.input_notes {
+            let note_record = get_input_note_with_id_prefix(client, &entry.id).await.map_err(|_| {
+                CliError::Input(format!(
+                    "Input note ID {} is neither a valid Note ID nor a prefix of a known Note ID",
+                    entry.id
+                ))
+            })?;
+            let args = entry.args.as_deref().map(parse_word).transpose()?;
+
+            match entry.authenticated {
+                Some(true) if !note_record.is_authenticated() => {
+                    return Err(invalid_file(&format!(
+                        "note {} has no inclusion proof yet, so it can't be authenticated",
+                        note_record.id()
+                    )));
+                },
+                Some(true) => authenticated_notes.push((note_record.id(), args)),
+                None if note_record.is_authenticated() => {
+                    authenticated_notes.push((note_record.id(), args));
+                },
+                Some(false) | None => {
+                    let note = Note::try_from(note_record).map_err(|err: NoteRecordError| {
+                        CliError::Transaction(
+                            err.into(),
+                            "Failed to convert note record".to_string(),
+                        )
+                    })?;
+                    unauthenticated_notes.push((note, args));
+                },
+            }
+        }
+        builder = builder
+            .authenticated_input_notes(authenticated_notes)
+            .unauthenticated_input_notes(unauthenticated_notes);
+
+        // OUTPUT NOTES
+        let mut output_notes = Vec::new();
+        for entry in self.output_notes {
+            let note = build_output_note(client, account_id, entry, base_dir).await?;
+            output_notes.push(OutputNote::Full(note));
+        }
+        builder = builder.own_output_notes(output_notes);
+
+        // SCRIPT
+        if let Some(script) = &self.script {
+            let code = script.read(base_dir)?;
+            let tx_script = client.script_builder().compile_tx_script(&code)?;
+            builder = builder.custom_script(tx_script);
+        }
+        if let Some(script_arg) = &self.script_arg {
+            builder = builder.script_arg(parse_word(script_arg)?);
+        }
+
+        // ADVICE MAP
+        let advice_entries = self
+            .advice_map
+            .into_iter()
+            .map(CliTxInput::into_advice_entry)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| invalid_file(&format!("invalid advice map entry: {err}")))?;
+        builder = builder.extend_advice_map(advice_entries);
+
+        // FOREIGN ACCOUNTS
+        let mut foreign_accounts = Vec::new();
+        for entry in self.foreign_accounts {
+            foreign_accounts.push(build_foreign_account(client, entry).await?);
+        }
+        builder = builder.foreign_accounts(foreign_accounts);
+
+        if let Some(expiration_delta) = self.expiration_delta {
+            builder = builder.expiration_delta(expiration_delta);
+        }
+
+        let transaction_request = builder.build().map_err(|err| {
+            CliError::Transaction(err.into(), "Failed to build transaction request".to_string())
+        })?;
+
+        Ok((account_id, transaction_request))
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+async fn build_output_note<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &mut Client<AUTH>,
+    sender: AccountId,
+    entry: OutputNoteEntry,
+    base_dir: &Path,
+) -> Result<Note, CliError> {
+    let note = match entry {
+        OutputNoteEntry::P2id { target, assets, note_type } => {
+            let target = parse_account_id(client, &target).await?;
+            let assets = parse_assets(client, &assets).await?;
+            create_p2id_note(
+                sender,
+                target,
+                assets,
+                note_type.into(),
+                Felt::default(),
+                client.rng(),
+            )
+        },
+        OutputNoteEntry::P2ide {
+            target,
+            assets,
+            note_type,
+            reclaim_height,
+            timelock_height,
+        } => {
+            let target = parse_account_id(client, &target).await?;
+            let assets = parse_assets(client, &assets).await?;
+            create_p2ide_note(
+                sender,
+                target,
+                assets,
+                reclaim_height.map(BlockNumber::from),
+                timelock_height.map(BlockNumber::from),
+                note_type.into(),
+                Felt::default(),
+                client.rng(),
+            )
+        },
+        OutputNoteEntry::Crosschain { dest_chain, dest_address, asset, tag } => {
+            let asset = load_faucet_details_map()?.parse_fungible_asset(client, &asset).await?;
+            return build_crosschain_note(
+                client,
+                sender,
+                dest_chain,
+                &dest_address,
+                asset.faucet_id(),
+                asset.amount(),
+                tag,
+            );
+        },
+        OutputNoteEntry::Custom {
+            script,
+            inputs,
+            assets,
+            note_type,
+            tag,
+            serial_num,
+        } => {
+            let code = script.read(base_dir)?;
+            let note_script = client.script_builder().compile_note_script(&code)?;
+            let serial_num = match serial_num {
+                Some(serial_num) => parse_word(&serial_num)?,
+                None => client.rng().draw_word(),
+            };
+            let inputs =
+                NoteInputs::new(inputs.into_iter().map(Felt::new).collect()).map_err(|err| {
+                    CliError::Transaction(err.into(), "Invalid note inputs".to_string())
+                })?;
+            let assets = NoteAssets::new(parse_assets(client, &assets).await?).map_err(|err| {
+                CliError::Transaction(err.into(), "Invalid note assets".to_string())
+            })?;
+
+            NoteMetadata::new(
+                sender,
+                note_type.into(),
+                NoteTag::from(tag),
+                NoteExecutionHint::always(),
+                Felt::default(),
+            )
+            .map(|metadata| {
+                Note::new(assets, metadata, NoteRecipient::new(serial_num, note_script, inputs))
+            })
+        },
+    };
+
+    note.map_err(|err| {
+        CliError::Transaction(err.into(), "Failed to create output note".to_string())
+    })
+}
+
+/// Returns the [`ForeignAccount`]s for the entries passed with `--foreign-account`.
+pub(crate) async fn build_foreign_accounts<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &Client<AUTH>,
+    entries: &[ForeignAccountEntry],
+) -> Result<Vec<ForeignAccount>, CliError> {
+    let mut foreign_accounts = Vec::with_capacity(entries.len());
+    for entry in entries {
+        foreign_accounts.push(build_foreign_account(client, entry.clone()).await?);
+    }
+
+    Ok(foreign_accounts)
+}
+
+/// Returns the [`ForeignAccount`] for the entry. Private foreign accounts must be tracked by the
+/// client, since their state is passed to the transaction.
+pub(crate) async fn build_foreign_account<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &Client<AUTH>,
+    entry: ForeignAccountEntry,
+) -> Result<ForeignAccount, CliError> {
+    let account_id = parse_account_id(client, &entry.id).await?;
+
+    let foreign_account = if account_id.is_public() {
+        let storage_maps = entry
+            .storage_maps
+            .iter()
+            .map(|map| {
+                let keys =
+                    map.keys.iter().map(|key| parse_word(key)).collect::<Result<Vec<_>, _>>()?;
+                Ok((map.slot, keys))
+            })
+            .collect::<Result<Vec<_>, CliError>>()?;
+
+        ForeignAccount::public(
+            account_id,
+            AccountStorageRequirements::new(
+                storage_maps.iter().map(|(slot, keys)| (*slot, keys.iter())),
+            ),
+        )
+    } else {
+        if !entry.storage_maps.is_empty() {
+            return Err(CliError::Input(format!(
+                "Storage maps can't be requested for private foreign account {account_id}"
+            )));
+        }
+
+        let account_record = client.get_account(account_id).await?.ok_or_else(|| {
+            CliError::Input(format!(
+                "Private foreign account {account_id} must be tracked by the client"
+            ))
+        })?;
+        ForeignAccount::private(account_record.account().clone())
+    };
+
+    foreign_account.map_err(|err| {
+        CliError::Transaction(err.into(), format!("Invalid foreign account {account_id}"))
+    })
+}
+
+async fn parse_assets<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &Client<AUTH>,
+    assets: &[String],
+) -> Result<Vec<Asset>, CliError> {
+    let faucet_details_map = load_faucet_details_map()?;
+
+    let mut parsed_assets = Vec::with_capacity(assets.len());
+    for asset in assets {
+        parsed_assets.push(faucet_details_map.parse_fungible_asset(client, asset).await?.into());
+    }
+    Ok(parsed_assets)
+}
+
+fn parse_word(hex: &str) -> Result<Word, CliError> {
+    Word::try_from(hex).map_err(|err| invalid_file(&format!("invalid word {hex}: {err}")))
+}
+
+fn invalid_file(message: &str) -> CliError {
+    CliError::Input(format!("Invalid transaction request file: {message}"))
+}
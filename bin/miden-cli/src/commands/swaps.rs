@@ -0,0 +1,152 @@
+use std::path::PathBuf;
+
+use clap::Subcommand;
+use miden_client::Client;
+use miden_client::asset::Asset;
+use miden_client::auth::TransactionAuthenticator;
+use miden_client::note::SwapStatus;
+
+use crate::commands::new_transactions::execute_transaction;
+use crate::errors::CliError;
+use crate::faucet_details_map::FaucetDetailsMap;
+use crate::utils::load_faucet_details_map;
+use crate::{Parser, create_dynamic_table, get_output_note_with_id_prefix};
+
+#[derive(Debug, Parser, Clone)]
+#[command(about = "View and cancel the swaps created with the `swap` command")]
+pub struct SwapsCmd {
+    #[command(subcommand)]
+    command: SwapsSubcommand,
+}
+
+#[derive(Debug, Subcommand, Clone)]
+pub enum SwapsSubcommand {
+    /// List the swaps created by the client's accounts, with the assets they offer and request
+    /// and whether they are open, filled or reclaimed.
+    List {
+        /// Only list open swaps.
+        #[arg(long, default_value_t = false)]
+        open: bool,
+    },
+    /// Cancel an open swap by consuming its SWAP note with the account that created it. The SWAP
+    /// script pays the requested asset back to the creator, so the account must hold it.
+    Cancel {
+        /// ID of the SWAP note or its hex prefix.
+        note_id: String,
+        /// Flag to submit the executed transaction without asking for confirmation.
+        #[arg(long, default_value_t = false)]
+        force: bool,
+        /// Flag to delegate proving to the remote prover specified in the config file.
+        #[arg(long, default_value_t = false)]
+        delegate_proving: bool,
+        /// Export the executed transaction to this file instead of proving and submitting it.
+        #[arg(long, value_name = "FILE")]
+        export_unproven: Option<PathBuf>,
+        /// Print the effects of the transaction as JSON without proving or submitting it.
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+}
+
+impl SwapsCmd {
+    /// Returns whether the transaction is exported unproven instead of being submitted.
+    pub(crate) fn exports_unproven(&self) -> bool {
+        matches!(&self.command, SwapsSubcommand::Cancel { export_unproven: Some(_), .. })
+    }
+
+    pub async fn execute<AUTH: TransactionAuthenticator + Sync + 'static>(
+        &self,
+        mut client: Client<AUTH>,
+    ) -> Result<(), CliError> {
+        match &self.command {
+            SwapsSubcommand::List { open } => list_swaps(&client, *open).await,
+            SwapsSubcommand::Cancel {
+                note_id,
+                force,
+                delegate_proving,
+                export_unproven,
+                dry_run,
+            } => {
+                let note_id = get_output_note_with_id_prefix(&client, note_id)
+                    .await
+                    .map_err(|err| CliError::Input(err.to_string()))?
+                    .id();
+                let Some(swap) = client.get_swap(note_id).await? else {
+                    return Err(CliError::Input(format!(
+                        "Note {} is not a SWAP note created by the client",
+                        note_id.to_hex()
+                    )));
+                };
+
+                let transaction_request = client.cancel_swap_request(note_id).await?;
+                execute_transaction(
+                    &mut client,
+                    swap.account_id,
+                    transaction_request,
+                    *force,
+                    *delegate_proving,
+                    export_unproven.as_deref(),
+                    *dry_run,
+                )
+                .await
+            },
+        }
+    }
+}
+
+// LIST SWAPS
+// ================================================================================================
+async fn list_swaps<AUTH: TransactionAuthenticator + Sync>(
+    client: &Client<AUTH>,
+    only_open: bool,
+) -> Result<(), CliError> {
+    let faucet_details_map = load_faucet_details_map()?;
+
+    let mut table = create_dynamic_table(&[
+        "Note ID",
+        "Account ID",
+        "Offered",
+        "Requested",
+        "Created At",
+        "Status",
+    ]);
+
+    for swap in client.get_swaps().await? {
+        if only_open && swap.status != SwapStatus::Open {
+            continue;
+        }
+
+        let offered = swap
+            .offered_assets
+            .iter()
+            .map(|asset| format_asset(&faucet_details_map, asset))
+            .collect::<Result<Vec<_>, _>>()?
+            .join("\n");
+
+        table.add_row(vec![
+            swap.note_id.to_hex(),
+            swap.account_id.to_hex(),
+            offered,
+            format_asset(&faucet_details_map, &swap.requested_asset)?,
+            swap.created_at.to_string(),
+            swap.status.to_string(),
+        ]);
+    }
+
+    println!("{table}");
+    Ok(())
+}
+
+// HELPERS
+// ================================================================================================
+fn format_asset(faucet_details_map: &FaucetDetailsMap, asset: &Asset) -> Result<String, CliError> {
+    match asset {
+        Asset::Fungible(fungible_asset) => {
+            let (faucet, amount) = faucet_details_map.format_fungible_asset(fungible_asset)?;
+            Ok(format!("{amount} {faucet}"))
+        },
+        Asset::NonFungible(non_fungible_asset) => {
+            Ok(format!("NFT {}", non_fungible_asset.faucet_id_prefix().to_hex()))
+        },
+    }
+}
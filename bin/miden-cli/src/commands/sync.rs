@@ -1,19 +1,68 @@
+use std::cell::Cell;
+use std::io::{self, IsTerminal};
+use std::sync::Arc;
+use std::time::Duration;
+
 use clap::Parser;
 use miden_client::Client;
 use miden_client::auth::TransactionAuthenticator;
+use miden_client::rpc::{PrometheusFileExporter, RpcMetrics};
+use miden_client::sync::{AutoSyncConfig, SharedClient, SyncEvent, SyncOptions};
+use tokio::task::LocalSet;
 
 use crate::errors::CliError;
 
 #[derive(Debug, Parser, Clone)]
 #[command(about = "Sync this client with the latest state of the Miden network")]
-pub struct SyncCmd {}
+pub struct SyncCmd {
+    /// Stop syncing once this block is reached. The client may end up synced past it, since the
+    /// node sends updates up to the next block relevant to the client.
+    #[clap(long, value_name = "BLOCK")]
+    to_block: Option<u32>,
+
+    /// Save the progress every this many sync steps instead of at the end, so that an interrupted
+    /// sync resumes from the last saved block.
+    #[clap(long, value_name = "STEPS")]
+    steps_per_commit: Option<usize>,
+
+    /// Keep syncing in the foreground every this many seconds, until interrupted with Ctrl-C.
+    /// The RPC metrics are written to the configured `metrics_file` after every sync.
+    #[clap(long, value_name = "SECONDS", conflicts_with_all = ["to_block", "steps_per_commit"])]
+    watch: Option<u64>,
+}
 
 impl SyncCmd {
     pub async fn execute<AUTH: TransactionAuthenticator + Sync + 'static>(
         &self,
         mut client: Client<AUTH>,
+        rpc_metrics: Option<(Arc<RpcMetrics>, PrometheusFileExporter)>,
     ) -> Result<(), CliError> {
-        let new_details = client.sync_state().await?;
+        if let Some(interval) = self.watch {
+            return watch(client, Duration::from_secs(interval), rpc_metrics).await;
+        }
+
+        // Show the progress of long catch-ups, without cluttering redirected output
+        if io::stderr().is_terminal() {
+            let reported_progress = Cell::new(false);
+            client.add_sync_listener(Arc::new(move |event: &SyncEvent| match event {
+                SyncEvent::Progress { block_num, chain_tip } => {
+                    eprint!("\rSyncing block {block_num} of {chain_tip}...");
+                    reported_progress.set(true);
+                },
+                SyncEvent::Finished { .. } if reported_progress.get() => eprintln!(),
+                _ => {},
+            }));
+        }
+
+        let mut options = SyncOptions::default();
+        if let Some(to_block) = self.to_block {
+            options = options.with_to_block(to_block.into());
+        }
+        if let Some(steps_per_commit) = self.steps_per_commit {
+            options = options.with_steps_per_commit(steps_per_commit);
+        }
+
+        let new_details = client.sync_state_with_options(options).await?;
 
         println!("State synced to block {}", new_details.block_num);
         println!("New public notes: {}", new_details.new_public_notes.len());
@@ -22,6 +71,44 @@ impl SyncCmd {
         println!("Tracked accounts updated: {}", new_details.updated_accounts.len());
         println!("Locked accounts: {}", new_details.locked_accounts.len());
         println!("Committed transactions: {}", new_details.committed_transactions.len());
+        for note_id in &new_details.filled_swaps {
+            println!("Swap {} was filled", note_id.to_hex());
+        }
+
+        if client.transaction_retry_policy().is_some() {
+            let retries = client.retry_discarded_transactions().await?;
+            println!("Retried discarded transactions: {}", retries.len());
+        }
         Ok(())
     }
 }
+
+/// Syncs the client in the background every `interval` until Ctrl-C is pressed, exporting the RPC
+/// metrics after every sync.
+async fn watch<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: Client<AUTH>,
+    interval: Duration,
+    rpc_metrics: Option<(Arc<RpcMetrics>, PrometheusFileExporter)>,
+) -> Result<(), CliError> {
+    let client = SharedClient::new(client);
+    let config = AutoSyncConfig::new(interval);
+
+    LocalSet::new()
+        .run_until(async {
+            let handle = match rpc_metrics {
+                Some((metrics, exporter)) => {
+                    client.start_auto_sync_with_metrics(config, metrics, exporter)
+                },
+                None => client.start_auto_sync(config),
+            };
+            println!("Syncing every {}s, press Ctrl-C to stop", interval.as_secs());
+
+            let interrupted = tokio::signal::ctrl_c().await;
+            let syncs = handle.syncs();
+            handle.stop().await;
+            println!("Stopped after {syncs} syncs");
+
+            interrupted.map_err(CliError::from)
+        })
+        .await
+}
@@ -1,8 +1,25 @@
-use miden_client::Client;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use clap::Subcommand;
 use miden_client::auth::TransactionAuthenticator;
 use miden_client::store::TransactionFilter;
-use miden_client::transaction::TransactionRecord;
+use miden_client::transaction::{
+    LocalTransactionProver,
+    TransactionFile,
+    TransactionId,
+    TransactionProver,
+    TransactionRecord,
+};
+use miden_client::utils::{Deserializable, Serializable};
+use miden_client::{Client, ClientError};
 
+use crate::commands::new_transactions::{
+    execute_transaction,
+    load_remote_prover,
+    submit_executed_transaction,
+};
+use crate::commands::request_file::{ForeignAccountEntry, TransactionRequestFile};
 use crate::errors::CliError;
 use crate::{Parser, create_dynamic_table};
 
@@ -12,14 +29,138 @@ pub struct TransactionCmd {
     /// List currently tracked transactions.
     #[arg(short, long, group = "action")]
     list: bool,
+
+    #[command(subcommand)]
+    command: Option<TransactionSubcommand>,
+}
+
+/// Commands to run transactions described in request files, and to prove and submit
+/// transactions exported with `--export-unproven`.
+#[derive(Debug, Subcommand, Clone)]
+pub enum TransactionSubcommand {
+    /// Execute the transaction described in a TOML request file. See the CLI reference for the
+    /// format of the file.
+    Run {
+        /// Path to the request file.
+        file: PathBuf,
+        /// Account read through foreign procedure invocation, in addition to the ones in the
+        /// request file, in the `<ID>[:<SLOT>=<KEY>[,<KEY>...]...]` format. Can be repeated.
+        #[arg(long = "foreign-account", value_name = "ACCOUNT")]
+        foreign_accounts: Vec<ForeignAccountEntry>,
+        /// Flag to submit the executed transaction without asking for confirmation.
+        #[arg(short, long, default_value_t = false)]
+        force: bool,
+        /// Flag to delegate proving to the remote prover specified in the config file.
+        #[arg(long, default_value_t = false)]
+        delegate_proving: bool,
+        /// Export the executed transaction to this file instead of proving and submitting it.
+        #[arg(long, value_name = "FILE")]
+        export_unproven: Option<PathBuf>,
+        /// Print the effects of the transaction as JSON without proving or submitting it.
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+    /// Execute the request of a transaction that was discarded as stale or expired again, against
+    /// the current state of the account. The new transaction is linked to the discarded one.
+    Retry {
+        /// ID of the discarded transaction.
+        transaction_id: String,
+        /// Flag to submit the executed transaction without asking for confirmation.
+        #[arg(short, long, default_value_t = false)]
+        force: bool,
+        /// Flag to delegate proving to the remote prover specified in the config file.
+        #[arg(long, default_value_t = false)]
+        delegate_proving: bool,
+        /// Export the executed transaction to this file instead of proving and submitting it.
+        #[arg(long, value_name = "FILE")]
+        export_unproven: Option<PathBuf>,
+        /// Print the effects of the transaction as JSON without proving or submitting it.
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+    /// Prove an unproven transaction file and write the proven transaction to another file.
+    Prove {
+        /// File with the unproven transaction.
+        input: PathBuf,
+        /// File where the proven transaction is written.
+        output: PathBuf,
+        /// Flag to delegate proving to the remote prover specified in the config file.
+        #[arg(long, default_value_t = false)]
+        delegate_proving: bool,
+    },
+    /// Submit a proven transaction file to the network. If the executing account is tracked by
+    /// the client, the transaction is tracked as well.
+    Submit {
+        /// File with the proven transaction.
+        file: PathBuf,
+    },
 }
 
 impl TransactionCmd {
+    /// Returns whether the command runs without the node: proving a transaction file, or running
+    /// a transaction that is exported unproven.
+    pub(crate) fn works_offline(&self) -> bool {
+        matches!(
+            &self.command,
+            Some(
+                TransactionSubcommand::Prove { .. }
+                    | TransactionSubcommand::Run { export_unproven: Some(_), .. }
+                    | TransactionSubcommand::Retry { export_unproven: Some(_), .. }
+            )
+        )
+    }
+
     pub async fn execute<AUTH: TransactionAuthenticator + Sync + 'static>(
         &self,
         client: Client<AUTH>,
     ) -> Result<(), CliError> {
-        list_transactions(client).await?;
+        match &self.command {
+            Some(TransactionSubcommand::Run {
+                file,
+                foreign_accounts,
+                force,
+                delegate_proving,
+                export_unproven,
+                dry_run,
+            }) => {
+                run_transaction(
+                    client,
+                    file,
+                    foreign_accounts,
+                    *force,
+                    *delegate_proving,
+                    export_unproven.as_deref(),
+                    *dry_run,
+                )
+                .await?;
+            },
+            Some(TransactionSubcommand::Retry {
+                transaction_id,
+                force,
+                delegate_proving,
+                export_unproven,
+                dry_run,
+            }) => {
+                retry_transaction(
+                    client,
+                    transaction_id,
+                    *force,
+                    *delegate_proving,
+                    export_unproven.as_deref(),
+                    *dry_run,
+                )
+                .await?;
+            },
+            Some(TransactionSubcommand::Prove { input, output, delegate_proving }) => {
+                prove_transaction(client, input, output, *delegate_proving).await?;
+            },
+            Some(TransactionSubcommand::Submit { file }) => {
+                submit_transaction(client, file).await?;
+            },
+            None => {
+                list_transactions(client).await?;
+            },
+        }
         Ok(())
     }
 }
@@ -34,8 +175,164 @@ async fn list_transactions<AUTH: TransactionAuthenticator + Sync + 'static>(
     Ok(())
 }
 
+// RUN TRANSACTION
+// ================================================================================================
+async fn run_transaction<AUTH: TransactionAuthenticator + Sync + 'static>(
+    mut client: Client<AUTH>,
+    file: &Path,
+    foreign_accounts: &[ForeignAccountEntry],
+    force: bool,
+    delegate_proving: bool,
+    export_unproven: Option<&Path>,
+    dry_run: bool,
+) -> Result<(), CliError> {
+    let request_file =
+        TransactionRequestFile::read(file)?.with_foreign_accounts(foreign_accounts.iter().cloned());
+    let base_dir = file.parent().unwrap_or(Path::new("."));
+    let (account_id, transaction_request) =
+        request_file.into_request(&mut client, base_dir).await?;
+
+    execute_transaction(
+        &mut client,
+        account_id,
+        transaction_request,
+        force,
+        delegate_proving,
+        export_unproven,
+        dry_run,
+    )
+    .await
+}
+
+// RETRY TRANSACTION
+// ================================================================================================
+async fn retry_transaction<AUTH: TransactionAuthenticator + Sync + 'static>(
+    mut client: Client<AUTH>,
+    transaction_id: &str,
+    force: bool,
+    delegate_proving: bool,
+    export_unproven: Option<&Path>,
+    dry_run: bool,
+) -> Result<(), CliError> {
+    let transaction_id = parse_transaction_id(&client, transaction_id).await?;
+
+    if !dry_run {
+        println!("Executing transaction {transaction_id} again...");
+    }
+    let tx_result = client.retry_transaction(transaction_id).await?;
+
+    submit_executed_transaction(
+        &mut client,
+        tx_result,
+        force,
+        delegate_proving,
+        export_unproven,
+        dry_run,
+    )
+    .await
+}
+
+// PROVE AND SUBMIT TRANSACTIONS
+// ================================================================================================
+async fn prove_transaction<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: Client<AUTH>,
+    input: &Path,
+    output: &Path,
+    delegate_proving: bool,
+) -> Result<(), CliError> {
+    let TransactionFile::Unproven(tx_result) = read_transaction_file(input)? else {
+        return Err(CliError::Input(format!(
+            "Transaction in {} is already proven",
+            input.display()
+        )));
+    };
+
+    let tx_prover: Arc<dyn TransactionProver> = if delegate_proving {
+        load_remote_prover()?
+    } else {
+        Arc::new(LocalTransactionProver::default())
+    };
+
+    println!("Proving transaction {}...", tx_result.executed_transaction().id());
+    let proven_transaction = client.prove_transaction(&tx_result, tx_prover).await?;
+
+    let tx_file = TransactionFile::Proven { tx_result, proven_transaction };
+    std::fs::write(output, tx_file.to_bytes())?;
+
+    println!("Proven transaction written to {}", output.display());
+    Ok(())
+}
+
+async fn submit_transaction<AUTH: TransactionAuthenticator + Sync + 'static>(
+    mut client: Client<AUTH>,
+    file: &Path,
+) -> Result<(), CliError> {
+    let TransactionFile::Proven { tx_result, proven_transaction } = read_transaction_file(file)?
+    else {
+        return Err(CliError::Input(format!(
+            "Transaction in {} isn't proven yet. Prove it with `tx prove` first",
+            file.display()
+        )));
+    };
+
+    let transaction_id = proven_transaction.id();
+    let account_id = tx_result.executed_transaction().account_id();
+
+    println!("Submitting transaction {transaction_id} to the network...");
+    let submission_height = client.submit_proven_transaction(proven_transaction).await?;
+    println!("Successfully submitted transaction {transaction_id}");
+
+    // Transactions exported by this client are already tracked since they were exported
+    let is_tracked = !client
+        .get_transactions(TransactionFilter::Ids(vec![transaction_id]))
+        .await?
+        .is_empty();
+    if is_tracked {
+        println!("Transaction is already tracked by the client");
+    } else if client.get_account_header_by_id(account_id).await?.is_some() {
+        client.apply_transaction(submission_height, tx_result).await?;
+        println!("Transaction is now tracked by the client");
+    } else {
+        println!(
+            "Account {account_id} isn't tracked by the client, so the transaction won't be tracked"
+        );
+    }
+
+    Ok(())
+}
+
 // HELPERS
 // ================================================================================================
+/// Returns the ID of the tracked transaction whose ID starts with `transaction_id_prefix`.
+async fn parse_transaction_id<AUTH: TransactionAuthenticator + Sync + 'static>(
+    client: &Client<AUTH>,
+    transaction_id_prefix: &str,
+) -> Result<TransactionId, CliError> {
+    let mut transaction_ids = client
+        .get_transactions(TransactionFilter::All)
+        .await?
+        .into_iter()
+        .map(|tx| tx.id)
+        .filter(|id| id.to_string().starts_with(transaction_id_prefix))
+        .collect::<Vec<_>>();
+
+    match transaction_ids.len() {
+        0 => Err(CliError::Input(format!(
+            "No tracked transaction matches the ID prefix {transaction_id_prefix}"
+        ))),
+        1 => Ok(transaction_ids.pop().expect("there should be one transaction ID")),
+        _ => Err(CliError::Input(format!(
+            "Multiple tracked transactions match the ID prefix {transaction_id_prefix}"
+        ))),
+    }
+}
+
+fn read_transaction_file(path: &Path) -> Result<TransactionFile, CliError> {
+    let contents = std::fs::read(path)?;
+    TransactionFile::read_from_bytes(&contents)
+        .map_err(|err| CliError::Client(ClientError::DataDeserializationError(err)))
+}
+
 fn print_transactions_summary<'a, I>(executed_transactions: I)
 where
     I: IntoIterator<Item = &'a TransactionRecord>,
@@ -47,6 +344,7 @@ where
         "Script Root",
         "Input Notes Count",
         "Output Notes Count",
+        "Retry Of",
     ]);
 
     for tx in executed_transactions {
@@ -57,6 +355,7 @@ where
             tx.script.as_ref().map_or("-".to_string(), |x| x.root().to_string()),
             tx.details.input_note_nullifiers.len().to_string(),
             tx.details.output_notes.num_notes().to_string(),
+            tx.retry_of.map_or("-".to_string(), |id| id.to_string()),
         ]);
     }
 
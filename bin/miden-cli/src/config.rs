@@ -1,17 +1,33 @@
 use core::fmt::Debug;
+use std::collections::BTreeMap;
 use std::fmt::Display;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 
 use figment::value::{Dict, Map};
 use figment::{Metadata, Profile, Provider};
 use miden_client::consts::MIXER_DEFAULT_URL;
-use miden_client::rpc::Endpoint;
+use miden_client::keystore::SignerTransport;
+use miden_client::rpc::{
+    Endpoint,
+    FailoverRpcClient,
+    NodeRpcClient,
+    NodeRpcClientEndpoint,
+    RetryingRpcClient,
+    RpcRateLimit,
+    RpcRetryPolicy,
+    TonicRpcClient,
+};
+use miden_client::transaction::{DEFAULT_PROVER_TIMEOUT, ProverPool, ProverSelection};
 use serde::{Deserialize, Serialize};
 
 use crate::errors::CliError;
 
 const TOKEN_SYMBOL_MAP_FILEPATH: &str = "token_symbol_map.toml";
+/// Name of the file, next to the store, where the state of the prover pool is kept.
+const PROVER_POOL_STATE_FILENAME: &str = "prover_pool_state.bin";
 const DEFAULT_COMPONENT_TEMPLATE_DIR: &str = "./templates";
 
 // CLI CONFIG
@@ -25,12 +41,20 @@ pub struct CliConfig {
     pub store_filepath: PathBuf,
     /// Path to the directory that contains the secret key files.
     pub secret_keys_directory: PathBuf,
+    /// Whether the secret key files are encrypted with a passphrase. When set, the passphrase is
+    /// read from the `MIDEN_KEYSTORE_PASSPHRASE` environment variable or prompted for.
+    #[serde(default)]
+    pub encrypt_keystore: bool,
+    /// External signer that signs transactions instead of the keys in the keystore.
+    pub external_signer: Option<ExternalSignerConfig>,
     /// Default address to be used when executing transaction in case none is provided.
     pub default_account_id: Option<String>,
     /// Path to the file containing the token symbol map.
     pub token_symbol_map_filepath: PathBuf,
     /// RPC endpoint for the remote prover. If this isn't present, a local prover will be used.
     pub remote_prover_endpoint: Option<CliEndpoint>,
+    /// Pool of remote provers used instead of `remote_prover_endpoint` when delegating proving.
+    pub prover_pool: Option<ProverPoolConfig>,
     /// Path to the directory from where account component template files will be loaded.
     pub component_template_directory: PathBuf,
     /// Mixer offchain operator url
@@ -38,6 +62,42 @@ pub struct CliConfig {
     /// Maximum number of blocks the client can be behind the network for transactions and account
     /// proofs to be considered valid.
     pub max_block_number_delta: Option<u32>,
+    /// Maximum number of times a transaction discarded as stale or expired is retried by the
+    /// `sync` command, which retries discarded transactions after syncing when this is present.
+    /// Otherwise, discarded transactions are only retried with `tx retry`.
+    pub max_transaction_retries: Option<u32>,
+    /// Trusted block, in the `<BLOCK NUMBER>:<COMMITMENT>` format, from which a new client starts
+    /// syncing instead of genesis.
+    pub sync_checkpoint: Option<String>,
+    /// Address of the OTLP collector, such as `http://localhost:4317`, to which the tracing spans
+    /// are exported over gRPC. Requires the CLI to be built with the `otlp` feature.
+    pub otlp_endpoint: Option<String>,
+}
+
+impl CliConfig {
+    /// Returns the configured prover pool, if any.
+    ///
+    /// The round-robin position and the stats of the provers are kept in a file next to the
+    /// store, so that they carry over from one invocation of the CLI to the next.
+    pub fn load_prover_pool(&self) -> Option<ProverPool> {
+        let config = self.prover_pool.as_ref()?;
+
+        let mut pool =
+            ProverPool::new(config.endpoints.iter().map(|prover| prover.endpoint().to_string()))
+                .with_selection(config.selection.into())
+                .with_timeout(Duration::from_millis(config.timeout_ms))
+                .with_local_fallback(config.local_fallback);
+        for prover in &config.endpoints {
+            if let Some(timeout_ms) = prover.timeout_ms() {
+                pool = pool.with_prover_timeout(
+                    &prover.endpoint().to_string(),
+                    Duration::from_millis(timeout_ms),
+                );
+            }
+        }
+
+        Some(pool.with_state_file(self.store_filepath.with_file_name(PROVER_POOL_STATE_FILENAME)))
+    }
 }
 
 // Make `ClientConfig` a provider itself for composability.
@@ -68,12 +128,18 @@ impl Default for CliConfig {
             rpc: RpcConfig::default(),
             store_filepath: exec_dir.join(STORE_FILENAME),
             secret_keys_directory: exec_dir.join(KEYSTORE_DIRECTORY),
+            encrypt_keystore: false,
+            external_signer: None,
             default_account_id: None,
             token_symbol_map_filepath: Path::new(TOKEN_SYMBOL_MAP_FILEPATH).to_path_buf(),
             remote_prover_endpoint: None,
+            prover_pool: None,
             component_template_directory: Path::new(DEFAULT_COMPONENT_TEMPLATE_DIR).to_path_buf(),
             mixer_url: MIXER_DEFAULT_URL.try_into().unwrap(),
             max_block_number_delta: None,
+            max_transaction_retries: None,
+            sync_checkpoint: None,
+            otlp_endpoint: None,
         }
     }
 }
@@ -88,6 +154,58 @@ pub struct RpcConfig {
     pub endpoint: CliEndpoint,
     /// Timeout for the RPC api requests, in milliseconds.
     pub timeout_ms: u64,
+    /// Addresses of other nodes of the same network, used in order when the node at `endpoint`
+    /// can't be reached.
+    #[serde(default)]
+    pub fallback_endpoints: Vec<CliEndpoint>,
+    /// Maximum number of times a request that failed with a transient error, such as the node
+    /// being unavailable, is retried.
+    #[serde(default = "default_rpc_max_retries")]
+    pub max_retries: u32,
+    /// Maximum number of requests per second sent to each RPC method, by method name.
+    #[serde(default)]
+    pub rate_limits: BTreeMap<String, u32>,
+    /// File where the metrics of the RPC requests sent by each command are written, in the
+    /// Prometheus text format. `sync --watch` rewrites it after every sync.
+    #[serde(default)]
+    pub metrics_file: Option<PathBuf>,
+}
+
+impl RpcConfig {
+    /// Returns the endpoint of the node followed by the fallback endpoints.
+    pub fn endpoints(&self) -> Vec<Endpoint> {
+        std::iter::once(&self.endpoint)
+            .chain(&self.fallback_endpoints)
+            .map(Endpoint::from)
+            .collect()
+    }
+}
+
+fn default_rpc_max_retries() -> u32 {
+    RpcRetryPolicy::default().max_retries
+}
+
+impl TryFrom<&RpcConfig> for RetryingRpcClient {
+    type Error = CliError;
+
+    fn try_from(config: &RpcConfig) -> Result<Self, Self::Error> {
+        let inner: Arc<dyn NodeRpcClient> = if config.fallback_endpoints.is_empty() {
+            Arc::new(TonicRpcClient::new(&config.endpoint.clone().into(), config.timeout_ms))
+        } else {
+            Arc::new(FailoverRpcClient::new(&config.endpoints(), config.timeout_ms))
+        };
+
+        let mut client = RetryingRpcClient::new(inner)
+            .with_retry_policy(RpcRetryPolicy::new(config.max_retries));
+        for (method, max_requests) in &config.rate_limits {
+            let endpoint = NodeRpcClientEndpoint::from_str(method).map_err(|err| {
+                CliError::Config(err.clone().into(), format!("Invalid RPC rate limit: {err}"))
+            })?;
+            client = client.with_rate_limit(endpoint, RpcRateLimit::per_second(*max_requests));
+        }
+
+        Ok(client)
+    }
 }
 
 impl Default for RpcConfig {
@@ -95,6 +213,130 @@ impl Default for RpcConfig {
         Self {
             endpoint: Endpoint::default().into(),
             timeout_ms: 10000,
+            fallback_endpoints: Vec::new(),
+            max_retries: default_rpc_max_retries(),
+            rate_limits: BTreeMap::new(),
+            metrics_file: None,
+        }
+    }
+}
+
+// EXTERNAL SIGNER CONFIG
+// ================================================================================================
+
+/// Settings for the external signer, selected with the `transport` field.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "transport", rename_all = "snake_case")]
+pub enum ExternalSignerConfig {
+    /// The signer listens on a Unix socket.
+    UnixSocket {
+        /// Path to the socket.
+        path: PathBuf,
+    },
+    /// The signer is spawned by the CLI and spoken to through its stdin and stdout.
+    Subprocess {
+        /// Program to run.
+        program: PathBuf,
+        /// Arguments passed to the program.
+        #[serde(default)]
+        args: Vec<String>,
+    },
+}
+
+impl TryFrom<&ExternalSignerConfig> for SignerTransport {
+    type Error = CliError;
+
+    fn try_from(config: &ExternalSignerConfig) -> Result<Self, Self::Error> {
+        match config {
+            #[cfg(unix)]
+            ExternalSignerConfig::UnixSocket { path } => {
+                Ok(SignerTransport::UnixSocket(path.clone()))
+            },
+            #[cfg(not(unix))]
+            ExternalSignerConfig::UnixSocket { .. } => Err(CliError::Config(
+                "Unsupported external signer transport".to_string().into(),
+                "Unix sockets aren't supported on this platform".to_string(),
+            )),
+            ExternalSignerConfig::Subprocess { program, args } => Ok(SignerTransport::Subprocess {
+                program: program.clone(),
+                args: args.clone(),
+            }),
+        }
+    }
+}
+
+// PROVER POOL CONFIG
+// ================================================================================================
+
+/// Settings for a pool of remote provers.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProverPoolConfig {
+    /// The remote provers.
+    pub endpoints: Vec<ProverEndpointConfig>,
+    /// Order in which the remote provers are tried.
+    #[serde(default)]
+    pub selection: ProverSelectionConfig,
+    /// Time each remote prover is given to prove a transaction, in milliseconds.
+    #[serde(default = "default_prover_timeout_ms")]
+    pub timeout_ms: u64,
+    /// Whether transactions are proven locally when every remote prover fails.
+    #[serde(default = "default_local_fallback")]
+    pub local_fallback: bool,
+}
+
+fn default_prover_timeout_ms() -> u64 {
+    DEFAULT_PROVER_TIMEOUT
+        .as_millis()
+        .try_into()
+        .expect("default timeout fits in a u64")
+}
+
+fn default_local_fallback() -> bool {
+    true
+}
+
+/// A remote prover of a pool, given either as its RPC endpoint or as a table with the endpoint
+/// and the time it's given to prove a transaction, instead of the pool's timeout.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ProverEndpointConfig {
+    Endpoint(CliEndpoint),
+    WithTimeout { endpoint: CliEndpoint, timeout_ms: u64 },
+}
+
+impl ProverEndpointConfig {
+    /// Returns the RPC endpoint of the prover.
+    pub fn endpoint(&self) -> &CliEndpoint {
+        match self {
+            Self::Endpoint(endpoint) | Self::WithTimeout { endpoint, .. } => endpoint,
+        }
+    }
+
+    /// Returns the timeout of the prover, if it overrides the pool's.
+    pub fn timeout_ms(&self) -> Option<u64> {
+        match self {
+            Self::Endpoint(_) => None,
+            Self::WithTimeout { timeout_ms, .. } => Some(*timeout_ms),
+        }
+    }
+}
+
+/// Order in which the remote provers of a pool are tried.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProverSelectionConfig {
+    /// Each transaction starts with the next prover.
+    #[default]
+    RoundRobin,
+    /// Provers are tried from the fastest to the slowest.
+    LeastLatency,
+}
+
+impl From<ProverSelectionConfig> for ProverSelection {
+    fn from(config: ProverSelectionConfig) -> Self {
+        match config {
+            ProverSelectionConfig::RoundRobin => ProverSelection::RoundRobin,
+            ProverSelectionConfig::LeastLatency => ProverSelection::LeastLatency,
         }
     }
 }
@@ -1,8 +1,11 @@
+use std::collections::BTreeMap;
+
+use alloy_primitives::Address;
 use miden_bridge::accounts::token_wrapper::bridge_note_tag;
 use miden_bridge::notes::bridge::croschain;
 use miden_bridge::utils::{AddressFormatError, evm_address_to_felts};
 use miden_objects::account::AccountId;
-use miden_objects::asset::FungibleAsset;
+use miden_objects::asset::{Asset, FungibleAsset};
 use miden_objects::note::{NoteAssets, NoteDetails, NoteFile, NoteId, NoteInputs, NoteRecipient};
 use miden_objects::utils::parse_hex_string_as_word;
 use miden_objects::{AccountIdError, AssetError, Felt, FieldElement, NoteError, Word};
@@ -36,6 +39,52 @@ pub fn build_crosschain_recipient(
     ))
 }
 
+// NOTE INPUTS DECODING
+// ================================================================================================
+
+/// Decodes the inputs of a CROSSCHAIN note, laid out as in [`build_crosschain_recipient`]: the
+/// serial number of the BRIDGE note in reverse order, followed by the destination.
+pub fn decode_crosschain_inputs(inputs: &[Felt]) -> Option<BTreeMap<String, String>> {
+    let bridge_serial_number = Word::new([*inputs.get(3)?, inputs[2], inputs[1], inputs[0]]);
+
+    let mut decoded = decode_destination(inputs.get(4..)?)?;
+    decoded.insert("bridge_serial_number".to_string(), bridge_serial_number.to_hex());
+    Some(decoded)
+}
+
+/// Decodes the inputs of a BRIDGE note: the bridged asset, followed by the destination of the
+/// CROSSCHAIN note it was created from.
+pub fn decode_bridge_inputs(inputs: &[Felt]) -> Option<BTreeMap<String, String>> {
+    let asset = Asset::try_from(Word::try_from(inputs.get(..4)?).ok()?).ok()?;
+    let Asset::Fungible(asset) = asset else {
+        return None;
+    };
+
+    let mut decoded = decode_destination(inputs.get(4..)?)?;
+    decoded.insert("faucet_id".to_string(), asset.faucet_id().to_hex());
+    decoded.insert("amount".to_string(), asset.amount().to_string());
+    Some(decoded)
+}
+
+/// Decodes the destination chain, the EVM address (as three felts in reverse order, each holding
+/// up to 8 bytes in little-endian order) and the unlock timestamp of a bridge transfer.
+fn decode_destination(inputs: &[Felt]) -> Option<BTreeMap<String, String>> {
+    let [dest_chain, addr_2, addr_1, addr_0, unlock_timestamp, ..] = inputs else {
+        return None;
+    };
+
+    let mut address = Vec::with_capacity(20);
+    address.extend_from_slice(&addr_0.as_int().to_le_bytes());
+    address.extend_from_slice(&addr_1.as_int().to_le_bytes());
+    address.extend_from_slice(&u32::try_from(addr_2.as_int()).ok()?.to_le_bytes());
+
+    Some(BTreeMap::from([
+        ("dest_chain".to_string(), dest_chain.to_string()),
+        ("dest_address".to_string(), Address::from_slice(&address).to_string()),
+        ("unlock_timestamp".to_string(), unlock_timestamp.to_string()),
+    ]))
+}
+
 #[derive(Debug, Error)]
 pub enum CrosschainNoteReconstructionError {
     #[error("Unparsable hex word: {0}")]
@@ -4,6 +4,7 @@ use std::path::PathBuf;
 use miden_client::Client;
 use miden_client::account::AccountId;
 use miden_client::asset::FungibleAsset;
+use miden_client::transaction::TokenInfo;
 use miden_client::utils::{base_units_to_tokens, tokens_to_base_units};
 use serde::{Deserialize, Serialize};
 
@@ -69,6 +70,24 @@ impl FaucetDetailsMap {
             .map(|(symbol, _)| symbol.clone())
     }
 
+    /// Returns the token symbol and decimals of each faucet in the map, keyed by faucet ID.
+    /// Entries with an invalid faucet ID are skipped.
+    pub fn token_info(&self) -> BTreeMap<AccountId, TokenInfo> {
+        self.0
+            .iter()
+            .filter_map(|(symbol, faucet)| {
+                let faucet_id = AccountId::from_hex(&faucet.id).ok()?;
+                Some((
+                    faucet_id,
+                    TokenInfo {
+                        symbol: symbol.clone(),
+                        decimals: faucet.decimals,
+                    },
+                ))
+            })
+            .collect()
+    }
+
     pub fn get_token_symbol_or_default(&self, faucet_id: &AccountId) -> String {
         self.get_token_symbol(faucet_id).unwrap_or("Unknown".to_string())
     }
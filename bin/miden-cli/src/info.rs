@@ -2,11 +2,12 @@ use std::fs;
 
 use miden_client::Client;
 use miden_client::auth::TransactionAuthenticator;
+use miden_client::rpc::FailoverRpcClient;
 use miden_client::store::NoteFilter;
 
 use super::config::CliConfig;
 use crate::errors::CliError;
-use crate::load_config_file;
+use crate::{create_dynamic_table, load_config_file};
 
 pub async fn print_client_info<AUTH: TransactionAuthenticator + Sync + 'static>(
     client: &Client<AUTH>,
@@ -15,7 +16,10 @@ pub async fn print_client_info<AUTH: TransactionAuthenticator + Sync + 'static>(
 
     println!("Client version: {}", env!("CARGO_PKG_VERSION"));
     print_config_stats(&config)?;
-    print_client_stats(client).await
+    print_client_stats(client).await?;
+    print_rpc_stats(&config).await;
+    print_prover_stats(&config).await;
+    Ok(())
 }
 
 // HELPERS
@@ -24,6 +28,7 @@ async fn print_client_stats<AUTH: TransactionAuthenticator + Sync + 'static>(
     client: &Client<AUTH>,
 ) -> Result<(), CliError> {
     println!("Block number: {}", client.get_sync_height().await?);
+    println!("Sync checkpoint: {}", client.sync_checkpoint().await?);
     println!("Tracked accounts: {}", client.get_account_headers().await?.len());
     println!("Expected notes: {}", client.get_input_notes(NoteFilter::Expected).await?.len());
     Ok(())
@@ -39,3 +44,64 @@ fn print_config_stats(config: &CliConfig) -> Result<(), CliError> {
     );
     Ok(())
 }
+
+/// Prints the fallback RPC endpoints, if any, checking whether they are reachable and belong to
+/// the same network as the node.
+async fn print_rpc_stats(config: &CliConfig) {
+    if config.rpc.fallback_endpoints.is_empty() {
+        return;
+    }
+
+    let rpc_client = FailoverRpcClient::new(&config.rpc.endpoints(), config.rpc.timeout_ms);
+
+    let mut table = create_dynamic_table(&["RPC Endpoint", "Status", "Chain Tip"]);
+    for status in rpc_client.check_health().await {
+        let state = match (status.compatible, status.healthy) {
+            (false, _) => "other network",
+            (true, true) => "reachable",
+            (true, false) => "unreachable",
+        };
+        table.add_row(vec![
+            status.endpoint,
+            state.to_string(),
+            status.chain_tip.map_or("-".to_string(), ToString::to_string),
+        ]);
+    }
+    println!("{table}");
+}
+
+/// Prints the provers used when proving is delegated, checking whether the provers of the pool
+/// are reachable.
+async fn print_prover_stats(config: &CliConfig) {
+    let Some(pool) = config.load_prover_pool() else {
+        let remote_prover = config
+            .remote_prover_endpoint
+            .as_ref()
+            .map_or("-".to_string(), ToString::to_string);
+        println!("Remote prover: {remote_prover}");
+        return;
+    };
+
+    println!(
+        "Prover pool: {:?} selection, local fallback {}",
+        pool.selection(),
+        if pool.has_local_fallback() {
+            "enabled"
+        } else {
+            "disabled"
+        }
+    );
+
+    let mut table =
+        create_dynamic_table(&["Endpoint", "Status", "Average Latency", "Proven", "Failed"]);
+    for status in pool.check_health().await {
+        table.add_row(vec![
+            status.endpoint,
+            if status.healthy { "reachable" } else { "unreachable" }.to_string(),
+            status.average_latency.map_or("-".to_string(), |latency| format!("{latency:?}")),
+            status.successes.to_string(),
+            status.failures.to_string(),
+        ]);
+    }
+    println!("{table}");
+}
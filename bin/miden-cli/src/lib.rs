@@ -9,7 +9,15 @@ use miden_client::account::AccountHeader;
 use miden_client::auth::TransactionAuthenticator;
 use miden_client::builder::ClientBuilder;
 use miden_client::keystore::FilesystemKeyStore;
+use miden_client::rpc::{
+    MetricsRpcClient,
+    NodeRpcClient,
+    PrometheusFileExporter,
+    RetryingRpcClient,
+    RpcMetricsExporter,
+};
 use miden_client::store::{NoteFilter as ClientNoteFilter, OutputNoteRecord};
+use miden_client::transaction::TransactionRetryPolicy;
 use miden_client::{Client, DebugMode, IdPrefixFetchError};
 use rand::rngs::StdRng;
 use tracing::Level;
@@ -17,25 +25,32 @@ use tracing::Level;
 mod commands;
 use commands::account::AccountCmd;
 use commands::crosschain::CrosschainCmd;
+use commands::encrypt_keystore::EncryptKeystoreCmd;
 use commands::exec::ExecCmd;
 use commands::export::ExportCmd;
 use commands::import::ImportCmd;
 use commands::import_public::ImportPublicCmd;
 use commands::init::InitCmd;
+use commands::keys::KeysCmd;
 use commands::mix::MixCmd;
+use commands::multisig::MultisigCmd;
 use commands::new_account::{NewAccountCmd, NewWalletCmd};
 use commands::new_transactions::{ConsumeNotesCmd, MintCmd, SendCmd, SwapCmd};
 use commands::notes::NotesCmd;
 use commands::recipient::RecipientCmd;
 use commands::reconstruct::ReconstructCmd;
+use commands::recover::RecoverCmd;
+use commands::swaps::SwapsCmd;
 use commands::sync::SyncCmd;
 use commands::tags::TagsCmd;
 use commands::transactions::TransactionCmd;
 
-use self::utils::load_config_file;
+use self::authenticator::CliAuthenticator;
+use self::utils::{load_config_file, load_keystore};
 
 pub type CliKeyStore = FilesystemKeyStore<StdRng>;
 
+mod authenticator;
 mod config;
 mod crosschain;
 mod errors;
@@ -43,6 +58,7 @@ mod faucet_details_map;
 mod info;
 mod notes;
 mod public_notes;
+mod telemetry;
 mod utils;
 
 /// Config file name.
@@ -151,12 +167,34 @@ pub enum Command {
     Mint(MintCmd),
     Send(SendCmd),
     Swap(SwapCmd),
+    Swaps(SwapsCmd),
     ConsumeNotes(ConsumeNotesCmd),
     Exec(ExecCmd),
     Recipient(RecipientCmd),
     Reconstruct(ReconstructCmd),
     Crosschain(CrosschainCmd),
     Mix(MixCmd),
+    Multisig(MultisigCmd),
+    EncryptKeystore(EncryptKeystoreCmd),
+    Keys(KeysCmd),
+    Recover(RecoverCmd),
+}
+
+impl Command {
+    /// Returns whether the command can run without reaching the node, like proving a transaction
+    /// or exporting one unproven.
+    fn works_offline(&self) -> bool {
+        match self {
+            Command::Transaction(transaction) => transaction.works_offline(),
+            Command::Mint(mint) => mint.exports_unproven(),
+            Command::Send(send) => send.exports_unproven(),
+            Command::Swap(swap) => swap.exports_unproven(),
+            Command::Swaps(swaps) => swaps.exports_unproven(),
+            Command::ConsumeNotes(consume_notes) => consume_notes.exports_unproven(),
+            Command::Multisig(multisig) => multisig.exports_unproven(),
+            _ => false,
+        }
+    }
 }
 
 /// CLI entry point.
@@ -173,13 +211,14 @@ impl Cli {
             return Ok(());
         }
 
-        let log_level = if self.debug { Level::TRACE } else { Level::INFO };
-
-        let subscriber =
-            tracing_subscriber::FmtSubscriber::builder().with_max_level(log_level).finish();
+        // Create the client
+        let (cli_config, config_path) = load_config_file()?;
 
-        tracing::subscriber::set_global_default(subscriber)
-            .expect("setting default subscriber failed");
+        // Spans still buffered for export are flushed when the guard is dropped, once the command
+        // is done
+        let log_level = if self.debug { Level::TRACE } else { Level::INFO };
+        let _tracing_guard =
+            telemetry::init_tracing(log_level, cli_config.otlp_endpoint.as_deref())?;
 
         // Define whether we want to use the executor's debug mode based on the env var and
         // the flag override
@@ -188,19 +227,30 @@ impl Cli {
             _ => DebugMode::Disabled,
         };
 
-        // Create the client
-        let (cli_config, _config_path) = load_config_file()?;
+        // Migrating the keystore only needs the config, and must not require unlocking the
+        // keystore beforehand.
+        if let Command::EncryptKeystore(encrypt_keystore) = &self.action {
+            return encrypt_keystore.execute(cli_config, &config_path);
+        }
 
-        let keystore = CliKeyStore::new(cli_config.secret_keys_directory.clone())
-            .map_err(CliError::KeyStore)?;
+        let keystore = load_keystore(&cli_config)?;
+        let authenticator = CliAuthenticator::from_config(&cli_config, keystore.clone())?;
+
+        // RPC requests are only measured when their metrics are exported
+        let mut rpc_client: Arc<dyn NodeRpcClient> =
+            Arc::new(RetryingRpcClient::try_from(&cli_config.rpc)?);
+        let mut rpc_metrics = None;
+        if let Some(metrics_file) = &cli_config.rpc.metrics_file {
+            let metrics_client = MetricsRpcClient::new(rpc_client);
+            rpc_metrics =
+                Some((metrics_client.metrics(), PrometheusFileExporter::new(metrics_file)));
+            rpc_client = Arc::new(metrics_client);
+        }
 
         let mut builder = ClientBuilder::new()
             .sqlite_store(cli_config.store_filepath.to_str().expect("Store path should be valid"))
-            .tonic_rpc_client(
-                &cli_config.rpc.endpoint.clone().into(),
-                Some(cli_config.rpc.timeout_ms),
-            )
-            .authenticator(Arc::new(keystore.clone()))
+            .rpc(rpc_client)
+            .authenticator(Arc::new(authenticator.clone()))
             .in_debug_mode(in_debug_mode)
             .tx_graceful_blocks(Some(TX_GRACEFUL_BLOCK_DELTA));
 
@@ -208,23 +258,45 @@ impl Cli {
             builder = builder.max_block_number_delta(delta);
         }
 
+        if let Some(max_retries) = cli_config.max_transaction_retries {
+            builder = builder.transaction_retry_policy(TransactionRetryPolicy { max_retries });
+        }
+
+        // The first sync of a client that never synced starts from the configured checkpoint
+        // instead of genesis
+        if let Some(checkpoint) = &cli_config.sync_checkpoint {
+            builder = builder.start_checkpoint(checkpoint.parse()?);
+        }
+
         let mut client = builder.build().await?;
+        // Screen the public notes received while syncing on as many threads as there are cores
+        client.set_note_screening_parallelism(
+            std::thread::available_parallelism().map_or(1, core::num::NonZeroUsize::get),
+        );
 
-        client.ensure_genesis_in_place().await?;
+        // Offline commands must not reach the node. They only work on a client that already
+        // synced, so the genesis block is in the store anyway
+        if !self.action.works_offline() {
+            client.ensure_genesis_in_place().await?;
+        }
 
         // Execute CLI command
-        match &self.action {
+        let result = match &self.action {
             Command::Account(account) => account.execute(client).await,
-            Command::NewWallet(new_wallet) => Box::pin(new_wallet.execute(client, keystore)).await,
+            Command::NewWallet(new_wallet) => {
+                Box::pin(new_wallet.execute(client, keystore, authenticator.external_signer()))
+                    .await
+            },
             Command::NewAccount(new_account) => {
-                Box::pin(new_account.execute(client, keystore)).await
+                Box::pin(new_account.execute(client, keystore, authenticator.external_signer()))
+                    .await
             },
             Command::Import(import) => import.execute(client, keystore).await,
             Command::ImportPublic(import_public) => import_public.execute(client).await,
             Command::Init(_) => Ok(()),
             Command::Info => info::print_client_info(&client).await,
             Command::Notes(notes) => Box::pin(notes.execute(client)).await,
-            Command::Sync(sync) => sync.execute(client).await,
+            Command::Sync(sync) => sync.execute(client, rpc_metrics.clone()).await,
             Command::Tags(tags) => tags.execute(client).await,
             Command::Transaction(transaction) => transaction.execute(client).await,
             Command::Exec(execute_program) => Box::pin(execute_program.execute(client)).await,
@@ -232,12 +304,23 @@ impl Cli {
             Command::Mint(mint) => Box::pin(mint.execute(client)).await,
             Command::Send(send) => Box::pin(send.execute(client)).await,
             Command::Swap(swap) => Box::pin(swap.execute(client)).await,
+            Command::Swaps(swaps) => Box::pin(swaps.execute(client)).await,
             Command::ConsumeNotes(consume_notes) => Box::pin(consume_notes.execute(client)).await,
             Command::Recipient(recipient) => recipient.execute(client).await,
             Command::Reconstruct(reconstruct) => reconstruct.execute(&mut client).await,
             Command::Crosschain(crosschain) => crosschain.execute(client).await,
             Command::Mix(mix) => mix.execute(&mut client, cli_config.mixer_url).await,
+            Command::Multisig(multisig) => Box::pin(multisig.execute(client, authenticator)).await,
+            Command::EncryptKeystore(_) => Ok(()),
+            Command::Keys(keys) => keys.execute(client, keystore).await,
+            Command::Recover(recover) => recover.execute(client, keystore).await,
+        };
+
+        if let Some((metrics, exporter)) = rpc_metrics {
+            exporter.export(&metrics).map_err(CliError::Internal)?;
         }
+
+        result
     }
 
     fn set_external(mut self) -> Self {
@@ -0,0 +1,97 @@
+//! Sets up the tracing subscriber of the CLI, which logs to the terminal and, when the CLI is
+//! built with the `otlp` feature and an `otlp_endpoint` is configured, exports the spans to an
+//! OTLP collector.
+
+#[cfg(feature = "otlp")]
+use opentelemetry::trace::TracerProvider;
+#[cfg(feature = "otlp")]
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+#[cfg(feature = "otlp")]
+use opentelemetry_sdk::Resource;
+#[cfg(feature = "otlp")]
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use tracing::Level;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::{Layer, Registry};
+
+use crate::errors::CliError;
+
+/// Service name under which the spans are exported.
+#[cfg(feature = "otlp")]
+const OTLP_SERVICE_NAME: &str = "miden-client-cli";
+
+/// Keeps the span exporter alive. Dropping it exports the spans that are still buffered.
+pub struct TracingGuard {
+    #[cfg(feature = "otlp")]
+    tracer_provider: Option<SdkTracerProvider>,
+}
+
+#[cfg(feature = "otlp")]
+impl Drop for TracingGuard {
+    fn drop(&mut self) {
+        if let Some(tracer_provider) = self.tracer_provider.take()
+            && let Err(err) = tracer_provider.shutdown()
+        {
+            eprintln!("Failed to export the remaining spans: {err}");
+        }
+    }
+}
+
+/// Installs the global tracing subscriber. Events up to `log_level` are logged to the terminal,
+/// and spans up to `DEBUG`, such as those of the RPC requests, are exported to `otlp_endpoint`.
+pub fn init_tracing(
+    log_level: Level,
+    otlp_endpoint: Option<&str>,
+) -> Result<TracingGuard, CliError> {
+    let subscriber = Registry::default();
+
+    #[cfg(feature = "otlp")]
+    let tracer_provider = otlp_endpoint.map(otlp_tracer_provider).transpose()?;
+    #[cfg(feature = "otlp")]
+    let subscriber = subscriber.with(tracer_provider.as_ref().map(|tracer_provider| {
+        tracing_opentelemetry::layer()
+            .with_tracer(tracer_provider.tracer(OTLP_SERVICE_NAME))
+            .with_filter(LevelFilter::DEBUG)
+    }));
+
+    #[cfg(not(feature = "otlp"))]
+    if otlp_endpoint.is_some() {
+        return Err(CliError::Config(
+            "Unsupported OTLP export".to_string().into(),
+            "The CLI was built without the `otlp` feature, so `otlp_endpoint` can't be used"
+                .to_string(),
+        ));
+    }
+
+    let subscriber = subscriber
+        .with(tracing_subscriber::fmt::layer().with_filter(LevelFilter::from_level(log_level)));
+    tracing::subscriber::set_global_default(subscriber).expect("setting default subscriber failed");
+
+    Ok(TracingGuard {
+        #[cfg(feature = "otlp")]
+        tracer_provider,
+    })
+}
+
+/// Returns a tracer provider that exports the spans in batches to the OTLP collector listening
+/// at `endpoint` over gRPC. It must be built within a tokio runtime.
+#[cfg(feature = "otlp")]
+fn otlp_tracer_provider(endpoint: &str) -> Result<SdkTracerProvider, CliError> {
+    let exporter =
+        SpanExporter::builder()
+            .with_tonic()
+            .with_endpoint(endpoint)
+            .build()
+            .map_err(|err| {
+                CliError::Config(
+                    "Failed to set up the OTLP exporter".to_string().into(),
+                    err.to_string(),
+                )
+            })?;
+
+    Ok(SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(Resource::builder().with_service_name(OTLP_SERVICE_NAME).build())
+        .build())
+}
@@ -1,5 +1,5 @@
 use std::fs::File;
-use std::io::Write;
+use std::io::{IsTerminal, Write};
 use std::path::{Path, PathBuf};
 
 use figment::Figment;
@@ -7,18 +7,23 @@ use figment::providers::{Format, Toml};
 use miden_bridge::notes::BRIDGE_USECASE;
 use miden_client::Client;
 use miden_client::account::AccountId;
+use miden_client::account::mnemonic::WalletMnemonic;
 use miden_objects::address::Address;
 use miden_objects::note::NoteTag;
 use tracing::info;
 
 use super::config::CliConfig;
-use super::{CLIENT_CONFIG_FILE_NAME, get_account_with_id_prefix};
+use super::{CLIENT_CONFIG_FILE_NAME, CliKeyStore, get_account_with_id_prefix};
 use crate::errors::CliError;
 use crate::faucet_details_map::FaucetDetailsMap;
 
 pub(crate) fn bridge_note_tag() -> NoteTag {
     NoteTag::for_local_use_case(BRIDGE_USECASE, 0).unwrap()
 }
+
+/// Environment variable from which the keystore passphrase is read before prompting for it.
+pub(crate) const KEYSTORE_PASSPHRASE_ENV: &str = "MIDEN_KEYSTORE_PASSPHRASE";
+
 pub(crate) const SHARED_TOKEN_DOCUMENTATION: &str = "There are two accepted formats for the asset:
 - `<AMOUNT>::<FAUCET_ID>` where `<AMOUNT>` is in the faucet base units.
 - `<AMOUNT>::<TOKEN_SYMBOL>` where `<AMOUNT>` is a decimal number representing the quantity of
@@ -124,3 +129,70 @@ pub fn load_faucet_details_map() -> Result<FaucetDetailsMap, CliError> {
     let (config, _) = load_config_file()?;
     FaucetDetailsMap::new(config.token_symbol_map_filepath)
 }
+
+/// Opens the keystore defined in the config, unlocking it with a passphrase if the config
+/// requires the keys to be encrypted.
+pub(crate) fn load_keystore(cli_config: &CliConfig) -> Result<CliKeyStore, CliError> {
+    let keystore =
+        CliKeyStore::new(cli_config.secret_keys_directory.clone()).map_err(CliError::KeyStore)?;
+
+    // With an external signer, secret keys are held by the signer, so there's nothing to unlock
+    if !cli_config.encrypt_keystore || cli_config.external_signer.is_some() {
+        return Ok(keystore);
+    }
+
+    unlock_keystore(keystore)
+}
+
+/// Unlocks the keystore with the passphrase read by [`read_keystore_passphrase`].
+///
+/// A keystore without encrypted keys accepts any passphrase, which then becomes its passphrase,
+/// so in that case the user is asked to confirm it.
+pub(crate) fn unlock_keystore(keystore: CliKeyStore) -> Result<CliKeyStore, CliError> {
+    let confirm = !keystore.has_encrypted_keys().map_err(CliError::KeyStore)?;
+    let passphrase = read_keystore_passphrase(confirm)?;
+    keystore.with_passphrase(&passphrase).map_err(CliError::KeyStore)
+}
+
+/// Reads the keystore passphrase from the `MIDEN_KEYSTORE_PASSPHRASE` environment variable or,
+/// if it isn't set, prompts the user for it. When `confirm` is set, the user is asked to type the
+/// passphrase twice.
+pub(crate) fn read_keystore_passphrase(confirm: bool) -> Result<String, CliError> {
+    if let Ok(passphrase) = std::env::var(KEYSTORE_PASSPHRASE_ENV) {
+        return Ok(passphrase);
+    }
+
+    let passphrase = rpassword::prompt_password("Keystore passphrase: ")?;
+    if passphrase.is_empty() {
+        return Err(CliError::Input("Keystore passphrase can't be empty".to_string()));
+    }
+
+    if confirm && rpassword::prompt_password("Confirm keystore passphrase: ")? != passphrase {
+        return Err(CliError::Input("Keystore passphrases don't match".to_string()));
+    }
+
+    Ok(passphrase)
+}
+
+/// Returns the wallet mnemonic stored in the keystore, if any.
+pub(crate) fn load_mnemonic(keystore: &CliKeyStore) -> Result<Option<WalletMnemonic>, CliError> {
+    let Some(phrase) = keystore.get_mnemonic_phrase().map_err(CliError::KeyStore)? else {
+        return Ok(None);
+    };
+
+    WalletMnemonic::from_phrase(&phrase)
+        .map(Some)
+        .map_err(|err| CliError::Input(format!("Invalid mnemonic in the keystore: {err}")))
+}
+
+/// Reads the wallet mnemonic phrase from stdin. When stdin is a terminal, the user is prompted
+/// for it and the input isn't echoed.
+pub(crate) fn read_mnemonic_phrase() -> Result<String, CliError> {
+    if std::io::stdin().is_terminal() {
+        return Ok(rpassword::prompt_password("Mnemonic phrase: ")?);
+    }
+
+    let mut phrase = String::new();
+    std::io::stdin().read_line(&mut phrase)?;
+    Ok(phrase)
+}
@@ -7,7 +7,9 @@ use std::sync::Arc;
 use anyhow::Result;
 use assert_cmd::Command;
 use miden_client::account::{AccountId, AccountStorageMode};
-use miden_client::crypto::{FeltRng, RpoRandomCoin};
+use miden_client::auth::{AuthSecretKey, SigningInputs};
+use miden_client::crypto::{FeltRng, RpoRandomCoin, SecretKey};
+use miden_client::keystore::external_signer::{SignRequest, SignResponse};
 use miden_client::note::{
     Note,
     NoteAssets,
@@ -32,7 +34,7 @@ use miden_client::testing::common::{
 };
 use miden_client::transaction::{OutputNote, TransactionRequestBuilder};
 use miden_client::utils::Serializable;
-use miden_client::{self, Client, ExecutionOptions, Felt};
+use miden_client::{self, Client, ExecutionOptions, Felt, Word};
 use miden_client_cli::CliKeyStore;
 use miden_objects::{MAX_TX_EXECUTION_CYCLES, MIN_TX_EXECUTION_CYCLES};
 use predicates::str::contains;
@@ -89,6 +91,148 @@ fn init_with_params() {
     init_cmd.current_dir(&temp_dir).assert().failure();
 }
 
+#[test]
+fn encrypt_keystore_migrates_plaintext_keys() {
+    let store_path = create_test_store_path();
+    let temp_dir = init_cli_with_store_path(&store_path, &Endpoint::devnet());
+    let keys_directory = temp_dir.join("keystore");
+
+    // Add a plaintext key to the keystore before migrating it
+    let key = SecretKey::new();
+    let pub_key = Word::from(key.public_key());
+    CliKeyStore::new(keys_directory.clone())
+        .unwrap()
+        .add_key(&AuthSecretKey::RpoFalcon512(key))
+        .unwrap();
+
+    let mut encrypt_cmd = Command::cargo_bin("miden-client").unwrap();
+    encrypt_cmd
+        .args(["encrypt-keystore"])
+        .env("MIDEN_KEYSTORE_PASSPHRASE", "passphrase");
+    encrypt_cmd.current_dir(&temp_dir).assert().success();
+
+    let config_file_str = fs::read_to_string(temp_dir.join("miden-client.toml")).unwrap();
+    assert!(config_file_str.contains("encrypt_keystore = true"));
+
+    // The key can only be read after unlocking the keystore with the passphrase
+    assert!(CliKeyStore::new(keys_directory.clone()).unwrap().get_key(pub_key).is_err());
+    let keystore = CliKeyStore::new(keys_directory).unwrap().with_passphrase("passphrase").unwrap();
+    assert!(keystore.get_key(pub_key).unwrap().is_some());
+
+    // Commands fail to unlock the keystore with the wrong passphrase
+    let mut sync_cmd = Command::cargo_bin("miden-client").unwrap();
+    sync_cmd.args(["sync"]).env("MIDEN_KEYSTORE_PASSPHRASE", "wrong passphrase");
+    assert_command_fails_but_does_not_panic(sync_cmd.current_dir(&temp_dir));
+}
+
+#[test]
+fn init_generates_and_restores_mnemonic() {
+    let store_path = create_test_store_path();
+    let temp_dir = init_cli_with_store_path(&store_path, &Endpoint::devnet());
+
+    let phrase = CliKeyStore::new(temp_dir.join("keystore"))
+        .unwrap()
+        .get_mnemonic_phrase()
+        .unwrap()
+        .expect("init should store a mnemonic");
+
+    // Restoring the mnemonic in a different directory results in the same mnemonic
+    let restore_dir = temp_dir.join("restored");
+    fs::create_dir_all(&restore_dir).unwrap();
+
+    let mut init_cmd = Command::cargo_bin("miden-client").unwrap();
+    init_cmd
+        .args(["init", "--network", "devnet", "--restore"])
+        .write_stdin(phrase.as_str());
+    init_cmd.current_dir(&restore_dir).assert().success();
+
+    let restored = CliKeyStore::new(restore_dir.join("keystore"))
+        .unwrap()
+        .get_mnemonic_phrase()
+        .unwrap()
+        .expect("restoring should store the mnemonic");
+    assert_eq!(restored.as_str(), phrase.as_str());
+
+    // Invalid phrases are rejected
+    let invalid_dir = temp_dir.join("invalid");
+    fs::create_dir_all(&invalid_dir).unwrap();
+
+    let mut init_cmd = Command::cargo_bin("miden-client").unwrap();
+    init_cmd
+        .args(["init", "--network", "devnet", "--restore"])
+        .write_stdin("not a mnemonic");
+    assert_command_fails_but_does_not_panic(init_cmd.current_dir(&invalid_dir));
+    assert!(!invalid_dir.join("miden-client.toml").exists());
+}
+
+#[test]
+fn init_stores_sync_checkpoint() {
+    let temp_dir = temp_dir().join(format!("cli-test-{}", rand::rng().random::<u64>()));
+    fs::create_dir_all(&temp_dir).unwrap();
+
+    let checkpoint = format!("1234:{}", Word::from([Felt::new(7); 4]).to_hex());
+    let checkpoint_file = temp_dir.join("checkpoint.txt");
+    fs::write(&checkpoint_file, format!("{checkpoint}\n")).unwrap();
+
+    let mut init_cmd = Command::cargo_bin("miden-client").unwrap();
+    init_cmd
+        .args(["init", "--network", "devnet", "--checkpoint-file"])
+        .arg(&checkpoint_file);
+    init_cmd.current_dir(&temp_dir).assert().success();
+
+    let config_file_str = fs::read_to_string(temp_dir.join("miden-client.toml")).unwrap();
+    assert!(config_file_str.contains(&format!("sync_checkpoint = \"{checkpoint}\"")));
+
+    // Malformed checkpoints are rejected before the config file is created
+    let invalid_dir = temp_dir.join("invalid");
+    fs::create_dir_all(&invalid_dir).unwrap();
+
+    let mut init_cmd = Command::cargo_bin("miden-client").unwrap();
+    init_cmd.args(["init", "--network", "devnet", "--checkpoint", "1234"]);
+    assert_command_fails_but_does_not_panic(init_cmd.current_dir(&invalid_dir));
+    assert!(!invalid_dir.join("miden-client.toml").exists());
+}
+
+#[test]
+fn reference_signer_signs_requests_over_stdin() {
+    let keys_directory = temp_dir().join(format!("signer-test-{}", rand::rng().random::<u64>()));
+    let key = SecretKey::new();
+    let pub_key = Word::from(key.public_key());
+    CliKeyStore::new(keys_directory.clone())
+        .unwrap()
+        .add_key(&AuthSecretKey::RpoFalcon512(key))
+        .unwrap();
+
+    let signing_inputs = SigningInputs::Blind(Word::from([Felt::new(5); 4]));
+    let requests = [
+        SignRequest {
+            pub_key,
+            signing_inputs: signing_inputs.clone(),
+        }
+        .to_line(),
+        SignRequest {
+            pub_key: Word::from([Felt::new(9); 4]),
+            signing_inputs,
+        }
+        .to_line(),
+    ]
+    .join("\n");
+
+    let mut signer_cmd = Command::cargo_bin("miden-signer").unwrap();
+    signer_cmd
+        .args(["--keystore", keys_directory.to_str().unwrap()])
+        .write_stdin(requests);
+    let output = signer_cmd.assert().success().get_output().stdout.clone();
+
+    let responses = String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(|line| SignResponse::from_line(line).unwrap())
+        .collect::<Vec<_>>();
+    assert!(matches!(&responses[0], SignResponse::Signature(signature) if !signature.is_empty()));
+    assert_eq!(responses[1], SignResponse::UnknownKey);
+}
+
 // TX TESTS
 // ================================================================================================
 
@@ -360,6 +504,77 @@ async fn cli_export_import_account() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn keys_export_import_and_remove() {
+    const KEY_FILENAME: &str = "exported.key";
+
+    let (_, temp_dir, _) = init_cli();
+    let keys_directory = temp_dir.join("keystore");
+
+    // A key that no tracked account uses
+    let key = SecretKey::new();
+    let pub_key = Word::from(key.public_key()).to_hex();
+    CliKeyStore::new(keys_directory.clone())
+        .unwrap()
+        .add_key(&AuthSecretKey::RpoFalcon512(key))
+        .unwrap();
+
+    let mut export_cmd = Command::cargo_bin("miden-client").unwrap();
+    export_cmd.args(["keys", "--export", &pub_key, "--filename", KEY_FILENAME]);
+    export_cmd.current_dir(&temp_dir).assert().success();
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = fs::metadata(temp_dir.join(KEY_FILENAME)).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    // Exporting again doesn't overwrite the existing file
+    let mut export_cmd = Command::cargo_bin("miden-client").unwrap();
+    export_cmd.args(["keys", "--export", &pub_key, "--filename", KEY_FILENAME]);
+    assert_command_fails_but_does_not_panic(export_cmd.current_dir(&temp_dir));
+
+    let mut remove_cmd = Command::cargo_bin("miden-client").unwrap();
+    remove_cmd.args(["keys", "--remove", &pub_key]);
+    remove_cmd.current_dir(&temp_dir).assert().success();
+
+    let mut import_cmd = Command::cargo_bin("miden-client").unwrap();
+    import_cmd.args(["keys", "--import", KEY_FILENAME]);
+    import_cmd.current_dir(&temp_dir).assert().success();
+
+    let mut list_cmd = Command::cargo_bin("miden-client").unwrap();
+    list_cmd.args(["keys", "--list"]);
+    list_cmd
+        .current_dir(&temp_dir)
+        .assert()
+        .success()
+        .stdout(contains(pub_key.as_str()));
+
+    // Keys used by tracked accounts are only removed with `--force`
+    new_wallet_cli(&temp_dir, AccountStorageMode::Private);
+    let wallet_pub_key = CliKeyStore::new(keys_directory.clone())
+        .unwrap()
+        .list_keys()
+        .unwrap()
+        .into_iter()
+        .map(|pub_key| pub_key.to_hex())
+        .find(|wallet_pub_key| *wallet_pub_key != pub_key)
+        .unwrap();
+
+    let mut remove_cmd = Command::cargo_bin("miden-client").unwrap();
+    remove_cmd.args(["keys", "--remove", &wallet_pub_key]);
+    assert_command_fails_but_does_not_panic(remove_cmd.current_dir(&temp_dir));
+
+    let mut remove_cmd = Command::cargo_bin("miden-client").unwrap();
+    remove_cmd.args(["keys", "--remove", &wallet_pub_key, "--force"]);
+    remove_cmd.current_dir(&temp_dir).assert().success();
+
+    let remaining_keys = CliKeyStore::new(keys_directory).unwrap().list_keys().unwrap();
+    assert_eq!(remaining_keys.len(), 1);
+}
+
 #[test]
 fn cli_empty_commands() {
     let temp_dir = init_cli().1;
@@ -380,6 +595,11 @@ fn cli_empty_commands() {
 
     let mut swam_cmd = Command::cargo_bin("miden-client").unwrap();
     assert_command_fails_but_does_not_panic(swam_cmd.args(["swap"]).current_dir(&temp_dir));
+
+    let mut cancel_swap_cmd = Command::cargo_bin("miden-client").unwrap();
+    assert_command_fails_but_does_not_panic(
+        cancel_swap_cmd.args(["swaps", "cancel"]).current_dir(&temp_dir),
+    );
 }
 
 #[tokio::test]
@@ -402,6 +622,131 @@ async fn consume_unauthenticated_note() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn run_transaction_request_file() -> Result<()> {
+    let temp_dir = init_cli().1;
+
+    let wallet_account_id = new_wallet_cli(&temp_dir, AccountStorageMode::Private);
+    let fungible_faucet_account_id = new_faucet_cli(&temp_dir, AccountStorageMode::Public);
+
+    sync_cli(&temp_dir);
+
+    let note_id = mint_cli(&temp_dir, &wallet_account_id, &fungible_faucet_account_id);
+    sync_until_committed_note(&temp_dir);
+
+    // Consume the minted note and send part of it back in the same transaction
+    let request = format!(
+        r#"
+account = "{wallet_account_id}"
+expiration_delta = 20
+
+[[input_notes]]
+id = "{note_id}"
+
+[[output_notes]]
+type = "p2id"
+target = "{fungible_faucet_account_id}"
+assets = ["10::{fungible_faucet_account_id}"]
+note_type = "private"
+"#
+    );
+    std::fs::write(temp_dir.join("request.toml"), request)?;
+
+    let mut run_cmd = Command::cargo_bin("miden-client").unwrap();
+    run_cmd.args(["tx", "run", "request.toml", "--force"]);
+    run_cmd
+        .current_dir(&temp_dir)
+        .assert()
+        .success()
+        .stdout(contains("Successfully created transaction."));
+
+    // Unknown fields are rejected before executing anything
+    std::fs::write(temp_dir.join("invalid.toml"), "unknown_field = 1")?;
+    let mut run_cmd = Command::cargo_bin("miden-client").unwrap();
+    run_cmd.args(["tx", "run", "invalid.toml", "--force"]);
+    run_cmd.current_dir(&temp_dir).assert().failure();
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn multisig_transaction_is_signed_by_co_signer() -> Result<()> {
+    let temp_dir = init_cli().1;
+    let co_signer_dir = init_cli().1;
+
+    // The co-signer's key is only in its own keystore
+    let co_signer_key = SecretKey::new();
+    let co_signer_pub_key = Word::from(co_signer_key.public_key()).to_hex();
+    CliKeyStore::new(co_signer_dir.join("keystore"))?
+        .add_key(&AuthSecretKey::RpoFalcon512(co_signer_key))?;
+
+    let mut create_wallet_cmd = Command::cargo_bin("miden-client").unwrap();
+    create_wallet_cmd.args(["new-wallet", "--threshold", "2", "--approvers", &co_signer_pub_key]);
+    let output = create_wallet_cmd.current_dir(&temp_dir).output().unwrap();
+    assert!(output.status.success());
+    let wallet_account_id = std::str::from_utf8(&output.stdout)
+        .unwrap()
+        .split_whitespace()
+        .skip_while(|&word| word != "-s")
+        .nth(1)
+        .unwrap()
+        .to_string();
+
+    let fungible_faucet_account_id = new_faucet_cli(&temp_dir, AccountStorageMode::Public);
+    sync_cli(&temp_dir);
+
+    let note_id = mint_cli(&temp_dir, &wallet_account_id, &fungible_faucet_account_id);
+    sync_until_committed_note(&temp_dir);
+
+    let request = format!(
+        r#"
+account = "{wallet_account_id}"
+
+[[input_notes]]
+id = "{note_id}"
+"#
+    );
+    std::fs::write(temp_dir.join("request.toml"), request)?;
+
+    let mut propose_cmd = Command::cargo_bin("miden-client").unwrap();
+    propose_cmd.args(["multisig", "propose", "request.toml", "--output", "consume.msig"]);
+    propose_cmd
+        .current_dir(&temp_dir)
+        .assert()
+        .success()
+        .stdout(contains("1/2 required signatures collected"));
+
+    // The proposer's signature alone isn't enough
+    let mut finalize_cmd = Command::cargo_bin("miden-client").unwrap();
+    finalize_cmd.args(["multisig", "finalize", "consume.msig", "--force"]);
+    assert_command_fails_but_does_not_panic(finalize_cmd.current_dir(&temp_dir));
+
+    std::fs::copy(temp_dir.join("consume.msig"), co_signer_dir.join("consume.msig"))?;
+    let mut sign_cmd = Command::cargo_bin("miden-client").unwrap();
+    sign_cmd.args(["multisig", "sign", "consume.msig", "--force"]);
+    sign_cmd
+        .current_dir(&co_signer_dir)
+        .assert()
+        .success()
+        .stdout(contains(format!("Signed with key {co_signer_pub_key}")));
+
+    let mut finalize_cmd = Command::cargo_bin("miden-client").unwrap();
+    finalize_cmd.args([
+        "multisig",
+        "finalize",
+        "consume.msig",
+        co_signer_dir.join("consume.msig").to_str().unwrap(),
+        "--force",
+    ]);
+    finalize_cmd
+        .current_dir(&temp_dir)
+        .assert()
+        .success()
+        .stdout(contains("Successfully created transaction."));
+
+    Ok(())
+}
+
 // DEVNET & TESTNET TESTS
 // ================================================================================================
 
@@ -815,3 +1160,37 @@ fn exec_parse() {
 
     failure_cmd.current_dir(&temp_dir).assert().failure();
 }
+
+#[test]
+fn exec_rejects_invalid_foreign_accounts() {
+    let success_script =
+        fs::canonicalize("tests/files/test_cli_advice_inputs_expect_success.masm").unwrap();
+    let toml_path = fs::canonicalize("tests/files/test_cli_advice_inputs_input.toml").unwrap();
+
+    let temp_dir = init_cli().1;
+    let basic_account_id = new_wallet_cli(&temp_dir, AccountStorageMode::Private);
+
+    for foreign_account in [
+        // Storage slots must be followed by their map keys
+        format!("{basic_account_id}:1"),
+        // Map keys must be words
+        format!("{basic_account_id}:1=0x01"),
+        // Storage maps can't be requested for private accounts
+        format!("{basic_account_id}:1={}", Word::default().to_hex()),
+    ] {
+        let mut exec_cmd = Command::cargo_bin("miden-client").unwrap();
+        exec_cmd.args([
+            "exec",
+            "-s",
+            success_script.to_str().unwrap(),
+            "-a",
+            &basic_account_id,
+            "-i",
+            toml_path.to_str().unwrap(),
+            "--foreign-account",
+            &foreign_account,
+        ]);
+
+        assert_command_fails_but_does_not_panic(exec_cmd.current_dir(&temp_dir));
+    }
+}
@@ -0,0 +1,135 @@
+//! Compares the time spent screening notes sequentially, in parallel and from the cache, on a
+//! synthetic store with many wallets and notes.
+//!
+//! Run with `cargo bench -p miden-client --bench note_screening`. The number of wallets and notes
+//! can be set with the `SCREENING_BENCH_WALLETS` and `SCREENING_BENCH_NOTES` environment
+//! variables.
+
+use std::env::{self, temp_dir};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use miden_client::Felt;
+use miden_client::account::AccountStorageMode;
+use miden_client::asset::{Asset, FungibleAsset};
+use miden_client::builder::ClientBuilder;
+use miden_client::keystore::FilesystemKeyStore;
+use miden_client::note::{Note, NoteConsumability, NoteScreeningCache, NoteType, create_p2id_note};
+use miden_client::store::sqlite_store::SqliteStore;
+use miden_client::testing::common::{
+    TestClient,
+    create_test_store_path,
+    insert_new_fungible_faucet,
+    insert_new_wallet,
+};
+use miden_client::testing::mock::MockRpcApi;
+use uuid::Uuid;
+
+const DEFAULT_WALLETS: usize = 16;
+const DEFAULT_NOTES: usize = 32;
+
+fn main() {
+    let num_wallets = env_or("SCREENING_BENCH_WALLETS", DEFAULT_WALLETS);
+    let num_notes = env_or("SCREENING_BENCH_NOTES", DEFAULT_NOTES);
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .expect("runtime should be built");
+
+    runtime.block_on(async {
+        let (client, notes) = setup(num_wallets, num_notes).await;
+        println!(
+            "Screening {num_notes} notes against {} accounts",
+            num_wallets + 1 // the faucet is tracked as well
+        );
+
+        let (sequential_time, sequential) =
+            screen(&client, &notes, 1, Arc::new(NoteScreeningCache::new())).await;
+        report("sequential", sequential_time, sequential_time);
+
+        let parallelism = std::thread::available_parallelism().map_or(1, usize::from);
+        let cache = Arc::new(NoteScreeningCache::new());
+        let (parallel_time, parallel) = screen(&client, &notes, parallelism, cache.clone()).await;
+        report(&format!("parallel ({parallelism} threads)"), parallel_time, sequential_time);
+        assert_eq!(parallel, sequential, "parallel screening should match sequential screening");
+
+        let (cached_time, cached) = screen(&client, &notes, parallelism, cache).await;
+        report("cached", cached_time, sequential_time);
+        assert_eq!(cached, sequential, "cached screening should match sequential screening");
+    });
+}
+
+/// Returns a client tracking a faucet and `num_wallets` wallets, along with `num_notes` P2ID notes
+/// sent by the faucet to the wallets.
+async fn setup(num_wallets: usize, num_notes: usize) -> (TestClient, Vec<Note>) {
+    let keystore_path = temp_dir().join(Uuid::new_v4().to_string());
+    let keystore = FilesystemKeyStore::new(keystore_path.clone()).unwrap();
+    let store = SqliteStore::new(create_test_store_path()).await.unwrap();
+
+    let mut client = ClientBuilder::new()
+        .rpc(Arc::new(MockRpcApi::default()))
+        .store(Arc::new(store))
+        .filesystem_keystore(keystore_path.to_str().unwrap())
+        .tx_graceful_blocks(None)
+        .build()
+        .await
+        .unwrap();
+    client.ensure_genesis_in_place().await.unwrap();
+
+    let (faucet, ..) =
+        insert_new_fungible_faucet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+    let mut wallets = Vec::with_capacity(num_wallets);
+    for _ in 0..num_wallets {
+        let (wallet, ..) = insert_new_wallet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+        wallets.push(wallet);
+    }
+
+    let asset = Asset::Fungible(FungibleAsset::new(faucet.id(), 100).unwrap());
+    let notes = (0..num_notes)
+        .map(|index| {
+            create_p2id_note(
+                faucet.id(),
+                wallets[index % wallets.len()].id(),
+                vec![asset],
+                NoteType::Public,
+                Felt::new(0),
+                client.rng(),
+            )
+            .unwrap()
+        })
+        .collect();
+
+    (client, notes)
+}
+
+/// Screens the notes with the given parallelism and cache, returning the time it took along with
+/// the relevance of each note.
+async fn screen(
+    client: &TestClient,
+    notes: &[Note],
+    parallelism: usize,
+    cache: Arc<NoteScreeningCache>,
+) -> (Duration, Vec<Vec<NoteConsumability>>) {
+    let note_screener = client.note_screener().with_parallelism(parallelism).with_cache(cache);
+
+    let start = Instant::now();
+    let relevances = note_screener.check_relevance_batch(notes).await.unwrap();
+    (start.elapsed(), relevances)
+}
+
+fn report(label: &str, time: Duration, sequential_time: Duration) {
+    println!(
+        "{label:>24}: {:>10.2?} ({:.1}x)",
+        time,
+        sequential_time.as_secs_f64() / time.as_secs_f64().max(f64::EPSILON)
+    );
+}
+
+fn env_or(variable: &str, default: usize) -> usize {
+    env::var(variable).ok().and_then(|value| value.parse().ok()).unwrap_or(default)
+}
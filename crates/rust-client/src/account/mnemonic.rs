@@ -0,0 +1,320 @@
+//! Deterministic derivation of wallet accounts from a BIP-39 mnemonic.
+//!
+//! A [`WalletMnemonic`] can be used to derive, for any index, the init seed and Falcon secret key
+//! of a wallet account. Since public wallet account IDs only depend on these and on the account's
+//! components (see [`build_wallet_id`]), the mnemonic alone is enough to recover every public
+//! wallet created from it with [`Client::recover_wallets`] or [`Client::recover_accounts`].
+//!
+//! The mnemonic phrase itself is kept by the keystore (see
+//! [`FilesystemKeyStore::set_mnemonic_phrase`](crate::keystore::FilesystemKeyStore::set_mnemonic_phrase)),
+//! while [`Client::next_wallet_index`] finds the next unused derivation index from the tracked
+//! accounts.
+
+use alloc::collections::BTreeSet;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use miden_lib::account::auth::AuthRpoFalcon512;
+use miden_lib::account::wallets::BasicWallet;
+use miden_objects::Word;
+use miden_objects::account::{
+    Account,
+    AccountBuilder,
+    AccountComponent,
+    AccountId,
+    AccountStorageMode,
+    AccountType,
+};
+use miden_objects::crypto::dsa::rpo_falcon512::SecretKey;
+use miden_objects::crypto::hash::rpo::Rpo256;
+use miden_objects::crypto::rand::RpoRandomCoin;
+use rand::RngCore;
+use thiserror::Error;
+
+use super::build_wallet_id;
+use crate::Client;
+use crate::errors::ClientError;
+use crate::rpc::{GrpcError, RpcError};
+use crate::utils::get_public_keys_from_account;
+
+/// Domain separator used when deriving the secrets of an account from the mnemonic seed.
+const ACCOUNT_DERIVATION_DOMAIN: &[u8] = b"miden-client/wallet-account";
+
+/// Number of bytes of entropy of generated mnemonics, which results in 24 words.
+const MNEMONIC_ENTROPY_LEN: usize = 32;
+
+// WALLET MNEMONIC
+// ================================================================================================
+
+/// A BIP-39 mnemonic from which wallet account secrets are derived.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WalletMnemonic {
+    mnemonic: bip39::Mnemonic,
+}
+
+impl WalletMnemonic {
+    /// Generates a new random 24-word mnemonic.
+    pub fn generate<R: RngCore>(rng: &mut R) -> Self {
+        let mut entropy = [0u8; MNEMONIC_ENTROPY_LEN];
+        rng.fill_bytes(&mut entropy);
+
+        let mnemonic =
+            bip39::Mnemonic::from_entropy(&entropy).expect("entropy length should be valid");
+        Self { mnemonic }
+    }
+
+    /// Parses a mnemonic from its phrase.
+    ///
+    /// # Errors
+    /// Returns [`MnemonicError::InvalidPhrase`] if the phrase isn't a valid BIP-39 mnemonic.
+    pub fn from_phrase(phrase: &str) -> Result<Self, MnemonicError> {
+        let mnemonic = bip39::Mnemonic::parse(phrase.trim())
+            .map_err(|err| MnemonicError::InvalidPhrase(err.to_string()))?;
+        Ok(Self { mnemonic })
+    }
+
+    /// Returns the phrase of the mnemonic.
+    pub fn phrase(&self) -> String {
+        self.mnemonic.to_string()
+    }
+
+    /// Derives the secrets of the wallet account with the given index.
+    ///
+    /// The same mnemonic and index always result in the same secrets.
+    pub fn derive_account_secrets(&self, index: u32) -> DerivedAccountSecrets {
+        let seed = self.mnemonic.to_seed("");
+
+        let mut derivation_input = Vec::with_capacity(
+            ACCOUNT_DERIVATION_DOMAIN.len() + seed.len() + core::mem::size_of::<u32>(),
+        );
+        derivation_input.extend_from_slice(ACCOUNT_DERIVATION_DOMAIN);
+        derivation_input.extend_from_slice(&seed);
+        derivation_input.extend_from_slice(&index.to_le_bytes());
+
+        // The init seed must be drawn before the secret key, as changing the order would change
+        // the derived accounts
+        let mut rng = RpoRandomCoin::new(Rpo256::hash(&derivation_input));
+        let mut init_seed = [0u8; 32];
+        rng.fill_bytes(&mut init_seed);
+        let secret_key = SecretKey::with_rng(&mut rng);
+
+        DerivedAccountSecrets { index, init_seed, secret_key }
+    }
+
+    /// Returns the ID of the wallet account with the given index, built with [`build_wallet_id`].
+    pub fn wallet_id(
+        &self,
+        index: u32,
+        storage_mode: AccountStorageMode,
+        is_mutable: bool,
+    ) -> Result<AccountId, ClientError> {
+        let secrets = self.derive_account_secrets(index);
+        build_wallet_id(
+            secrets.init_seed,
+            secrets.secret_key.public_key(),
+            storage_mode,
+            is_mutable,
+        )
+    }
+}
+
+/// The secrets of a wallet account derived from a [`WalletMnemonic`].
+#[derive(Debug, Clone)]
+pub struct DerivedAccountSecrets {
+    /// Derivation index of the account.
+    pub index: u32,
+    /// Init seed to pass to [`AccountBuilder::new`](miden_objects::account::AccountBuilder::new).
+    pub init_seed: [u8; 32],
+    /// Secret key of the account's Falcon auth component.
+    pub secret_key: SecretKey,
+}
+
+// MNEMONIC ERROR
+// ================================================================================================
+
+#[derive(Debug, Error)]
+pub enum MnemonicError {
+    #[error("invalid mnemonic phrase: {0}")]
+    InvalidPhrase(String),
+}
+
+// RECOVERY
+// ================================================================================================
+
+/// A public wallet account recovered from a [`WalletMnemonic`].
+#[derive(Debug, Clone)]
+pub struct RecoveredWallet {
+    /// ID of the recovered account.
+    pub account_id: AccountId,
+    /// Secrets the account was derived from. The secret key needs to be added to the keystore in
+    /// order to sign transactions with the account.
+    pub secrets: DerivedAccountSecrets,
+}
+
+impl<AUTH> Client<AUTH> {
+    /// Recovers the public wallet accounts derived from the mnemonic, importing them from the
+    /// network.
+    ///
+    /// This only finds wallets made of the [`BasicWallet`] component and a Falcon auth component,
+    /// as created by [`build_wallet_id`]. Use [`Client::recover_accounts`] for wallets that were
+    /// created with other components.
+    ///
+    /// # Errors
+    /// Returns an error if a request to the node fails for any reason other than the account not
+    /// being found.
+    pub async fn recover_wallets(
+        &mut self,
+        mnemonic: &WalletMnemonic,
+        gap_limit: u32,
+    ) -> Result<Vec<RecoveredWallet>, ClientError> {
+        self.recover_accounts(mnemonic, gap_limit, &[BasicWallet.into()]).await
+    }
+
+    /// Recovers the public regular accounts derived from the mnemonic that are made of the given
+    /// components and a Falcon auth component with the derived key, importing them from the
+    /// network.
+    ///
+    /// Indices are scanned from zero, looking for both mutable and immutable accounts, until
+    /// `gap_limit` consecutive indices have no account on the network. Accounts that are already
+    /// tracked by the client are returned without being imported again.
+    ///
+    /// Accounts whose auth component depends on more than the derived key, such as multisig
+    /// accounts, can't be found this way.
+    ///
+    /// # Errors
+    /// Returns an error if the components can't be built into an account, or if a request to the
+    /// node fails for any reason other than the account not being found.
+    pub async fn recover_accounts(
+        &mut self,
+        mnemonic: &WalletMnemonic,
+        gap_limit: u32,
+        components: &[AccountComponent],
+    ) -> Result<Vec<RecoveredWallet>, ClientError> {
+        let mut recovered = Vec::new();
+        let mut index = 0;
+        let mut consecutive_misses = 0;
+
+        while consecutive_misses < gap_limit {
+            let secrets = mnemonic.derive_account_secrets(index);
+            let mut found = false;
+
+            for is_mutable in [false, true] {
+                let account_id = derived_account_id(&secrets, components, is_mutable)?;
+
+                if self.get_account_header_by_id(account_id).await?.is_none() {
+                    match self.import_account_by_id(account_id).await {
+                        Ok(()) => {},
+                        Err(ClientError::RpcError(RpcError::GrpcError {
+                            error_kind: GrpcError::NotFound,
+                            ..
+                        })) => continue,
+                        Err(err) => return Err(err),
+                    }
+                }
+
+                recovered.push(RecoveredWallet { account_id, secrets: secrets.clone() });
+                found = true;
+            }
+
+            consecutive_misses = if found { 0 } else { consecutive_misses + 1 };
+            index += 1;
+        }
+
+        Ok(recovered)
+    }
+
+    /// Returns the lowest derivation index of the mnemonic whose key isn't used by any account
+    /// tracked by the client, which is the index to derive the next wallet from.
+    ///
+    /// The index is computed from the store, so it doesn't need to be persisted and it accounts
+    /// for wallets recovered with [`Client::recover_wallets`].
+    pub async fn next_wallet_index(&self, mnemonic: &WalletMnemonic) -> Result<u32, ClientError> {
+        let mut used_keys = BTreeSet::new();
+        for (header, _) in self.store.get_account_headers().await? {
+            if let Some(record) = self.store.get_account(header.id()).await? {
+                let account: Account = record.into();
+                used_keys.extend(get_public_keys_from_account(&account));
+            }
+        }
+
+        let mut index = 0;
+        while used_keys
+            .contains(&Word::from(mnemonic.derive_account_secrets(index).secret_key.public_key()))
+        {
+            index += 1;
+        }
+
+        Ok(index)
+    }
+}
+
+/// Returns the ID of the public regular account made of the given components and a Falcon auth
+/// component with the derived key.
+fn derived_account_id(
+    secrets: &DerivedAccountSecrets,
+    components: &[AccountComponent],
+    is_mutable: bool,
+) -> Result<AccountId, ClientError> {
+    let account_type = if is_mutable {
+        AccountType::RegularAccountUpdatableCode
+    } else {
+        AccountType::RegularAccountImmutableCode
+    };
+
+    let mut builder = AccountBuilder::new(secrets.init_seed)
+        .account_type(account_type)
+        .storage_mode(AccountStorageMode::Public)
+        .with_auth_component(AuthRpoFalcon512::new(secrets.secret_key.public_key()));
+    for component in components {
+        builder = builder.with_component(component.clone());
+    }
+
+    let (account, _) = builder.build()?;
+    Ok(account.id())
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use miden_objects::Word;
+    use miden_objects::account::AccountStorageMode;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::WalletMnemonic;
+
+    #[test]
+    fn derivation_is_deterministic() {
+        let mnemonic = WalletMnemonic::generate(&mut StdRng::seed_from_u64(0));
+        let restored = WalletMnemonic::from_phrase(&mnemonic.phrase()).unwrap();
+        assert_eq!(mnemonic.phrase().split_whitespace().count(), 24);
+
+        let secrets = mnemonic.derive_account_secrets(3);
+        let restored_secrets = restored.derive_account_secrets(3);
+        assert_eq!(secrets.init_seed, restored_secrets.init_seed);
+        assert_eq!(
+            Word::from(secrets.secret_key.public_key()),
+            Word::from(restored_secrets.secret_key.public_key())
+        );
+
+        assert_eq!(
+            mnemonic.wallet_id(3, AccountStorageMode::Public, false).unwrap(),
+            restored.wallet_id(3, AccountStorageMode::Public, false).unwrap()
+        );
+
+        // Different indices result in different accounts
+        let other_secrets = mnemonic.derive_account_secrets(4);
+        assert_ne!(secrets.init_seed, other_secrets.init_seed);
+        assert_ne!(
+            Word::from(secrets.secret_key.public_key()),
+            Word::from(other_secrets.secret_key.public_key())
+        );
+    }
+
+    #[test]
+    fn invalid_phrase_is_rejected() {
+        assert!(WalletMnemonic::from_phrase("not a valid mnemonic").is_err());
+    }
+}
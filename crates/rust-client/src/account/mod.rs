@@ -70,6 +70,9 @@ use crate::errors::ClientError;
 use crate::rpc::domain::account::FetchedAccount;
 use crate::store::{AccountRecord, AccountStatus};
 
+#[cfg(feature = "std")]
+pub mod mnemonic;
+
 pub mod component {
     pub const COMPONENT_TEMPLATE_EXTENSION: &str = "mct";
 
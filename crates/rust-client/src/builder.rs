@@ -1,21 +1,25 @@
 use alloc::string::{String, ToString};
 use alloc::sync::Arc;
+use alloc::vec::Vec;
 use std::boxed::Box;
 
 use miden_objects::crypto::rand::{FeltRng, RpoRandomCoin};
-use miden_objects::{Felt, MAX_TX_EXECUTION_CYCLES, MIN_TX_EXECUTION_CYCLES};
+use miden_objects::{Felt, MAX_TX_EXECUTION_CYCLES, MIN_TX_EXECUTION_CYCLES, Word};
 use miden_tx::ExecutionOptions;
 use miden_tx::auth::TransactionAuthenticator;
 use rand::Rng;
 
 use crate::consts::MIXER_DEFAULT_URL;
 use crate::keystore::FilesystemKeyStore;
+use crate::note::NoteRelevanceChecker;
 use crate::rpc::NodeRpcClient;
 #[cfg(feature = "tonic")]
-use crate::rpc::{Endpoint, TonicRpcClient};
+use crate::rpc::{Endpoint, FailoverRpcClient, TonicRpcClient};
 use crate::store::Store;
 #[cfg(feature = "sqlite")]
 use crate::store::sqlite_store::SqliteStore;
+use crate::sync::{SyncCheckpoint, SyncListener};
+use crate::transaction::TransactionRetryPolicy;
 use crate::{Client, ClientError, DebugMode};
 
 // CONSTANTS
@@ -67,6 +71,14 @@ pub struct ClientBuilder<AUTH> {
     /// Maximum number of blocks the client can be behind the network for transactions and account
     /// proofs to be considered valid.
     max_block_number_delta: Option<u32>,
+    /// Policy used to retry discarded transactions.
+    tx_retry_policy: Option<TransactionRetryPolicy>,
+    /// Checkpoint the first sync starts from instead of genesis.
+    start_checkpoint: Option<SyncCheckpoint>,
+    /// Listeners notified of the progress and the changes of each sync.
+    sync_listeners: Vec<Arc<dyn SyncListener>>,
+    /// Checkers used instead of executing notes to check their relevance, by script root.
+    note_relevance_checkers: Vec<(Word, Arc<dyn NoteRelevanceChecker>)>,
 }
 
 impl<AUTH> Default for ClientBuilder<AUTH> {
@@ -81,6 +93,10 @@ impl<AUTH> Default for ClientBuilder<AUTH> {
             in_debug_mode: DebugMode::Disabled,
             tx_graceful_blocks: Some(TX_GRACEFUL_BLOCKS),
             max_block_number_delta: None,
+            tx_retry_policy: None,
+            start_checkpoint: None,
+            sync_listeners: Vec::new(),
+            note_relevance_checkers: Vec::new(),
         }
     }
 }
@@ -117,6 +133,16 @@ where
         self
     }
 
+    /// Sets a [`FailoverRpcClient`] that sends requests to the first available of the endpoints,
+    /// with the optional timeout.
+    #[cfg(feature = "tonic")]
+    #[must_use]
+    pub fn failover_rpc_client(mut self, endpoints: &[Endpoint], timeout_ms: Option<u64>) -> Self {
+        self.rpc_api =
+            Some(Arc::new(FailoverRpcClient::new(endpoints, timeout_ms.unwrap_or(10_000))));
+        self
+    }
+
     /// Optionally set a custom store path.
     #[cfg(feature = "sqlite")]
     #[must_use]
@@ -163,6 +189,44 @@ where
         self
     }
 
+    /// Optionally set the policy used by `Client::retry_discarded_transactions`, which otherwise
+    /// uses the default policy. Discarded transactions are only retried when that method is
+    /// called: syncing never retries them on its own.
+    #[must_use]
+    pub fn transaction_retry_policy(mut self, policy: TransactionRetryPolicy) -> Self {
+        self.tx_retry_policy = Some(policy);
+        self
+    }
+
+    /// Optionally set the checkpoint the client starts from if it never synced. Its first sync
+    /// then begins right after the checkpoint block instead of genesis.
+    #[must_use]
+    pub fn start_checkpoint(mut self, checkpoint: SyncCheckpoint) -> Self {
+        self.start_checkpoint = Some(checkpoint);
+        self
+    }
+
+    /// Registers a listener notified of the progress and the changes of each sync. Can be called
+    /// several times to register several listeners.
+    #[must_use]
+    pub fn sync_listener(mut self, listener: Arc<dyn SyncListener>) -> Self {
+        self.sync_listeners.push(listener);
+        self
+    }
+
+    /// Registers a checker used instead of executing the notes whose script has the given root
+    /// when checking their relevance. Can be called several times to register checkers for
+    /// several scripts.
+    #[must_use]
+    pub fn note_relevance_checker(
+        mut self,
+        script_root: Word,
+        checker: Arc<dyn NoteRelevanceChecker>,
+    ) -> Self {
+        self.note_relevance_checkers.push((script_root, checker));
+        self
+    }
+
     /// **Required:** Provide the keystore path as a string.
     ///
     /// This stores the keystore path as a configuration option so that actual keystore
@@ -230,7 +294,7 @@ where
             None => None,
         };
 
-        Client::new(
+        let mut client = Client::new(
             rpc_api,
             rng,
             arc_store,
@@ -246,6 +310,16 @@ where
             self.max_block_number_delta,
             MIXER_DEFAULT_URL.try_into().unwrap(),
         )
-        .await
+        .await?;
+        client.set_transaction_retry_policy(self.tx_retry_policy);
+        client.set_start_checkpoint(self.start_checkpoint);
+        for listener in self.sync_listeners {
+            client.add_sync_listener(listener);
+        }
+        for (script_root, checker) in self.note_relevance_checkers {
+            client.register_note_relevance_checker(script_root, checker);
+        }
+
+        Ok(client)
     }
 }
@@ -5,6 +5,7 @@ use miden_lib::account::interface::AccountInterfaceError;
 use miden_objects::account::AccountId;
 use miden_objects::crypto::merkle::MerkleError;
 use miden_objects::note::NoteId;
+use miden_objects::transaction::TransactionId;
 use miden_objects::{
     AccountError,
     AssetError,
@@ -24,7 +25,7 @@ use thiserror::Error;
 use crate::note::NoteScreenerError;
 use crate::rpc::RpcError;
 use crate::store::{NoteRecordError, StoreError};
-use crate::transaction::TransactionRequestError;
+use crate::transaction::{MultisigError, TransactionRequestError};
 
 // CLIENT ERROR
 // ================================================================================================
@@ -58,6 +59,8 @@ pub enum ClientError {
     HexParseError(#[from] HexParseError),
     #[error("partial MMR has a forest that does not fit within a u32")]
     InvalidPartialMmrForest,
+    #[error("invalid sync checkpoint: {0}")]
+    InvalidSyncCheckpoint(String),
     #[error("can't add new account without seed")]
     AddNewAccountWithoutSeed,
     #[error("error with merkle path")]
@@ -74,6 +77,8 @@ pub enum ClientError {
     NoteImportError(String),
     #[error("error while converting input note")]
     NoteRecordConversionError(#[from] NoteRecordError),
+    #[error("multisig transaction error")]
+    MultisigError(#[from] MultisigError),
     #[error("no consumable note for account {0}")]
     NoConsumableNoteForAccount(AccountId),
     #[error("rpc api error")]
@@ -84,8 +89,12 @@ pub enum ClientError {
     NoteScreenerError(#[from] NoteScreenerError),
     #[error("store error")]
     StoreError(#[from] StoreError),
+    #[error("swap note {0} can't be cancelled because {1}")]
+    SwapNotCancellable(NoteId, String),
     #[error("transaction executor error")]
     TransactionExecutorError(#[from] TransactionExecutorError),
+    #[error("transaction {0} can't be retried because {1}")]
+    TransactionNotRetryable(TransactionId, String),
     #[error("transaction input error")]
     TransactionInputError(#[source] TransactionInputError),
     #[error("transaction prover error")]
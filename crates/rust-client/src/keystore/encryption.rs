@@ -0,0 +1,272 @@
+//! Passphrase-based encryption of the key files stored by the
+//! [`FilesystemKeyStore`](super::FilesystemKeyStore).
+//!
+//! Each encrypted key file has the following layout:
+//!
+//! ```text
+//! | magic (4) | version (1) | m_cost (4) | t_cost (4) | p_cost (4) | salt (16) | nonce (24) | ciphertext |
+//! ```
+//!
+//! The file encryption key is derived from the passphrase and the per-file salt with Argon2id
+//! using the parameters stored in the header. The serialized secret key is then encrypted with
+//! XChaCha20-Poly1305, and the whole header is authenticated as associated data.
+//!
+//! The header can't be authenticated before the key is derived, so its parameters are checked
+//! against [`KdfParams::MAX`] first. The passphrase, derived keys and decrypted secrets are zeroed
+//! when dropped.
+
+use std::collections::BTreeMap;
+use std::string::ToString;
+use std::sync::Mutex;
+use std::vec::Vec;
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+use zeroize::Zeroizing;
+
+use super::KeyStoreError;
+
+/// Magic bytes that identify an encrypted key file.
+const MAGIC: &[u8; 4] = b"MDNK";
+/// Current version of the encrypted key file format.
+const VERSION: u8 = 1;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+const KEY_LEN: usize = 32;
+const HEADER_LEN: usize = MAGIC.len() + 1 + 3 * 4 + SALT_LEN + NONCE_LEN;
+
+// KDF PARAMS
+// ================================================================================================
+
+/// Argon2id cost parameters used to derive a file encryption key from the passphrase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct KdfParams {
+    /// Memory cost, in KiB.
+    m_cost: u32,
+    /// Number of iterations.
+    t_cost: u32,
+    /// Degree of parallelism.
+    p_cost: u32,
+}
+
+impl KdfParams {
+    /// Upper bounds for the parameters read from a key file: 1 GiB of memory, 16 iterations and
+    /// 16 lanes. A tampered header could otherwise make key derivation exhaust the host.
+    const MAX: Self = Self {
+        m_cost: 1024 * 1024,
+        t_cost: 16,
+        p_cost: 16,
+    };
+
+    /// Returns `true` if none of the parameters exceeds [`KdfParams::MAX`].
+    fn within_limits(self) -> bool {
+        self.m_cost <= Self::MAX.m_cost
+            && self.t_cost <= Self::MAX.t_cost
+            && self.p_cost <= Self::MAX.p_cost
+    }
+}
+
+impl Default for KdfParams {
+    #[cfg(not(test))]
+    fn default() -> Self {
+        Self { m_cost: 19 * 1024, t_cost: 2, p_cost: 1 }
+    }
+
+    // Unoptimized builds make the production parameters painfully slow, so tests use cheaper
+    // ones. The parameters are stored in each file, so both kinds of files remain readable.
+    #[cfg(test)]
+    fn default() -> Self {
+        Self { m_cost: 256, t_cost: 1, p_cost: 1 }
+    }
+}
+
+// KEY ENCRYPTION
+// ================================================================================================
+
+/// Encrypts and decrypts key files with a passphrase.
+///
+/// Derived keys are cached by salt so that signing with the same key repeatedly doesn't pay the
+/// key derivation cost every time.
+pub(super) struct KeyEncryption {
+    passphrase: Zeroizing<Vec<u8>>,
+    derived_keys: Mutex<BTreeMap<(KdfParams, [u8; SALT_LEN]), Zeroizing<[u8; KEY_LEN]>>>,
+}
+
+impl KeyEncryption {
+    pub fn new(passphrase: &str) -> Self {
+        Self {
+            passphrase: Zeroizing::new(passphrase.as_bytes().to_vec()),
+            derived_keys: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Returns `true` if the contents correspond to an encrypted key file.
+    pub fn is_encrypted(contents: &[u8]) -> bool {
+        contents.starts_with(MAGIC)
+    }
+
+    /// Encrypts the serialized secret key, returning the full contents of the key file.
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, KeyStoreError> {
+        let params = KdfParams::default();
+
+        let mut rng = rand::rng();
+        let mut salt = [0u8; SALT_LEN];
+        rng.fill_bytes(&mut salt);
+        let mut nonce = [0u8; NONCE_LEN];
+        rng.fill_bytes(&mut nonce);
+
+        let mut contents = Vec::with_capacity(HEADER_LEN + plaintext.len() + 16);
+        contents.extend_from_slice(MAGIC);
+        contents.push(VERSION);
+        contents.extend_from_slice(&params.m_cost.to_le_bytes());
+        contents.extend_from_slice(&params.t_cost.to_le_bytes());
+        contents.extend_from_slice(&params.p_cost.to_le_bytes());
+        contents.extend_from_slice(&salt);
+        contents.extend_from_slice(&nonce);
+
+        let key = self.derive_key(params, salt)?;
+        let ciphertext = XChaCha20Poly1305::new(Key::from_slice(key.as_slice()))
+            .encrypt(XNonce::from_slice(&nonce), Payload { msg: plaintext, aad: &contents })
+            .map_err(|_| KeyStoreError::EncryptionError("failed to encrypt key".to_string()))?;
+        contents.extend_from_slice(&ciphertext);
+
+        Ok(contents)
+    }
+
+    /// Decrypts the contents of an encrypted key file, returning the serialized secret key.
+    ///
+    /// # Errors
+    /// Returns [`KeyStoreError::InvalidPassphrase`] if the contents can't be authenticated with
+    /// the keystore's passphrase, and [`KeyStoreError::DecodingError`] if the header is malformed
+    /// or its key derivation parameters exceed [`KdfParams::MAX`].
+    pub fn decrypt(&self, contents: &[u8]) -> Result<Zeroizing<Vec<u8>>, KeyStoreError> {
+        if contents.len() < HEADER_LEN || !Self::is_encrypted(contents) {
+            return Err(KeyStoreError::DecodingError(
+                "key file is not a valid encrypted key file".to_string(),
+            ));
+        }
+
+        let (header, ciphertext) = contents.split_at(HEADER_LEN);
+        let version = header[MAGIC.len()];
+        if version != VERSION {
+            return Err(KeyStoreError::DecodingError(format!(
+                "unsupported encrypted key file version {version}"
+            )));
+        }
+
+        let read_u32 = |offset: usize| {
+            u32::from_le_bytes(header[offset..offset + 4].try_into().expect("slice has 4 bytes"))
+        };
+        let params_offset = MAGIC.len() + 1;
+        let params = KdfParams {
+            m_cost: read_u32(params_offset),
+            t_cost: read_u32(params_offset + 4),
+            p_cost: read_u32(params_offset + 8),
+        };
+        if !params.within_limits() {
+            return Err(KeyStoreError::DecodingError(
+                "key derivation parameters of the key file exceed the allowed limits".to_string(),
+            ));
+        }
+
+        let salt_offset = params_offset + 12;
+        let salt: [u8; SALT_LEN] = header[salt_offset..salt_offset + SALT_LEN]
+            .try_into()
+            .expect("slice has salt length");
+        let nonce = &header[salt_offset + SALT_LEN..];
+
+        let key = self.derive_key(params, salt)?;
+        XChaCha20Poly1305::new(Key::from_slice(key.as_slice()))
+            .decrypt(XNonce::from_slice(nonce), Payload { msg: ciphertext, aad: header })
+            .map(Zeroizing::new)
+            .map_err(|_| KeyStoreError::InvalidPassphrase)
+    }
+
+    fn derive_key(
+        &self,
+        params: KdfParams,
+        salt: [u8; SALT_LEN],
+    ) -> Result<Zeroizing<[u8; KEY_LEN]>, KeyStoreError> {
+        let mut derived_keys = self.derived_keys.lock().expect("poisoned lock");
+        if let Some(key) = derived_keys.get(&(params, salt)) {
+            return Ok(key.clone());
+        }
+
+        let argon2_params = Params::new(params.m_cost, params.t_cost, params.p_cost, Some(KEY_LEN))
+            .map_err(|err| {
+                KeyStoreError::EncryptionError(format!("invalid key derivation params: {err}"))
+            })?;
+        let mut key = Zeroizing::new([0u8; KEY_LEN]);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, argon2_params)
+            .hash_password_into(&self.passphrase, &salt, key.as_mut_slice())
+            .map_err(|err| {
+                KeyStoreError::EncryptionError(format!("failed to derive encryption key: {err}"))
+            })?;
+
+        derived_keys.insert((params, salt), key.clone());
+        Ok(key)
+    }
+}
+
+impl core::fmt::Debug for KeyEncryption {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("KeyEncryption").finish_non_exhaustive()
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{HEADER_LEN, KdfParams, KeyEncryption, MAGIC};
+    use crate::keystore::KeyStoreError;
+
+    #[test]
+    fn encrypt_decrypt_round_trip() {
+        let encryption = KeyEncryption::new("correct horse");
+        let contents = encryption.encrypt(b"secret key bytes").unwrap();
+
+        assert!(KeyEncryption::is_encrypted(&contents));
+        assert!(contents.len() > HEADER_LEN);
+        assert_eq!(encryption.decrypt(&contents).unwrap().as_slice(), b"secret key bytes");
+
+        // A fresh instance with the same passphrase must be able to decrypt as well
+        let reopened = KeyEncryption::new("correct horse");
+        assert_eq!(reopened.decrypt(&contents).unwrap().as_slice(), b"secret key bytes");
+    }
+
+    #[test]
+    fn wrong_passphrase_is_rejected() {
+        let contents = KeyEncryption::new("correct horse").encrypt(b"secret key bytes").unwrap();
+
+        let result = KeyEncryption::new("battery staple").decrypt(&contents);
+        assert!(matches!(result, Err(KeyStoreError::InvalidPassphrase)));
+    }
+
+    #[test]
+    fn tampered_header_is_rejected() {
+        let encryption = KeyEncryption::new("correct horse");
+        let mut contents = encryption.encrypt(b"secret key bytes").unwrap();
+
+        // Flip a bit of the salt, which is authenticated as part of the header
+        contents[HEADER_LEN - 30] ^= 1;
+        assert!(encryption.decrypt(&contents).is_err());
+    }
+
+    #[test]
+    fn excessive_kdf_params_are_rejected() {
+        let encryption = KeyEncryption::new("correct horse");
+        let mut contents = encryption.encrypt(b"secret key bytes").unwrap();
+
+        // Raise the memory cost above the limit, which must be caught before deriving the key
+        let m_cost_offset = MAGIC.len() + 1;
+        contents[m_cost_offset..m_cost_offset + 4]
+            .copy_from_slice(&(KdfParams::MAX.m_cost + 1).to_le_bytes());
+
+        assert!(matches!(encryption.decrypt(&contents), Err(KeyStoreError::DecodingError(_))));
+    }
+}
@@ -0,0 +1,517 @@
+//! A [`TransactionAuthenticator`] that forwards signing requests to an external signer, so that
+//! secret keys never have to be loaded into the client process.
+//!
+//! # Protocol
+//!
+//! The client and the signer exchange newline-terminated UTF-8 messages. Every request is answered
+//! with exactly one response before the next request is sent.
+//!
+//! ```text
+//! request:  SIGN <pub_key> <signing_inputs>
+//!         | NEW_KEY
+//! response: OK <signature>
+//!         | KEY <pub_key>
+//!         | ERR UNKNOWN_KEY
+//!         | ERR <message>
+//! ```
+//!
+//! - `SIGN` requests are answered with `OK` or `ERR`, and `NEW_KEY` requests, which ask the signer
+//!   to generate a new key pair, with `KEY` or `ERR`.
+//! - `<pub_key>` is the public key commitment, formatted as in [`Word::to_hex`].
+//! - `<signing_inputs>` is the hex-encoded serialization of the [`SigningInputs`].
+//! - `<signature>` is the hex-encoded serialization of the `Vec<Felt>` returned by
+//!   [`TransactionAuthenticator::get_signature`], ready to be pushed to the advice provider.
+//! - `ERR UNKNOWN_KEY` means that the signer has no secret key for the public key, while any other
+//!   `<message>` describes why the signature couldn't be produced.
+//!
+//! When connecting over a Unix socket, a new connection is opened for each request. When using a
+//! subprocess, the signer is spawned on first use and serves every request through its stdin and
+//! stdout. It's expected to exit once its stdin is closed.
+//!
+//! Signers can be built on top of any [`SignerBackend`] with [`serve_signer`].
+
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use miden_objects::{Felt, Word};
+use miden_tx::AuthenticationError;
+use miden_tx::auth::{SigningInputs, TransactionAuthenticator};
+use miden_tx::utils::{Deserializable, Serializable};
+use rand::Rng;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+#[cfg(unix)]
+use tokio::net::UnixStream;
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tokio::sync::Mutex;
+
+use super::{FilesystemKeyStore, KeyStoreError};
+
+const SIGN_REQUEST: &str = "SIGN";
+const NEW_KEY_REQUEST: &str = "NEW_KEY";
+const OK_RESPONSE: &str = "OK";
+const KEY_RESPONSE: &str = "KEY";
+const ERROR_RESPONSE: &str = "ERR";
+const UNKNOWN_KEY_ERROR: &str = "UNKNOWN_KEY";
+
+// MESSAGES
+// ================================================================================================
+
+/// A request to sign the signing inputs with the secret key of the public key.
+#[derive(Debug, Clone)]
+pub struct SignRequest {
+    pub pub_key: Word,
+    pub signing_inputs: SigningInputs,
+}
+
+impl SignRequest {
+    /// Encodes the request as a protocol line, without the trailing newline.
+    pub fn to_line(&self) -> String {
+        format!(
+            "{SIGN_REQUEST} {} {}",
+            self.pub_key.to_hex(),
+            hex::encode(self.signing_inputs.to_bytes())
+        )
+    }
+
+    /// Decodes a request from a protocol line.
+    pub fn from_line(line: &str) -> Result<Self, KeyStoreError> {
+        let mut parts = line.split_whitespace();
+        let (Some(SIGN_REQUEST), Some(pub_key), Some(signing_inputs), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(KeyStoreError::DecodingError(format!("invalid sign request: {line}")));
+        };
+
+        let pub_key = Word::try_from(pub_key).map_err(|err| {
+            KeyStoreError::DecodingError(format!("invalid public key in sign request: {err}"))
+        })?;
+        let signing_inputs = decode_hex(signing_inputs)?;
+
+        Ok(Self { pub_key, signing_inputs })
+    }
+}
+
+/// The response of a signer to a [`SignRequest`] or a `NEW_KEY` request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignResponse {
+    /// The requested signature.
+    Signature(Vec<Felt>),
+    /// The public key of the key pair generated by the signer.
+    NewKey(Word),
+    /// The signer has no secret key for the requested public key.
+    UnknownKey,
+    /// The signer failed to produce the signature.
+    Error(String),
+}
+
+impl SignResponse {
+    /// Encodes the response as a protocol line, without the trailing newline.
+    pub fn to_line(&self) -> String {
+        match self {
+            SignResponse::Signature(signature) => {
+                format!("{OK_RESPONSE} {}", hex::encode(signature.to_bytes()))
+            },
+            SignResponse::NewKey(pub_key) => format!("{KEY_RESPONSE} {}", pub_key.to_hex()),
+            SignResponse::UnknownKey => format!("{ERROR_RESPONSE} {UNKNOWN_KEY_ERROR}"),
+            // Messages must fit in a single line
+            SignResponse::Error(message) => {
+                format!("{ERROR_RESPONSE} {}", message.replace(['\n', '\r'], " "))
+            },
+        }
+    }
+
+    /// Decodes a response from a protocol line.
+    pub fn from_line(line: &str) -> Result<Self, KeyStoreError> {
+        let line = line.trim_end();
+        let (kind, payload) = line.split_once(' ').unwrap_or((line, ""));
+
+        match (kind, payload) {
+            (OK_RESPONSE, signature) => Ok(SignResponse::Signature(decode_hex(signature)?)),
+            (KEY_RESPONSE, pub_key) => {
+                Word::try_from(pub_key).map(SignResponse::NewKey).map_err(|err| {
+                    KeyStoreError::DecodingError(format!("invalid public key in response: {err}"))
+                })
+            },
+            (ERROR_RESPONSE, UNKNOWN_KEY_ERROR) => Ok(SignResponse::UnknownKey),
+            (ERROR_RESPONSE, message) => Ok(SignResponse::Error(message.to_string())),
+            _ => Err(KeyStoreError::DecodingError(format!("invalid sign response: {line}"))),
+        }
+    }
+}
+
+// EXTERNAL SIGNER
+// ================================================================================================
+
+/// The channel used to reach an external signer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignerTransport {
+    /// The signer listens on the Unix socket at the given path.
+    #[cfg(unix)]
+    UnixSocket(PathBuf),
+    /// The signer is spawned as a subprocess and spoken to through its stdin and stdout.
+    Subprocess { program: PathBuf, args: Vec<String> },
+}
+
+/// A [`TransactionAuthenticator`] that requests signatures from an external signer. See the
+/// [module documentation](self) for the protocol.
+#[derive(Debug, Clone)]
+pub struct ExternalSigner {
+    transport: SignerTransport,
+    /// The signer subprocess, if the transport is [`SignerTransport::Subprocess`] and it was
+    /// already spawned.
+    process: Arc<Mutex<Option<SignerProcess>>>,
+}
+
+impl ExternalSigner {
+    pub fn new(transport: SignerTransport) -> Self {
+        Self {
+            transport,
+            process: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Returns the transport used to reach the signer.
+    pub fn transport(&self) -> &SignerTransport {
+        &self.transport
+    }
+
+    /// Asks the signer to generate a new key pair and returns its public key. The secret key is
+    /// kept by the signer.
+    pub async fn new_key(&self) -> Result<Word, KeyStoreError> {
+        match self.request(NEW_KEY_REQUEST).await? {
+            SignResponse::NewKey(pub_key) => Ok(pub_key),
+            SignResponse::Error(message) => {
+                Err(KeyStoreError::StorageError(format!("external signer error: {message}")))
+            },
+            response => Err(KeyStoreError::DecodingError(format!(
+                "unexpected response to a new key request: {}",
+                response.to_line()
+            ))),
+        }
+    }
+
+    /// Sends the request line to the signer and returns its response.
+    async fn request(&self, request: &str) -> Result<SignResponse, KeyStoreError> {
+        let line = match &self.transport {
+            #[cfg(unix)]
+            SignerTransport::UnixSocket(path) => {
+                let mut stream = UnixStream::connect(path).await.map_err(|err| {
+                    KeyStoreError::StorageError(format!(
+                        "error connecting to signer socket {}: {err}",
+                        path.display()
+                    ))
+                })?;
+                let (reader, mut writer) = stream.split();
+                exchange(&mut writer, &mut BufReader::new(reader), request).await?
+            },
+            SignerTransport::Subprocess { program, args } => {
+                let mut process = self.process.lock().await;
+
+                // The signer is only put back once the exchange completes, so that it's respawned
+                // on the next request if it failed or was cancelled midway
+                let mut signer = match process.take() {
+                    Some(signer) => signer,
+                    None => SignerProcess::spawn(program, args)?,
+                };
+                let line = exchange(&mut signer.stdin, &mut signer.stdout, request).await?;
+                *process = Some(signer);
+                line
+            },
+        };
+
+        SignResponse::from_line(&line)
+    }
+}
+
+impl TransactionAuthenticator for ExternalSigner {
+    /// Gets a signature over a message, given a public key, from the external signer.
+    ///
+    /// # Errors
+    /// If the signer doesn't have the secret key of the public key,
+    /// [`AuthenticationError::UnknownPublicKey`] is returned.
+    async fn get_signature(
+        &self,
+        pub_key: Word,
+        signing_inputs: &SigningInputs,
+    ) -> Result<Vec<Felt>, AuthenticationError> {
+        let request = SignRequest {
+            pub_key,
+            signing_inputs: signing_inputs.clone(),
+        };
+
+        match self
+            .request(&request.to_line())
+            .await
+            .map_err(|err| AuthenticationError::other(err.to_string()))?
+        {
+            SignResponse::Signature(signature) => Ok(signature),
+            SignResponse::UnknownKey => {
+                Err(AuthenticationError::UnknownPublicKey(pub_key.to_hex()))
+            },
+            SignResponse::Error(message) => {
+                Err(AuthenticationError::other(format!("external signer error: {message}")))
+            },
+            SignResponse::NewKey(_) => Err(AuthenticationError::other(
+                "external signer answered a sign request with a new key".to_string(),
+            )),
+        }
+    }
+}
+
+/// A running signer subprocess.
+#[derive(Debug)]
+struct SignerProcess {
+    // Kept so that the process handle isn't dropped while in use
+    _child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl SignerProcess {
+    fn spawn(program: &Path, args: &[String]) -> Result<Self, KeyStoreError> {
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|err| {
+                KeyStoreError::StorageError(format!(
+                    "error spawning signer {}: {err}",
+                    program.display()
+                ))
+            })?;
+
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = BufReader::new(child.stdout.take().expect("stdout is piped"));
+
+        Ok(Self { _child: child, stdin, stdout })
+    }
+}
+
+// SIGNER SERVER
+// ================================================================================================
+
+/// The keys behind a signer served with [`serve_signer`]: an authenticator that can also generate
+/// new key pairs for `NEW_KEY` requests.
+pub trait SignerBackend: TransactionAuthenticator {
+    /// Generates a new key pair, keeps its secret key and returns its public key.
+    fn new_key(&self) -> Result<Word, KeyStoreError>;
+}
+
+impl<R: Rng + Send + Sync> SignerBackend for FilesystemKeyStore<R> {
+    fn new_key(&self) -> Result<Word, KeyStoreError> {
+        self.generate_key()
+    }
+}
+
+/// Serves the requests read from `reader` with the backend, writing the responses to `writer`,
+/// until `reader` is exhausted.
+///
+/// This is the signer side of the protocol. Requests that can't be decoded are answered with an
+/// error response instead of ending the connection.
+pub async fn serve_signer<B: SignerBackend>(
+    backend: &B,
+    mut reader: impl BufRead,
+    mut writer: impl Write,
+) -> std::io::Result<()> {
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+
+        let response = if line.trim() == NEW_KEY_REQUEST {
+            match backend.new_key() {
+                Ok(pub_key) => SignResponse::NewKey(pub_key),
+                Err(err) => SignResponse::Error(err.to_string()),
+            }
+        } else {
+            match SignRequest::from_line(&line) {
+                Ok(request) => {
+                    match backend.get_signature(request.pub_key, &request.signing_inputs).await {
+                        Ok(signature) => SignResponse::Signature(signature),
+                        Err(AuthenticationError::UnknownPublicKey(_)) => SignResponse::UnknownKey,
+                        Err(err) => SignResponse::Error(err.to_string()),
+                    }
+                },
+                Err(err) => SignResponse::Error(err.to_string()),
+            }
+        };
+
+        writeln!(writer, "{}", response.to_line())?;
+        writer.flush()?;
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Writes the request line and reads the response line.
+async fn exchange(
+    writer: &mut (impl AsyncWrite + Unpin),
+    reader: &mut (impl AsyncBufRead + Unpin),
+    request: &str,
+) -> Result<String, KeyStoreError> {
+    writer
+        .write_all(format!("{request}\n").as_bytes())
+        .await
+        .map_err(signer_io_error)?;
+    writer.flush().await.map_err(signer_io_error)?;
+
+    let mut line = String::new();
+    if reader.read_line(&mut line).await.map_err(signer_io_error)? == 0 {
+        return Err(KeyStoreError::StorageError(
+            "signer closed the connection without responding".to_string(),
+        ));
+    }
+
+    Ok(line)
+}
+
+fn signer_io_error(err: std::io::Error) -> KeyStoreError {
+    KeyStoreError::StorageError(format!("error communicating with signer: {err}"))
+}
+
+fn decode_hex<T: Deserializable>(value: &str) -> Result<T, KeyStoreError> {
+    let bytes = hex::decode(value)
+        .map_err(|err| KeyStoreError::DecodingError(format!("error decoding hex: {err}")))?;
+
+    T::read_from_bytes(&bytes)
+        .map_err(|err| KeyStoreError::DecodingError(format!("error deserializing: {err}")))
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+    use std::io::Cursor;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    use miden_objects::account::AuthSecretKey;
+    use miden_objects::crypto::dsa::rpo_falcon512::SecretKey;
+    use miden_objects::{Felt, Word};
+    use miden_tx::AuthenticationError;
+    use miden_tx::auth::{SigningInputs, TransactionAuthenticator};
+    use uuid::Uuid;
+
+    use super::{ExternalSigner, SignRequest, SignResponse, SignerTransport, serve_signer};
+    use crate::keystore::FilesystemKeyStore;
+
+    #[test]
+    fn messages_round_trip() {
+        let request = SignRequest {
+            pub_key: Word::from([Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)]),
+            signing_inputs: SigningInputs::Blind(Word::from([Felt::new(5); 4])),
+        };
+        let decoded = SignRequest::from_line(&request.to_line()).unwrap();
+        assert_eq!(decoded.pub_key, request.pub_key);
+        assert_eq!(decoded.signing_inputs.to_commitment(), request.signing_inputs.to_commitment());
+
+        for response in [
+            SignResponse::Signature(vec![Felt::new(7), Felt::new(8)]),
+            SignResponse::NewKey(Word::from([Felt::new(6); 4])),
+            SignResponse::UnknownKey,
+            SignResponse::Error("signing\nfailed".to_string()),
+        ] {
+            let expected = match &response {
+                SignResponse::Error(message) => SignResponse::Error(message.replace('\n', " ")),
+                response => response.clone(),
+            };
+            assert_eq!(SignResponse::from_line(&response.to_line()).unwrap(), expected);
+        }
+
+        assert!(SignRequest::from_line("SIGN 0x01").is_err());
+        assert!(SignResponse::from_line("MAYBE").is_err());
+    }
+
+    #[tokio::test]
+    async fn serve_signer_answers_requests() {
+        let keystore =
+            FilesystemKeyStore::new(temp_dir().join(Uuid::new_v4().to_string())).unwrap();
+        let key = SecretKey::new();
+        let pub_key = Word::from(key.public_key());
+        keystore.add_key(&AuthSecretKey::RpoFalcon512(key)).unwrap();
+
+        let signing_inputs = SigningInputs::Blind(Word::from([Felt::new(5); 4]));
+        let unknown_key = Word::from([Felt::new(9); 4]);
+        let requests = [
+            SignRequest {
+                pub_key,
+                signing_inputs: signing_inputs.clone(),
+            }
+            .to_line(),
+            SignRequest { pub_key: unknown_key, signing_inputs }.to_line(),
+            "garbage".to_string(),
+            "NEW_KEY".to_string(),
+        ]
+        .join("\n");
+
+        let mut output = Vec::new();
+        serve_signer(&keystore, Cursor::new(requests), &mut output).await.unwrap();
+
+        let responses = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| SignResponse::from_line(line).unwrap())
+            .collect::<Vec<_>>();
+        assert!(
+            matches!(&responses[0], SignResponse::Signature(signature) if !signature.is_empty())
+        );
+        assert_eq!(responses[1], SignResponse::UnknownKey);
+        assert!(matches!(responses[2], SignResponse::Error(_)));
+
+        // New keys are generated and kept by the signer
+        let SignResponse::NewKey(new_key) = responses[3] else {
+            panic!("expected a new key, got {:?}", responses[3]);
+        };
+        assert!(keystore.get_key(new_key).unwrap().is_some());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn signs_over_unix_socket() {
+        use std::os::unix::net::UnixListener;
+
+        let keystore =
+            FilesystemKeyStore::new(temp_dir().join(Uuid::new_v4().to_string())).unwrap();
+        let key = SecretKey::new();
+        let pub_key = Word::from(key.public_key());
+        keystore.add_key(&AuthSecretKey::RpoFalcon512(key)).unwrap();
+
+        let socket_path = temp_dir().join(format!("{}.sock", Uuid::new_v4()));
+        let listener = UnixListener::bind(&socket_path).unwrap();
+
+        // Serve the four connections the signer opens below
+        let runtime = tokio::runtime::Handle::current();
+        let server = tokio::task::spawn_blocking(move || {
+            for _ in 0..4 {
+                let (stream, _) = listener.accept().unwrap();
+                let reader = std::io::BufReader::new(stream.try_clone().unwrap());
+                runtime.block_on(serve_signer(&keystore, reader, stream)).unwrap();
+            }
+        });
+
+        let signer = ExternalSigner::new(SignerTransport::UnixSocket(socket_path));
+        let signing_inputs = SigningInputs::Blind(Word::from([Felt::new(5); 4]));
+
+        let signature = signer.get_signature(pub_key, &signing_inputs).await.unwrap();
+        assert!(!signature.is_empty());
+
+        let result = signer.get_signature(Word::from([Felt::new(9); 4]), &signing_inputs).await;
+        assert!(matches!(result, Err(AuthenticationError::UnknownPublicKey(_))));
+
+        let new_key = signer.new_key().await.unwrap();
+        assert!(!signer.get_signature(new_key, &signing_inputs).await.unwrap().is_empty());
+
+        server.await.unwrap();
+    }
+}
@@ -1,25 +1,38 @@
 use alloc::string::String;
 use std::fs::OpenOptions;
 use std::hash::{DefaultHasher, Hash, Hasher};
-use std::io::{BufRead, BufReader, BufWriter, Write};
-use std::path::PathBuf;
+use std::io::{BufWriter, Write};
+#[cfg(unix)]
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::path::{Path, PathBuf};
 use std::string::ToString;
 use std::sync::{Arc, RwLock};
 use std::vec::Vec;
 
 use miden_objects::account::AuthSecretKey;
+use miden_objects::crypto::dsa::rpo_falcon512::SecretKey;
 use miden_objects::{Felt, Word};
 use miden_tx::AuthenticationError;
 use miden_tx::auth::{SigningInputs, TransactionAuthenticator};
 use miden_tx::utils::{Deserializable, Serializable};
 use rand::{Rng, SeedableRng};
+use zeroize::Zeroizing;
 
-use super::KeyStoreError;
+use super::encryption::KeyEncryption;
+use super::{KeyStoreError, auth_pub_key, decode_key_hex, encode_key_hex};
+
+/// Name of the file in the keys directory where the wallet mnemonic phrase is stored.
+const MNEMONIC_FILENAME: &str = "mnemonic";
 
 /// A filesystem-based keystore that stores keys in separate files and provides transaction
 /// authentication functionality. The public key is hashed and the result is used as the filename
 /// and the contents of the file are the serialized public and secret key.
 ///
+/// By default keys are stored as plaintext hex. If the keystore is unlocked with a passphrase (see
+/// [`FilesystemKeyStore::with_passphrase`]), new keys are encrypted at rest and encrypted keys can
+/// be read back. Plaintext keys remain readable either way, and can be encrypted in place with
+/// [`FilesystemKeyStore::encrypt_plaintext_keys`].
+///
 /// The keystore requires an RNG component for generating Falcon signatures at the moment of
 /// transaction signing.
 #[derive(Debug, Clone)]
@@ -28,6 +41,8 @@ pub struct FilesystemKeyStore<R: Rng + Send + Sync> {
     rng: Arc<RwLock<R>>,
     /// The directory where the keys are stored and read from.
     keys_directory: PathBuf,
+    /// The encryption used for the key files, if the keystore was unlocked with a passphrase.
+    encryption: Option<Arc<KeyEncryption>>,
 }
 
 impl<R: Rng + Send + Sync> FilesystemKeyStore<R> {
@@ -41,65 +56,265 @@ impl<R: Rng + Send + Sync> FilesystemKeyStore<R> {
         Ok(FilesystemKeyStore {
             keys_directory,
             rng: Arc::new(RwLock::new(rng)),
+            encryption: None,
         })
     }
 
+    /// Unlocks the keystore with the given passphrase. From then on, added keys are encrypted
+    /// with it and encrypted keys are decrypted with it.
+    ///
+    /// If the keystore doesn't contain encrypted keys yet (see
+    /// [`FilesystemKeyStore::has_encrypted_keys`]), there is nothing to check the passphrase
+    /// against and any passphrase is accepted, so callers should have the user confirm it.
+    ///
+    /// # Errors
+    /// Returns [`KeyStoreError::InvalidPassphrase`] if the keystore already contains encrypted
+    /// keys and the passphrase doesn't decrypt them.
+    pub fn with_passphrase(mut self, passphrase: &str) -> Result<Self, KeyStoreError> {
+        let encryption = KeyEncryption::new(passphrase);
+
+        // Check the passphrase against one of the existing encrypted keys, if any, so that a
+        // wrong passphrase is reported upfront instead of when signing
+        if let Some(contents) = self.first_encrypted_key_file()? {
+            encryption.decrypt(&contents)?;
+        }
+
+        self.encryption = Some(Arc::new(encryption));
+        Ok(self)
+    }
+
+    /// Returns `true` if the keystore contains at least one key encrypted with a passphrase.
+    pub fn has_encrypted_keys(&self) -> Result<bool, KeyStoreError> {
+        Ok(self.first_encrypted_key_file()?.is_some())
+    }
+
+    /// Returns `true` if the keystore was unlocked with a passphrase.
+    pub fn is_encrypted(&self) -> bool {
+        self.encryption.is_some()
+    }
+
     /// Adds a secret key to the keystore.
     pub fn add_key(&self, key: &AuthSecretKey) -> Result<(), KeyStoreError> {
-        let pub_key = match key {
-            AuthSecretKey::RpoFalcon512(k) => Word::from(k.public_key()),
-        };
+        let filename = hash_pub_key(auth_pub_key(key));
+
+        let file_path = self.keys_directory.join(filename);
+        write_key_file(&file_path, &self.encode_key(key)?)
+    }
 
+    /// Retrieves a secret key from the keystore given its public key.
+    pub fn get_key(&self, pub_key: Word) -> Result<Option<AuthSecretKey>, KeyStoreError> {
         let filename = hash_pub_key(pub_key);
 
         let file_path = self.keys_directory.join(filename);
-        let file = OpenOptions::new()
-            .write(true)
-            .create(true)
-            .truncate(true)
-            .open(file_path)
-            .map_err(|err| {
-                KeyStoreError::StorageError(format!("error opening secret key file: {err:?}"))
-            })?;
+        if !file_path.exists() {
+            return Ok(None);
+        }
 
-        let mut writer = BufWriter::new(file);
-        let key_pair_hex = hex::encode(key.to_bytes());
-        writer.write_all(key_pair_hex.as_bytes()).map_err(|err| {
-            KeyStoreError::StorageError(format!("error writing secret key file: {err:?}"))
+        let contents = read_key_file(&file_path)?;
+        Ok(Some(self.decode_key(&contents)?))
+    }
+
+    /// Returns the public keys of all the secret keys in the keystore.
+    pub fn list_keys(&self) -> Result<Vec<Word>, KeyStoreError> {
+        self.key_files()?
+            .iter()
+            .map(|file_path| {
+                let contents = read_key_file(file_path)?;
+                Ok(auth_pub_key(&self.decode_key(&contents)?))
+            })
+            .collect()
+    }
+
+    /// Removes the secret key that corresponds to the given public key from the keystore. Returns
+    /// `true` if the key was in the keystore.
+    pub fn remove_key(&self, pub_key: Word) -> Result<bool, KeyStoreError> {
+        let file_path = self.keys_directory.join(hash_pub_key(pub_key));
+        if !file_path.exists() {
+            return Ok(false);
+        }
+
+        std::fs::remove_file(file_path).map_err(|err| {
+            KeyStoreError::StorageError(format!("error removing secret key file: {err:?}"))
         })?;
 
-        Ok(())
+        Ok(true)
     }
 
-    /// Retrieves a secret key from the keystore given its public key.
-    pub fn get_key(&self, pub_key: Word) -> Result<Option<AuthSecretKey>, KeyStoreError> {
-        let filename = hash_pub_key(pub_key);
+    /// Generates a new Falcon key pair with the keystore's RNG, stores its secret key and returns
+    /// its public key.
+    pub fn generate_key(&self) -> Result<Word, KeyStoreError> {
+        let key = {
+            let mut rng = self.rng.write().expect("poisoned lock");
+            AuthSecretKey::RpoFalcon512(SecretKey::with_rng(&mut *rng))
+        };
+        self.add_key(&key)?;
 
-        let file_path = self.keys_directory.join(filename);
+        Ok(auth_pub_key(&key))
+    }
+
+    /// Exports the secret key that corresponds to the given public key as a hex string, which can
+    /// be imported back with [`FilesystemKeyStore::import_key`].
+    ///
+    /// The exported key is never encrypted, even if the keystore is.
+    pub fn export_key(&self, pub_key: Word) -> Result<Option<String>, KeyStoreError> {
+        Ok(self.get_key(pub_key)?.as_ref().map(encode_key_hex))
+    }
+
+    /// Imports a secret key exported with [`FilesystemKeyStore::export_key`] and returns its
+    /// public key.
+    pub fn import_key(&self, key_hex: &str) -> Result<Word, KeyStoreError> {
+        let key = decode_key_hex(key_hex)?;
+        self.add_key(&key)?;
+
+        Ok(auth_pub_key(&key))
+    }
+
+    /// Stores the phrase of the mnemonic from which wallet accounts are derived (see
+    /// [`WalletMnemonic`](crate::account::mnemonic::WalletMnemonic)), replacing any previous one.
+    ///
+    /// The phrase is stored as is, without being validated, and is encrypted with the keystore's
+    /// passphrase the same way keys are.
+    pub fn set_mnemonic_phrase(&self, phrase: &str) -> Result<(), KeyStoreError> {
+        let contents = match &self.encryption {
+            Some(encryption) => encryption.encrypt(phrase.as_bytes())?,
+            None => phrase.as_bytes().to_vec(),
+        };
+
+        write_key_file(&self.keys_directory.join(MNEMONIC_FILENAME), &contents)
+    }
+
+    /// Retrieves the mnemonic phrase stored with [`FilesystemKeyStore::set_mnemonic_phrase`], if
+    /// any.
+    pub fn get_mnemonic_phrase(&self) -> Result<Option<Zeroizing<String>>, KeyStoreError> {
+        let file_path = self.keys_directory.join(MNEMONIC_FILENAME);
         if !file_path.exists() {
             return Ok(None);
         }
 
-        let file = OpenOptions::new().read(true).open(file_path).map_err(|err| {
-            KeyStoreError::StorageError(format!("error opening secret key file: {err:?}"))
+        let contents = Zeroizing::new(read_key_file(&file_path)?);
+        let phrase = if KeyEncryption::is_encrypted(&contents) {
+            let encryption = self.encryption.as_ref().ok_or_else(|| {
+                KeyStoreError::EncryptionError(
+                    "mnemonic is encrypted but the keystore is locked".to_string(),
+                )
+            })?;
+            encryption.decrypt(&contents)?
+        } else {
+            contents
+        };
+
+        let phrase = core::str::from_utf8(&phrase).map_err(|err| {
+            KeyStoreError::DecodingError(format!("error reading mnemonic: {err:?}"))
         })?;
-        let mut reader = BufReader::new(file);
-        let mut key_pair_hex = String::new();
-        reader.read_line(&mut key_pair_hex).map_err(|err| {
-            KeyStoreError::StorageError(format!("error reading secret key file: {err:?}"))
+        Ok(Some(Zeroizing::new(phrase.to_string())))
+    }
+
+    /// Encrypts every plaintext key in the keystore with the keystore's passphrase and returns
+    /// the number of keys that were encrypted. Keys that are already encrypted are left as is.
+    ///
+    /// Each key file is replaced atomically, so an interrupted migration can be safely resumed.
+    ///
+    /// # Errors
+    /// Returns [`KeyStoreError::EncryptionError`] if the keystore wasn't unlocked with a
+    /// passphrase.
+    pub fn encrypt_plaintext_keys(&self) -> Result<usize, KeyStoreError> {
+        if self.encryption.is_none() {
+            return Err(KeyStoreError::EncryptionError(
+                "a passphrase is required to encrypt the keystore".to_string(),
+            ));
+        }
+
+        let mut encrypted_keys = 0;
+        for file_path in self.key_files()? {
+            let contents = read_key_file(&file_path)?;
+            if KeyEncryption::is_encrypted(&contents) {
+                continue;
+            }
+
+            let key = self.decode_key(&contents)?;
+            let tmp_path = file_path.with_extension("tmp");
+            write_key_file(&tmp_path, &self.encode_key(&key)?)?;
+            std::fs::rename(&tmp_path, &file_path).map_err(|err| {
+                KeyStoreError::StorageError(format!("error replacing secret key file: {err:?}"))
+            })?;
+
+            encrypted_keys += 1;
+        }
+
+        // The mnemonic isn't counted as a key, but it must not be left in plaintext either
+        if let Some(phrase) = self.get_mnemonic_phrase()? {
+            let file_path = self.keys_directory.join(MNEMONIC_FILENAME);
+            if !KeyEncryption::is_encrypted(&read_key_file(&file_path)?) {
+                self.set_mnemonic_phrase(&phrase)?;
+            }
+        }
+
+        Ok(encrypted_keys)
+    }
+
+    /// Serializes the key into the contents of its key file.
+    fn encode_key(&self, key: &AuthSecretKey) -> Result<Vec<u8>, KeyStoreError> {
+        match &self.encryption {
+            Some(encryption) => encryption.encrypt(&Zeroizing::new(key.to_bytes())),
+            None => Ok(encode_key_hex(key).into_bytes()),
+        }
+    }
+
+    /// Deserializes a key from the contents of its key file.
+    fn decode_key(&self, contents: &[u8]) -> Result<AuthSecretKey, KeyStoreError> {
+        if !KeyEncryption::is_encrypted(contents) {
+            return decode_key_hex(&String::from_utf8_lossy(contents));
+        }
+
+        let encryption = self.encryption.as_ref().ok_or_else(|| {
+            KeyStoreError::EncryptionError(
+                "secret key is encrypted but the keystore is locked".to_string(),
+            )
         })?;
+        let secret_key_bytes = encryption.decrypt(contents)?;
+
+        AuthSecretKey::read_from_bytes(secret_key_bytes.as_slice()).map_err(|err| {
+            KeyStoreError::DecodingError(format!("error reading secret key from bytes: {err:?}"))
+        })
+    }
+
+    /// Returns the contents of the first encrypted key file found in the keys directory, if any.
+    fn first_encrypted_key_file(&self) -> Result<Option<Vec<u8>>, KeyStoreError> {
+        for file_path in self.key_files()? {
+            let contents = read_key_file(&file_path)?;
+            if KeyEncryption::is_encrypted(&contents) {
+                return Ok(Some(contents));
+            }
+        }
+
+        Ok(None)
+    }
 
-        let secret_key_bytes = hex::decode(key_pair_hex.trim()).map_err(|err| {
-            KeyStoreError::DecodingError(format!("error decoding secret key hex: {err:?}"))
+    /// Returns the paths of all key files in the keys directory.
+    fn key_files(&self) -> Result<Vec<PathBuf>, KeyStoreError> {
+        let entries = std::fs::read_dir(&self.keys_directory).map_err(|err| {
+            KeyStoreError::StorageError(format!("error reading keys directory: {err:?}"))
         })?;
-        let secret_key =
-            AuthSecretKey::read_from_bytes(secret_key_bytes.as_slice()).map_err(|err| {
-                KeyStoreError::DecodingError(format!(
-                    "error reading secret key from bytes: {err:?}"
-                ))
-            })?;
 
-        Ok(Some(secret_key))
+        let mut files = Vec::new();
+        for entry in entries {
+            let path = entry
+                .map_err(|err| {
+                    KeyStoreError::StorageError(format!("error reading keys directory: {err:?}"))
+                })?
+                .path();
+
+            // Key files are named after the public key hash, so any other file is skipped
+            let is_key_file = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.parse::<u64>().is_ok());
+            if path.is_file() && is_key_file {
+                files.push(path);
+            }
+        }
+
+        Ok(files)
     }
 }
 
@@ -150,3 +365,181 @@ fn hash_pub_key(pub_key: Word) -> String {
     pub_key.hash(&mut hasher);
     hasher.finish().to_string()
 }
+
+/// Reads the full contents of a key file.
+fn read_key_file(file_path: &Path) -> Result<Vec<u8>, KeyStoreError> {
+    std::fs::read(file_path).map_err(|err| {
+        KeyStoreError::StorageError(format!("error reading secret key file: {err:?}"))
+    })
+}
+
+/// Writes the contents of a key file, replacing any previous contents. On unix, the file is only
+/// readable and writable by its owner, including when it already existed.
+fn write_key_file(file_path: &Path, contents: &[u8]) -> Result<(), KeyStoreError> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let file = options.open(file_path).map_err(|err| {
+        KeyStoreError::StorageError(format!("error opening secret key file: {err:?}"))
+    })?;
+
+    // The mode only applies to new files, so key files written by older versions are restricted
+    // when they're rewritten, for instance when a plaintext keystore is encrypted
+    #[cfg(unix)]
+    file.set_permissions(std::fs::Permissions::from_mode(0o600)).map_err(|err| {
+        KeyStoreError::StorageError(format!("error restricting secret key file: {err:?}"))
+    })?;
+
+    let mut writer = BufWriter::new(file);
+    writer.write_all(contents).map_err(|err| {
+        KeyStoreError::StorageError(format!("error writing secret key file: {err:?}"))
+    })?;
+    writer.flush().map_err(|err| {
+        KeyStoreError::StorageError(format!("error writing secret key file: {err:?}"))
+    })
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+
+    use miden_objects::Word;
+    use miden_objects::account::AuthSecretKey;
+    use miden_objects::crypto::dsa::rpo_falcon512::SecretKey;
+    use miden_tx::utils::Serializable;
+    use uuid::Uuid;
+
+    use super::FilesystemKeyStore;
+    use crate::keystore::KeyStoreError;
+
+    fn new_key() -> (Word, AuthSecretKey) {
+        let key = SecretKey::new();
+        (Word::from(key.public_key()), AuthSecretKey::RpoFalcon512(key))
+    }
+
+    fn assert_key_eq(key: Option<AuthSecretKey>, expected: &AuthSecretKey) {
+        assert_eq!(key.expect("key should be in the keystore").to_bytes(), expected.to_bytes());
+    }
+
+    #[test]
+    fn encrypted_keys_require_the_passphrase() {
+        let keys_directory = temp_dir().join(Uuid::new_v4().to_string());
+        let keystore = FilesystemKeyStore::new(keys_directory.clone())
+            .unwrap()
+            .with_passphrase("passphrase")
+            .unwrap();
+
+        let (pub_key, key) = new_key();
+        keystore.add_key(&key).unwrap();
+        assert_key_eq(keystore.get_key(pub_key).unwrap(), &key);
+
+        // The key can't be read without unlocking the keystore
+        let locked = FilesystemKeyStore::new(keys_directory.clone()).unwrap();
+        assert!(matches!(locked.get_key(pub_key), Err(KeyStoreError::EncryptionError(_))));
+
+        // Unlocking with the wrong passphrase fails upfront
+        let result = FilesystemKeyStore::new(keys_directory.clone())
+            .unwrap()
+            .with_passphrase("wrong passphrase");
+        assert!(matches!(result, Err(KeyStoreError::InvalidPassphrase)));
+
+        let unlocked = FilesystemKeyStore::new(keys_directory)
+            .unwrap()
+            .with_passphrase("passphrase")
+            .unwrap();
+        assert_key_eq(unlocked.get_key(pub_key).unwrap(), &key);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn key_files_are_only_accessible_by_the_owner() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let keys_directory = temp_dir().join(Uuid::new_v4().to_string());
+        let keystore = FilesystemKeyStore::new(keys_directory.clone()).unwrap();
+        keystore.add_key(&new_key().1).unwrap();
+
+        for entry in std::fs::read_dir(keys_directory).unwrap() {
+            let mode = entry.unwrap().metadata().unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+
+    #[test]
+    fn list_remove_export_and_import_keys() {
+        let keystore =
+            FilesystemKeyStore::new(temp_dir().join(Uuid::new_v4().to_string())).unwrap();
+
+        let (first_pub_key, first_key) = new_key();
+        let (second_pub_key, second_key) = new_key();
+        keystore.add_key(&first_key).unwrap();
+        keystore.add_key(&second_key).unwrap();
+
+        let mut pub_keys = keystore.list_keys().unwrap();
+        pub_keys.sort();
+        let mut expected = vec![first_pub_key, second_pub_key];
+        expected.sort();
+        assert_eq!(pub_keys, expected);
+
+        let exported = keystore.export_key(first_pub_key).unwrap().unwrap();
+        assert!(keystore.remove_key(first_pub_key).unwrap());
+        assert!(!keystore.remove_key(first_pub_key).unwrap());
+        assert!(keystore.get_key(first_pub_key).unwrap().is_none());
+        assert_eq!(keystore.list_keys().unwrap(), vec![second_pub_key]);
+        assert!(keystore.export_key(first_pub_key).unwrap().is_none());
+
+        // The exported key can be imported into a different, encrypted keystore
+        let other = FilesystemKeyStore::new(temp_dir().join(Uuid::new_v4().to_string()))
+            .unwrap()
+            .with_passphrase("passphrase")
+            .unwrap();
+        assert_eq!(other.import_key(&exported).unwrap(), first_pub_key);
+        assert_key_eq(other.get_key(first_pub_key).unwrap(), &first_key);
+    }
+
+    #[test]
+    fn plaintext_keys_are_migrated() {
+        let keys_directory = temp_dir().join(Uuid::new_v4().to_string());
+        let plaintext = FilesystemKeyStore::new(keys_directory.clone()).unwrap();
+
+        let keys = [new_key(), new_key()];
+        for (_, key) in &keys {
+            plaintext.add_key(key).unwrap();
+        }
+
+        let keystore = plaintext.with_passphrase("passphrase").unwrap();
+        assert_eq!(keystore.encrypt_plaintext_keys().unwrap(), 2);
+        // Running the migration again is a no-op
+        assert_eq!(keystore.encrypt_plaintext_keys().unwrap(), 0);
+
+        let locked = FilesystemKeyStore::new(keys_directory).unwrap();
+        for (pub_key, key) in keys {
+            assert_key_eq(keystore.get_key(pub_key).unwrap(), &key);
+            assert!(locked.get_key(pub_key).is_err());
+        }
+    }
+
+    #[test]
+    fn mnemonic_is_stored_and_encrypted() {
+        let keys_directory = temp_dir().join(Uuid::new_v4().to_string());
+        let plaintext = FilesystemKeyStore::new(keys_directory.clone()).unwrap();
+        assert!(plaintext.get_mnemonic_phrase().unwrap().is_none());
+
+        let phrase = "legal winner thank year wave sausage worth useful legal winner thank yellow";
+        plaintext.set_mnemonic_phrase(phrase).unwrap();
+        assert_eq!(plaintext.get_mnemonic_phrase().unwrap().unwrap().as_str(), phrase);
+        // The mnemonic file is not listed as a key
+        assert!(plaintext.list_keys().unwrap().is_empty());
+
+        let keystore = plaintext.with_passphrase("passphrase").unwrap();
+        assert_eq!(keystore.encrypt_plaintext_keys().unwrap(), 0);
+        assert_eq!(keystore.get_mnemonic_phrase().unwrap().unwrap().as_str(), phrase);
+
+        let locked = FilesystemKeyStore::new(keys_directory).unwrap();
+        assert!(matches!(locked.get_mnemonic_phrase(), Err(KeyStoreError::EncryptionError(_))));
+    }
+}
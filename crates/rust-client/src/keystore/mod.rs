@@ -1,5 +1,8 @@
 use alloc::string::String;
 
+use miden_objects::Word;
+use miden_objects::account::AuthSecretKey;
+use miden_tx::utils::{Deserializable, Serializable};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -8,8 +11,18 @@ pub enum KeyStoreError {
     StorageError(String),
     #[error("decoding error: {0}")]
     DecodingError(String),
+    #[error("encryption error: {0}")]
+    EncryptionError(String),
+    #[error("invalid keystore passphrase")]
+    InvalidPassphrase,
 }
 
+#[cfg(feature = "std")]
+mod encryption;
+#[cfg(feature = "std")]
+pub mod external_signer;
+#[cfg(feature = "std")]
+pub use external_signer::{ExternalSigner, SignerTransport};
 #[cfg(feature = "std")]
 mod fs_keystore;
 #[cfg(feature = "std")]
@@ -19,3 +32,29 @@ pub use fs_keystore::FilesystemKeyStore;
 mod web_keystore;
 #[cfg(feature = "idxdb")]
 pub use web_keystore::WebKeyStore;
+
+// HELPERS
+// ================================================================================================
+
+/// Returns the public key that identifies the given secret key in a keystore.
+pub(crate) fn auth_pub_key(key: &AuthSecretKey) -> Word {
+    match key {
+        AuthSecretKey::RpoFalcon512(k) => Word::from(k.public_key()),
+    }
+}
+
+/// Encodes a secret key as the hex string used to export and import keys.
+pub(crate) fn encode_key_hex(key: &AuthSecretKey) -> String {
+    hex::encode(key.to_bytes())
+}
+
+/// Decodes a secret key from the hex string used to export and import keys.
+pub(crate) fn decode_key_hex(key_hex: &str) -> Result<AuthSecretKey, KeyStoreError> {
+    let secret_key_bytes = hex::decode(key_hex.trim()).map_err(|err| {
+        KeyStoreError::DecodingError(format!("error decoding secret key hex: {err:?}"))
+    })?;
+
+    AuthSecretKey::read_from_bytes(secret_key_bytes.as_slice()).map_err(|err| {
+        KeyStoreError::DecodingError(format!("error reading secret key from bytes: {err:?}"))
+    })
+}
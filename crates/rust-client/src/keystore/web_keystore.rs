@@ -1,14 +1,19 @@
-use alloc::string::ToString;
+use alloc::string::{String, ToString};
 use alloc::sync::Arc;
 use alloc::vec::Vec;
 
-use miden_lib::utils::{Deserializable, Serializable};
+use miden_lib::utils::Deserializable;
 use miden_tx::auth::SigningInputs;
 use rand::Rng;
 
-use super::KeyStoreError;
+use super::{KeyStoreError, auth_pub_key, decode_key_hex, encode_key_hex};
 use crate::auth::{AuthSecretKey, TransactionAuthenticator};
-use crate::store::web_store::account::utils::{get_account_auth_by_pub_key, insert_account_auth};
+use crate::store::web_store::account::utils::{
+    get_account_auth_by_pub_key,
+    get_account_auth_pub_keys,
+    insert_account_auth,
+    remove_account_auth,
+};
 use crate::utils::RwLock;
 use crate::{AuthenticationError, Felt, Word};
 
@@ -27,10 +32,8 @@ impl<R: Rng> WebKeyStore<R> {
     }
 
     pub async fn add_key(&self, key: &AuthSecretKey) -> Result<(), KeyStoreError> {
-        let pub_key = match &key {
-            AuthSecretKey::RpoFalcon512(k) => Word::from(k.public_key()).to_hex(),
-        };
-        let secret_key_hex = hex::encode(key.to_bytes());
+        let pub_key = auth_pub_key(key).to_hex();
+        let secret_key_hex = encode_key_hex(key);
 
         insert_account_auth(pub_key, secret_key_hex).await.map_err(|_| {
             KeyStoreError::StorageError("Failed to insert item into local storage".to_string())
@@ -55,6 +58,52 @@ impl<R: Rng> WebKeyStore<R> {
 
         Ok(Some(secret_key))
     }
+
+    /// Returns the public keys of all the secret keys in the keystore.
+    pub async fn list_keys(&self) -> Result<Vec<Word>, KeyStoreError> {
+        let pub_keys = get_account_auth_pub_keys().await.map_err(|err| {
+            KeyStoreError::StorageError(format!("failed to get items from local storage: {err:?}"))
+        })?;
+
+        pub_keys
+            .iter()
+            .map(|pub_key| {
+                Word::try_from(pub_key.as_str()).map_err(|err| {
+                    KeyStoreError::DecodingError(format!("error decoding public key: {err:?}"))
+                })
+            })
+            .collect()
+    }
+
+    /// Removes the secret key that corresponds to the given public key from the keystore. Returns
+    /// `true` if the key was in the keystore.
+    pub async fn remove_key(&self, pub_key: Word) -> Result<bool, KeyStoreError> {
+        if !self.list_keys().await?.contains(&pub_key) {
+            return Ok(false);
+        }
+
+        remove_account_auth(pub_key.to_hex()).await.map_err(|err| {
+            KeyStoreError::StorageError(format!(
+                "failed to remove item from local storage: {err:?}"
+            ))
+        })?;
+
+        Ok(true)
+    }
+
+    /// Exports the secret key that corresponds to the given public key as a hex string, which can
+    /// be imported back with [`WebKeyStore::import_key`].
+    pub async fn export_key(&self, pub_key: Word) -> Result<Option<String>, KeyStoreError> {
+        Ok(self.get_key(pub_key).await?.as_ref().map(encode_key_hex))
+    }
+
+    /// Imports a secret key exported with [`WebKeyStore::export_key`] and returns its public key.
+    pub async fn import_key(&self, key_hex: &str) -> Result<Word, KeyStoreError> {
+        let key = decode_key_hex(key_hex)?;
+        self.add_key(&key).await?;
+
+        Ok(auth_pub_key(&key))
+    }
 }
 
 impl<R: Rng> TransactionAuthenticator for WebKeyStore<R> {
@@ -192,7 +192,7 @@ pub mod block {
 /// network. It re-exports commonly used types and random number generators like `FeltRng` from
 /// the `miden_objects` crate.
 pub mod crypto {
-    pub use miden_objects::crypto::dsa::rpo_falcon512::SecretKey;
+    pub use miden_objects::crypto::dsa::rpo_falcon512::{PublicKey, SecretKey};
     pub use miden_objects::crypto::hash::rpo::Rpo256;
     pub use miden_objects::crypto::merkle::{
         InOrderIndex,
@@ -227,17 +227,25 @@ pub mod testing {
     pub use crate::test_utils::*;
 }
 
+use alloc::collections::BTreeMap;
 use alloc::sync::Arc;
+use alloc::vec::Vec;
 
 pub use miden_lib::utils::ScriptBuilder;
+use miden_objects::Word;
 use miden_objects::block::BlockNumber;
 use miden_objects::crypto::rand::FeltRng;
 use miden_objects::note::{NoteId, NoteInclusionProof};
 use miden_tx::LocalTransactionProver;
 use miden_tx::auth::TransactionAuthenticator;
+#[cfg(feature = "tonic")]
+use note::ParallelScreening;
+use note::{NoteRelevanceChecker, NoteScreeningCache};
 use rand::RngCore;
 use rpc::NodeRpcClient;
 use store::Store;
+use sync::{SyncCheckpoint, SyncListener};
+use transaction::TransactionRetryPolicy;
 
 use crate::rpc::RpcError;
 use crate::rpc::domain::note::FetchedNote;
@@ -275,6 +283,20 @@ pub struct Client<AUTH> {
     max_block_number_delta: Option<u32>,
     /// Mixer operator url
     mixer_url: alloc::string::String,
+    /// Policy used to retry discarded transactions.
+    tx_retry_policy: Option<TransactionRetryPolicy>,
+    /// Checkpoint the first sync starts from instead of genesis.
+    start_checkpoint: Option<SyncCheckpoint>,
+    /// Listeners notified of the progress and the changes of each sync.
+    sync_listeners: Vec<Arc<dyn SyncListener>>,
+    /// Checkers used instead of executing notes to check their relevance, by the root of the
+    /// script they decode.
+    note_relevance_checkers: BTreeMap<Word, Arc<dyn NoteRelevanceChecker>>,
+    /// Results of executing notes while screening them, shared by the client's note screeners.
+    note_screening_cache: Arc<NoteScreeningCache>,
+    /// How the client's note screeners execute notes in parallel, if they do.
+    #[cfg(feature = "tonic")]
+    parallel_note_screening: Option<ParallelScreening<AUTH>>,
 }
 
 /// Construction and access methods.
@@ -334,6 +356,13 @@ where
             tx_graceful_blocks,
             max_block_number_delta,
             mixer_url,
+            tx_retry_policy: None,
+            start_checkpoint: None,
+            sync_listeners: Vec::new(),
+            note_relevance_checkers: BTreeMap::new(),
+            note_screening_cache: Arc::new(NoteScreeningCache::new()),
+            #[cfg(feature = "tonic")]
+            parallel_note_screening: None,
         })
     }
 
@@ -347,6 +376,30 @@ where
         ScriptBuilder::new(self.in_debug_mode())
     }
 
+    /// Sets the policy used by `Client::retry_discarded_transactions`, which uses the default
+    /// policy if it's `None`. Discarded transactions are only retried when that method is called:
+    /// syncing never retries them on its own.
+    pub fn set_transaction_retry_policy(&mut self, policy: Option<TransactionRetryPolicy>) {
+        self.tx_retry_policy = policy;
+    }
+
+    /// Returns the policy used to retry discarded transactions, if one was set.
+    pub fn transaction_retry_policy(&self) -> Option<TransactionRetryPolicy> {
+        self.tx_retry_policy
+    }
+
+    /// Sets the checkpoint the client starts from if it never synced. The next sync of such a
+    /// client calls `Client::start_from_checkpoint` before syncing, so it begins right after the
+    /// checkpoint block instead of genesis. Clients that already synced ignore it.
+    pub fn set_start_checkpoint(&mut self, checkpoint: Option<SyncCheckpoint>) {
+        self.start_checkpoint = checkpoint;
+    }
+
+    /// Returns the checkpoint the client starts from if it never synced, if one was set.
+    pub fn start_checkpoint(&self) -> Option<SyncCheckpoint> {
+        self.start_checkpoint
+    }
+
     /// Returns a reference to the client's random number generator. This can be used to generate
     /// randomness for various purposes such as serial numbers, keys, etc.
     pub fn rng(&mut self) -> &mut ClientRng {
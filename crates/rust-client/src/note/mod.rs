@@ -14,6 +14,7 @@
 //! - Compile note scripts from source code with `compile_note_script`.
 //! - Retrieve an input note by a prefix of its ID using the helper function
 //!   [`get_input_note_with_id_prefix`].
+//! - List the swaps created by the client's accounts and cancel open ones.
 //!
 //! ## Example
 //!
@@ -63,12 +64,15 @@ use alloc::vec::Vec;
 use miden_objects::account::AccountId;
 use miden_tx::auth::TransactionAuthenticator;
 
-use crate::store::{InputNoteRecord, NoteFilter, OutputNoteRecord};
+use crate::store::{InputNoteRecord, NoteFilter, NoteRecordError, OutputNoteRecord};
 use crate::{Client, ClientError, IdPrefixFetchError};
 
 mod import;
 mod note_screener;
 mod note_update_tracker;
+mod relevance;
+mod screening_cache;
+mod swap;
 
 // RE-EXPORTS
 // ================================================================================================
@@ -95,6 +99,8 @@ pub use miden_objects::note::{
     NoteType,
     Nullifier,
 };
+#[cfg(feature = "tonic")]
+pub(crate) use note_screener::ParallelScreening;
 pub use note_screener::{NoteConsumability, NoteRelevance, NoteScreener, NoteScreenerError};
 pub use note_update_tracker::{
     InputNoteUpdate,
@@ -102,6 +108,10 @@ pub use note_update_tracker::{
     NoteUpdateType,
     OutputNoteUpdate,
 };
+pub use relevance::{NoteRelevanceCheck, NoteRelevanceChecker};
+pub use screening_cache::NoteScreeningCache;
+pub use swap::{SwapOrder, SwapStatus};
+pub(crate) use swap::{decode_swap_inputs, is_reclaimed};
 
 /// Note retrieval methods.
 impl<AUTH> Client<AUTH>
@@ -137,13 +147,15 @@ where
     ) -> Result<Vec<(InputNoteRecord, Vec<NoteConsumability>)>, ClientError> {
         let committed_notes = self.store.get_input_notes(NoteFilter::Committed).await?;
 
-        let note_screener = NoteScreener::new(self.store.clone(), self.authenticator.clone());
+        let notes = committed_notes
+            .iter()
+            .map(TryInto::try_into)
+            .collect::<Result<Vec<Note>, NoteRecordError>>()?;
+        let note_relevances = self.note_screener().check_relevance_batch(&notes).await?;
 
         let mut relevant_notes = Vec::new();
-        for input_note in committed_notes {
-            let mut account_relevance =
-                note_screener.check_relevance(&input_note.clone().try_into()?).await?;
-
+        let screened_notes = committed_notes.into_iter().zip(note_relevances);
+        for (input_note, mut account_relevance) in screened_notes {
             if let Some(account_id) = account_id {
                 account_relevance.retain(|(id, _)| *id == account_id);
             }
@@ -167,7 +179,7 @@ where
         &self,
         note: InputNoteRecord,
     ) -> Result<Vec<NoteConsumability>, ClientError> {
-        let note_screener = NoteScreener::new(self.store.clone(), self.authenticator.clone());
+        let note_screener = self.note_screener();
         note_screener
             .check_relevance(&note.clone().try_into()?)
             .await
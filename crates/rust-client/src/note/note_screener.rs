@@ -1,4 +1,8 @@
 use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+#[cfg(feature = "tonic")]
+use alloc::string::ToString;
 use alloc::sync::Arc;
 use alloc::vec::Vec;
 use core::fmt;
@@ -6,9 +10,10 @@ use core::fmt;
 use miden_lib::account::interface::AccountInterface;
 use miden_lib::note::well_known_note::WellKnownNote;
 use miden_objects::account::{Account, AccountId};
+use miden_objects::block::BlockNumber;
 use miden_objects::note::{Note, NoteId};
 use miden_objects::transaction::InputNote;
-use miden_objects::{AccountError, AssetError};
+use miden_objects::{AccountError, AssetError, Word};
 use miden_tx::auth::TransactionAuthenticator;
 use miden_tx::{
     NoteCheckerError,
@@ -18,7 +23,10 @@ use miden_tx::{
 };
 use thiserror::Error;
 use tonic::async_trait;
+use tracing::{instrument, warn};
 
+use super::screening_cache::ScreeningResult;
+use super::{NoteRelevanceCheck, NoteRelevanceChecker, NoteScreeningCache};
 use crate::ClientError;
 use crate::rpc::domain::note::CommittedNote;
 use crate::store::data_store::ClientDataStore;
@@ -56,11 +64,27 @@ impl fmt::Display for NoteRelevance {
 /// tracked in the provided `store`. This can be derived in a number of ways, such as looking
 /// at the combination of script root and note inputs. For example, a P2ID note is relevant
 /// for a specific account ID if this ID is its first note input.
+///
+/// Notes whose script has a registered [`NoteRelevanceChecker`] are checked by it first, and only
+/// executed if it can't tell whether the account can consume them. The results of executing notes
+/// are kept in a [`NoteScreeningCache`], by account commitment and note ID, so they're only
+/// computed again once the account changes. Results that depend on the chain height are also
+/// computed again once the client syncs to another block.
+///
+/// With the `tonic` feature, [`NoteScreener::with_parallelism`] executes the notes on tokio's
+/// blocking threads when the screener is used within a tokio runtime.
 pub struct NoteScreener<AUTH> {
     /// A reference to the client's store, used to fetch necessary data to check consumability.
     store: Arc<dyn Store>,
     /// A reference to the transaction authenticator
     authenticator: Option<Arc<AUTH>>,
+    /// Checkers used instead of executing the notes, by the root of the script they decode.
+    relevance_checkers: BTreeMap<Word, Arc<dyn NoteRelevanceChecker>>,
+    /// Results of the notes executed by this screener and the others sharing the cache.
+    cache: Arc<NoteScreeningCache>,
+    /// How notes are executed in parallel. Without it, they're executed one at a time.
+    #[cfg(feature = "tonic")]
+    parallel_screening: Option<ParallelScreening<AUTH>>,
 }
 
 impl<AUTH> NoteScreener<AUTH>
@@ -68,7 +92,44 @@ where
     AUTH: TransactionAuthenticator + Sync,
 {
     pub fn new(store: Arc<dyn Store>, authenticator: Option<Arc<AUTH>>) -> Self {
-        Self { store, authenticator }
+        Self {
+            store,
+            authenticator,
+            relevance_checkers: BTreeMap::new(),
+            cache: Arc::new(NoteScreeningCache::new()),
+            #[cfg(feature = "tonic")]
+            parallel_screening: None,
+        }
+    }
+
+    /// Sets the checkers used instead of executing the notes, by the root of the script they
+    /// decode.
+    #[must_use]
+    pub fn with_relevance_checkers(
+        mut self,
+        relevance_checkers: BTreeMap<Word, Arc<dyn NoteRelevanceChecker>>,
+    ) -> Self {
+        self.relevance_checkers = relevance_checkers;
+        self
+    }
+
+    /// Sets the cache where the results of executing notes are kept, which can be shared between
+    /// screeners.
+    #[must_use]
+    pub fn with_cache(mut self, cache: Arc<NoteScreeningCache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Sets how notes are executed in parallel, as configured on the client.
+    #[cfg(feature = "tonic")]
+    #[must_use]
+    pub(crate) fn with_parallel_screening(
+        mut self,
+        parallel_screening: Option<ParallelScreening<AUTH>>,
+    ) -> Self {
+        self.parallel_screening = parallel_screening;
+        self
     }
 
     /// Returns a vector of tuples describing the relevance of the provided note to the
@@ -79,110 +140,386 @@ where
         &self,
         note: &Note,
     ) -> Result<Vec<NoteConsumability>, NoteScreenerError> {
-        let mut note_relevances = vec![];
+        let mut note_relevances = self.check_relevance_batch(core::slice::from_ref(note)).await?;
+        Ok(note_relevances.pop().unwrap_or_default())
+    }
+
+    /// Returns the relevance of each of the provided notes to the accounts monitored by this
+    /// screener, in the same order as the notes.
+    ///
+    /// The notes that have to be executed are executed for all the accounts at once, in parallel
+    /// if the screener was set up to.
+    #[instrument(level = "debug", skip_all, fields(notes = notes.len()))]
+    pub async fn check_relevance_batch(
+        &self,
+        notes: &[Note],
+    ) -> Result<Vec<Vec<NoteConsumability>>, NoteScreenerError> {
+        let mut accounts = Vec::new();
         for id in self.store.get_account_ids().await? {
             let account_record = self
                 .store
                 .get_account(id)
                 .await?
                 .ok_or(NoteScreenerError::AccountDataNotFound(id))?;
+            accounts.push(account_record.account().clone());
+        }
+        let block_num = self.store.get_sync_height().await?;
 
-            match self.check_standard_consumability(account_record.account(), note).await {
-                Ok(Some(relevance)) => {
-                    note_relevances.push((id, relevance));
-                },
-                Ok(None) => {
-                    // The note might be consumable after a certain block height if the note is
-                    // p2ide
-                    let script_root = note.script().root();
+        // The relevance of each note to each account, in the order of the accounts
+        let mut relevances: Vec<Vec<Option<NoteRelevance>>> =
+            vec![vec![None; accounts.len()]; notes.len()];
+        let mut pending_checks = Vec::new();
 
-                    if script_root == WellKnownNote::P2IDE.script_root()
-                        && let Some(relevance) = Self::check_p2ide_recall_consumability(note, &id)?
-                    {
-                        note_relevances.push((id, relevance));
+        for (note_index, note) in notes.iter().enumerate() {
+            let relevance_checker = self.relevance_checkers.get(&note.script().root());
+
+            for (account_index, account) in accounts.iter().enumerate() {
+                if let Some(checker) = relevance_checker {
+                    match checker.check_relevance(note, account) {
+                        Ok(NoteRelevanceCheck::Consumable(relevance)) => {
+                            relevances[note_index][account_index] = Some(relevance);
+                            continue;
+                        },
+                        Ok(NoteRelevanceCheck::NotConsumable) => continue,
+                        // Malformed notes are not relevant, as with the generic check
+                        Err(err) => {
+                            warn!(
+                                "Relevance checker failed for note {} and account {}: {err}",
+                                note.id(),
+                                account.id()
+                            );
+                            continue;
+                        },
+                        Ok(NoteRelevanceCheck::Unknown) => {},
                     }
+                }
+
+                match self.cache.get(account.commitment(), note.id(), block_num) {
+                    Some(relevance) => relevances[note_index][account_index] = relevance,
+                    None => pending_checks.push((note_index, account_index)),
+                }
+            }
+        }
+
+        let results = self.check_consumability(&accounts, notes, &pending_checks, block_num).await;
+
+        for ((note_index, account_index), result) in pending_checks.into_iter().zip(results) {
+            let (account, note) = (&accounts[account_index], &notes[note_index]);
+            match result {
+                Ok(result) => {
+                    self.cache.insert(account.commitment(), note.id(), result);
+                    relevances[note_index][account_index] = result.relevance;
                 },
-                // If an error occurs while checking consumability, we count it as not relevant for
-                // that account
-                Err(_) => {},
+                // Failed checks aren't cached, so that the next screening tries them again
+                Err(err) => warn!(
+                    "Failed to check whether account {} can consume note {}: {err}",
+                    account.id(),
+                    note.id()
+                ),
             }
         }
 
-        Ok(note_relevances)
+        Ok(relevances
+            .into_iter()
+            .map(|note_relevances| {
+                accounts
+                    .iter()
+                    .zip(note_relevances)
+                    .filter_map(|(account, relevance)| Some((account.id(), relevance?)))
+                    .collect()
+            })
+            .collect())
     }
 
-    /// Tries to execute a standard consume transaction to check if the note is consumable by the
-    /// account.
-    async fn check_standard_consumability(
+    /// Executes the notes against the accounts, for each pair of note and account indices in
+    /// `checks`, returning the results in the same order.
+    #[instrument(level = "debug", skip_all, fields(checks = checks.len()))]
+    async fn check_consumability(
         &self,
-        account: &Account,
-        note: &Note,
-    ) -> Result<Option<NoteRelevance>, NoteScreenerError> {
-        let transaction_request =
-            TransactionRequestBuilder::new().build_consume_notes(vec![note.id()])?;
+        accounts: &[Account],
+        notes: &[Note],
+        checks: &[(usize, usize)],
+        block_num: BlockNumber,
+    ) -> Vec<Result<ScreeningResult, NoteScreenerError>> {
+        #[cfg(feature = "tonic")]
+        if let Some(parallel_screening) = self.parallel_screening
+            && checks.len() > 1
+            && let Ok(runtime) = tokio::runtime::Handle::try_current()
+        {
+            return (parallel_screening.check_consumability)(
+                self,
+                accounts,
+                notes,
+                checks,
+                block_num,
+                parallel_screening.parallelism,
+                &runtime,
+            )
+            .await;
+        }
+
+        let authenticator = self.authenticator.as_deref();
+        let mut results = Vec::with_capacity(checks.len());
+        for (note_index, account_index) in checks {
+            let result = check_account_consumability(
+                &self.store,
+                authenticator,
+                &accounts[*account_index],
+                &notes[*note_index],
+                block_num,
+            )
+            .await;
+            results.push(result);
+        }
+        results
+    }
+}
+
+/// Parallel screening methods, which need the authenticator to be sent to tokio's blocking threads.
+#[cfg(feature = "tonic")]
+impl<AUTH> NoteScreener<AUTH>
+where
+    AUTH: TransactionAuthenticator + Send + Sync + 'static,
+{
+    /// Sets the maximum number of notes executed at the same time, on tokio's blocking threads.
+    /// By default, and when it's zero or one, the notes are executed one at a time.
+    #[must_use]
+    pub fn with_parallelism(mut self, parallelism: usize) -> Self {
+        self.parallel_screening = ParallelScreening::new(parallelism);
+        self
+    }
 
-        let tx_script = transaction_request.build_transaction_script(
-            &AccountInterface::from(account),
-            crate::DebugMode::Disabled,
-        )?;
+    /// Splits the checks between up to `parallelism` blocking tasks, each driving its checks on
+    /// the tokio runtime so that the store can be used from them.
+    ///
+    /// The checks' futures aren't required to be `Send`, since each one is created and driven on
+    /// the thread of its task. Only the blocking tasks are awaited here, so the runtime keeps
+    /// driving IO and timers meanwhile.
+    async fn check_consumability_in_parallel(
+        &self,
+        accounts: &[Account],
+        notes: &[Note],
+        checks: &[(usize, usize)],
+        block_num: BlockNumber,
+        parallelism: usize,
+        runtime: &tokio::runtime::Handle,
+    ) -> Vec<Result<ScreeningResult, NoteScreenerError>> {
+        let accounts: Arc<[Account]> = accounts.into();
+        let notes: Arc<[Note]> = notes.into();
+        let chunk_size = checks.len().div_ceil(parallelism);
+
+        let workers: Vec<_> = checks
+            .chunks(chunk_size)
+            .map(|chunk| {
+                let chunk = chunk.to_vec();
+                let (accounts, notes) = (accounts.clone(), notes.clone());
+                let (store, authenticator) = (self.store.clone(), self.authenticator.clone());
+                let runtime = runtime.clone();
 
-        let tx_args = transaction_request.clone().into_transaction_args(tx_script, vec![]);
+                let num_checks = chunk.len();
+                let worker = tokio::task::spawn_blocking(move || {
+                    runtime.block_on(async {
+                        let mut results = Vec::with_capacity(chunk.len());
+                        for (note_index, account_index) in chunk {
+                            let result = check_account_consumability(
+                                &store,
+                                authenticator.as_deref(),
+                                &accounts[account_index],
+                                &notes[note_index],
+                                block_num,
+                            )
+                            .await;
+                            results.push(result);
+                        }
+                        results
+                    })
+                });
+                (num_checks, worker)
+            })
+            .collect();
 
-        let data_store = ClientDataStore::new(self.store.clone());
-        let mut transaction_executor = TransactionExecutor::new(&data_store);
-        if let Some(authenticator) = &self.authenticator {
-            transaction_executor = transaction_executor.with_authenticator(authenticator.as_ref());
+        let mut results = Vec::with_capacity(checks.len());
+        for (num_checks, worker) in workers {
+            match worker.await {
+                Ok(worker_results) => results.extend(worker_results),
+                // The checks of a task that didn't finish fail, like those that couldn't run
+                Err(err) => results.extend(
+                    (0..num_checks)
+                        .map(|_| Err(NoteScreenerError::ScreeningTaskFailed(err.to_string()))),
+                ),
+            }
         }
+        results
+    }
+}
 
-        let consumption_checker = NoteConsumptionChecker::new(&transaction_executor);
+/// Executes the consumability checks of a [`NoteScreener`] on up to `parallelism` of tokio's
+/// blocking threads, returning their results in the same order.
+#[cfg(feature = "tonic")]
+type ParallelConsumabilityCheck<AUTH> = for<'a> fn(
+    &'a NoteScreener<AUTH>,
+    &'a [Account],
+    &'a [Note],
+    &'a [(usize, usize)],
+    BlockNumber,
+    usize,
+    &'a tokio::runtime::Handle,
+) -> core::pin::Pin<
+    Box<dyn Future<Output = Vec<Result<ScreeningResult, NoteScreenerError>>> + 'a>,
+>;
 
-        data_store.mast_store().load_account_code(account.code());
-        let note_consumption_check = consumption_checker
-            .can_consume(
-                account.id(),
-                self.store.get_sync_height().await?,
-                InputNote::unauthenticated(note.clone()),
-                tx_args,
-            )
-            .await?;
+/// How a [`NoteScreener`] executes notes in parallel.
+///
+/// It can only be created for authenticators that can be sent to other threads, so that the
+/// screener and the client don't require them to be `Send` when the notes are executed one at a
+/// time.
+#[cfg(feature = "tonic")]
+pub(crate) struct ParallelScreening<AUTH> {
+    /// Maximum number of notes executed at the same time.
+    parallelism: usize,
+    /// Executes the checks of the screener in parallel.
+    check_consumability: ParallelConsumabilityCheck<AUTH>,
+}
 
-        let result = match note_consumption_check {
-            NoteConsumptionStatus::ConsumableAfter(block_number) => {
-                Some(NoteRelevance::After(block_number.as_u32()))
+#[cfg(feature = "tonic")]
+impl<AUTH> ParallelScreening<AUTH>
+where
+    AUTH: TransactionAuthenticator + Send + Sync + 'static,
+{
+    /// Returns the parallel screening executing up to `parallelism` notes at the same time, or
+    /// `None` if it's zero or one.
+    pub(crate) fn new(parallelism: usize) -> Option<Self> {
+        (parallelism > 1).then_some(Self {
+            parallelism,
+            check_consumability: |screener,
+                                  accounts,
+                                  notes,
+                                  checks,
+                                  block_num,
+                                  parallelism,
+                                  runtime| {
+                Box::pin(screener.check_consumability_in_parallel(
+                    accounts,
+                    notes,
+                    checks,
+                    block_num,
+                    parallelism,
+                    runtime,
+                ))
             },
-            NoteConsumptionStatus::Consumable
-            | NoteConsumptionStatus::UnconsumableWithoutAuthorization => Some(NoteRelevance::Now),
-            // NOTE: NoteConsumptionStatus::Unconsumable means that state-related context does not
-            // allow for consumption, so don't keep for now. In the next version, we should be more
-            // careful about this
-            NoteConsumptionStatus::Unconsumable | NoteConsumptionStatus::Incompatible => None,
-        };
-        Ok(result)
+        })
     }
+}
 
-    /// Special relevance check for P2IDE notes. It checks if the sender account can consume and
-    /// recall the note.
-    fn check_p2ide_recall_consumability(
-        note: &Note,
-        account_id: &AccountId,
-    ) -> Result<Option<NoteRelevance>, NoteScreenerError> {
-        let note_inputs = note.inputs().values();
-        if note_inputs.len() != 4 {
-            return Err(InvalidNoteInputsError::WrongNumInputs(note.id(), 4).into());
-        }
+#[cfg(feature = "tonic")]
+impl<AUTH> Clone for ParallelScreening<AUTH> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
 
-        let recall_height_felt = note_inputs[2];
+#[cfg(feature = "tonic")]
+impl<AUTH> Copy for ParallelScreening<AUTH> {}
 
-        let sender = note.metadata().sender();
-        let recall_height: u32 = recall_height_felt.as_int().try_into().map_err(|_err| {
-            InvalidNoteInputsError::BlockNumberError(note.id(), recall_height_felt.as_int())
-        })?;
+// HELPERS
+// ================================================================================================
 
-        if sender == *account_id {
-            Ok(Some(NoteRelevance::After(recall_height)))
-        } else {
-            Ok(None)
-        }
+/// Returns the relevance of the note to the account at the given block, found by executing a
+/// standard consume transaction. P2IDE notes that can't be consumed this way are relevant to their
+/// sender once they can be recalled.
+async fn check_account_consumability<AUTH>(
+    store: &Arc<dyn Store>,
+    authenticator: Option<&AUTH>,
+    account: &Account,
+    note: &Note,
+    block_num: BlockNumber,
+) -> Result<ScreeningResult, NoteScreenerError>
+where
+    AUTH: TransactionAuthenticator + Sync,
+{
+    let mut result =
+        check_standard_consumability(store, authenticator, account, note, block_num).await?;
+
+    // The note might be consumable after a certain block height if the note is p2ide
+    if result.relevance.is_none() && note.script().root() == WellKnownNote::P2IDE.script_root() {
+        result.relevance = check_p2ide_recall_consumability(note, &account.id())?;
+    }
+    Ok(result)
+}
+
+/// Tries to execute a standard consume transaction to check if the note is consumable by the
+/// account.
+async fn check_standard_consumability<AUTH>(
+    store: &Arc<dyn Store>,
+    authenticator: Option<&AUTH>,
+    account: &Account,
+    note: &Note,
+    block_num: BlockNumber,
+) -> Result<ScreeningResult, NoteScreenerError>
+where
+    AUTH: TransactionAuthenticator + Sync,
+{
+    let transaction_request =
+        TransactionRequestBuilder::new().build_consume_notes(vec![note.id()])?;
+
+    let tx_script = transaction_request
+        .build_transaction_script(&AccountInterface::from(account), crate::DebugMode::Disabled)?;
+
+    let tx_args = transaction_request.clone().into_transaction_args(tx_script, vec![]);
+
+    let data_store = ClientDataStore::new(store.clone());
+    let mut transaction_executor = TransactionExecutor::new(&data_store);
+    if let Some(authenticator) = authenticator {
+        transaction_executor = transaction_executor.with_authenticator(authenticator);
+    }
+
+    let consumption_checker = NoteConsumptionChecker::new(&transaction_executor);
+
+    data_store.mast_store().load_account_code(account.code());
+    let note_consumption_check = consumption_checker
+        .can_consume(account.id(), block_num, InputNote::unauthenticated(note.clone()), tx_args)
+        .await?;
+
+    // Notes that can't be consumed yet may become consumable at a later block
+    let result = match note_consumption_check {
+        NoteConsumptionStatus::ConsumableAfter(block_number) => {
+            ScreeningResult::at_block(Some(NoteRelevance::After(block_number.as_u32())), block_num)
+        },
+        NoteConsumptionStatus::Consumable
+        | NoteConsumptionStatus::UnconsumableWithoutAuthorization => {
+            ScreeningResult::new(Some(NoteRelevance::Now))
+        },
+        // NOTE: NoteConsumptionStatus::Unconsumable means that state-related context does not
+        // allow for consumption, so don't keep for now. In the next version, we should be more
+        // careful about this
+        NoteConsumptionStatus::Unconsumable => ScreeningResult::at_block(None, block_num),
+        NoteConsumptionStatus::Incompatible => ScreeningResult::new(None),
+    };
+    Ok(result)
+}
+
+/// Special relevance check for P2IDE notes. It checks if the sender account can consume and
+/// recall the note.
+fn check_p2ide_recall_consumability(
+    note: &Note,
+    account_id: &AccountId,
+) -> Result<Option<NoteRelevance>, NoteScreenerError> {
+    let note_inputs = note.inputs().values();
+    if note_inputs.len() != 4 {
+        return Err(InvalidNoteInputsError::WrongNumInputs(note.id(), 4).into());
+    }
+
+    let recall_height_felt = note_inputs[2];
+
+    let sender = note.metadata().sender();
+    let recall_height: u32 = recall_height_felt.as_int().try_into().map_err(|_err| {
+        InvalidNoteInputsError::BlockNumberError(note.id(), recall_height_felt.as_int())
+    })?;
+
+    if sender == *account_id {
+        Ok(Some(NoteRelevance::After(recall_height)))
+    } else {
+        Ok(None)
     }
 }
 
@@ -203,49 +540,78 @@ where
         committed_note: CommittedNote,
         public_note: Option<InputNoteRecord>,
     ) -> Result<NoteUpdateAction, ClientError> {
-        let note_id = *committed_note.note_id();
+        let mut actions = self.on_notes_received(vec![(committed_note, public_note)]).await?;
+        Ok(actions.pop().unwrap_or(NoteUpdateAction::Discard))
+    }
+
+    /// Default implementation of the [`OnNoteReceived`] callback for the notes of a sync step.
+    /// It decides on each note as [`OnNoteReceived::on_note_received`] does, but screens the new
+    /// public notes together, so the tracked accounts are only loaded once.
+    async fn on_notes_received(
+        &self,
+        notes: Vec<(CommittedNote, Option<InputNoteRecord>)>,
+    ) -> Result<Vec<NoteUpdateAction>, ClientError> {
+        let note_tags = self.store.get_unique_note_tags().await?;
+
+        let mut actions = Vec::with_capacity(notes.len());
+        // Public notes to screen, along with the index of their action
+        let mut screened_notes = Vec::new();
+        for (committed_note, public_note) in notes {
+            let note_id = *committed_note.note_id();
+
+            let input_note_present =
+                !self.store.get_input_notes(NoteFilter::Unique(note_id)).await?.is_empty();
+            let output_note_present =
+                !self.store.get_output_notes(NoteFilter::Unique(note_id)).await?.is_empty();
+
+            if input_note_present || output_note_present {
+                // The note is being tracked by the client so it is relevant
+                actions.push(NoteUpdateAction::Commit(committed_note));
+                continue;
+            }
 
-        let input_note_present =
-            !self.store.get_input_notes(NoteFilter::Unique(note_id)).await?.is_empty();
-        let output_note_present =
-            !self.store.get_output_notes(NoteFilter::Unique(note_id)).await?.is_empty();
+            match public_note {
+                Some(public_note) => {
+                    // If tracked by the user, keep note regardless of inputs and extra checks
+                    if let Some(metadata) = public_note.metadata()
+                        && note_tags.contains(&metadata.tag())
+                    {
+                        actions.push(NoteUpdateAction::Insert(public_note));
+                        continue;
+                    }
 
-        if input_note_present || output_note_present {
-            // The note is being tracked by the client so it is relevant
-            return Ok(NoteUpdateAction::Commit(committed_note));
+                    // The note is not being tracked by the client and is public so we can screen
+                    // it, and it's discarded unless it's relevant
+                    screened_notes.push((actions.len(), public_note));
+                    actions.push(NoteUpdateAction::Discard);
+                },
+                None => {
+                    // The note is not being tracked by the client and is private so we can't
+                    // determine if it is relevant
+                    actions.push(NoteUpdateAction::Discard);
+                },
+            }
         }
 
-        match public_note {
-            Some(public_note) => {
-                // If tracked by the user, keep note regardless of inputs and extra checks
-                if let Some(metadata) = public_note.metadata()
-                    && self.store.get_unique_note_tags().await?.contains(&metadata.tag())
-                {
-                    return Ok(NoteUpdateAction::Insert(public_note));
-                }
+        if screened_notes.is_empty() {
+            return Ok(actions);
+        }
 
-                // The note is not being tracked by the client and is public so we can screen it
-                let new_note_relevance = self
-                    .check_relevance(
-                        &public_note
-                            .clone()
-                            .try_into()
-                            .map_err(ClientError::NoteRecordConversionError)?,
-                    )
-                    .await?;
-                let is_relevant = !new_note_relevance.is_empty();
-                if is_relevant {
-                    Ok(NoteUpdateAction::Insert(public_note))
-                } else {
-                    Ok(NoteUpdateAction::Discard)
-                }
-            },
-            None => {
-                // The note is not being tracked by the client and is private so we can't determine
-                // if it is relevant
-                Ok(NoteUpdateAction::Discard)
-            },
+        let notes = screened_notes
+            .iter()
+            .map(|(_, public_note)| public_note.clone().try_into())
+            .collect::<Result<Vec<Note>, _>>()
+            .map_err(ClientError::NoteRecordConversionError)?;
+        let note_relevances = self.check_relevance_batch(&notes).await?;
+
+        for ((action_index, public_note), note_relevance) in
+            screened_notes.into_iter().zip(note_relevances)
+        {
+            if !note_relevance.is_empty() {
+                actions[action_index] = NoteUpdateAction::Insert(public_note);
+            }
         }
+        Ok(actions)
     }
 }
 
@@ -265,6 +631,8 @@ pub enum NoteScreenerError {
     NoteCheckerError(#[from] NoteCheckerError),
     #[error("error while building transaction request")]
     TransactionRequestError(#[from] TransactionRequestError),
+    #[error("note screening task failed: {0}")]
+    ScreeningTaskFailed(String),
 }
 
 #[derive(Debug, Error)]
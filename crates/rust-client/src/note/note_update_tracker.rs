@@ -225,11 +225,22 @@ impl NoteUpdateTracker {
         self.input_notes.is_empty() && self.output_notes.is_empty()
     }
 
+    /// Returns the nullifiers of the tracked notes that weren't consumed yet. Output notes are
+    /// only included if their recipient is known, which is needed to compute the nullifier.
     pub fn unspent_nullifiers(&self) -> impl Iterator<Item = Nullifier> + '_ {
-        self.input_notes
+        let input_note_nullifiers = self
+            .input_notes
             .values()
             .filter(|note| !note.inner().is_consumed())
-            .map(|note| note.inner().nullifier())
+            .map(|note| note.inner().nullifier());
+
+        let output_note_nullifiers = self
+            .output_notes
+            .values()
+            .filter(|note| !note.inner().is_consumed())
+            .filter_map(|note| note.inner().nullifier());
+
+        input_note_nullifiers.chain(output_note_nullifiers)
     }
 
     // UPDATE METHODS
@@ -0,0 +1,99 @@
+//! Provides [`NoteRelevanceChecker`], which lets applications decide the relevance of notes with
+//! specific scripts without executing them.
+//!
+//! By default, the [`crate::note::NoteScreener`] checks whether each tracked account can consume a
+//! note by executing a consume transaction for it. For notes whose scripts are known, such as the
+//! scripts of a bridge or of an application, the relevance can often be decoded from the note
+//! inputs instead. Checkers are registered on the client for the root of the script they decode,
+//! and can return [`NoteRelevanceCheck::Unknown`] to fall back to the generic check.
+
+use alloc::sync::Arc;
+
+use miden_objects::Word;
+use miden_objects::account::Account;
+use miden_objects::note::Note;
+use miden_tx::auth::TransactionAuthenticator;
+
+#[cfg(feature = "tonic")]
+use super::ParallelScreening;
+use super::{NoteRelevance, NoteScreener, NoteScreenerError};
+use crate::Client;
+
+// NOTE RELEVANCE CHECKER
+// ================================================================================================
+
+/// Result of a [`NoteRelevanceChecker`] for a note and an account.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NoteRelevanceCheck {
+    /// The account can consume the note, at the specified moment.
+    Consumable(NoteRelevance),
+    /// The account can't consume the note.
+    NotConsumable,
+    /// The checker can't tell, so the generic consumption check is used.
+    Unknown,
+}
+
+/// Decides whether tracked accounts can consume the notes with a specific script, without
+/// executing them.
+///
+/// Checkers are registered with [`Client::register_note_relevance_checker`] or with
+/// `ClientBuilder::note_relevance_checker`, for the root of the script they decode. They are
+/// called for each tracked account while screening notes, so they should return quickly.
+pub trait NoteRelevanceChecker {
+    /// Returns whether the account can consume the note.
+    ///
+    /// # Errors
+    ///
+    /// An error means the note is malformed, and makes it irrelevant to the account. The error is
+    /// logged as a warning.
+    fn check_relevance(
+        &self,
+        note: &Note,
+        account: &Account,
+    ) -> Result<NoteRelevanceCheck, NoteScreenerError>;
+}
+
+/// Note relevance methods.
+impl<AUTH> Client<AUTH>
+where
+    AUTH: TransactionAuthenticator + Sync,
+{
+    /// Registers a checker used instead of executing the notes whose script has the given root
+    /// when checking their relevance. A checker previously registered for the root is replaced.
+    pub fn register_note_relevance_checker(
+        &mut self,
+        script_root: Word,
+        checker: Arc<dyn NoteRelevanceChecker>,
+    ) {
+        self.note_relevance_checkers.insert(script_root, checker);
+    }
+
+    /// Returns a note screener for the client's store, using the registered relevance checkers
+    /// and the client's [`NoteScreeningCache`].
+    pub fn note_screener(&self) -> NoteScreener<AUTH> {
+        let note_screener = NoteScreener::new(self.store.clone(), self.authenticator.clone())
+            .with_relevance_checkers(self.note_relevance_checkers.clone())
+            .with_cache(self.note_screening_cache.clone());
+
+        #[cfg(feature = "tonic")]
+        let note_screener = note_screener.with_parallel_screening(self.parallel_note_screening);
+
+        note_screener
+    }
+}
+
+/// Parallel note screening methods.
+#[cfg(feature = "tonic")]
+impl<AUTH> Client<AUTH>
+where
+    AUTH: TransactionAuthenticator + Send + Sync + 'static,
+{
+    /// Sets the maximum number of notes executed at the same time by the client's note
+    /// screeners, including the one checking the public notes received while syncing. The notes
+    /// are executed on tokio's blocking threads, so the authenticator has to be `Send`.
+    ///
+    /// By default, and when it's zero or one, the notes are executed one at a time.
+    pub fn set_note_screening_parallelism(&mut self, parallelism: usize) {
+        self.parallel_note_screening = ParallelScreening::new(parallelism);
+    }
+}
@@ -0,0 +1,164 @@
+use alloc::collections::BTreeMap;
+
+use miden_objects::Word;
+use miden_objects::block::BlockNumber;
+use miden_objects::note::NoteId;
+
+use super::NoteRelevance;
+use crate::utils::RwLock;
+
+/// Maximum number of results kept by a [`NoteScreeningCache`]. Once reached, the cache is cleared.
+const MAX_CACHED_RESULTS: usize = 16_384;
+
+// NOTE SCREENING CACHE
+// ================================================================================================
+
+/// Results of executing notes against accounts while screening them, by account commitment and
+/// note ID.
+///
+/// Since the account commitment changes with the account's state, results are computed again
+/// once the account changes. Results that depend on the chain height, such as notes that can't be
+/// consumed yet, are also keyed by the block they were computed at.
+pub struct NoteScreeningCache {
+    results: RwLock<BTreeMap<(Word, NoteId, Option<BlockNumber>), Option<NoteRelevance>>>,
+}
+
+impl NoteScreeningCache {
+    /// Returns an empty cache.
+    pub fn new() -> Self {
+        Self { results: RwLock::new(BTreeMap::new()) }
+    }
+
+    /// Returns the number of cached results.
+    pub fn len(&self) -> usize {
+        self.results.read().len()
+    }
+
+    /// Returns whether the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.results.read().is_empty()
+    }
+
+    /// Removes every cached result.
+    pub fn clear(&self) {
+        self.results.write().clear();
+    }
+
+    /// Returns the cached relevance of the note to the account with the given commitment at the
+    /// given block, if the note was executed against it.
+    pub(crate) fn get(
+        &self,
+        account_commitment: Word,
+        note_id: NoteId,
+        block_num: BlockNumber,
+    ) -> Option<Option<NoteRelevance>> {
+        let results = self.results.read();
+        results
+            .get(&(account_commitment, note_id, None))
+            .or_else(|| results.get(&(account_commitment, note_id, Some(block_num))))
+            .copied()
+    }
+
+    /// Caches the result of executing the note against the account with the given commitment.
+    pub(crate) fn insert(
+        &self,
+        account_commitment: Word,
+        note_id: NoteId,
+        result: ScreeningResult,
+    ) {
+        let mut results = self.results.write();
+        if results.len() >= MAX_CACHED_RESULTS {
+            results.clear();
+        }
+        results.insert((account_commitment, note_id, result.block_num), result.relevance);
+    }
+}
+
+impl Default for NoteScreeningCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// SCREENING RESULT
+// ================================================================================================
+
+/// The relevance of a note to an account, found by executing the note against it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ScreeningResult {
+    /// The relevance of the note, or `None` if the account can't consume it.
+    pub relevance: Option<NoteRelevance>,
+    /// The block the result was computed at, if it may change once the chain grows.
+    pub block_num: Option<BlockNumber>,
+}
+
+impl ScreeningResult {
+    /// Returns a result that holds at any block.
+    pub fn new(relevance: Option<NoteRelevance>) -> Self {
+        Self { relevance, block_num: None }
+    }
+
+    /// Returns a result that only holds at the given block.
+    pub fn at_block(relevance: Option<NoteRelevance>, block_num: BlockNumber) -> Self {
+        Self { relevance, block_num: Some(block_num) }
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use miden_objects::block::BlockNumber;
+    use miden_objects::note::NoteId;
+    use miden_objects::{Felt, Word};
+
+    use super::{MAX_CACHED_RESULTS, NoteScreeningCache, ScreeningResult};
+    use crate::note::NoteRelevance;
+
+    fn word(value: u64) -> Word {
+        Word::from([Felt::new(value), Felt::new(0), Felt::new(0), Felt::new(0)])
+    }
+
+    #[test]
+    fn results_are_cached_by_account_commitment_and_note() {
+        let cache = NoteScreeningCache::new();
+        let note_id = NoteId::new(word(1), word(2));
+
+        let block_num = BlockNumber::from(5);
+
+        cache.insert(word(10), note_id, ScreeningResult::new(Some(NoteRelevance::Now)));
+        cache.insert(word(11), note_id, ScreeningResult::new(None));
+
+        assert_eq!(cache.get(word(10), note_id, block_num), Some(Some(NoteRelevance::Now)));
+        assert_eq!(cache.get(word(11), note_id, block_num), Some(None));
+        assert_eq!(cache.get(word(12), note_id, block_num), None);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn height_dependent_results_are_cached_by_block() {
+        let cache = NoteScreeningCache::new();
+        let note_id = NoteId::new(word(1), word(2));
+        let block_num = BlockNumber::from(5);
+
+        cache.insert(word(10), note_id, ScreeningResult::at_block(None, block_num));
+
+        assert_eq!(cache.get(word(10), note_id, block_num), Some(None));
+        assert_eq!(cache.get(word(10), note_id, BlockNumber::from(6)), None);
+    }
+
+    #[test]
+    fn full_cache_is_cleared() {
+        let cache = NoteScreeningCache::new();
+        let note_id = NoteId::new(word(1), word(2));
+
+        for commitment in 0..MAX_CACHED_RESULTS as u64 {
+            cache.insert(word(commitment), note_id, ScreeningResult::new(None));
+        }
+        assert_eq!(cache.len(), MAX_CACHED_RESULTS);
+
+        cache.insert(word(u64::from(u32::MAX)), note_id, ScreeningResult::new(None));
+        assert_eq!(cache.len(), 1);
+    }
+}
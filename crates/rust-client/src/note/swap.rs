@@ -0,0 +1,296 @@
+//! Provides the APIs to follow the SWAP notes created by the client's accounts.
+//!
+//! A SWAP note created with [`TransactionRequestBuilder::build_swap`] offers its assets to
+//! whoever pays the requested asset back to its creator in a payback note. The client keeps the
+//! SWAP note as an output note and the payback note as an expected input note, and derives the
+//! status of each swap from them:
+//!
+//! - [`SwapStatus::Open`] while the SWAP note hasn't been consumed.
+//! - [`SwapStatus::Filled`] once the SWAP note was consumed by another account, or the payback note
+//!   was committed.
+//! - [`SwapStatus::Reclaimed`] once the SWAP note was consumed by a transaction of the account that
+//!   created it.
+//!
+//! An open swap can be cancelled by consuming the SWAP note with the account that created it,
+//! using the request returned by [`Client::cancel_swap_request`]. The SWAP script doesn't have a
+//! reclaim branch, so the creator fills its own swap: the account must hold the requested asset,
+//! which is paid back to itself in the payback note, and gets the offered assets back.
+//!
+//! During sync, the client follows the nullifiers of the committed SWAP notes whose payback note
+//! wasn't received yet, so the cost of syncing doesn't grow with the swaps that were already
+//! settled.
+//!
+//! [`TransactionRequestBuilder::build_swap`]: crate::transaction::TransactionRequestBuilder::build_swap
+
+use alloc::collections::BTreeSet;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::fmt;
+
+use miden_lib::note::well_known_note::WellKnownNote;
+use miden_objects::Word;
+use miden_objects::account::AccountId;
+use miden_objects::asset::Asset;
+use miden_objects::block::BlockNumber;
+use miden_objects::note::{Note, NoteAssets, NoteId, NoteRecipient};
+use miden_tx::auth::TransactionAuthenticator;
+
+use crate::store::{NoteFilter, OutputNoteRecord, TransactionFilter};
+use crate::transaction::{
+    TransactionRecord,
+    TransactionRequest,
+    TransactionRequestBuilder,
+    TransactionStatus,
+};
+use crate::{Client, ClientError};
+
+// SWAP STATUS
+// ================================================================================================
+
+/// Status of a swap created by the client.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwapStatus {
+    /// The SWAP note wasn't consumed yet.
+    Open,
+    /// The SWAP note was consumed by another account, which paid the requested asset back.
+    Filled,
+    /// The SWAP note was consumed by the account that created it.
+    Reclaimed,
+}
+
+impl fmt::Display for SwapStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SwapStatus::Open => write!(f, "Open"),
+            SwapStatus::Filled => write!(f, "Filled"),
+            SwapStatus::Reclaimed => write!(f, "Reclaimed"),
+        }
+    }
+}
+
+// SWAP ORDER
+// ================================================================================================
+
+/// A swap created by one of the client's accounts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwapOrder {
+    /// ID of the SWAP note.
+    pub note_id: NoteId,
+    /// ID of the account that created the swap.
+    pub account_id: AccountId,
+    /// Assets offered by the SWAP note.
+    pub offered_assets: Vec<Asset>,
+    /// Asset requested in exchange for the offered assets.
+    pub requested_asset: Asset,
+    /// ID of the note that pays the requested asset back to the creator.
+    pub payback_note_id: NoteId,
+    /// Block at which the SWAP note was expected to be committed when it was created.
+    pub created_at: BlockNumber,
+    /// Current status of the swap.
+    pub status: SwapStatus,
+}
+
+impl SwapOrder {
+    /// Returns the swap described by the provided output note, or `None` if it isn't a SWAP note
+    /// or its recipient isn't known. The status is set to [`SwapStatus::Filled`] if the note was
+    /// consumed and to [`SwapStatus::Open`] otherwise.
+    fn from_output_note(note: &OutputNoteRecord) -> Option<Self> {
+        let (requested_asset, payback_recipient) = decode_swap_inputs(note.recipient()?)?;
+        let payback_assets = NoteAssets::new(vec![requested_asset]).ok()?;
+
+        Some(SwapOrder {
+            note_id: note.id(),
+            account_id: note.metadata().sender(),
+            offered_assets: note.assets().iter().copied().collect(),
+            requested_asset,
+            payback_note_id: NoteId::new(payback_recipient, payback_assets.commitment()),
+            created_at: note.expected_height(),
+            status: if note.is_consumed() {
+                SwapStatus::Filled
+            } else {
+                SwapStatus::Open
+            },
+        })
+    }
+}
+
+/// Returns the requested asset and the payback recipient digest from the inputs of a SWAP note,
+/// or `None` if the recipient isn't one of a SWAP note.
+pub(crate) fn decode_swap_inputs(recipient: &NoteRecipient) -> Option<(Asset, Word)> {
+    if recipient.script().root() != WellKnownNote::SWAP.script_root() {
+        return None;
+    }
+
+    // The inputs start with the payback recipient digest followed by the requested asset
+    let inputs = recipient.inputs().values();
+    let payback_recipient = Word::try_from(inputs.get(0..4)?).ok()?;
+    let requested_asset = Asset::try_from(Word::try_from(inputs.get(4..8)?).ok()?).ok()?;
+
+    Some((requested_asset, payback_recipient))
+}
+
+/// Returns whether one of the transactions was executed by the account that created the swap and
+/// consumed its SWAP note.
+pub(crate) fn is_reclaimed<'a>(
+    swap_note: &OutputNoteRecord,
+    transactions: impl IntoIterator<Item = &'a TransactionRecord>,
+) -> bool {
+    let Some(nullifier) = swap_note.nullifier() else {
+        return false;
+    };
+
+    transactions.into_iter().any(|transaction| {
+        !matches!(transaction.status, TransactionStatus::Discarded(_))
+            && transaction.details.account_id == swap_note.metadata().sender()
+            && transaction.details.input_note_nullifiers.contains(&nullifier.as_word())
+    })
+}
+
+/// Swap management methods.
+impl<AUTH> Client<AUTH>
+where
+    AUTH: TransactionAuthenticator + Sync,
+{
+    /// Returns the swaps created by the client's accounts, along with their status.
+    pub async fn get_swaps(&self) -> Result<Vec<SwapOrder>, ClientError> {
+        self.swaps_from_output_notes(NoteFilter::All).await
+    }
+
+    /// Returns the swap for the SWAP note with the provided ID, or `None` if the client doesn't
+    /// have an output note with that ID or it isn't a SWAP note.
+    pub async fn get_swap(&self, note_id: NoteId) -> Result<Option<SwapOrder>, ClientError> {
+        Ok(self.swaps_from_output_notes(NoteFilter::List(vec![note_id])).await?.pop())
+    }
+
+    /// Returns a [`TransactionRequest`] that cancels an open swap by consuming its SWAP note. The
+    /// request must be executed against the account that created the swap, which needs to hold the
+    /// requested asset to pay it back to itself.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ClientError::SwapNotCancellable`] if the note isn't a SWAP note created by the
+    /// client or the swap isn't open.
+    pub async fn cancel_swap_request(
+        &self,
+        note_id: NoteId,
+    ) -> Result<TransactionRequest, ClientError> {
+        let not_a_swap = || {
+            ClientError::SwapNotCancellable(
+                note_id,
+                "it isn't a SWAP note created by the client".to_string(),
+            )
+        };
+
+        let swap = self.get_swap(note_id).await?.ok_or_else(not_a_swap)?;
+        if swap.status != SwapStatus::Open {
+            return Err(ClientError::SwapNotCancellable(
+                note_id,
+                format!("the swap is already {}", swap.status.to_string().to_lowercase()),
+            ));
+        }
+
+        let note_record = self
+            .store
+            .get_output_notes(NoteFilter::List(vec![note_id]))
+            .await?
+            .pop()
+            .ok_or_else(not_a_swap)?;
+        let note = Note::try_from(note_record)?;
+
+        // The note is consumed as an unauthenticated note so that it can be cancelled before it's
+        // committed, and because the client may not track it as an input note
+        TransactionRequestBuilder::new()
+            .unauthenticated_input_notes(vec![(note, None)])
+            .build()
+            .map_err(Into::into)
+    }
+
+    /// Returns the committed SWAP notes created by the client whose payback note wasn't received
+    /// yet. Only the nullifiers of these output notes are tracked during sync, to tell when
+    /// their swaps are filled or reclaimed.
+    pub(crate) async fn pending_swap_notes(&self) -> Result<Vec<OutputNoteRecord>, ClientError> {
+        let swap_notes: Vec<(OutputNoteRecord, NoteId)> = self
+            .store
+            .get_output_notes(NoteFilter::Committed)
+            .await?
+            .into_iter()
+            .filter_map(|note| {
+                let payback_note_id = SwapOrder::from_output_note(&note)?.payback_note_id;
+                Some((note, payback_note_id))
+            })
+            .collect();
+
+        let received_paybacks = self
+            .received_paybacks(swap_notes.iter().map(|(_, payback_id)| *payback_id))
+            .await?;
+
+        Ok(swap_notes
+            .into_iter()
+            .filter(|(_, payback_note_id)| !received_paybacks.contains(payback_note_id))
+            .map(|(note, _)| note)
+            .collect())
+    }
+
+    /// Returns the swaps described by the output notes that match the filter, checking whether
+    /// each SWAP note was consumed by its creator and whether its payback note was received.
+    async fn swaps_from_output_notes(
+        &self,
+        filter: NoteFilter,
+    ) -> Result<Vec<SwapOrder>, ClientError> {
+        let output_notes = self.store.get_output_notes(filter).await?;
+        let transactions = self.store.get_transactions(TransactionFilter::All).await?;
+
+        let mut swaps = Vec::new();
+        for note in &output_notes {
+            let Some(mut swap) = SwapOrder::from_output_note(note) else {
+                continue;
+            };
+
+            if is_reclaimed(note, &transactions) {
+                swap.status = SwapStatus::Reclaimed;
+            }
+
+            swaps.push(swap);
+        }
+
+        // The nullifier of the SWAP note may not have been received yet while the payback note
+        // was already committed
+        let received_paybacks = self
+            .received_paybacks(
+                swaps
+                    .iter()
+                    .filter(|swap| swap.status == SwapStatus::Open)
+                    .map(|swap| swap.payback_note_id),
+            )
+            .await?;
+        for swap in &mut swaps {
+            if swap.status == SwapStatus::Open && received_paybacks.contains(&swap.payback_note_id)
+            {
+                swap.status = SwapStatus::Filled;
+            }
+        }
+
+        Ok(swaps)
+    }
+
+    /// Returns the payback notes among the provided ones that were committed, which means their
+    /// swaps were filled.
+    async fn received_paybacks(
+        &self,
+        payback_note_ids: impl Iterator<Item = NoteId>,
+    ) -> Result<BTreeSet<NoteId>, ClientError> {
+        let payback_note_ids: Vec<NoteId> = payback_note_ids.collect();
+        if payback_note_ids.is_empty() {
+            return Ok(BTreeSet::new());
+        }
+
+        Ok(self
+            .store
+            .get_input_notes(NoteFilter::List(payback_note_ids))
+            .await?
+            .into_iter()
+            .filter(|payback_note| payback_note.is_committed() || payback_note.is_consumed())
+            .map(|payback_note| payback_note.id())
+            .collect())
+    }
+}
@@ -38,6 +38,36 @@ pub enum RpcError {
     },
     #[error("note with id {0} was not found")]
     NoteNotFound(NoteId),
+    #[error("no response was recorded for the {0} request")]
+    UnrecordedRequest(NodeRpcClientEndpoint),
+}
+
+impl RpcError {
+    /// Returns whether the request may succeed if it's sent again: the node couldn't be reached,
+    /// or the request failed with a transient [`GrpcError`].
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::ConnectionError(_) => true,
+            Self::GrpcError { error_kind, .. } => error_kind.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Returns a short label for the kind of error, such as `connection` or `grpc_unavailable`,
+    /// used to group errors in metrics.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::AcceptHeaderError(_) => "accept_header",
+            Self::AccountUpdateForPrivateAccountReceived(_) => "private_account_update",
+            Self::ConnectionError(_) => "connection",
+            Self::DeserializationError(_) => "deserialization",
+            Self::ExpectedDataMissing(_) => "missing_data",
+            Self::InvalidResponse(_) => "invalid_response",
+            Self::GrpcError { error_kind, .. } => error_kind.kind(),
+            Self::NoteNotFound(_) => "note_not_found",
+            Self::UnrecordedRequest(_) => "unrecorded_request",
+        }
+    }
 }
 
 impl From<DeserializationError> for RpcError {
@@ -148,6 +178,62 @@ impl GrpcError {
             ),
         }
     }
+
+    /// Returns whether the error is transient, so that the request may succeed if it's sent
+    /// again.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::Unavailable | Self::DeadlineExceeded | Self::ResourceExhausted | Self::Aborted
+        )
+    }
+
+    /// Returns a short label for the gRPC status, such as `grpc_unavailable`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::NotFound => "grpc_not_found",
+            Self::InvalidArgument => "grpc_invalid_argument",
+            Self::PermissionDenied => "grpc_permission_denied",
+            Self::AlreadyExists => "grpc_already_exists",
+            Self::ResourceExhausted => "grpc_resource_exhausted",
+            Self::FailedPrecondition => "grpc_failed_precondition",
+            Self::Cancelled => "grpc_cancelled",
+            Self::DeadlineExceeded => "grpc_deadline_exceeded",
+            Self::Unavailable => "grpc_unavailable",
+            Self::Internal => "grpc_internal",
+            Self::Unimplemented => "grpc_unimplemented",
+            Self::Unauthenticated => "grpc_unauthenticated",
+            Self::Aborted => "grpc_aborted",
+            Self::OutOfRange => "grpc_out_of_range",
+            Self::DataLoss => "grpc_data_loss",
+            Self::Unknown(_) => "grpc_unknown",
+        }
+    }
+
+    /// Returns the error with the given label, as returned by [`GrpcError::kind`]. The message is
+    /// only kept by [`GrpcError::Unknown`].
+    pub fn from_kind(kind: &str, message: String) -> Option<Self> {
+        let error = match kind {
+            "grpc_not_found" => Self::NotFound,
+            "grpc_invalid_argument" => Self::InvalidArgument,
+            "grpc_permission_denied" => Self::PermissionDenied,
+            "grpc_already_exists" => Self::AlreadyExists,
+            "grpc_resource_exhausted" => Self::ResourceExhausted,
+            "grpc_failed_precondition" => Self::FailedPrecondition,
+            "grpc_cancelled" => Self::Cancelled,
+            "grpc_deadline_exceeded" => Self::DeadlineExceeded,
+            "grpc_unavailable" => Self::Unavailable,
+            "grpc_internal" => Self::Internal,
+            "grpc_unimplemented" => Self::Unimplemented,
+            "grpc_unauthenticated" => Self::Unauthenticated,
+            "grpc_aborted" => Self::Aborted,
+            "grpc_out_of_range" => Self::OutOfRange,
+            "grpc_data_loss" => Self::DataLoss,
+            "grpc_unknown" => Self::Unknown(message),
+            _ => return None,
+        };
+        Some(error)
+    }
 }
 
 // ACCEPT HEADER ERROR
@@ -0,0 +1,639 @@
+//! Provides [`FailoverRpcClient`], a [`NodeRpcClient`] that sends requests to the first available
+//! node out of several, so that the client keeps working when a node goes down.
+//!
+//! Before an endpoint is used for the first time, the commitment of its genesis block is fetched
+//! and compared with the client's genesis commitment, so that requests are never answered by a
+//! node of a different network.
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeSet;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::future::Future;
+use core::time::Duration;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::Instant;
+
+use miden_objects::Word;
+use miden_objects::account::{AccountCode, AccountId};
+use miden_objects::block::{BlockHeader, BlockNumber, ProvenBlock};
+use miden_objects::crypto::merkle::{MmrProof, SmtProof};
+use miden_objects::note::{NoteId, NoteTag, Nullifier};
+use miden_objects::transaction::ProvenTransaction;
+use tracing::warn;
+
+use super::domain::account::{AccountProofs, FetchedAccount};
+use super::domain::note::{FetchedNote, NoteSyncInfo};
+use super::domain::nullifier::NullifierUpdate;
+use super::domain::sync::StateSyncInfo;
+use super::{Endpoint, GrpcError, NodeRpcClient, RpcError, TonicRpcClient};
+use crate::transaction::ForeignAccount;
+
+/// Default time an endpoint is skipped after it fails.
+pub const DEFAULT_RPC_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+// ENDPOINT STATUS
+// ================================================================================================
+
+/// Snapshot of the state of an endpoint of a [`FailoverRpcClient`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RpcEndpointStatus {
+    /// Endpoint of the node.
+    pub endpoint: String,
+    /// Whether the endpoint is currently preferred. An endpoint is unhealthy for a while after a
+    /// request or a health check fails to reach it.
+    pub healthy: bool,
+    /// Whether the endpoint can be used, which is the case unless its genesis commitment differs
+    /// from the client's.
+    pub compatible: bool,
+    /// Commitment of the node's genesis block, if it was fetched.
+    pub genesis_commitment: Option<Word>,
+    /// Latest block of the node, as of the last health check.
+    pub chain_tip: Option<BlockNumber>,
+    /// Number of requests and health checks that failed to reach the endpoint.
+    pub failures: u64,
+}
+
+#[derive(Debug, Default)]
+struct EndpointStats {
+    last_failure: Option<Instant>,
+    genesis_commitment: Option<Word>,
+    chain_tip: Option<BlockNumber>,
+    failures: u64,
+}
+
+impl EndpointStats {
+    fn is_healthy(&self, retry_after: Duration) -> bool {
+        self.last_failure.is_none_or(|failure| failure.elapsed() >= retry_after)
+    }
+
+    fn record_failure(&mut self) {
+        self.last_failure = Some(Instant::now());
+        self.failures += 1;
+    }
+}
+
+struct RpcEndpoint {
+    endpoint: String,
+    client: Box<dyn NodeRpcClient>,
+    stats: Mutex<EndpointStats>,
+}
+
+impl RpcEndpoint {
+    fn stats(&self) -> MutexGuard<'_, EndpointStats> {
+        self.stats.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+// FAILOVER RPC CLIENT
+// ================================================================================================
+
+/// A [`NodeRpcClient`] that holds a client for each of several nodes of the same network.
+///
+/// Requests are sent to the endpoints in the order they were provided, skipping the endpoints
+/// that recently failed, until a node answers. A request fails over to the next endpoint when
+/// the node can't be reached, is unavailable, times out or rejects the client's version or
+/// genesis commitment; any other error is returned as is. Endpoints that recently failed are
+/// still tried, after the rest, before giving up.
+///
+/// Proven transactions are only sent to the next endpoint if the node couldn't be reached or
+/// rejected the client's version. A node that is unavailable or times out may have accepted the
+/// transaction already, so the error is returned instead of submitting the transaction twice.
+///
+/// Endpoints whose genesis block doesn't match the client's genesis commitment are never used.
+/// Until the client's genesis commitment is set with
+/// [`NodeRpcClient::set_genesis_commitment`], the genesis commitment of the first endpoint that
+/// answers is used instead.
+pub struct FailoverRpcClient {
+    endpoints: Vec<RpcEndpoint>,
+    retry_after: Duration,
+    genesis_commitment: Mutex<Option<Word>>,
+}
+
+impl FailoverRpcClient {
+    /// Returns a new client with a [`TonicRpcClient`] for each of the endpoints, which use the
+    /// given timeout in milliseconds.
+    pub fn new<'a, I>(endpoints: I, timeout_ms: u64) -> Self
+    where
+        I: IntoIterator<Item = &'a Endpoint>,
+    {
+        Self::from_clients(endpoints.into_iter().map(|endpoint| {
+            let client: Box<dyn NodeRpcClient> =
+                Box::new(TonicRpcClient::new(endpoint, timeout_ms));
+            (endpoint.to_string(), client)
+        }))
+    }
+
+    /// Returns a new client that sends the requests to the provided clients, each identified by
+    /// the endpoint of its node.
+    pub fn from_clients<I>(clients: I) -> Self
+    where
+        I: IntoIterator<Item = (String, Box<dyn NodeRpcClient>)>,
+    {
+        let endpoints = clients
+            .into_iter()
+            .map(|(endpoint, client)| RpcEndpoint {
+                endpoint,
+                client,
+                stats: Mutex::new(EndpointStats::default()),
+            })
+            .collect();
+
+        Self {
+            endpoints,
+            retry_after: DEFAULT_RPC_RETRY_AFTER,
+            genesis_commitment: Mutex::new(None),
+        }
+    }
+
+    /// Sets the time an endpoint is skipped after it fails.
+    #[must_use]
+    pub fn with_retry_after(mut self, retry_after: Duration) -> Self {
+        self.retry_after = retry_after;
+        self
+    }
+
+    /// Returns the current status of each endpoint.
+    pub fn status(&self) -> Vec<RpcEndpointStatus> {
+        let genesis_commitment = self.expected_genesis_commitment();
+        self.endpoints
+            .iter()
+            .map(|endpoint| {
+                let stats = endpoint.stats();
+                RpcEndpointStatus {
+                    endpoint: endpoint.endpoint.clone(),
+                    healthy: stats.is_healthy(self.retry_after),
+                    compatible: is_compatible(&stats, genesis_commitment),
+                    genesis_commitment: stats.genesis_commitment,
+                    chain_tip: stats.chain_tip,
+                    failures: stats.failures,
+                }
+            })
+            .collect()
+    }
+
+    /// Fetches the latest block header from each endpoint, as well as its genesis block header if
+    /// it wasn't fetched yet, and returns the updated status of the endpoints. Endpoints that
+    /// can't be reached are marked as unhealthy, and reachable endpoints are marked as healthy
+    /// again.
+    pub async fn check_health(&self) -> Vec<RpcEndpointStatus> {
+        for endpoint in &self.endpoints {
+            let latest_header = endpoint.client.get_block_header_by_number(None, false).await;
+            let result = match latest_header {
+                Ok((header, _)) => {
+                    endpoint.stats().chain_tip = Some(header.block_num());
+                    self.fetch_genesis_commitment(endpoint).await.map(|_| ())
+                },
+                Err(err) => Err(err),
+            };
+
+            match result {
+                Ok(()) => endpoint.stats().last_failure = None,
+                Err(err) => {
+                    warn!("RPC endpoint {} is unreachable: {err}", endpoint.endpoint);
+                    endpoint.stats().record_failure();
+                },
+            }
+        }
+
+        self.status()
+    }
+
+    /// Returns the genesis commitment the endpoints must match: the client's if it was set, or
+    /// else the one of the first endpoint whose genesis block was fetched.
+    fn expected_genesis_commitment(&self) -> Option<Word> {
+        let genesis_commitment =
+            *self.genesis_commitment.lock().unwrap_or_else(PoisonError::into_inner);
+
+        genesis_commitment.or_else(|| {
+            self.endpoints.iter().find_map(|endpoint| endpoint.stats().genesis_commitment)
+        })
+    }
+
+    /// Returns whether an endpoint with the given genesis commitment belongs to the client's
+    /// network.
+    fn matches_genesis_commitment(&self, commitment: Word) -> bool {
+        self.expected_genesis_commitment().is_none_or(|expected| expected == commitment)
+    }
+
+    /// Returns the commitment of the endpoint's genesis block, fetching it if it wasn't fetched
+    /// yet.
+    async fn fetch_genesis_commitment(&self, endpoint: &RpcEndpoint) -> Result<Word, RpcError> {
+        let known_commitment = endpoint.stats().genesis_commitment;
+        if let Some(commitment) = known_commitment {
+            return Ok(commitment);
+        }
+
+        let (genesis, _) = endpoint
+            .client
+            .get_block_header_by_number(Some(BlockNumber::GENESIS), false)
+            .await?;
+        let commitment = genesis.commitment();
+        endpoint.stats().genesis_commitment = Some(commitment);
+
+        if !self.matches_genesis_commitment(commitment) {
+            warn!(
+                "RPC endpoint {} has genesis commitment {}, which doesn't match the client's",
+                endpoint.endpoint,
+                commitment.to_hex()
+            );
+        }
+
+        Ok(commitment)
+    }
+
+    /// Returns the indices of the endpoints in the order they should be tried: the healthy ones
+    /// first, then the rest. Endpoints known to belong to another network are left out.
+    fn candidates(&self) -> Vec<usize> {
+        let genesis_commitment = self.expected_genesis_commitment();
+        let (mut healthy, unhealthy): (Vec<usize>, Vec<usize>) = (0..self.endpoints.len())
+            .filter(|&index| is_compatible(&self.endpoints[index].stats(), genesis_commitment))
+            .partition(|&index| self.endpoints[index].stats().is_healthy(self.retry_after));
+
+        healthy.extend(unhealthy);
+        healthy
+    }
+
+    /// Sends the request to the endpoints until one of them answers, and returns its answer.
+    async fn with_failover<'a, T, F, Fut>(&'a self, request: F) -> Result<T, RpcError>
+    where
+        F: Fn(&'a dyn NodeRpcClient) -> Fut,
+        Fut: Future<Output = Result<T, RpcError>>,
+    {
+        self.with_failover_on(request, is_endpoint_failure).await
+    }
+
+    /// Sends the request to the endpoints until one of them answers, and returns its answer.
+    /// Endpoint failures for which `fails_over` returns `false` are returned without trying the
+    /// next endpoint.
+    async fn with_failover_on<'a, T, F, Fut>(
+        &'a self,
+        request: F,
+        fails_over: fn(&RpcError) -> bool,
+    ) -> Result<T, RpcError>
+    where
+        F: Fn(&'a dyn NodeRpcClient) -> Fut,
+        Fut: Future<Output = Result<T, RpcError>>,
+    {
+        let mut last_error = None;
+
+        for index in self.candidates() {
+            let endpoint = &self.endpoints[index];
+
+            let result = match self.fetch_genesis_commitment(endpoint).await {
+                Ok(commitment) if !self.matches_genesis_commitment(commitment) => continue,
+                Ok(_) => request(endpoint.client.as_ref()).await,
+                Err(err) => Err(err),
+            };
+
+            match result {
+                Err(err) if is_endpoint_failure(&err) => {
+                    warn!("RPC endpoint {} failed: {err}", endpoint.endpoint);
+                    endpoint.stats().record_failure();
+                    if !fails_over(&err) {
+                        return Err(err);
+                    }
+                    last_error = Some(err);
+                },
+                result => {
+                    endpoint.stats().last_failure = None;
+                    return result;
+                },
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            RpcError::ConnectionError("no RPC endpoint of the client's network is available".into())
+        }))
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl NodeRpcClient for FailoverRpcClient {
+    /// Sets the genesis commitment the endpoints must match, and sets it on the client of each
+    /// endpoint. Endpoints that can't be reached get it when they reconnect. If the genesis
+    /// commitment is already set, this method does nothing.
+    async fn set_genesis_commitment(&self, commitment: Word) -> Result<(), RpcError> {
+        {
+            let mut genesis_commitment =
+                self.genesis_commitment.lock().unwrap_or_else(PoisonError::into_inner);
+            if genesis_commitment.is_some() {
+                return Ok(());
+            }
+            *genesis_commitment = Some(commitment);
+        }
+
+        for endpoint in &self.endpoints {
+            if let Err(err) = endpoint.client.set_genesis_commitment(commitment).await {
+                warn!("Failed to set the genesis commitment of {}: {err}", endpoint.endpoint);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn submit_proven_transaction(
+        &self,
+        proven_transaction: ProvenTransaction,
+    ) -> Result<BlockNumber, RpcError> {
+        self.with_failover_on(
+            |client| client.submit_proven_transaction(proven_transaction.clone()),
+            is_unreachable_endpoint,
+        )
+        .await
+    }
+
+    async fn get_block_header_by_number(
+        &self,
+        block_num: Option<BlockNumber>,
+        include_mmr_proof: bool,
+    ) -> Result<(BlockHeader, Option<MmrProof>), RpcError> {
+        self.with_failover(|client| client.get_block_header_by_number(block_num, include_mmr_proof))
+            .await
+    }
+
+    async fn get_block_by_number(&self, block_num: BlockNumber) -> Result<ProvenBlock, RpcError> {
+        self.with_failover(|client| client.get_block_by_number(block_num)).await
+    }
+
+    async fn get_notes_by_id(&self, note_ids: &[NoteId]) -> Result<Vec<FetchedNote>, RpcError> {
+        self.with_failover(|client| client.get_notes_by_id(note_ids)).await
+    }
+
+    async fn sync_state(
+        &self,
+        block_num: BlockNumber,
+        account_ids: &[AccountId],
+        note_tags: &BTreeSet<NoteTag>,
+    ) -> Result<StateSyncInfo, RpcError> {
+        self.with_failover(|client| client.sync_state(block_num, account_ids, note_tags))
+            .await
+    }
+
+    async fn get_account_details(&self, account_id: AccountId) -> Result<FetchedAccount, RpcError> {
+        self.with_failover(|client| client.get_account_details(account_id)).await
+    }
+
+    async fn sync_notes(
+        &self,
+        block_num: BlockNumber,
+        note_tags: &BTreeSet<NoteTag>,
+    ) -> Result<NoteSyncInfo, RpcError> {
+        self.with_failover(|client| client.sync_notes(block_num, note_tags)).await
+    }
+
+    async fn check_nullifiers_by_prefix(
+        &self,
+        prefix: &[u16],
+        block_num: BlockNumber,
+    ) -> Result<Vec<NullifierUpdate>, RpcError> {
+        self.with_failover(|client| client.check_nullifiers_by_prefix(prefix, block_num))
+            .await
+    }
+
+    async fn check_nullifiers(&self, nullifiers: &[Nullifier]) -> Result<Vec<SmtProof>, RpcError> {
+        self.with_failover(|client| client.check_nullifiers(nullifiers)).await
+    }
+
+    async fn get_account_proofs(
+        &self,
+        account_storage_requests: &BTreeSet<ForeignAccount>,
+        known_account_codes: Vec<AccountCode>,
+    ) -> Result<AccountProofs, RpcError> {
+        self.with_failover(|client| {
+            client.get_account_proofs(account_storage_requests, known_account_codes.clone())
+        })
+        .await
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns whether an endpoint can be used given the genesis commitment the endpoints must
+/// match. Endpoints whose genesis block wasn't fetched yet can be used.
+fn is_compatible(stats: &EndpointStats, genesis_commitment: Option<Word>) -> bool {
+    match (stats.genesis_commitment, genesis_commitment) {
+        (Some(commitment), Some(expected)) => commitment == expected,
+        _ => true,
+    }
+}
+
+/// Returns whether the error is caused by the endpoint rather than by the request, in which case
+/// the request is sent to the next endpoint.
+fn is_endpoint_failure(err: &RpcError) -> bool {
+    matches!(
+        err,
+        RpcError::ConnectionError(_)
+            | RpcError::AcceptHeaderError(_)
+            | RpcError::GrpcError {
+                error_kind: GrpcError::Unavailable | GrpcError::DeadlineExceeded,
+                ..
+            }
+    )
+}
+
+/// Returns whether the request certainly didn't reach the node, because the endpoint couldn't be
+/// reached or rejected the client's version.
+fn is_unreachable_endpoint(err: &RpcError) -> bool {
+    matches!(err, RpcError::ConnectionError(_) | RpcError::AcceptHeaderError(_))
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::boxed::Box;
+    use alloc::collections::BTreeSet;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+    use core::sync::atomic::{AtomicU32, Ordering};
+
+    use miden_objects::account::{AccountCode, AccountId};
+    use miden_objects::block::{BlockHeader, BlockNumber, ProvenBlock};
+    use miden_objects::crypto::merkle::{MmrProof, SmtProof};
+    use miden_objects::note::{NoteId, NoteTag, Nullifier};
+    use miden_objects::transaction::ProvenTransaction;
+    use miden_objects::{Felt, Word};
+
+    use super::{FailoverRpcClient, is_unreachable_endpoint};
+    use crate::rpc::domain::account::{AccountProofs, FetchedAccount};
+    use crate::rpc::domain::note::{FetchedNote, NoteSyncInfo};
+    use crate::rpc::domain::nullifier::NullifierUpdate;
+    use crate::rpc::domain::sync::StateSyncInfo;
+    use crate::rpc::{GrpcError, NodeRpcClient, NodeRpcClientEndpoint, RpcError};
+    use crate::testing::mock::MockRpcApi;
+    use crate::transaction::ForeignAccount;
+
+    /// A node that can't be reached.
+    struct UnreachableRpcClient;
+
+    fn connection_error() -> RpcError {
+        RpcError::ConnectionError("connection refused".into())
+    }
+
+    #[async_trait::async_trait]
+    impl NodeRpcClient for UnreachableRpcClient {
+        async fn set_genesis_commitment(&self, _commitment: Word) -> Result<(), RpcError> {
+            Ok(())
+        }
+
+        async fn submit_proven_transaction(
+            &self,
+            _proven_transaction: ProvenTransaction,
+        ) -> Result<BlockNumber, RpcError> {
+            Err(connection_error())
+        }
+
+        async fn get_block_header_by_number(
+            &self,
+            _block_num: Option<BlockNumber>,
+            _include_mmr_proof: bool,
+        ) -> Result<(BlockHeader, Option<MmrProof>), RpcError> {
+            Err(connection_error())
+        }
+
+        async fn get_block_by_number(
+            &self,
+            _block_num: BlockNumber,
+        ) -> Result<ProvenBlock, RpcError> {
+            Err(connection_error())
+        }
+
+        async fn get_notes_by_id(
+            &self,
+            _note_ids: &[NoteId],
+        ) -> Result<Vec<FetchedNote>, RpcError> {
+            Err(connection_error())
+        }
+
+        async fn sync_state(
+            &self,
+            _block_num: BlockNumber,
+            _account_ids: &[AccountId],
+            _note_tags: &BTreeSet<NoteTag>,
+        ) -> Result<StateSyncInfo, RpcError> {
+            Err(connection_error())
+        }
+
+        async fn get_account_details(
+            &self,
+            _account_id: AccountId,
+        ) -> Result<FetchedAccount, RpcError> {
+            Err(connection_error())
+        }
+
+        async fn sync_notes(
+            &self,
+            _block_num: BlockNumber,
+            _note_tags: &BTreeSet<NoteTag>,
+        ) -> Result<NoteSyncInfo, RpcError> {
+            Err(connection_error())
+        }
+
+        async fn check_nullifiers_by_prefix(
+            &self,
+            _prefix: &[u16],
+            _block_num: BlockNumber,
+        ) -> Result<Vec<NullifierUpdate>, RpcError> {
+            Err(connection_error())
+        }
+
+        async fn check_nullifiers(
+            &self,
+            _nullifiers: &[Nullifier],
+        ) -> Result<Vec<SmtProof>, RpcError> {
+            Err(connection_error())
+        }
+
+        async fn get_account_proofs(
+            &self,
+            _account_storage_requests: &BTreeSet<ForeignAccount>,
+            _known_account_codes: Vec<AccountCode>,
+        ) -> Result<AccountProofs, RpcError> {
+            Err(connection_error())
+        }
+    }
+
+    #[tokio::test]
+    async fn requests_fail_over_to_reachable_endpoints() {
+        let mock_rpc_api = MockRpcApi::default();
+        let rpc_client = FailoverRpcClient::from_clients([
+            (
+                "unreachable".to_string(),
+                Box::new(UnreachableRpcClient) as Box<dyn NodeRpcClient>,
+            ),
+            ("mock".to_string(), Box::new(mock_rpc_api.clone()) as Box<dyn NodeRpcClient>),
+        ]);
+
+        let sync_info = rpc_client
+            .sync_state(BlockNumber::GENESIS, &[], &BTreeSet::new())
+            .await
+            .unwrap();
+        assert_eq!(sync_info.chain_tip, mock_rpc_api.get_chain_tip_block_num());
+
+        let status = rpc_client.status();
+        assert!(!status[0].healthy);
+        assert_eq!(status[0].failures, 1);
+        assert!(status[1].healthy);
+        assert!(status[1].genesis_commitment.is_some());
+
+        // The failed endpoint is tried last until it can be retried.
+        assert_eq!(rpc_client.candidates(), [1, 0]);
+
+        let status = rpc_client.check_health().await;
+        assert_eq!(status[1].chain_tip, Some(mock_rpc_api.get_chain_tip_block_num()));
+    }
+
+    #[tokio::test]
+    async fn submissions_do_not_fail_over_after_timeouts() {
+        let rpc_client = FailoverRpcClient::from_clients([
+            ("first".to_string(), Box::new(MockRpcApi::default()) as Box<dyn NodeRpcClient>),
+            ("second".to_string(), Box::new(MockRpcApi::default()) as Box<dyn NodeRpcClient>),
+        ]);
+
+        let submit = async |error: fn() -> RpcError| {
+            let attempts = AtomicU32::new(0);
+            let result: Result<(), RpcError> = rpc_client
+                .with_failover_on(
+                    |_| {
+                        attempts.fetch_add(1, Ordering::Relaxed);
+                        async move { Err(error()) }
+                    },
+                    is_unreachable_endpoint,
+                )
+                .await;
+            assert!(result.is_err());
+            attempts.load(Ordering::Relaxed)
+        };
+
+        // The first node may have accepted a transaction it didn't answer in time
+        let timeout = || RpcError::GrpcError {
+            endpoint: NodeRpcClientEndpoint::SubmitProvenTx,
+            error_kind: GrpcError::DeadlineExceeded,
+            source: None,
+        };
+        assert_eq!(submit(timeout).await, 1);
+        assert_eq!(submit(connection_error).await, 2);
+    }
+
+    #[tokio::test]
+    async fn endpoints_of_another_network_are_not_used() {
+        let rpc_client = FailoverRpcClient::from_clients([(
+            "mock".to_string(),
+            Box::new(MockRpcApi::default()) as Box<dyn NodeRpcClient>,
+        )]);
+
+        let other_genesis = Word::from([Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)]);
+        rpc_client.set_genesis_commitment(other_genesis).await.unwrap();
+
+        let result = rpc_client.get_block_header_by_number(None, false).await;
+        assert!(matches!(result, Err(RpcError::ConnectionError(_))));
+
+        let status = rpc_client.status();
+        assert!(!status[0].compatible);
+        assert!(rpc_client.candidates().is_empty());
+    }
+}
@@ -0,0 +1,495 @@
+//! Provides [`MetricsRpcClient`], a [`NodeRpcClient`] that measures the requests sent with each
+//! RPC method.
+//!
+//! For each method, the [`RpcMetrics`] keep the number of requests, the failures by
+//! [`RpcError::kind`], the time spent waiting for responses, and the size of the requests and
+//! responses. Sizes are those of the serialized domain types, which approximate the size of the
+//! gRPC messages. Each request also runs in a `debug` tracing span named after its method, which
+//! records the same measurements.
+//!
+//! The metrics can be exported periodically with an [`RpcMetricsExporter`], such as the
+//! [`PrometheusFileExporter`].
+
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt::Write;
+use core::future::Future;
+use core::time::Duration;
+use std::path::PathBuf;
+use std::sync::{Mutex, PoisonError};
+use std::time::Instant;
+
+use miden_objects::Word;
+use miden_objects::account::{AccountCode, AccountId};
+use miden_objects::block::{BlockHeader, BlockNumber, ProvenBlock};
+use miden_objects::crypto::merkle::{MmrProof, SmtProof};
+use miden_objects::note::{NoteId, NoteTag, Nullifier};
+use miden_objects::transaction::ProvenTransaction;
+use tracing::field::Empty;
+use tracing::{Instrument, debug_span};
+
+use super::domain::account::{AccountProofs, FetchedAccount};
+use super::domain::note::{FetchedNote, NoteSyncInfo};
+use super::domain::nullifier::NullifierUpdate;
+use super::domain::sync::StateSyncInfo;
+use super::recording::{
+    account_proofs_request,
+    block_header_request,
+    nullifiers_by_prefix_request,
+    sync_notes_request,
+    sync_state_request,
+    write_optional_mmr_proof,
+};
+use super::{NodeRpcClient, NodeRpcClientEndpoint, RpcError};
+use crate::transaction::ForeignAccount;
+use crate::utils::Serializable;
+
+// RPC METRICS
+// ================================================================================================
+
+/// Metrics of the requests sent with an RPC method.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RpcMethodMetrics {
+    /// Number of requests sent, including the failed ones.
+    pub requests: u64,
+    /// Number of failed requests, by [`RpcError::kind`].
+    pub errors: BTreeMap<&'static str, u64>,
+    /// Total time spent waiting for responses.
+    pub total_latency: Duration,
+    /// Longest time spent waiting for a response.
+    pub max_latency: Duration,
+    /// Total size of the requests, in bytes.
+    pub request_bytes: u64,
+    /// Total size of the successful responses, in bytes.
+    pub response_bytes: u64,
+}
+
+impl RpcMethodMetrics {
+    /// Returns the number of failed requests.
+    pub fn failures(&self) -> u64 {
+        self.errors.values().sum()
+    }
+
+    /// Returns the mean time spent waiting for a response, if any request was sent.
+    pub fn mean_latency(&self) -> Option<Duration> {
+        let requests = u32::try_from(self.requests).ok().filter(|requests| *requests > 0)?;
+        Some(self.total_latency / requests)
+    }
+}
+
+/// Metrics of the requests sent by a [`MetricsRpcClient`], by RPC method.
+///
+/// The metrics are shared, so they can be read while the client is in use, for instance to
+/// export them periodically.
+#[derive(Debug, Default)]
+pub struct RpcMetrics {
+    methods: Mutex<BTreeMap<NodeRpcClientEndpoint, RpcMethodMetrics>>,
+}
+
+impl RpcMetrics {
+    /// Returns empty metrics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the metrics of each RPC method that was called.
+    pub fn snapshot(&self) -> BTreeMap<NodeRpcClientEndpoint, RpcMethodMetrics> {
+        self.methods.lock().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Returns the metrics of an RPC method, if it was called.
+    pub fn get(&self, endpoint: NodeRpcClientEndpoint) -> Option<RpcMethodMetrics> {
+        self.methods
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&endpoint)
+            .cloned()
+    }
+
+    /// Removes the metrics of every RPC method.
+    pub fn reset(&self) {
+        self.methods.lock().unwrap_or_else(PoisonError::into_inner).clear();
+    }
+
+    /// Returns the metrics in the Prometheus text exposition format, labelled by RPC method.
+    ///
+    /// The output can be served by an HTTP endpoint or written to a file read by the textfile
+    /// collector of the Prometheus node exporter.
+    pub fn to_prometheus_text(&self) -> String {
+        let methods = self.snapshot();
+        let mut text = String::new();
+
+        let mut write_counter = |name: &str, help: &str, value: fn(&RpcMethodMetrics) -> String| {
+            _ = writeln!(text, "# HELP {name} {help}");
+            _ = writeln!(text, "# TYPE {name} counter");
+            for (endpoint, metrics) in &methods {
+                _ = writeln!(text, "{name}{{method=\"{endpoint}\"}} {}", value(metrics));
+            }
+        };
+        write_counter("miden_rpc_requests_total", "Number of RPC requests sent.", |metrics| {
+            metrics.requests.to_string()
+        });
+        write_counter(
+            "miden_rpc_request_bytes_total",
+            "Total size of the RPC requests, in bytes.",
+            |metrics| metrics.request_bytes.to_string(),
+        );
+        write_counter(
+            "miden_rpc_response_bytes_total",
+            "Total size of the RPC responses, in bytes.",
+            |metrics| metrics.response_bytes.to_string(),
+        );
+
+        _ = writeln!(text, "# HELP miden_rpc_errors_total Number of failed RPC requests.");
+        _ = writeln!(text, "# TYPE miden_rpc_errors_total counter");
+        for (endpoint, metrics) in &methods {
+            for (kind, errors) in &metrics.errors {
+                _ = writeln!(
+                    text,
+                    "miden_rpc_errors_total{{method=\"{endpoint}\",kind=\"{kind}\"}} {errors}"
+                );
+            }
+        }
+
+        _ = writeln!(
+            text,
+            "# HELP miden_rpc_request_duration_seconds Time spent waiting for RPC responses."
+        );
+        _ = writeln!(text, "# TYPE miden_rpc_request_duration_seconds summary");
+        for (endpoint, metrics) in &methods {
+            _ = writeln!(
+                text,
+                "miden_rpc_request_duration_seconds_sum{{method=\"{endpoint}\"}} {}",
+                metrics.total_latency.as_secs_f64()
+            );
+            _ = writeln!(
+                text,
+                "miden_rpc_request_duration_seconds_count{{method=\"{endpoint}\"}} {}",
+                metrics.requests
+            );
+        }
+
+        _ = writeln!(
+            text,
+            "# HELP miden_rpc_request_duration_seconds_max Longest time spent waiting for an RPC \
+             response."
+        );
+        _ = writeln!(text, "# TYPE miden_rpc_request_duration_seconds_max gauge");
+        for (endpoint, metrics) in &methods {
+            _ = writeln!(
+                text,
+                "miden_rpc_request_duration_seconds_max{{method=\"{endpoint}\"}} {}",
+                metrics.max_latency.as_secs_f64()
+            );
+        }
+
+        text
+    }
+
+    /// Records a request sent with an RPC method, along with the size of its response, or the
+    /// error it failed with.
+    fn record(
+        &self,
+        endpoint: NodeRpcClientEndpoint,
+        latency: Duration,
+        request_size: usize,
+        response: Result<usize, &RpcError>,
+    ) {
+        let mut methods = self.methods.lock().unwrap_or_else(PoisonError::into_inner);
+        let metrics = methods.entry(endpoint).or_default();
+
+        metrics.requests += 1;
+        metrics.total_latency += latency;
+        metrics.max_latency = metrics.max_latency.max(latency);
+        metrics.request_bytes += request_size as u64;
+        match response {
+            Ok(response_size) => metrics.response_bytes += response_size as u64,
+            Err(err) => *metrics.errors.entry(err.kind()).or_default() += 1,
+        }
+    }
+}
+
+// METRICS EXPORT
+// ================================================================================================
+
+/// Destination to which [`RpcMetrics`] are exported, for instance after every sync of the
+/// background task started with
+/// [`SharedClient::start_auto_sync_with_metrics`](crate::sync::SharedClient::start_auto_sync_with_metrics).
+pub trait RpcMetricsExporter {
+    /// Exports the current value of the metrics.
+    fn export(&self, metrics: &RpcMetrics) -> Result<(), Box<dyn Error + Send + Sync + 'static>>;
+}
+
+/// Exporter that writes the metrics to a file in the Prometheus text format, for the textfile
+/// collector of the Prometheus node exporter.
+#[derive(Clone, Debug)]
+pub struct PrometheusFileExporter {
+    path: PathBuf,
+}
+
+impl PrometheusFileExporter {
+    /// Returns an exporter that writes the metrics to the file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl RpcMetricsExporter for PrometheusFileExporter {
+    /// Replaces the file at once, so that collectors reading it never see a partial write.
+    fn export(&self, metrics: &RpcMetrics) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+        let temp_file = self.path.with_extension("tmp");
+        std::fs::write(&temp_file, metrics.to_prometheus_text())?;
+        std::fs::rename(temp_file, &self.path)?;
+        Ok(())
+    }
+}
+
+// METRICS RPC CLIENT
+// ================================================================================================
+
+/// A [`NodeRpcClient`] that sends the requests to another client and measures them in its
+/// [`RpcMetrics`].
+pub struct MetricsRpcClient {
+    inner: Arc<dyn NodeRpcClient>,
+    metrics: Arc<RpcMetrics>,
+}
+
+impl MetricsRpcClient {
+    /// Returns a client that measures the requests sent to `inner`.
+    pub fn new(inner: Arc<dyn NodeRpcClient>) -> Self {
+        Self {
+            inner,
+            metrics: Arc::new(RpcMetrics::new()),
+        }
+    }
+
+    /// Returns the metrics of the requests sent so far.
+    pub fn metrics(&self) -> Arc<RpcMetrics> {
+        self.metrics.clone()
+    }
+
+    /// Sends a request with the RPC method in a tracing span, and records it in the metrics and
+    /// in the span.
+    async fn with_metrics<T>(
+        &self,
+        endpoint: NodeRpcClientEndpoint,
+        request_size: usize,
+        response_size: impl FnOnce(&T) -> usize,
+        request: impl Future<Output = Result<T, RpcError>>,
+    ) -> Result<T, RpcError> {
+        let span = debug_span!(
+            "rpc_request",
+            method = %endpoint,
+            request_bytes = request_size,
+            response_bytes = Empty,
+            error_kind = Empty,
+        );
+
+        let start = Instant::now();
+        let result = request.instrument(span.clone()).await;
+        let response = result.as_ref().map(response_size);
+        match response {
+            Ok(response_size) => span.record("response_bytes", response_size),
+            Err(err) => span.record("error_kind", err.kind()),
+        };
+        self.metrics.record(endpoint, start.elapsed(), request_size, response);
+
+        result
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl NodeRpcClient for MetricsRpcClient {
+    async fn set_genesis_commitment(&self, commitment: Word) -> Result<(), RpcError> {
+        self.inner.set_genesis_commitment(commitment).await
+    }
+
+    async fn submit_proven_transaction(
+        &self,
+        proven_transaction: ProvenTransaction,
+    ) -> Result<BlockNumber, RpcError> {
+        let request_size = proven_transaction.to_bytes().len();
+        self.with_metrics(
+            NodeRpcClientEndpoint::SubmitProvenTx,
+            request_size,
+            serialized_size,
+            self.inner.submit_proven_transaction(proven_transaction),
+        )
+        .await
+    }
+
+    async fn get_block_header_by_number(
+        &self,
+        block_num: Option<BlockNumber>,
+        include_mmr_proof: bool,
+    ) -> Result<(BlockHeader, Option<MmrProof>), RpcError> {
+        self.with_metrics(
+            NodeRpcClientEndpoint::GetBlockHeaderByNumber,
+            block_header_request(block_num, include_mmr_proof).len(),
+            |(block_header, mmr_proof)| {
+                let mut response = block_header.to_bytes();
+                write_optional_mmr_proof(mmr_proof.as_ref(), &mut response);
+                response.len()
+            },
+            self.inner.get_block_header_by_number(block_num, include_mmr_proof),
+        )
+        .await
+    }
+
+    async fn get_block_by_number(&self, block_num: BlockNumber) -> Result<ProvenBlock, RpcError> {
+        self.with_metrics(
+            NodeRpcClientEndpoint::GetBlockByNumber,
+            serialized_size(&block_num),
+            serialized_size,
+            self.inner.get_block_by_number(block_num),
+        )
+        .await
+    }
+
+    async fn get_notes_by_id(&self, note_ids: &[NoteId]) -> Result<Vec<FetchedNote>, RpcError> {
+        self.with_metrics(
+            NodeRpcClientEndpoint::GetNotesById,
+            serialized_size(&note_ids.to_vec()),
+            serialized_size,
+            self.inner.get_notes_by_id(note_ids),
+        )
+        .await
+    }
+
+    async fn sync_state(
+        &self,
+        block_num: BlockNumber,
+        account_ids: &[AccountId],
+        note_tags: &BTreeSet<NoteTag>,
+    ) -> Result<StateSyncInfo, RpcError> {
+        self.with_metrics(
+            NodeRpcClientEndpoint::SyncState,
+            sync_state_request(block_num, account_ids, note_tags).len(),
+            serialized_size,
+            self.inner.sync_state(block_num, account_ids, note_tags),
+        )
+        .await
+    }
+
+    async fn get_account_details(&self, account_id: AccountId) -> Result<FetchedAccount, RpcError> {
+        self.with_metrics(
+            NodeRpcClientEndpoint::GetAccountDetails,
+            serialized_size(&account_id),
+            serialized_size,
+            self.inner.get_account_details(account_id),
+        )
+        .await
+    }
+
+    async fn sync_notes(
+        &self,
+        block_num: BlockNumber,
+        note_tags: &BTreeSet<NoteTag>,
+    ) -> Result<NoteSyncInfo, RpcError> {
+        self.with_metrics(
+            NodeRpcClientEndpoint::SyncNotes,
+            sync_notes_request(block_num, note_tags).len(),
+            serialized_size,
+            self.inner.sync_notes(block_num, note_tags),
+        )
+        .await
+    }
+
+    async fn check_nullifiers_by_prefix(
+        &self,
+        prefix: &[u16],
+        block_num: BlockNumber,
+    ) -> Result<Vec<NullifierUpdate>, RpcError> {
+        self.with_metrics(
+            NodeRpcClientEndpoint::CheckNullifiersByPrefix,
+            nullifiers_by_prefix_request(prefix, block_num).len(),
+            serialized_size,
+            self.inner.check_nullifiers_by_prefix(prefix, block_num),
+        )
+        .await
+    }
+
+    async fn check_nullifiers(&self, nullifiers: &[Nullifier]) -> Result<Vec<SmtProof>, RpcError> {
+        self.with_metrics(
+            NodeRpcClientEndpoint::CheckNullifiers,
+            serialized_size(&nullifiers.to_vec()),
+            serialized_size,
+            self.inner.check_nullifiers(nullifiers),
+        )
+        .await
+    }
+
+    async fn get_account_proofs(
+        &self,
+        account_storage_requests: &BTreeSet<ForeignAccount>,
+        known_account_codes: Vec<AccountCode>,
+    ) -> Result<AccountProofs, RpcError> {
+        let request_size =
+            account_proofs_request(account_storage_requests, &known_account_codes).len();
+        self.with_metrics(
+            NodeRpcClientEndpoint::GetAccountProofs,
+            request_size,
+            |(block_num, account_proofs)| {
+                serialized_size(block_num) + serialized_size(account_proofs)
+            },
+            self.inner.get_account_proofs(account_storage_requests, known_account_codes),
+        )
+        .await
+    }
+}
+
+/// Returns the size of the serialized value, in bytes.
+fn serialized_size<T: Serializable>(value: &T) -> usize {
+    value.to_bytes().len()
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::sync::Arc;
+
+    use miden_objects::block::BlockNumber;
+
+    use super::MetricsRpcClient;
+    use crate::rpc::{NodeRpcClient, NodeRpcClientEndpoint, RecordingRpcClient, ReplayRpcClient};
+    use crate::testing::mock::MockRpcApi;
+
+    #[tokio::test]
+    async fn requests_are_measured_by_method() {
+        // Only the chain tip is recorded, so other requests fail
+        let recorder = RecordingRpcClient::new(Arc::new(MockRpcApi::default()));
+        recorder.get_block_header_by_number(None, false).await.unwrap();
+        let replay = ReplayRpcClient::new(recorder.fixture());
+
+        let client = MetricsRpcClient::new(Arc::new(replay));
+        client.get_block_header_by_number(None, false).await.unwrap();
+        client.get_block_header_by_number(None, false).await.unwrap();
+        assert!(client.get_block_by_number(BlockNumber::GENESIS).await.is_err());
+
+        let metrics = client.metrics();
+        let header_metrics = metrics.get(NodeRpcClientEndpoint::GetBlockHeaderByNumber).unwrap();
+        assert_eq!(header_metrics.requests, 2);
+        assert_eq!(header_metrics.failures(), 0);
+        assert!(header_metrics.request_bytes > 0);
+        assert!(header_metrics.response_bytes > 0);
+
+        let block_metrics = metrics.get(NodeRpcClientEndpoint::GetBlockByNumber).unwrap();
+        assert_eq!(block_metrics.requests, 1);
+        assert_eq!(block_metrics.errors.get("connection"), Some(&1));
+        assert_eq!(block_metrics.response_bytes, 0);
+        assert!(metrics.get(NodeRpcClientEndpoint::SyncState).is_none());
+
+        let text = metrics.to_prometheus_text();
+        assert!(text.contains("miden_rpc_requests_total{method=\"get_block_header_by_number\"} 2"));
+        assert!(text.contains(
+            "miden_rpc_errors_total{method=\"get_block_by_number\",kind=\"connection\"} 1"
+        ));
+    }
+}
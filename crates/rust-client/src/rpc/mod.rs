@@ -44,6 +44,7 @@ use alloc::collections::BTreeSet;
 use alloc::string::String;
 use alloc::vec::Vec;
 use core::fmt;
+use core::str::FromStr;
 
 use domain::account::{AccountProofs, FetchedAccount};
 use domain::note::{FetchedNote, NoteSyncInfo};
@@ -79,6 +80,30 @@ mod tonic_client;
 #[cfg(any(feature = "tonic", feature = "web-tonic"))]
 pub use tonic_client::TonicRpcClient;
 
+#[cfg(feature = "tonic")]
+mod failover;
+#[cfg(feature = "tonic")]
+pub use failover::{DEFAULT_RPC_RETRY_AFTER, FailoverRpcClient, RpcEndpointStatus};
+
+#[cfg(feature = "tonic")]
+mod retry;
+#[cfg(feature = "tonic")]
+pub use retry::{RetryingRpcClient, RpcRateLimit, RpcRetryPolicy};
+
+#[cfg(feature = "tonic")]
+mod metrics;
+#[cfg(feature = "tonic")]
+pub use metrics::{
+    MetricsRpcClient,
+    PrometheusFileExporter,
+    RpcMethodMetrics,
+    RpcMetrics,
+    RpcMetricsExporter,
+};
+
+mod recording;
+pub use recording::{RecordingRpcClient, ReplayRpcClient, RpcFixture};
+
 use crate::store::InputNoteRecord;
 use crate::store::input_note_states::UnverifiedNoteState;
 use crate::transaction::ForeignAccount;
@@ -303,7 +328,7 @@ pub trait NodeRpcClient: Send + Sync {
 // ================================================================================================
 //
 /// RPC methods for the Miden protocol.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum NodeRpcClientEndpoint {
     CheckNullifiers,
     CheckNullifiersByPrefix,
@@ -339,3 +364,25 @@ impl fmt::Display for NodeRpcClientEndpoint {
         }
     }
 }
+
+impl FromStr for NodeRpcClientEndpoint {
+    type Err = String;
+
+    /// Parses the name of an RPC method, as displayed.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "check_nullifiers" => Ok(NodeRpcClientEndpoint::CheckNullifiers),
+            "check_nullifiers_by_prefix" => Ok(NodeRpcClientEndpoint::CheckNullifiersByPrefix),
+            "get_account_details" => Ok(NodeRpcClientEndpoint::GetAccountDetails),
+            "get_account_state_delta" => Ok(NodeRpcClientEndpoint::GetAccountStateDelta),
+            "get_account_proofs" => Ok(NodeRpcClientEndpoint::GetAccountProofs),
+            "get_block_by_number" => Ok(NodeRpcClientEndpoint::GetBlockByNumber),
+            "get_block_header_by_number" => Ok(NodeRpcClientEndpoint::GetBlockHeaderByNumber),
+            "get_notes_by_id" => Ok(NodeRpcClientEndpoint::GetNotesById),
+            "sync_state" => Ok(NodeRpcClientEndpoint::SyncState),
+            "submit_proven_transaction" => Ok(NodeRpcClientEndpoint::SubmitProvenTx),
+            "sync_notes" => Ok(NodeRpcClientEndpoint::SyncNotes),
+            _ => Err(format!("unknown RPC method `{name}`")),
+        }
+    }
+}
@@ -0,0 +1,983 @@
+//! Provides [`RecordingRpcClient`] and [`ReplayRpcClient`], which turn the traffic between a client
+//! and a node into an [`RpcFixture`] and serve it back without a node.
+//!
+//! A recording client wraps another [`NodeRpcClient`] and keeps each request along with its
+//! response, or with the kind and message of the error it failed with. The fixture can then be
+//! written to a file, for instance to attach a failing sync against testnet to a bug report, and
+//! loaded into a replay client for an offline regression test. Requests are matched by RPC method
+//! and by their exact arguments, so the replayed client has to track the same accounts and notes
+//! as the recorded one.
+
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+use miden_objects::Word;
+use miden_objects::account::{
+    Account,
+    AccountCode,
+    AccountHeader,
+    AccountId,
+    AccountStorageHeader,
+};
+use miden_objects::block::{AccountWitness, BlockHeader, BlockNumber, ProvenBlock};
+use miden_objects::crypto::merkle::{
+    Forest,
+    MerklePath,
+    MmrDelta,
+    MmrProof,
+    SmtProof,
+    SparseMerklePath,
+};
+use miden_objects::note::{Note, NoteId, NoteInclusionProof, NoteMetadata, NoteTag, Nullifier};
+use miden_objects::transaction::{ProvenTransaction, TransactionId};
+use miden_objects::utils::SliceReader;
+
+use super::domain::account::{
+    AccountProof,
+    AccountProofs,
+    AccountUpdateSummary,
+    FetchedAccount,
+    StateHeaders,
+};
+use super::domain::note::{CommittedNote, FetchedNote, NoteSyncInfo};
+use super::domain::nullifier::NullifierUpdate;
+use super::domain::sync::StateSyncInfo;
+use super::domain::transaction::TransactionInclusion;
+use super::{GrpcError, NodeRpcClient, NodeRpcClientEndpoint, RpcError};
+use crate::transaction::ForeignAccount;
+use crate::utils::{
+    ByteReader,
+    ByteWriter,
+    Deserializable,
+    DeserializationError,
+    RwLock,
+    Serializable,
+};
+
+/// Version of the fixture encoding, written at the start of each fixture.
+const FIXTURE_VERSION: u8 = 1;
+
+// RPC FIXTURE
+// ================================================================================================
+
+/// A request sent to a node and the node's response, both serialized, or the error the request
+/// failed with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct RecordedCall {
+    endpoint: NodeRpcClientEndpoint,
+    request: Vec<u8>,
+    response: Result<Vec<u8>, RecordedError>,
+}
+
+/// An error a recorded request failed with, as its kind, given by [`RpcError::kind`], and its
+/// message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct RecordedError {
+    kind: String,
+    message: String,
+}
+
+impl RecordedError {
+    /// Returns the error replayed for a request to the endpoint. Errors whose kind can't be
+    /// rebuilt are replayed as [`RpcError::InvalidResponse`] with their kind and message.
+    fn to_rpc_error(&self, endpoint: NodeRpcClientEndpoint) -> RpcError {
+        let message = self.message.clone();
+        if let Some(error_kind) = GrpcError::from_kind(&self.kind, message.clone()) {
+            let source = (!message.is_empty()).then(|| message.into());
+            return RpcError::GrpcError { endpoint, error_kind, source };
+        }
+
+        match self.kind.as_str() {
+            "connection" => RpcError::ConnectionError(message.into()),
+            "deserialization" => RpcError::DeserializationError(message),
+            "missing_data" => RpcError::ExpectedDataMissing(message),
+            "invalid_response" => RpcError::InvalidResponse(message),
+            kind => RpcError::InvalidResponse(format!("{kind}: {message}")),
+        }
+    }
+}
+
+impl From<&RpcError> for RecordedError {
+    fn from(err: &RpcError) -> Self {
+        let message = match err {
+            RpcError::ConnectionError(source) => source.to_string(),
+            RpcError::DeserializationError(message)
+            | RpcError::ExpectedDataMissing(message)
+            | RpcError::InvalidResponse(message)
+            | RpcError::GrpcError {
+                error_kind: GrpcError::Unknown(message), ..
+            } => message.clone(),
+            RpcError::GrpcError { source, .. } => {
+                source.as_ref().map(ToString::to_string).unwrap_or_default()
+            },
+            err => err.to_string(),
+        };
+
+        Self { kind: err.kind().to_string(), message }
+    }
+}
+
+impl Serializable for RecordedError {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.kind.write_into(target);
+        self.message.write_into(target);
+    }
+}
+
+impl Deserializable for RecordedError {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            kind: String::read_from(source)?,
+            message: String::read_from(source)?,
+        })
+    }
+}
+
+/// The requests sent by a [`RecordingRpcClient`] and the responses it received, in order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RpcFixture {
+    calls: Vec<RecordedCall>,
+}
+
+impl RpcFixture {
+    /// Returns the number of recorded requests.
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Returns whether no request was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    /// Returns the RPC method of each recorded request, in order.
+    pub fn endpoints(&self) -> Vec<NodeRpcClientEndpoint> {
+        self.calls.iter().map(|call| call.endpoint).collect()
+    }
+
+    /// Writes the fixture to a file, replacing it if it exists.
+    #[cfg(feature = "std")]
+    pub fn write_to_file(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_bytes())
+    }
+
+    /// Reads a fixture written with [`RpcFixture::write_to_file`].
+    #[cfg(feature = "std")]
+    pub fn read_from_file(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let bytes = std::fs::read(path)?;
+        Self::read_from_bytes(&bytes)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+    }
+}
+
+impl Serializable for RpcFixture {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(FIXTURE_VERSION);
+        target.write_usize(self.calls.len());
+        for call in &self.calls {
+            let endpoint = call.endpoint.to_string();
+            target.write_usize(endpoint.len());
+            target.write_bytes(endpoint.as_bytes());
+            call.request.write_into(target);
+            match &call.response {
+                Ok(response) => {
+                    target.write_bool(true);
+                    response.write_into(target);
+                },
+                Err(err) => {
+                    target.write_bool(false);
+                    err.write_into(target);
+                },
+            }
+        }
+    }
+}
+
+impl Deserializable for RpcFixture {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let version = source.read_u8()?;
+        if version != FIXTURE_VERSION {
+            return Err(DeserializationError::InvalidValue(format!(
+                "unsupported RPC fixture version {version}"
+            )));
+        }
+
+        let num_calls = source.read_usize()?;
+        let mut calls = Vec::new();
+        for _ in 0..num_calls {
+            let endpoint_len = source.read_usize()?;
+            let endpoint = String::from_utf8(source.read_vec(endpoint_len)?)
+                .map_err(|err| DeserializationError::InvalidValue(err.to_string()))?;
+            let endpoint = endpoint.parse().map_err(DeserializationError::InvalidValue)?;
+
+            let request = Vec::<u8>::read_from(source)?;
+            let response = if source.read_bool()? {
+                Ok(Vec::<u8>::read_from(source)?)
+            } else {
+                Err(RecordedError::read_from(source)?)
+            };
+
+            calls.push(RecordedCall { endpoint, request, response });
+        }
+
+        Ok(Self { calls })
+    }
+}
+
+// RECORDING RPC CLIENT
+// ================================================================================================
+
+/// A [`NodeRpcClient`] that sends the requests to another client and records each request along
+/// with its response or error.
+pub struct RecordingRpcClient {
+    inner: Arc<dyn NodeRpcClient>,
+    calls: RwLock<Vec<RecordedCall>>,
+}
+
+impl RecordingRpcClient {
+    /// Returns a client that records the requests sent to `inner`.
+    pub fn new(inner: Arc<dyn NodeRpcClient>) -> Self {
+        Self { inner, calls: RwLock::new(Vec::new()) }
+    }
+
+    /// Returns the requests recorded so far.
+    pub fn fixture(&self) -> RpcFixture {
+        RpcFixture { calls: self.calls.read().clone() }
+    }
+
+    fn record<T: Serializable>(
+        &self,
+        endpoint: NodeRpcClientEndpoint,
+        request: Vec<u8>,
+        result: &Result<T, RpcError>,
+    ) {
+        self.record_with(endpoint, request, result, T::to_bytes);
+    }
+
+    /// Records the request along with its response, serialized by `write_response`, or with its
+    /// error.
+    fn record_with<T>(
+        &self,
+        endpoint: NodeRpcClientEndpoint,
+        request: Vec<u8>,
+        result: &Result<T, RpcError>,
+        write_response: impl FnOnce(&T) -> Vec<u8>,
+    ) {
+        let response = match result {
+            Ok(response) => Ok(write_response(response)),
+            Err(err) => Err(RecordedError::from(err)),
+        };
+        self.calls.write().push(RecordedCall { endpoint, request, response });
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl NodeRpcClient for RecordingRpcClient {
+    async fn set_genesis_commitment(&self, commitment: Word) -> Result<(), RpcError> {
+        self.inner.set_genesis_commitment(commitment).await
+    }
+
+    async fn submit_proven_transaction(
+        &self,
+        proven_transaction: ProvenTransaction,
+    ) -> Result<BlockNumber, RpcError> {
+        let request = proven_transaction.to_bytes();
+        let result = self.inner.submit_proven_transaction(proven_transaction).await;
+        self.record(NodeRpcClientEndpoint::SubmitProvenTx, request, &result);
+        result
+    }
+
+    async fn get_block_header_by_number(
+        &self,
+        block_num: Option<BlockNumber>,
+        include_mmr_proof: bool,
+    ) -> Result<(BlockHeader, Option<MmrProof>), RpcError> {
+        let result = self.inner.get_block_header_by_number(block_num, include_mmr_proof).await;
+        self.record_with(
+            NodeRpcClientEndpoint::GetBlockHeaderByNumber,
+            block_header_request(block_num, include_mmr_proof),
+            &result,
+            |(block_header, mmr_proof)| {
+                let mut response = block_header.to_bytes();
+                write_optional_mmr_proof(mmr_proof.as_ref(), &mut response);
+                response
+            },
+        );
+        result
+    }
+
+    async fn get_block_by_number(&self, block_num: BlockNumber) -> Result<ProvenBlock, RpcError> {
+        let result = self.inner.get_block_by_number(block_num).await;
+        self.record(NodeRpcClientEndpoint::GetBlockByNumber, block_num.to_bytes(), &result);
+        result
+    }
+
+    async fn get_notes_by_id(&self, note_ids: &[NoteId]) -> Result<Vec<FetchedNote>, RpcError> {
+        let result = self.inner.get_notes_by_id(note_ids).await;
+        self.record(NodeRpcClientEndpoint::GetNotesById, note_ids.to_vec().to_bytes(), &result);
+        result
+    }
+
+    async fn sync_state(
+        &self,
+        block_num: BlockNumber,
+        account_ids: &[AccountId],
+        note_tags: &BTreeSet<NoteTag>,
+    ) -> Result<StateSyncInfo, RpcError> {
+        let result = self.inner.sync_state(block_num, account_ids, note_tags).await;
+        self.record(
+            NodeRpcClientEndpoint::SyncState,
+            sync_state_request(block_num, account_ids, note_tags),
+            &result,
+        );
+        result
+    }
+
+    async fn get_account_details(&self, account_id: AccountId) -> Result<FetchedAccount, RpcError> {
+        let result = self.inner.get_account_details(account_id).await;
+        self.record(NodeRpcClientEndpoint::GetAccountDetails, account_id.to_bytes(), &result);
+        result
+    }
+
+    async fn sync_notes(
+        &self,
+        block_num: BlockNumber,
+        note_tags: &BTreeSet<NoteTag>,
+    ) -> Result<NoteSyncInfo, RpcError> {
+        let result = self.inner.sync_notes(block_num, note_tags).await;
+        self.record(
+            NodeRpcClientEndpoint::SyncNotes,
+            sync_notes_request(block_num, note_tags),
+            &result,
+        );
+        result
+    }
+
+    async fn check_nullifiers_by_prefix(
+        &self,
+        prefix: &[u16],
+        block_num: BlockNumber,
+    ) -> Result<Vec<NullifierUpdate>, RpcError> {
+        let result = self.inner.check_nullifiers_by_prefix(prefix, block_num).await;
+        self.record(
+            NodeRpcClientEndpoint::CheckNullifiersByPrefix,
+            nullifiers_by_prefix_request(prefix, block_num),
+            &result,
+        );
+        result
+    }
+
+    async fn check_nullifiers(&self, nullifiers: &[Nullifier]) -> Result<Vec<SmtProof>, RpcError> {
+        let result = self.inner.check_nullifiers(nullifiers).await;
+        self.record(
+            NodeRpcClientEndpoint::CheckNullifiers,
+            nullifiers.to_vec().to_bytes(),
+            &result,
+        );
+        result
+    }
+
+    async fn get_account_proofs(
+        &self,
+        account_storage_requests: &BTreeSet<ForeignAccount>,
+        known_account_codes: Vec<AccountCode>,
+    ) -> Result<AccountProofs, RpcError> {
+        let request = account_proofs_request(account_storage_requests, &known_account_codes);
+        let result = self
+            .inner
+            .get_account_proofs(account_storage_requests, known_account_codes)
+            .await;
+        self.record_with(
+            NodeRpcClientEndpoint::GetAccountProofs,
+            request,
+            &result,
+            |(block_num, account_proofs)| {
+                let mut response = block_num.to_bytes();
+                account_proofs.write_into(&mut response);
+                response
+            },
+        );
+        result
+    }
+}
+
+// REPLAY RPC CLIENT
+// ================================================================================================
+
+#[derive(Debug)]
+struct RecordedResponses {
+    responses: Vec<Result<Vec<u8>, RecordedError>>,
+    next: usize,
+}
+
+/// A [`NodeRpcClient`] that answers requests with the responses of an [`RpcFixture`].
+///
+/// Each request gets the responses recorded for the same RPC method and arguments, in the order
+/// they were recorded, and recorded errors are returned again. Once they run out, the last one is
+/// repeated. Requests that weren't recorded fail with [`RpcError::UnrecordedRequest`], which
+/// isn't retried.
+pub struct ReplayRpcClient {
+    responses: RwLock<BTreeMap<NodeRpcClientEndpoint, BTreeMap<Vec<u8>, RecordedResponses>>>,
+}
+
+impl ReplayRpcClient {
+    /// Returns a client that serves the responses of the fixture.
+    pub fn new(fixture: RpcFixture) -> Self {
+        let mut responses = BTreeMap::<_, BTreeMap<_, RecordedResponses>>::new();
+        for call in fixture.calls {
+            responses
+                .entry(call.endpoint)
+                .or_default()
+                .entry(call.request)
+                .or_insert_with(|| RecordedResponses { responses: Vec::new(), next: 0 })
+                .responses
+                .push(call.response);
+        }
+
+        Self { responses: RwLock::new(responses) }
+    }
+
+    /// Returns the next recorded response to the request, or the error it failed with.
+    fn replay(&self, endpoint: NodeRpcClientEndpoint, request: &[u8]) -> Result<Vec<u8>, RpcError> {
+        let mut responses = self.responses.write();
+        let Some(recorded) =
+            responses.get_mut(&endpoint).and_then(|requests| requests.get_mut(request))
+        else {
+            return Err(RpcError::UnrecordedRequest(endpoint));
+        };
+
+        let index = recorded.next.min(recorded.responses.len() - 1);
+        recorded.next += 1;
+        recorded.responses[index].clone().map_err(|err| err.to_rpc_error(endpoint))
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl NodeRpcClient for ReplayRpcClient {
+    async fn set_genesis_commitment(&self, _commitment: Word) -> Result<(), RpcError> {
+        Ok(())
+    }
+
+    async fn submit_proven_transaction(
+        &self,
+        proven_transaction: ProvenTransaction,
+    ) -> Result<BlockNumber, RpcError> {
+        let response =
+            self.replay(NodeRpcClientEndpoint::SubmitProvenTx, &proven_transaction.to_bytes())?;
+        Ok(BlockNumber::read_from_bytes(&response)?)
+    }
+
+    async fn get_block_header_by_number(
+        &self,
+        block_num: Option<BlockNumber>,
+        include_mmr_proof: bool,
+    ) -> Result<(BlockHeader, Option<MmrProof>), RpcError> {
+        let response = self.replay(
+            NodeRpcClientEndpoint::GetBlockHeaderByNumber,
+            &block_header_request(block_num, include_mmr_proof),
+        )?;
+
+        let mut source = SliceReader::new(&response);
+        let block_header = BlockHeader::read_from(&mut source)?;
+        let mmr_proof = read_optional_mmr_proof(&mut source)?;
+        Ok((block_header, mmr_proof))
+    }
+
+    async fn get_block_by_number(&self, block_num: BlockNumber) -> Result<ProvenBlock, RpcError> {
+        let response =
+            self.replay(NodeRpcClientEndpoint::GetBlockByNumber, &block_num.to_bytes())?;
+        Ok(ProvenBlock::read_from_bytes(&response)?)
+    }
+
+    async fn get_notes_by_id(&self, note_ids: &[NoteId]) -> Result<Vec<FetchedNote>, RpcError> {
+        let response =
+            self.replay(NodeRpcClientEndpoint::GetNotesById, &note_ids.to_vec().to_bytes())?;
+        Ok(Vec::<FetchedNote>::read_from_bytes(&response)?)
+    }
+
+    async fn sync_state(
+        &self,
+        block_num: BlockNumber,
+        account_ids: &[AccountId],
+        note_tags: &BTreeSet<NoteTag>,
+    ) -> Result<StateSyncInfo, RpcError> {
+        let response = self.replay(
+            NodeRpcClientEndpoint::SyncState,
+            &sync_state_request(block_num, account_ids, note_tags),
+        )?;
+        Ok(StateSyncInfo::read_from_bytes(&response)?)
+    }
+
+    async fn get_account_details(&self, account_id: AccountId) -> Result<FetchedAccount, RpcError> {
+        let response =
+            self.replay(NodeRpcClientEndpoint::GetAccountDetails, &account_id.to_bytes())?;
+        Ok(FetchedAccount::read_from_bytes(&response)?)
+    }
+
+    async fn sync_notes(
+        &self,
+        block_num: BlockNumber,
+        note_tags: &BTreeSet<NoteTag>,
+    ) -> Result<NoteSyncInfo, RpcError> {
+        let response = self
+            .replay(NodeRpcClientEndpoint::SyncNotes, &sync_notes_request(block_num, note_tags))?;
+        Ok(NoteSyncInfo::read_from_bytes(&response)?)
+    }
+
+    async fn check_nullifiers_by_prefix(
+        &self,
+        prefix: &[u16],
+        block_num: BlockNumber,
+    ) -> Result<Vec<NullifierUpdate>, RpcError> {
+        let response = self.replay(
+            NodeRpcClientEndpoint::CheckNullifiersByPrefix,
+            &nullifiers_by_prefix_request(prefix, block_num),
+        )?;
+        Ok(Vec::<NullifierUpdate>::read_from_bytes(&response)?)
+    }
+
+    async fn check_nullifiers(&self, nullifiers: &[Nullifier]) -> Result<Vec<SmtProof>, RpcError> {
+        let response =
+            self.replay(NodeRpcClientEndpoint::CheckNullifiers, &nullifiers.to_vec().to_bytes())?;
+        Ok(Vec::<SmtProof>::read_from_bytes(&response)?)
+    }
+
+    async fn get_account_proofs(
+        &self,
+        account_storage_requests: &BTreeSet<ForeignAccount>,
+        known_account_codes: Vec<AccountCode>,
+    ) -> Result<AccountProofs, RpcError> {
+        let response = self.replay(
+            NodeRpcClientEndpoint::GetAccountProofs,
+            &account_proofs_request(account_storage_requests, &known_account_codes),
+        )?;
+
+        let mut source = SliceReader::new(&response);
+        let block_num = BlockNumber::read_from(&mut source)?;
+        let account_proofs = Vec::<AccountProof>::read_from(&mut source)?;
+        Ok((block_num, account_proofs))
+    }
+}
+
+// REQUESTS
+// ================================================================================================
+
+pub(super) fn block_header_request(
+    block_num: Option<BlockNumber>,
+    include_mmr_proof: bool,
+) -> Vec<u8> {
+    let mut request = Vec::new();
+    request.write_bool(block_num.is_some());
+    if let Some(block_num) = block_num {
+        block_num.write_into(&mut request);
+    }
+    request.write_bool(include_mmr_proof);
+    request
+}
+
+pub(super) fn sync_state_request(
+    block_num: BlockNumber,
+    account_ids: &[AccountId],
+    note_tags: &BTreeSet<NoteTag>,
+) -> Vec<u8> {
+    let mut request = block_num.to_bytes();
+    account_ids.to_vec().write_into(&mut request);
+    note_tags.iter().copied().collect::<Vec<NoteTag>>().write_into(&mut request);
+    request
+}
+
+pub(super) fn sync_notes_request(block_num: BlockNumber, note_tags: &BTreeSet<NoteTag>) -> Vec<u8> {
+    let mut request = block_num.to_bytes();
+    note_tags.iter().copied().collect::<Vec<NoteTag>>().write_into(&mut request);
+    request
+}
+
+pub(super) fn nullifiers_by_prefix_request(prefix: &[u16], block_num: BlockNumber) -> Vec<u8> {
+    let mut request = prefix.to_vec().to_bytes();
+    block_num.write_into(&mut request);
+    request
+}
+
+/// Returns the request for the account proofs, where the known codes are identified by their
+/// commitments in ascending order, since their order doesn't change the response.
+pub(super) fn account_proofs_request(
+    account_storage_requests: &BTreeSet<ForeignAccount>,
+    known_account_codes: &[AccountCode],
+) -> Vec<u8> {
+    let mut request = account_storage_requests.iter().cloned().collect::<Vec<_>>().to_bytes();
+    let code_commitments: BTreeSet<Word> =
+        known_account_codes.iter().map(AccountCode::commitment).collect();
+    code_commitments.into_iter().collect::<Vec<Word>>().write_into(&mut request);
+    request
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for StateSyncInfo {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.chain_tip.write_into(target);
+        self.block_header.write_into(target);
+        target.write_usize(self.mmr_delta.forest.num_leaves());
+        self.mmr_delta.data.write_into(target);
+        target.write_usize(self.account_commitment_updates.len());
+        for (account_id, commitment) in &self.account_commitment_updates {
+            account_id.write_into(target);
+            commitment.write_into(target);
+        }
+        self.note_inclusions.write_into(target);
+        self.transactions.write_into(target);
+    }
+}
+
+impl Deserializable for StateSyncInfo {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let chain_tip = BlockNumber::read_from(source)?;
+        let block_header = BlockHeader::read_from(source)?;
+        let mmr_delta = MmrDelta {
+            forest: Forest::new(source.read_usize()?),
+            data: Vec::<Word>::read_from(source)?,
+        };
+
+        let num_updates = source.read_usize()?;
+        let mut account_commitment_updates = Vec::new();
+        for _ in 0..num_updates {
+            account_commitment_updates
+                .push((AccountId::read_from(source)?, Word::read_from(source)?));
+        }
+
+        Ok(Self {
+            chain_tip,
+            block_header,
+            mmr_delta,
+            account_commitment_updates,
+            note_inclusions: Vec::<CommittedNote>::read_from(source)?,
+            transactions: Vec::<TransactionInclusion>::read_from(source)?,
+        })
+    }
+}
+
+impl Serializable for NoteSyncInfo {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.chain_tip);
+        self.block_header.write_into(target);
+        self.mmr_path.write_into(target);
+        self.notes.write_into(target);
+    }
+}
+
+impl Deserializable for NoteSyncInfo {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            chain_tip: source.read_u32()?,
+            block_header: BlockHeader::read_from(source)?,
+            mmr_path: MerklePath::read_from(source)?,
+            notes: Vec::<CommittedNote>::read_from(source)?,
+        })
+    }
+}
+
+impl Serializable for CommittedNote {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.note_id().write_into(target);
+        target.write_u16(self.note_index());
+        let (empty_nodes_mask, siblings) = self.inclusion_path().clone().into_parts();
+        target.write_u64(empty_nodes_mask);
+        siblings.write_into(target);
+        self.metadata().write_into(target);
+    }
+}
+
+impl Deserializable for CommittedNote {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let note_id = NoteId::read_from(source)?;
+        let note_index = source.read_u16()?;
+        let empty_nodes_mask = source.read_u64()?;
+        let siblings = Vec::<Word>::read_from(source)?;
+        let inclusion_path = SparseMerklePath::from_parts(empty_nodes_mask, siblings)
+            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))?;
+        let metadata = NoteMetadata::read_from(source)?;
+
+        Ok(Self::new(note_id, note_index, inclusion_path, metadata))
+    }
+}
+
+impl Serializable for TransactionInclusion {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.transaction_id.write_into(target);
+        target.write_u32(self.block_num);
+        self.account_id.write_into(target);
+    }
+}
+
+impl Deserializable for TransactionInclusion {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            transaction_id: TransactionId::read_from(source)?,
+            block_num: source.read_u32()?,
+            account_id: AccountId::read_from(source)?,
+        })
+    }
+}
+
+impl Serializable for FetchedNote {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            FetchedNote::Private(note_id, metadata, inclusion_proof) => {
+                target.write_u8(0);
+                note_id.write_into(target);
+                metadata.write_into(target);
+                inclusion_proof.write_into(target);
+            },
+            FetchedNote::Public(note, inclusion_proof) => {
+                target.write_u8(1);
+                note.write_into(target);
+                inclusion_proof.write_into(target);
+            },
+        }
+    }
+}
+
+impl Deserializable for FetchedNote {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(FetchedNote::Private(
+                NoteId::read_from(source)?,
+                NoteMetadata::read_from(source)?,
+                NoteInclusionProof::read_from(source)?,
+            )),
+            1 => Ok(FetchedNote::Public(
+                Note::read_from(source)?,
+                NoteInclusionProof::read_from(source)?,
+            )),
+            tag => {
+                Err(DeserializationError::InvalidValue(format!("invalid fetched note tag {tag}")))
+            },
+        }
+    }
+}
+
+impl Serializable for FetchedAccount {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        let summary = match self {
+            FetchedAccount::Private(account_id, summary) => {
+                target.write_u8(0);
+                account_id.write_into(target);
+                summary
+            },
+            FetchedAccount::Public(account, summary) => {
+                target.write_u8(1);
+                account.write_into(target);
+                summary
+            },
+        };
+        summary.commitment.write_into(target);
+        target.write_u32(summary.last_block_num);
+    }
+}
+
+impl Deserializable for FetchedAccount {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let read_summary = |source: &mut R| -> Result<_, DeserializationError> {
+            Ok(AccountUpdateSummary::new(Word::read_from(source)?, source.read_u32()?))
+        };
+
+        match source.read_u8()? {
+            0 => {
+                let account_id = AccountId::read_from(source)?;
+                Ok(FetchedAccount::Private(account_id, read_summary(source)?))
+            },
+            1 => {
+                let account = Account::read_from(source)?;
+                Ok(FetchedAccount::Public(account, read_summary(source)?))
+            },
+            tag => Err(DeserializationError::InvalidValue(format!(
+                "invalid fetched account tag {tag}"
+            ))),
+        }
+    }
+}
+
+impl Serializable for NullifierUpdate {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.nullifier.write_into(target);
+        target.write_u32(self.block_num);
+    }
+}
+
+impl Deserializable for NullifierUpdate {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            nullifier: Nullifier::read_from(source)?,
+            block_num: source.read_u32()?,
+        })
+    }
+}
+
+impl Serializable for AccountProof {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        let (account_witness, state_headers) = self.clone().into_parts();
+        account_witness.id().write_into(target);
+        account_witness.state_commitment().write_into(target);
+        account_witness.path().write_into(target);
+
+        target.write_bool(state_headers.is_some());
+        if let Some(state_headers) = state_headers {
+            state_headers.account_header.write_into(target);
+            state_headers.storage_header.write_into(target);
+            state_headers.code.write_into(target);
+            state_headers.storage_slots.write_into(target);
+        }
+    }
+}
+
+impl Deserializable for AccountProof {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let account_id = AccountId::read_from(source)?;
+        let state_commitment = Word::read_from(source)?;
+        let path = MerklePath::read_from(source)?;
+        let account_witness = AccountWitness::new(account_id, state_commitment, path)
+            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))?;
+
+        let state_headers = if source.read_bool()? {
+            Some(StateHeaders {
+                account_header: AccountHeader::read_from(source)?,
+                storage_header: AccountStorageHeader::read_from(source)?,
+                code: AccountCode::read_from(source)?,
+                storage_slots: BTreeMap::<u8, Vec<SmtProof>>::read_from(source)?,
+            })
+        } else {
+            None
+        };
+
+        AccountProof::new(account_witness, state_headers)
+            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))
+    }
+}
+
+pub(super) fn write_optional_mmr_proof<W: ByteWriter>(
+    mmr_proof: Option<&MmrProof>,
+    target: &mut W,
+) {
+    target.write_bool(mmr_proof.is_some());
+    if let Some(mmr_proof) = mmr_proof {
+        target.write_usize(mmr_proof.forest.num_leaves());
+        target.write_usize(mmr_proof.position);
+        mmr_proof.merkle_path.write_into(target);
+    }
+}
+
+fn read_optional_mmr_proof<R: ByteReader>(
+    source: &mut R,
+) -> Result<Option<MmrProof>, DeserializationError> {
+    if !source.read_bool()? {
+        return Ok(None);
+    }
+
+    Ok(Some(MmrProof {
+        forest: Forest::new(source.read_usize()?),
+        position: source.read_usize()?,
+        merkle_path: MerklePath::read_from(source)?,
+    }))
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::sync::Arc;
+
+    use miden_objects::block::BlockNumber;
+
+    use super::{
+        RecordedCall,
+        RecordedError,
+        RecordingRpcClient,
+        ReplayRpcClient,
+        RpcFixture,
+        block_header_request,
+    };
+    use crate::rpc::{GrpcError, NodeRpcClient, NodeRpcClientEndpoint, RpcError};
+    use crate::testing::mock::MockRpcApi;
+    use crate::utils::{Deserializable, Serializable};
+
+    #[tokio::test]
+    async fn replayed_responses_match_the_recorded_ones() {
+        let rpc_api = MockRpcApi::default();
+        let recorder = RecordingRpcClient::new(Arc::new(rpc_api.clone()));
+
+        let (chain_tip, _) = recorder.get_block_header_by_number(None, false).await.unwrap();
+        let (genesis, mmr_proof) = recorder
+            .get_block_header_by_number(Some(BlockNumber::GENESIS), true)
+            .await
+            .unwrap();
+
+        let fixture = RpcFixture::read_from_bytes(&recorder.fixture().to_bytes()).unwrap();
+        assert_eq!(fixture, recorder.fixture());
+        assert_eq!(fixture.endpoints(), [NodeRpcClientEndpoint::GetBlockHeaderByNumber; 2]);
+
+        let replay = ReplayRpcClient::new(fixture);
+        let (replayed_tip, _) = replay.get_block_header_by_number(None, false).await.unwrap();
+        assert_eq!(replayed_tip, chain_tip);
+
+        let (replayed_genesis, replayed_proof) = replay
+            .get_block_header_by_number(Some(BlockNumber::GENESIS), true)
+            .await
+            .unwrap();
+        assert_eq!(replayed_genesis, genesis);
+        assert_eq!(
+            replayed_proof.map(|proof| proof.merkle_path),
+            mmr_proof.map(|proof| proof.merkle_path)
+        );
+
+        // Requests that weren't recorded fail without being retried
+        let err = replay.get_block_header_by_number(Some(1.into()), false).await.unwrap_err();
+        assert!(matches!(
+            err,
+            RpcError::UnrecordedRequest(NodeRpcClientEndpoint::GetBlockHeaderByNumber)
+        ));
+        assert!(!err.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn failed_requests_are_recorded_and_replayed() {
+        let endpoint = NodeRpcClientEndpoint::GetBlockHeaderByNumber;
+        let error = RpcError::GrpcError {
+            endpoint,
+            error_kind: GrpcError::Unavailable,
+            source: Some("node is restarting".into()),
+        };
+        let node = ReplayRpcClient::new(RpcFixture {
+            calls: vec![RecordedCall {
+                endpoint,
+                request: block_header_request(None, false),
+                response: Err(RecordedError::from(&error)),
+            }],
+        });
+
+        let recorder = RecordingRpcClient::new(Arc::new(node));
+        assert!(recorder.get_block_header_by_number(None, false).await.is_err());
+
+        let fixture = RpcFixture::read_from_bytes(&recorder.fixture().to_bytes()).unwrap();
+        assert_eq!(fixture.endpoints(), [endpoint]);
+
+        let replay = ReplayRpcClient::new(fixture);
+        let err = replay.get_block_header_by_number(None, false).await.unwrap_err();
+        assert_eq!(err.to_string(), error.to_string());
+        let RpcError::GrpcError {
+            error_kind: GrpcError::Unavailable,
+            source: Some(source),
+            ..
+        } = err
+        else {
+            panic!("the replayed error should be the recorded one, got {err}");
+        };
+        assert_eq!(source.to_string(), "node is restarting");
+    }
+}
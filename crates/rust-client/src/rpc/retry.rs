@@ -0,0 +1,465 @@
+//! Provides [`RetryingRpcClient`], a [`NodeRpcClient`] that retries requests failing with
+//! transient errors and limits the rate at which each RPC method is called.
+//!
+//! Whether an error is transient is decided by [`RpcError::is_retryable`]. Proven transactions
+//! are only submitted again if the node couldn't be reached: a node that failed or timed out may
+//! have accepted the transaction already, so its error is returned instead of submitting the
+//! transaction twice.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::future::Future;
+use core::time::Duration;
+use std::sync::{Mutex, PoisonError};
+use std::time::Instant;
+
+use miden_objects::Word;
+use miden_objects::account::{AccountCode, AccountId};
+use miden_objects::block::{BlockHeader, BlockNumber, ProvenBlock};
+use miden_objects::crypto::merkle::{MmrProof, SmtProof};
+use miden_objects::note::{NoteId, NoteTag, Nullifier};
+use miden_objects::transaction::ProvenTransaction;
+use tracing::warn;
+
+use super::domain::account::{AccountProofs, FetchedAccount};
+use super::domain::note::{FetchedNote, NoteSyncInfo};
+use super::domain::nullifier::NullifierUpdate;
+use super::domain::sync::StateSyncInfo;
+use super::{NodeRpcClient, NodeRpcClientEndpoint, RpcError};
+use crate::transaction::ForeignAccount;
+
+// RETRY POLICY
+// ================================================================================================
+
+/// Policy used by a [`RetryingRpcClient`] to retry requests that failed with a transient error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RpcRetryPolicy {
+    /// Maximum number of times a request is retried.
+    pub max_retries: u32,
+    /// Time to wait before the first retry. The wait doubles with each retry.
+    pub initial_backoff: Duration,
+    /// Maximum time to wait before a retry.
+    pub max_backoff: Duration,
+}
+
+impl RpcRetryPolicy {
+    /// Returns a policy that retries requests up to `max_retries` times, with the default
+    /// backoff.
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            initial_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+
+    /// Sets the time to wait before the first retry.
+    #[must_use]
+    pub fn with_initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Sets the maximum time to wait before a retry.
+    #[must_use]
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Returns the time to wait before the `retry`-th retry.
+    fn backoff(&self, retry: u32) -> Duration {
+        let factor = 1u32 << retry.saturating_sub(1).min(16);
+        self.initial_backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+}
+
+impl Default for RpcRetryPolicy {
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+// RATE LIMIT
+// ================================================================================================
+
+/// Maximum rate at which a [`RetryingRpcClient`] sends requests to an RPC method.
+///
+/// Up to `max_requests` requests can be sent at once, after which requests are delayed so that no
+/// more than `max_requests` are sent per `period`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RpcRateLimit {
+    /// Maximum number of requests per period.
+    pub max_requests: u32,
+    /// Period over which requests are counted.
+    pub period: Duration,
+}
+
+impl RpcRateLimit {
+    /// Returns a limit of `max_requests` requests per `period`. At least one request per period
+    /// is allowed.
+    pub fn new(max_requests: u32, period: Duration) -> Self {
+        Self {
+            max_requests: max_requests.max(1),
+            period,
+        }
+    }
+
+    /// Returns a limit of `max_requests` requests per second.
+    pub fn per_second(max_requests: u32) -> Self {
+        Self::new(max_requests, Duration::from_secs(1))
+    }
+}
+
+#[derive(Debug)]
+struct RateLimiter {
+    limit: RpcRateLimit,
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl RateLimiter {
+    fn new(limit: RpcRateLimit) -> Self {
+        Self {
+            limit,
+            tokens: f64::from(limit.max_requests),
+            updated_at: Instant::now(),
+        }
+    }
+
+    /// Takes a token from the bucket and returns the time to wait before sending the request.
+    ///
+    /// The bucket can go into debt, so that concurrent requests queue up behind each other.
+    fn reserve(&mut self) -> Duration {
+        let capacity = f64::from(self.limit.max_requests);
+        let refill_rate = capacity / self.limit.period.as_secs_f64();
+
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * refill_rate).min(capacity) - 1.0;
+        self.updated_at = now;
+
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / refill_rate)
+        }
+    }
+}
+
+// RETRYING RPC CLIENT
+// ================================================================================================
+
+/// A [`NodeRpcClient`] that wraps another client, retrying the requests that fail with transient
+/// errors according to its [`RpcRetryPolicy`] and delaying the requests that exceed the
+/// [`RpcRateLimit`] of their RPC method.
+pub struct RetryingRpcClient {
+    inner: Arc<dyn NodeRpcClient>,
+    retry_policy: RpcRetryPolicy,
+    rate_limiters: BTreeMap<NodeRpcClientEndpoint, Mutex<RateLimiter>>,
+}
+
+impl RetryingRpcClient {
+    /// Returns a client that sends the requests to `inner`, with the default retry policy and no
+    /// rate limits.
+    pub fn new(inner: Arc<dyn NodeRpcClient>) -> Self {
+        Self {
+            inner,
+            retry_policy: RpcRetryPolicy::default(),
+            rate_limiters: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the policy used to retry requests.
+    #[must_use]
+    pub fn with_retry_policy(mut self, retry_policy: RpcRetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Limits the rate at which requests are sent to the RPC method, replacing its previous
+    /// limit.
+    #[must_use]
+    pub fn with_rate_limit(mut self, endpoint: NodeRpcClientEndpoint, limit: RpcRateLimit) -> Self {
+        self.rate_limiters.insert(endpoint, Mutex::new(RateLimiter::new(limit)));
+        self
+    }
+
+    /// Returns the policy used to retry requests.
+    pub fn retry_policy(&self) -> RpcRetryPolicy {
+        self.retry_policy
+    }
+
+    /// Waits until the rate limit of the RPC method, if any, allows sending a request.
+    async fn wait_for_rate_limit(&self, endpoint: NodeRpcClientEndpoint) {
+        let Some(rate_limiter) = self.rate_limiters.get(&endpoint) else {
+            return;
+        };
+
+        let delay = rate_limiter.lock().unwrap_or_else(PoisonError::into_inner).reserve();
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Sends the request until it succeeds, fails with an error that isn't transient, or the
+    /// retries of the policy run out.
+    async fn with_retries<'a, T, F, Fut>(
+        &'a self,
+        endpoint: NodeRpcClientEndpoint,
+        request: F,
+    ) -> Result<T, RpcError>
+    where
+        F: Fn(&'a dyn NodeRpcClient) -> Fut,
+        Fut: Future<Output = Result<T, RpcError>>,
+    {
+        self.with_retries_on(endpoint, request, RpcError::is_retryable).await
+    }
+
+    /// Sends the request until it succeeds, fails with an error for which `is_retryable` returns
+    /// `false`, or the retries of the policy run out.
+    async fn with_retries_on<'a, T, F, Fut>(
+        &'a self,
+        endpoint: NodeRpcClientEndpoint,
+        request: F,
+        is_retryable: fn(&RpcError) -> bool,
+    ) -> Result<T, RpcError>
+    where
+        F: Fn(&'a dyn NodeRpcClient) -> Fut,
+        Fut: Future<Output = Result<T, RpcError>>,
+    {
+        let mut retries = 0;
+        loop {
+            self.wait_for_rate_limit(endpoint).await;
+
+            match request(self.inner.as_ref()).await {
+                Err(err) if is_retryable(&err) && retries < self.retry_policy.max_retries => {
+                    retries += 1;
+                    let backoff = self.retry_policy.backoff(retries);
+                    warn!(
+                        "Request to {endpoint} failed, retrying in {backoff:?} ({retries}/{}): {err}",
+                        self.retry_policy.max_retries
+                    );
+                    tokio::time::sleep(backoff).await;
+                },
+                result => return result,
+            }
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl NodeRpcClient for RetryingRpcClient {
+    async fn set_genesis_commitment(&self, commitment: Word) -> Result<(), RpcError> {
+        self.inner.set_genesis_commitment(commitment).await
+    }
+
+    /// Submits the proven transaction, only retrying if the node couldn't be reached, so that a
+    /// transaction the node may have accepted is never submitted twice.
+    async fn submit_proven_transaction(
+        &self,
+        proven_transaction: ProvenTransaction,
+    ) -> Result<BlockNumber, RpcError> {
+        self.with_retries_on(
+            NodeRpcClientEndpoint::SubmitProvenTx,
+            |client| client.submit_proven_transaction(proven_transaction.clone()),
+            is_unsent,
+        )
+        .await
+    }
+
+    async fn get_block_header_by_number(
+        &self,
+        block_num: Option<BlockNumber>,
+        include_mmr_proof: bool,
+    ) -> Result<(BlockHeader, Option<MmrProof>), RpcError> {
+        self.with_retries(NodeRpcClientEndpoint::GetBlockHeaderByNumber, |client| {
+            client.get_block_header_by_number(block_num, include_mmr_proof)
+        })
+        .await
+    }
+
+    async fn get_block_by_number(&self, block_num: BlockNumber) -> Result<ProvenBlock, RpcError> {
+        self.with_retries(NodeRpcClientEndpoint::GetBlockByNumber, |client| {
+            client.get_block_by_number(block_num)
+        })
+        .await
+    }
+
+    async fn get_notes_by_id(&self, note_ids: &[NoteId]) -> Result<Vec<FetchedNote>, RpcError> {
+        self.with_retries(NodeRpcClientEndpoint::GetNotesById, |client| {
+            client.get_notes_by_id(note_ids)
+        })
+        .await
+    }
+
+    async fn sync_state(
+        &self,
+        block_num: BlockNumber,
+        account_ids: &[AccountId],
+        note_tags: &BTreeSet<NoteTag>,
+    ) -> Result<StateSyncInfo, RpcError> {
+        self.with_retries(NodeRpcClientEndpoint::SyncState, |client| {
+            client.sync_state(block_num, account_ids, note_tags)
+        })
+        .await
+    }
+
+    async fn get_account_details(&self, account_id: AccountId) -> Result<FetchedAccount, RpcError> {
+        self.with_retries(NodeRpcClientEndpoint::GetAccountDetails, |client| {
+            client.get_account_details(account_id)
+        })
+        .await
+    }
+
+    async fn sync_notes(
+        &self,
+        block_num: BlockNumber,
+        note_tags: &BTreeSet<NoteTag>,
+    ) -> Result<NoteSyncInfo, RpcError> {
+        self.with_retries(NodeRpcClientEndpoint::SyncNotes, |client| {
+            client.sync_notes(block_num, note_tags)
+        })
+        .await
+    }
+
+    async fn check_nullifiers_by_prefix(
+        &self,
+        prefix: &[u16],
+        block_num: BlockNumber,
+    ) -> Result<Vec<NullifierUpdate>, RpcError> {
+        self.with_retries(NodeRpcClientEndpoint::CheckNullifiersByPrefix, |client| {
+            client.check_nullifiers_by_prefix(prefix, block_num)
+        })
+        .await
+    }
+
+    async fn check_nullifiers(&self, nullifiers: &[Nullifier]) -> Result<Vec<SmtProof>, RpcError> {
+        self.with_retries(NodeRpcClientEndpoint::CheckNullifiers, |client| {
+            client.check_nullifiers(nullifiers)
+        })
+        .await
+    }
+
+    async fn get_account_proofs(
+        &self,
+        account_storage_requests: &BTreeSet<ForeignAccount>,
+        known_account_codes: Vec<AccountCode>,
+    ) -> Result<AccountProofs, RpcError> {
+        self.with_retries(NodeRpcClientEndpoint::GetAccountProofs, |client| {
+            client.get_account_proofs(account_storage_requests, known_account_codes.clone())
+        })
+        .await
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns whether the request certainly didn't reach the node, because no connection could be
+/// established.
+fn is_unsent(err: &RpcError) -> bool {
+    matches!(err, RpcError::ConnectionError(_))
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::sync::Arc;
+    use core::sync::atomic::{AtomicU32, Ordering};
+    use core::time::Duration;
+
+    use super::{RateLimiter, RetryingRpcClient, RpcRateLimit, RpcRetryPolicy, is_unsent};
+    use crate::rpc::{GrpcError, NodeRpcClientEndpoint, RpcError};
+    use crate::testing::mock::MockRpcApi;
+
+    fn grpc_error(error_kind: GrpcError) -> RpcError {
+        RpcError::GrpcError {
+            endpoint: NodeRpcClientEndpoint::SyncState,
+            error_kind,
+            source: None,
+        }
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_maximum() {
+        let policy = RpcRetryPolicy::new(5)
+            .with_initial_backoff(Duration::from_millis(100))
+            .with_max_backoff(Duration::from_millis(500));
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(400));
+        assert_eq!(policy.backoff(4), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn rate_limiter_delays_requests_past_the_burst() {
+        let mut rate_limiter = RateLimiter::new(RpcRateLimit::new(2, Duration::from_secs(10)));
+
+        assert_eq!(rate_limiter.reserve(), Duration::ZERO);
+        assert_eq!(rate_limiter.reserve(), Duration::ZERO);
+
+        let delay = rate_limiter.reserve();
+        assert!(delay > Duration::from_secs(4) && delay <= Duration::from_secs(5));
+        assert!(rate_limiter.reserve() > delay);
+    }
+
+    #[tokio::test]
+    async fn only_transient_errors_are_retried() {
+        let retry_policy = RpcRetryPolicy::new(2).with_initial_backoff(Duration::from_millis(1));
+        let rpc_client =
+            RetryingRpcClient::new(Arc::new(MockRpcApi::default())).with_retry_policy(retry_policy);
+
+        let attempts = AtomicU32::new(0);
+        let result: Result<(), RpcError> = rpc_client
+            .with_retries(NodeRpcClientEndpoint::SyncState, |_| {
+                attempts.fetch_add(1, Ordering::Relaxed);
+                async { Err(grpc_error(GrpcError::Unavailable)) }
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(attempts.load(Ordering::Relaxed), 3);
+
+        let attempts = AtomicU32::new(0);
+        let result: Result<(), RpcError> = rpc_client
+            .with_retries(NodeRpcClientEndpoint::SyncState, |_| {
+                attempts.fetch_add(1, Ordering::Relaxed);
+                async { Err(grpc_error(GrpcError::NotFound)) }
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(attempts.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn submissions_are_only_retried_if_unsent() {
+        let retry_policy = RpcRetryPolicy::new(2).with_initial_backoff(Duration::from_millis(1));
+        let rpc_client =
+            RetryingRpcClient::new(Arc::new(MockRpcApi::default())).with_retry_policy(retry_policy);
+
+        let submit = async |error: fn() -> RpcError| {
+            let attempts = AtomicU32::new(0);
+            let result: Result<(), RpcError> = rpc_client
+                .with_retries_on(
+                    NodeRpcClientEndpoint::SubmitProvenTx,
+                    |_| {
+                        attempts.fetch_add(1, Ordering::Relaxed);
+                        async move { Err(error()) }
+                    },
+                    is_unsent,
+                )
+                .await;
+            assert!(result.is_err());
+            attempts.load(Ordering::Relaxed)
+        };
+
+        // The node may have accepted a transaction it didn't answer in time
+        assert_eq!(submit(|| grpc_error(GrpcError::DeadlineExceeded)).await, 1);
+        assert_eq!(submit(|| RpcError::ConnectionError("connection refused".into())).await, 3);
+    }
+}
@@ -0,0 +1,926 @@
+//! This module provides an in-memory implementation of the [Store] trait.
+//!
+//! [`MemoryStore`] keeps accounts, transactions, notes, block headers and MMR nodes in plain
+//! collections guarded by a lock. It doesn't depend on `std` and is meant to be used in tests and
+//! by short-lived clients that don't need to persist their state. The whole state can be
+//! serialized into bytes (see [`MemoryStore::to_bytes`]) and restored later on.
+
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use miden_objects::Word;
+use miden_objects::account::{Account, AccountCode, AccountHeader, AccountId};
+use miden_objects::block::{BlockHeader, BlockNumber};
+use miden_objects::crypto::merkle::{Forest, InOrderIndex, MmrPeaks};
+use miden_objects::note::NoteTag;
+use miden_tx::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use super::{
+    AccountRecord,
+    AccountStatus,
+    BlockRelevance,
+    InputNoteRecord,
+    InputNoteState,
+    NoteFilter,
+    OutputNoteRecord,
+    OutputNoteState,
+    PartialBlockchainFilter,
+    Store,
+    StoreError,
+    TransactionFilter,
+};
+use crate::note::NoteUpdateTracker;
+use crate::sync::{NoteTagRecord, NoteTagSource, StateSyncUpdate};
+use crate::transaction::{
+    TransactionDetails,
+    TransactionRecord,
+    TransactionStatus,
+    TransactionStoreUpdate,
+};
+use crate::utils::RwLock;
+
+mod snapshot;
+
+// MEMORY STORE
+// ================================================================================================
+
+/// A [Store] implementation that keeps all of the client's data in memory.
+///
+/// Update methods mutate the state in place while holding the write lock. Every fallible check is
+/// performed before the first mutation, so failed updates leave the store untouched.
+pub struct MemoryStore {
+    state: RwLock<MemoryStoreState>,
+}
+
+impl MemoryStore {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new empty [`MemoryStore`].
+    pub fn new() -> Self {
+        Self {
+            state: RwLock::new(MemoryStoreState::default()),
+        }
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Executes the provided function on a read-only view of the store's state.
+    fn read<R>(&self, f: impl FnOnce(&MemoryStoreState) -> R) -> R {
+        f(&self.state.read())
+    }
+
+    /// Executes the provided function on the store's state while holding the write lock. The
+    /// function must not mutate the state before all of its fallible operations succeeded.
+    fn update<R>(
+        &self,
+        f: impl FnOnce(&mut MemoryStoreState) -> Result<R, StoreError>,
+    ) -> Result<R, StoreError> {
+        f(&mut self.state.write())
+    }
+}
+
+impl Default for MemoryStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+impl Store for MemoryStore {
+    fn get_current_timestamp(&self) -> Option<u64> {
+        let now = chrono::Utc::now();
+        Some(u64::try_from(now.timestamp()).expect("timestamp is always after epoch"))
+    }
+
+    // TRANSACTIONS
+    // --------------------------------------------------------------------------------------------
+
+    async fn get_transactions(
+        &self,
+        filter: TransactionFilter,
+    ) -> Result<Vec<TransactionRecord>, StoreError> {
+        Ok(self.read(|state| {
+            state
+                .transactions
+                .iter()
+                .filter(|transaction| transaction_matches(&filter, transaction))
+                .cloned()
+                .collect()
+        }))
+    }
+
+    async fn apply_transaction(&self, tx_update: TransactionStoreUpdate) -> Result<(), StoreError> {
+        let creation_timestamp = self.get_current_timestamp().unwrap_or_default();
+
+        self.update(|state| {
+            let executed_transaction = tx_update.executed_transaction();
+
+            // Apply the account delta before touching the state, as it's the only fallible step
+            let delta = executed_transaction.account_delta();
+            let mut account = state
+                .latest_account_state(delta.id())
+                .ok_or(StoreError::AccountDataNotFound(delta.id()))?
+                .account
+                .clone();
+            account.apply_delta(delta)?;
+
+            let details = TransactionDetails {
+                account_id: executed_transaction.account_id(),
+                init_account_state: executed_transaction.initial_account().commitment(),
+                final_account_state: executed_transaction.final_account().commitment(),
+                input_note_nullifiers: executed_transaction
+                    .input_notes()
+                    .iter()
+                    .map(|note| note.nullifier().as_word())
+                    .collect(),
+                output_notes: executed_transaction.output_notes().clone(),
+                block_num: executed_transaction.block_header().block_num(),
+                submission_height: tx_update.submission_height(),
+                expiration_block_num: executed_transaction.expiration_block_num(),
+                creation_timestamp,
+            };
+
+            state.upsert_transaction(
+                TransactionRecord::new(
+                    executed_transaction.id(),
+                    details,
+                    executed_transaction.tx_args().tx_script().cloned(),
+                    TransactionStatus::Pending,
+                )
+                .with_request(tx_update.request().cloned(), tx_update.retry_of()),
+            );
+
+            // Account data
+            state.insert_account_state(account, None);
+
+            // Note updates
+            state.apply_note_updates(tx_update.note_updates());
+
+            for tag_record in tx_update.new_tags() {
+                state.tags.push(*tag_record);
+            }
+
+            Ok(())
+        })
+    }
+
+    // NOTES
+    // --------------------------------------------------------------------------------------------
+
+    async fn get_input_notes(
+        &self,
+        filter: NoteFilter,
+    ) -> Result<Vec<InputNoteRecord>, StoreError> {
+        Ok(self.read(|state| {
+            state
+                .input_notes
+                .iter()
+                .filter(|note| input_note_matches(&filter, note))
+                .cloned()
+                .collect()
+        }))
+    }
+
+    async fn get_output_notes(
+        &self,
+        filter: NoteFilter,
+    ) -> Result<Vec<OutputNoteRecord>, StoreError> {
+        Ok(self.read(|state| {
+            state
+                .output_notes
+                .iter()
+                .filter(|note| output_note_matches(&filter, note))
+                .cloned()
+                .collect()
+        }))
+    }
+
+    async fn upsert_input_notes(&self, notes: &[InputNoteRecord]) -> Result<(), StoreError> {
+        self.update(|state| {
+            for note in notes {
+                state.upsert_input_note(note.clone());
+
+                // Whenever we insert a note, we also update block relevance
+                if let Some(inclusion_proof) = note.inclusion_proof() {
+                    state.set_block_has_client_notes(inclusion_proof.location().block_num());
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    // CHAIN DATA
+    // --------------------------------------------------------------------------------------------
+
+    async fn get_block_headers(
+        &self,
+        block_numbers: &BTreeSet<BlockNumber>,
+    ) -> Result<Vec<(BlockHeader, BlockRelevance)>, StoreError> {
+        Ok(self.read(|state| {
+            block_numbers
+                .iter()
+                .filter_map(|block_num| state.block_headers.get(block_num))
+                .map(|entry| (entry.header.clone(), entry.has_client_notes.into()))
+                .collect()
+        }))
+    }
+
+    async fn get_tracked_block_headers(&self) -> Result<Vec<BlockHeader>, StoreError> {
+        Ok(self.read(|state| {
+            state
+                .block_headers
+                .values()
+                .filter(|entry| entry.has_client_notes)
+                .map(|entry| entry.header.clone())
+                .collect()
+        }))
+    }
+
+    async fn get_partial_blockchain_nodes(
+        &self,
+        filter: PartialBlockchainFilter,
+    ) -> Result<BTreeMap<InOrderIndex, Word>, StoreError> {
+        Ok(self.read(|state| match filter {
+            PartialBlockchainFilter::All => state.partial_blockchain_nodes.clone(),
+            PartialBlockchainFilter::List(ids) => ids
+                .into_iter()
+                .filter_map(|id| state.partial_blockchain_nodes.get(&id).map(|node| (id, *node)))
+                .collect(),
+        }))
+    }
+
+    async fn insert_partial_blockchain_nodes(
+        &self,
+        nodes: &[(InOrderIndex, Word)],
+    ) -> Result<(), StoreError> {
+        self.update(|state| {
+            state.insert_partial_blockchain_nodes(nodes);
+            Ok(())
+        })
+    }
+
+    async fn get_partial_blockchain_peaks_by_block_num(
+        &self,
+        block_num: BlockNumber,
+    ) -> Result<MmrPeaks, StoreError> {
+        match self
+            .read(|state| state.block_headers.get(&block_num).map(|entry| entry.peaks.clone()))
+        {
+            Some(peaks) => Ok(peaks),
+            None => Ok(MmrPeaks::new(Forest::empty(), vec![])?),
+        }
+    }
+
+    async fn insert_block_header(
+        &self,
+        block_header: &BlockHeader,
+        partial_blockchain_peaks: MmrPeaks,
+        has_client_notes: bool,
+    ) -> Result<(), StoreError> {
+        self.update(|state| {
+            state.insert_block_header(block_header, &partial_blockchain_peaks, has_client_notes);
+            Ok(())
+        })
+    }
+
+    async fn prune_irrelevant_blocks(&self) -> Result<(), StoreError> {
+        self.update(|state| {
+            let sync_height = state.sync_height;
+            state.block_headers.retain(|block_num, entry| {
+                entry.has_client_notes
+                    || *block_num == BlockNumber::GENESIS
+                    || *block_num >= sync_height
+            });
+
+            Ok(())
+        })
+    }
+
+    // ACCOUNTS
+    // --------------------------------------------------------------------------------------------
+
+    async fn get_account_ids(&self) -> Result<Vec<AccountId>, StoreError> {
+        Ok(self.read(|state| state.accounts.keys().copied().collect()))
+    }
+
+    async fn get_account_headers(&self) -> Result<Vec<(AccountHeader, AccountStatus)>, StoreError> {
+        Ok(self.read(|state| {
+            state
+                .accounts
+                .keys()
+                .filter_map(|account_id| state.latest_account_state(*account_id))
+                .map(|account_state| (account_state.header(), account_state.status()))
+                .collect()
+        }))
+    }
+
+    async fn get_account_header(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Option<(AccountHeader, AccountStatus)>, StoreError> {
+        Ok(self.read(|state| {
+            state
+                .latest_account_state(account_id)
+                .map(|account_state| (account_state.header(), account_state.status()))
+        }))
+    }
+
+    async fn get_account_header_by_commitment(
+        &self,
+        account_commitment: Word,
+    ) -> Result<Option<AccountHeader>, StoreError> {
+        Ok(self.read(|state| {
+            state
+                .accounts
+                .values()
+                .flatten()
+                .find(|account_state| account_state.account.commitment() == account_commitment)
+                .map(StoredAccountState::header)
+        }))
+    }
+
+    async fn get_account(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Option<AccountRecord>, StoreError> {
+        Ok(self.read(|state| {
+            state.latest_account_state(account_id).map(|account_state| {
+                AccountRecord::new(account_state.account.clone(), account_state.status())
+            })
+        }))
+    }
+
+    async fn insert_account(
+        &self,
+        account: &Account,
+        account_seed: Option<Word>,
+    ) -> Result<(), StoreError> {
+        self.update(|state| {
+            state.insert_account_state(account.clone(), account_seed);
+            Ok(())
+        })
+    }
+
+    async fn upsert_foreign_account_code(
+        &self,
+        account_id: AccountId,
+        code: AccountCode,
+    ) -> Result<(), StoreError> {
+        self.update(|state| {
+            state.foreign_account_code.insert(account_id, code);
+            Ok(())
+        })
+    }
+
+    async fn get_foreign_account_code(
+        &self,
+        account_ids: Vec<AccountId>,
+    ) -> Result<BTreeMap<AccountId, AccountCode>, StoreError> {
+        Ok(self.read(|state| {
+            account_ids
+                .into_iter()
+                .filter_map(|account_id| {
+                    state
+                        .foreign_account_code
+                        .get(&account_id)
+                        .map(|code| (account_id, code.clone()))
+                })
+                .collect()
+        }))
+    }
+
+    async fn update_account(&self, new_account_state: &Account) -> Result<(), StoreError> {
+        self.update(|state| {
+            if !state.accounts.contains_key(&new_account_state.id()) {
+                return Err(StoreError::AccountDataNotFound(new_account_state.id()));
+            }
+
+            state.insert_account_state(new_account_state.clone(), None);
+            Ok(())
+        })
+    }
+
+    // SYNC
+    // --------------------------------------------------------------------------------------------
+
+    async fn get_note_tags(&self) -> Result<Vec<NoteTagRecord>, StoreError> {
+        Ok(self.read(|state| state.tags.clone()))
+    }
+
+    async fn get_unique_note_tags(&self) -> Result<BTreeSet<NoteTag>, StoreError> {
+        Ok(self.read(|state| state.tags.iter().map(|record| record.tag).collect()))
+    }
+
+    async fn add_note_tag(&self, tag: NoteTagRecord) -> Result<bool, StoreError> {
+        self.update(|state| {
+            if state.tags.contains(&tag) {
+                return Ok(false);
+            }
+
+            state.tags.push(tag);
+            Ok(true)
+        })
+    }
+
+    async fn remove_note_tag(&self, tag: NoteTagRecord) -> Result<usize, StoreError> {
+        self.update(|state| Ok(state.remove_note_tag(tag)))
+    }
+
+    async fn get_sync_height(&self) -> Result<BlockNumber, StoreError> {
+        Ok(self.read(|state| state.sync_height))
+    }
+
+    async fn get_setting(&self, name: String) -> Result<Option<Vec<u8>>, StoreError> {
+        Ok(self.read(|state| state.settings.get(&name).cloned()))
+    }
+
+    async fn set_setting(&self, name: String, value: Vec<u8>) -> Result<(), StoreError> {
+        self.update(|state| {
+            state.settings.insert(name, value);
+            Ok(())
+        })
+    }
+
+    async fn remove_setting(&self, name: String) -> Result<(), StoreError> {
+        self.update(|state| {
+            state.settings.remove(&name);
+            Ok(())
+        })
+    }
+
+    async fn list_setting_names(&self, prefix: String) -> Result<Vec<String>, StoreError> {
+        Ok(self.read(|state| {
+            state
+                .settings
+                .range(prefix.clone()..)
+                .map(|(name, _)| name)
+                .take_while(|name| name.starts_with(&prefix))
+                .cloned()
+                .collect()
+        }))
+    }
+
+    async fn apply_state_sync(&self, state_sync_update: StateSyncUpdate) -> Result<(), StoreError> {
+        let StateSyncUpdate {
+            block_num,
+            block_updates,
+            note_updates,
+            transaction_updates,
+            account_updates,
+        } = state_sync_update;
+
+        self.update(|state| {
+            state.sync_height = block_num;
+
+            for (block_header, block_has_relevant_notes, new_mmr_peaks) in
+                block_updates.block_headers()
+            {
+                state.insert_block_header(block_header, new_mmr_peaks, *block_has_relevant_notes);
+            }
+
+            state.insert_partial_blockchain_nodes(block_updates.new_authentication_nodes());
+
+            state.apply_note_updates(&note_updates);
+
+            // Remove the tags of notes that got committed
+            for note_update in note_updates.updated_input_notes() {
+                let note = note_update.inner();
+                if note.is_committed() {
+                    state.remove_note_tag(NoteTagRecord {
+                        tag: note.metadata().expect("Committed notes should have metadata").tag(),
+                        source: NoteTagSource::Note(note.id()),
+                    });
+                }
+            }
+
+            for transaction_record in transaction_updates
+                .committed_transactions()
+                .chain(transaction_updates.discarded_transactions())
+            {
+                state.upsert_transaction(transaction_record.clone());
+            }
+
+            // Remove the account states that are originated from the discarded transactions
+            let account_states_to_rollback: BTreeSet<Word> = transaction_updates
+                .discarded_transactions()
+                .map(|tx_record| tx_record.details.final_account_state)
+                .collect();
+            for account_states in state.accounts.values_mut() {
+                account_states.retain(|account_state| {
+                    !account_states_to_rollback.contains(&account_state.account.commitment())
+                });
+            }
+
+            // Update public accounts that have been updated onchain
+            for account in account_updates.updated_public_accounts() {
+                state.insert_account_state(account.clone(), None);
+            }
+
+            for (account_id, digest) in account_updates.mismatched_private_accounts() {
+                state.lock_account_on_unexpected_commitment(*account_id, *digest);
+            }
+
+            Ok(())
+        })
+    }
+}
+
+// MEMORY STORE STATE
+// ================================================================================================
+
+/// The data held by a [`MemoryStore`].
+#[derive(Default)]
+struct MemoryStoreState {
+    /// Tracked accounts along with all of their known states.
+    accounts: BTreeMap<AccountId, Vec<StoredAccountState>>,
+    /// Cached account code of foreign accounts.
+    foreign_account_code: BTreeMap<AccountId, AccountCode>,
+    /// Tracked transactions, in insertion order.
+    transactions: Vec<TransactionRecord>,
+    /// Tracked input notes, in insertion order.
+    input_notes: Vec<InputNoteRecord>,
+    /// Tracked output notes, in insertion order.
+    output_notes: Vec<OutputNoteRecord>,
+    /// Note tags the client is interested in.
+    tags: Vec<NoteTagRecord>,
+    /// Stored block headers along with the partial blockchain peaks at their height.
+    block_headers: BTreeMap<BlockNumber, BlockHeaderEntry>,
+    /// Authentication nodes of the partial blockchain.
+    partial_blockchain_nodes: BTreeMap<InOrderIndex, Word>,
+    /// Block number of the last state sync.
+    sync_height: BlockNumber,
+    /// Values of the client settings, by name.
+    settings: BTreeMap<String, Vec<u8>>,
+}
+
+impl MemoryStoreState {
+    /// Returns the account state with the highest nonce for the specified account.
+    fn latest_account_state(&self, account_id: AccountId) -> Option<&StoredAccountState> {
+        self.accounts
+            .get(&account_id)?
+            .iter()
+            .max_by_key(|account_state| account_state.account.nonce().as_int())
+    }
+
+    /// Inserts a new state for the account, replacing any state with the same commitment.
+    fn insert_account_state(&mut self, account: Account, seed: Option<Word>) {
+        let account_states = self.accounts.entry(account.id()).or_default();
+        account_states
+            .retain(|account_state| account_state.account.commitment() != account.commitment());
+        account_states.push(StoredAccountState { account, seed, locked: false });
+    }
+
+    /// Locks the account if the mismatched digest doesn't belong to a previous account state (stale
+    /// data).
+    fn lock_account_on_unexpected_commitment(&mut self, account_id: AccountId, digest: Word) {
+        let Some(account_states) = self.accounts.get_mut(&account_id) else {
+            return;
+        };
+
+        if account_states
+            .iter()
+            .any(|account_state| account_state.account.commitment() == digest)
+        {
+            return;
+        }
+
+        for account_state in account_states {
+            account_state.locked = true;
+        }
+    }
+
+    fn upsert_transaction(&mut self, transaction: TransactionRecord) {
+        match self.transactions.iter_mut().find(|stored| stored.id == transaction.id) {
+            Some(stored) => *stored = transaction,
+            None => self.transactions.push(transaction),
+        }
+    }
+
+    fn upsert_input_note(&mut self, note: InputNoteRecord) {
+        match self.input_notes.iter_mut().find(|stored| stored.id() == note.id()) {
+            Some(stored) => *stored = note,
+            None => self.input_notes.push(note),
+        }
+    }
+
+    fn upsert_output_note(&mut self, note: OutputNoteRecord) {
+        match self.output_notes.iter_mut().find(|stored| stored.id() == note.id()) {
+            Some(stored) => *stored = note,
+            None => self.output_notes.push(note),
+        }
+    }
+
+    fn apply_note_updates(&mut self, note_updates: &NoteUpdateTracker) {
+        for input_note in note_updates.updated_input_notes() {
+            self.upsert_input_note(input_note.inner().clone());
+        }
+
+        for output_note in note_updates.updated_output_notes() {
+            self.upsert_output_note(output_note.inner().clone());
+        }
+    }
+
+    /// Removes all occurrences of the tag record and returns how many were removed.
+    fn remove_note_tag(&mut self, tag: NoteTagRecord) -> usize {
+        let tags_before = self.tags.len();
+        self.tags.retain(|record| *record != tag);
+        tags_before - self.tags.len()
+    }
+
+    /// Inserts the block header if it isn't stored yet. If the block header exists and
+    /// `has_client_notes` is `true`, the stored entry is marked as having client notes.
+    fn insert_block_header(
+        &mut self,
+        block_header: &BlockHeader,
+        partial_blockchain_peaks: &MmrPeaks,
+        has_client_notes: bool,
+    ) {
+        let entry = self.block_headers.entry(block_header.block_num()).or_insert_with(|| {
+            BlockHeaderEntry {
+                header: block_header.clone(),
+                peaks: partial_blockchain_peaks.clone(),
+                has_client_notes,
+            }
+        });
+        entry.has_client_notes |= has_client_notes;
+    }
+
+    fn set_block_has_client_notes(&mut self, block_num: BlockNumber) {
+        if let Some(entry) = self.block_headers.get_mut(&block_num) {
+            entry.has_client_notes = true;
+        }
+    }
+
+    /// Inserts the authentication nodes, ignoring the ones that are already stored.
+    fn insert_partial_blockchain_nodes(&mut self, nodes: &[(InOrderIndex, Word)]) {
+        for (index, node) in nodes {
+            self.partial_blockchain_nodes.entry(*index).or_insert(*node);
+        }
+    }
+}
+
+/// A single stored state of a tracked account.
+#[derive(Clone)]
+struct StoredAccountState {
+    account: Account,
+    /// Seed used to create the account, only present on the state it was inserted with.
+    seed: Option<Word>,
+    locked: bool,
+}
+
+impl StoredAccountState {
+    fn header(&self) -> AccountHeader {
+        (&self.account).into()
+    }
+
+    fn status(&self) -> AccountStatus {
+        match (self.seed, self.locked) {
+            (_, true) => AccountStatus::Locked,
+            (Some(seed), _) => AccountStatus::New { seed },
+            _ => AccountStatus::Tracked,
+        }
+    }
+}
+
+/// A stored block header along with the partial blockchain peaks at its height.
+#[derive(Clone)]
+struct BlockHeaderEntry {
+    header: BlockHeader,
+    peaks: MmrPeaks,
+    has_client_notes: bool,
+}
+
+// FILTERS
+// ================================================================================================
+
+fn transaction_matches(filter: &TransactionFilter, transaction: &TransactionRecord) -> bool {
+    match filter {
+        TransactionFilter::All => true,
+        TransactionFilter::Uncommitted => matches!(
+            transaction.status,
+            TransactionStatus::Pending | TransactionStatus::Discarded(_)
+        ),
+        TransactionFilter::Ids(ids) => ids.contains(&transaction.id),
+        TransactionFilter::ExpiredBefore(block_num) => {
+            transaction.details.block_num < *block_num
+                && matches!(transaction.status, TransactionStatus::Pending)
+        },
+    }
+}
+
+fn input_note_matches(filter: &NoteFilter, note: &InputNoteRecord) -> bool {
+    let state = note.state().discriminant();
+    match filter {
+        NoteFilter::All => true,
+        NoteFilter::Committed => state == InputNoteState::STATE_COMMITTED,
+        NoteFilter::Consumed => [
+            InputNoteState::STATE_CONSUMED_AUTHENTICATED_LOCAL,
+            InputNoteState::STATE_CONSUMED_UNAUTHENTICATED_LOCAL,
+            InputNoteState::STATE_CONSUMED_EXTERNAL,
+        ]
+        .contains(&state),
+        NoteFilter::Expected => state == InputNoteState::STATE_EXPECTED,
+        NoteFilter::Processing => [
+            InputNoteState::STATE_PROCESSING_AUTHENTICATED,
+            InputNoteState::STATE_PROCESSING_UNAUTHENTICATED,
+        ]
+        .contains(&state),
+        NoteFilter::Unique(note_id) => note.id() == *note_id,
+        NoteFilter::List(note_ids) => note_ids.contains(&note.id()),
+        NoteFilter::Nullifiers(nullifiers) => nullifiers.contains(&note.nullifier()),
+        NoteFilter::Unverified => state == InputNoteState::STATE_UNVERIFIED,
+        NoteFilter::Unspent => [
+            InputNoteState::STATE_EXPECTED,
+            InputNoteState::STATE_PROCESSING_AUTHENTICATED,
+            InputNoteState::STATE_PROCESSING_UNAUTHENTICATED,
+            InputNoteState::STATE_UNVERIFIED,
+            InputNoteState::STATE_COMMITTED,
+        ]
+        .contains(&state),
+    }
+}
+
+fn output_note_matches(filter: &NoteFilter, note: &OutputNoteRecord) -> bool {
+    let state = note.state().discriminant();
+    match filter {
+        NoteFilter::All => true,
+        NoteFilter::Committed => {
+            [OutputNoteState::STATE_COMMITTED_PARTIAL, OutputNoteState::STATE_COMMITTED_FULL]
+                .contains(&state)
+        },
+        NoteFilter::Consumed => state == OutputNoteState::STATE_CONSUMED,
+        NoteFilter::Expected => {
+            [OutputNoteState::STATE_EXPECTED_PARTIAL, OutputNoteState::STATE_EXPECTED_FULL]
+                .contains(&state)
+        },
+        // There are no processing or unverified output notes
+        NoteFilter::Processing | NoteFilter::Unverified => false,
+        NoteFilter::Unique(note_id) => note.id() == *note_id,
+        NoteFilter::List(note_ids) => note_ids.contains(&note.id()),
+        NoteFilter::Nullifiers(nullifiers) => {
+            note.nullifier().is_some_and(|nullifier| nullifiers.contains(&nullifier))
+        },
+        NoteFilter::Unspent => {
+            [OutputNoteState::STATE_EXPECTED_FULL, OutputNoteState::STATE_COMMITTED_FULL]
+                .contains(&state)
+        },
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for MemoryStore {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.state.read().write_into(target);
+    }
+}
+
+impl Deserializable for MemoryStore {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            state: RwLock::new(MemoryStoreState::read_from(source)?),
+        })
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::boxed::Box;
+    use alloc::vec::Vec;
+
+    use miden_lib::account::auth::AuthRpoFalcon512;
+    use miden_lib::testing::mock_account::MockAccountExt;
+    use miden_lib::transaction::TransactionKernel;
+    use miden_objects::EMPTY_WORD;
+    use miden_objects::account::Account;
+    use miden_objects::block::BlockHeader;
+    use miden_objects::crypto::dsa::rpo_falcon512::PublicKey;
+    use miden_objects::crypto::merkle::{Forest, MmrPeaks};
+    use miden_objects::testing::account_id::ACCOUNT_ID_REGULAR_PRIVATE_ACCOUNT_UPDATABLE_CODE;
+    use miden_tx::utils::{Deserializable, Serializable};
+
+    use super::MemoryStore;
+    use crate::store::{AccountStatus, Store};
+    use crate::sync::{NoteTagRecord, NoteTagSource};
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn is_send_sync() {
+        assert_send_sync::<MemoryStore>();
+    }
+
+    async fn insert_dummy_block_headers(store: &MemoryStore) -> Vec<BlockHeader> {
+        let block_headers: Vec<BlockHeader> = (0..5)
+            .map(|block_num| {
+                BlockHeader::mock(
+                    block_num,
+                    None,
+                    None,
+                    &[],
+                    TransactionKernel::kernel_commitment(),
+                )
+            })
+            .collect();
+
+        let dummy_peaks = MmrPeaks::new(Forest::empty(), Vec::new()).unwrap();
+        for block_header in &block_headers {
+            store
+                .insert_block_header(block_header, dummy_peaks.clone(), false)
+                .await
+                .unwrap();
+        }
+
+        block_headers
+    }
+
+    #[tokio::test]
+    async fn insert_and_get_block_headers() {
+        let store = MemoryStore::new();
+        let block_headers = insert_dummy_block_headers(&store).await;
+
+        let block_header = store.get_block_header_by_num(3.into()).await.unwrap().unwrap();
+        assert_eq!(block_headers[3], block_header.0);
+
+        let fetched_headers: Vec<BlockHeader> = store
+            .get_block_headers(&[1.into(), 3.into()].into_iter().collect())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(block_header, _has_notes)| block_header)
+            .collect();
+        assert_eq!(&[block_headers[1].clone(), block_headers[3].clone()], &fetched_headers[..]);
+
+        // Re-inserting a header only upgrades its relevance
+        let dummy_peaks = MmrPeaks::new(Forest::empty(), Vec::new()).unwrap();
+        store.insert_block_header(&block_headers[2], dummy_peaks, true).await.unwrap();
+        assert_eq!(
+            store.get_tracked_block_headers().await.unwrap(),
+            vec![block_headers[2].clone()]
+        );
+    }
+
+    #[tokio::test]
+    async fn note_tags_are_tracked_once() {
+        let store = MemoryStore::new();
+        let tag = NoteTagRecord {
+            tag: 1.into(),
+            source: NoteTagSource::User,
+        };
+
+        assert!(store.add_note_tag(tag).await.unwrap());
+        assert!(!store.add_note_tag(tag).await.unwrap());
+        assert_eq!(store.get_note_tags().await.unwrap(), vec![tag]);
+
+        assert_eq!(store.remove_note_tag(tag).await.unwrap(), 1);
+        assert!(store.get_note_tags().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn failed_update_leaves_state_untouched() {
+        let store = MemoryStore::new();
+        let account = Account::mock(
+            ACCOUNT_ID_REGULAR_PRIVATE_ACCOUNT_UPDATABLE_CODE,
+            AuthRpoFalcon512::new(PublicKey::new(EMPTY_WORD)),
+        );
+
+        assert!(store.update_account(&account).await.is_err());
+        assert!(store.get_account_ids().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn snapshot_round_trip() {
+        let store = MemoryStore::new();
+        let block_headers = insert_dummy_block_headers(&store).await;
+        let account = Account::mock(
+            ACCOUNT_ID_REGULAR_PRIVATE_ACCOUNT_UPDATABLE_CODE,
+            AuthRpoFalcon512::new(PublicKey::new(EMPTY_WORD)),
+        );
+        store.insert_account(&account, Some(EMPTY_WORD)).await.unwrap();
+        store.add_note_tag((&account).into()).await.unwrap();
+
+        let restored = MemoryStore::read_from_bytes(&store.to_bytes()).unwrap();
+
+        let restored_account = restored.get_account(account.id()).await.unwrap().unwrap();
+        assert_eq!(restored_account.account(), &account);
+        assert!(matches!(restored_account.status(), AccountStatus::New { .. }));
+        assert_eq!(restored.get_note_tags().await.unwrap(), store.get_note_tags().await.unwrap());
+        assert_eq!(
+            restored.get_block_header_by_num(4.into()).await.unwrap().unwrap().0,
+            block_headers[4]
+        );
+
+        let restored: Box<dyn Store> = Box::new(restored);
+        assert_eq!(restored.get_sync_height().await.unwrap(), 0.into());
+    }
+}
@@ -0,0 +1,251 @@
+//! Serialization of the [`MemoryStore`](super::MemoryStore) state.
+//!
+//! A snapshot starts with a version byte followed by each of the state's collections. Every
+//! collection is written as its length followed by its elements. Snapshots of version 1 were
+//! written before settings were stored, and are read with no settings.
+
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::num::NonZeroUsize;
+
+use miden_objects::Word;
+use miden_objects::account::{Account, AccountCode, AccountId};
+use miden_objects::block::{BlockHeader, BlockNumber};
+use miden_objects::crypto::merkle::{Forest, InOrderIndex, MmrPeaks};
+use miden_objects::note::{NoteAssets, NoteMetadata, NoteTag};
+use miden_objects::transaction::{TransactionId, TransactionScript};
+use miden_tx::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use super::{BlockHeaderEntry, MemoryStoreState, StoredAccountState};
+use crate::store::{InputNoteRecord, OutputNoteRecord, OutputNoteState};
+use crate::sync::{NoteTagRecord, NoteTagSource};
+use crate::transaction::{
+    TransactionDetails,
+    TransactionRecord,
+    TransactionRequest,
+    TransactionStatus,
+};
+
+/// Version of the snapshot format, bumped whenever the layout changes.
+const SNAPSHOT_VERSION: u8 = 2;
+
+impl Serializable for MemoryStoreState {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(SNAPSHOT_VERSION);
+
+        target.write_usize(self.accounts.len());
+        for (account_id, account_states) in &self.accounts {
+            account_id.write_into(target);
+            account_states.write_into(target);
+        }
+
+        target.write_usize(self.foreign_account_code.len());
+        for (account_id, code) in &self.foreign_account_code {
+            account_id.write_into(target);
+            code.write_into(target);
+        }
+
+        target.write_usize(self.transactions.len());
+        for transaction in &self.transactions {
+            write_transaction_record(transaction, target);
+        }
+
+        self.input_notes.write_into(target);
+
+        target.write_usize(self.output_notes.len());
+        for note in &self.output_notes {
+            write_output_note_record(note, target);
+        }
+
+        target.write_usize(self.tags.len());
+        for tag in &self.tags {
+            tag.tag.write_into(target);
+            tag.source.write_into(target);
+        }
+
+        target.write_usize(self.block_headers.len());
+        for entry in self.block_headers.values() {
+            entry.write_into(target);
+        }
+
+        target.write_usize(self.partial_blockchain_nodes.len());
+        for (index, node) in &self.partial_blockchain_nodes {
+            target.write_usize(index.inner());
+            node.write_into(target);
+        }
+
+        self.sync_height.write_into(target);
+
+        target.write_usize(self.settings.len());
+        for (name, value) in &self.settings {
+            name.write_into(target);
+            value.write_into(target);
+        }
+    }
+}
+
+impl Deserializable for MemoryStoreState {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let version = source.read_u8()?;
+        if version != 1 && version != SNAPSHOT_VERSION {
+            return Err(DeserializationError::InvalidValue(format!(
+                "unsupported memory store snapshot version {version}"
+            )));
+        }
+
+        let mut accounts = BTreeMap::new();
+        for _ in 0..source.read_usize()? {
+            let account_id = AccountId::read_from(source)?;
+            accounts.insert(account_id, Vec::<StoredAccountState>::read_from(source)?);
+        }
+
+        let mut foreign_account_code = BTreeMap::new();
+        for _ in 0..source.read_usize()? {
+            let account_id = AccountId::read_from(source)?;
+            foreign_account_code.insert(account_id, AccountCode::read_from(source)?);
+        }
+
+        let transactions = (0..source.read_usize()?)
+            .map(|_| read_transaction_record(source))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let input_notes = Vec::<InputNoteRecord>::read_from(source)?;
+
+        let output_notes = (0..source.read_usize()?)
+            .map(|_| read_output_note_record(source))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let tags = (0..source.read_usize()?)
+            .map(|_| {
+                Ok(NoteTagRecord {
+                    tag: NoteTag::read_from(source)?,
+                    source: NoteTagSource::read_from(source)?,
+                })
+            })
+            .collect::<Result<Vec<_>, DeserializationError>>()?;
+
+        let mut block_headers = BTreeMap::new();
+        for _ in 0..source.read_usize()? {
+            let entry = BlockHeaderEntry::read_from(source)?;
+            block_headers.insert(entry.header.block_num(), entry);
+        }
+
+        let mut partial_blockchain_nodes = BTreeMap::new();
+        for _ in 0..source.read_usize()? {
+            let index = NonZeroUsize::new(source.read_usize()?).ok_or_else(|| {
+                DeserializationError::InvalidValue("in-order index must be non-zero".into())
+            })?;
+            partial_blockchain_nodes.insert(InOrderIndex::new(index), Word::read_from(source)?);
+        }
+
+        let sync_height = BlockNumber::read_from(source)?;
+
+        let mut settings = BTreeMap::new();
+        if version == SNAPSHOT_VERSION {
+            for _ in 0..source.read_usize()? {
+                let name = String::read_from(source)?;
+                settings.insert(name, Vec::<u8>::read_from(source)?);
+            }
+        }
+
+        Ok(Self {
+            accounts,
+            foreign_account_code,
+            transactions,
+            input_notes,
+            output_notes,
+            tags,
+            block_headers,
+            partial_blockchain_nodes,
+            sync_height,
+            settings,
+        })
+    }
+}
+
+impl Serializable for StoredAccountState {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.account.write_into(target);
+        self.seed.write_into(target);
+        target.write_bool(self.locked);
+    }
+}
+
+impl Deserializable for StoredAccountState {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            account: Account::read_from(source)?,
+            seed: Option::<Word>::read_from(source)?,
+            locked: source.read_bool()?,
+        })
+    }
+}
+
+impl Serializable for BlockHeaderEntry {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.header.write_into(target);
+        target.write_usize(self.peaks.forest().num_leaves());
+        self.peaks.peaks().to_vec().write_into(target);
+        target.write_bool(self.has_client_notes);
+    }
+}
+
+impl Deserializable for BlockHeaderEntry {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let header = BlockHeader::read_from(source)?;
+        let forest = Forest::new(source.read_usize()?);
+        let peaks = MmrPeaks::new(forest, Vec::<Word>::read_from(source)?)
+            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))?;
+        let has_client_notes = source.read_bool()?;
+
+        Ok(Self { header, peaks, has_client_notes })
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+fn write_transaction_record<W: ByteWriter>(transaction: &TransactionRecord, target: &mut W) {
+    transaction.id.write_into(target);
+    transaction.details.write_into(target);
+    transaction.script.write_into(target);
+    transaction.status.write_into(target);
+    transaction.request.write_into(target);
+    transaction.retry_of.write_into(target);
+}
+
+fn read_transaction_record<R: ByteReader>(
+    source: &mut R,
+) -> Result<TransactionRecord, DeserializationError> {
+    Ok(TransactionRecord::new(
+        TransactionId::read_from(source)?,
+        TransactionDetails::read_from(source)?,
+        Option::<TransactionScript>::read_from(source)?,
+        TransactionStatus::read_from(source)?,
+    )
+    .with_request(
+        Option::<TransactionRequest>::read_from(source)?,
+        Option::<TransactionId>::read_from(source)?,
+    ))
+}
+
+fn write_output_note_record<W: ByteWriter>(note: &OutputNoteRecord, target: &mut W) {
+    note.recipient_digest().write_into(target);
+    note.assets().write_into(target);
+    note.metadata().write_into(target);
+    note.state().write_into(target);
+    note.expected_height().write_into(target);
+}
+
+fn read_output_note_record<R: ByteReader>(
+    source: &mut R,
+) -> Result<OutputNoteRecord, DeserializationError> {
+    Ok(OutputNoteRecord::new(
+        Word::read_from(source)?,
+        NoteAssets::read_from(source)?,
+        NoteMetadata::read_from(source)?,
+        OutputNoteState::read_from(source)?,
+        BlockNumber::read_from(source)?,
+    ))
+}
@@ -22,6 +22,7 @@
 
 use alloc::boxed::Box;
 use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
 use alloc::vec::Vec;
 use core::fmt::Debug;
 
@@ -55,6 +56,8 @@ pub mod sqlite_store;
 #[cfg(feature = "idxdb")]
 pub mod web_store;
 
+pub mod memory_store;
+
 mod account;
 pub use account::{AccountRecord, AccountStatus, AccountUpdates};
 mod note_record;
@@ -314,6 +317,48 @@ pub trait Store: Send + Sync {
     /// - Storing new MMR authentication nodes.
     /// - Updating the tracked public accounts.
     async fn apply_state_sync(&self, state_sync_update: StateSyncUpdate) -> Result<(), StoreError>;
+
+    // SETTINGS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the value of the setting with the given name, or `None` if it was never set.
+    ///
+    /// Settings hold client state that isn't part of the chain data but must persist between
+    /// runs, such as the transaction queue.
+    ///
+    /// The default implementation returns an error, for stores that don't support settings.
+    /// Features that rely on settings, such as the transaction queue, fail with such stores.
+    async fn get_setting(&self, name: String) -> Result<Option<Vec<u8>>, StoreError> {
+        Err(settings_not_supported(&name))
+    }
+
+    /// Sets the value of the setting with the given name, replacing any previous value.
+    ///
+    /// The default implementation returns an error, for stores that don't support settings.
+    async fn set_setting(&self, name: String, _value: Vec<u8>) -> Result<(), StoreError> {
+        Err(settings_not_supported(&name))
+    }
+
+    /// Removes the setting with the given name. Removing a setting that was never set does
+    /// nothing.
+    ///
+    /// The default implementation returns an error, for stores that don't support settings.
+    async fn remove_setting(&self, name: String) -> Result<(), StoreError> {
+        Err(settings_not_supported(&name))
+    }
+
+    /// Returns the names of the settings that start with the given prefix, in lexicographic
+    /// order.
+    ///
+    /// The default implementation returns an error, for stores that don't support settings.
+    async fn list_setting_names(&self, prefix: String) -> Result<Vec<String>, StoreError> {
+        Err(settings_not_supported(&prefix))
+    }
+}
+
+/// Returns the error of the default implementations of the settings methods of [`Store`].
+fn settings_not_supported(name: &str) -> StoreError {
+    StoreError::DatabaseError(format!("the store doesn't support settings (setting `{name}`)"))
 }
 
 // PARTIAL BLOCKCHAIN NODE FILTER
@@ -870,6 +870,9 @@ fn query_account_headers(
         .collect::<Result<Vec<(AccountHeader, AccountStatus)>, StoreError>>()
 }
 
+// Tests of the SQL behind the account tables, such as the deduplication of account code and the
+// application of deltas. The behavior seen through the `Store` trait is tested against every
+// backend in `crate::tests`.
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
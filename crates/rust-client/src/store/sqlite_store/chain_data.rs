@@ -352,79 +352,3 @@ pub(crate) fn set_block_header_has_client_notes(
     tx.execute(QUERY, params![has_client_notes, block_num])?;
     Ok(())
 }
-
-#[cfg(test)]
-mod test {
-    use alloc::vec::Vec;
-
-    use miden_lib::transaction::TransactionKernel;
-    use miden_objects::block::BlockHeader;
-    use miden_objects::crypto::merkle::{Forest, MmrPeaks};
-
-    use crate::store::Store;
-    use crate::store::sqlite_store::SqliteStore;
-    use crate::store::sqlite_store::tests::create_test_store;
-
-    async fn insert_dummy_block_headers(store: &mut SqliteStore) -> Vec<BlockHeader> {
-        let block_headers: Vec<BlockHeader> = (0..5)
-            .map(|block_num| {
-                BlockHeader::mock(
-                    block_num,
-                    None,
-                    None,
-                    &[],
-                    TransactionKernel::kernel_commitment(),
-                )
-            })
-            .collect();
-
-        let block_headers_clone = block_headers.clone();
-        store
-            .interact_with_connection(move |conn| {
-                let tx = conn.transaction().unwrap();
-                let dummy_peaks = MmrPeaks::new(Forest::empty(), Vec::new()).unwrap();
-                (0..5).for_each(|block_num| {
-                    SqliteStore::insert_block_header_tx(
-                        &tx,
-                        &block_headers_clone[block_num],
-                        &dummy_peaks,
-                        false,
-                    )
-                    .unwrap();
-                });
-                tx.commit().unwrap();
-                Ok(())
-            })
-            .await
-            .unwrap();
-
-        block_headers
-    }
-
-    #[tokio::test]
-    async fn insert_and_get_block_headers_by_number() {
-        let mut store = create_test_store().await;
-        let block_headers = insert_dummy_block_headers(&mut store).await;
-
-        let block_header = Store::get_block_header_by_num(&store, 3.into()).await.unwrap().unwrap();
-        assert_eq!(block_headers[3], block_header.0);
-    }
-
-    #[tokio::test]
-    async fn insert_and_get_block_headers_by_list() {
-        let mut store = create_test_store().await;
-        let mock_block_headers = insert_dummy_block_headers(&mut store).await;
-
-        let block_headers: Vec<BlockHeader> =
-            Store::get_block_headers(&store, &[1.into(), 3.into()].into_iter().collect())
-                .await
-                .unwrap()
-                .into_iter()
-                .map(|(block_header, _has_notes)| block_header)
-                .collect();
-        assert_eq!(
-            &[mock_block_headers[1].clone(), mock_block_headers[3].clone()],
-            &block_headers[..]
-        );
-    }
-}
@@ -0,0 +1,4 @@
+-- Keep the request each transaction was executed from, so that discarded transactions can be
+-- retried, and link retries to the transaction they replace.
+ALTER TABLE transactions ADD COLUMN request BLOB;
+ALTER TABLE transactions ADD COLUMN retry_of TEXT;
@@ -57,10 +57,11 @@ macro_rules! insert_sql {
 
 type Hash = Blake3Digest<20>;
 
-const MIGRATION_SCRIPTS: [&str; 3] = [
+const MIGRATION_SCRIPTS: [&str; 4] = [
     include_str!("../store.sql"),
     include_str!("./migrations/001_schema_updates.sql"),
     include_str!("./migrations/002_index_updates.sql"),
+    include_str!("./migrations/003_transaction_requests.sql"),
 ];
 static MIGRATION_HASHES: LazyLock<Vec<Hash>> = LazyLock::new(compute_migration_hashes);
 static MIGRATIONS: LazyLock<Migrations> = LazyLock::new(prepare_migrations);
@@ -147,6 +148,20 @@ pub fn set_settings_value<T: ToSql>(conn: &Connection, name: &str, value: &T) ->
     Ok(())
 }
 
+pub fn remove_settings_value(conn: &Connection, name: &str) -> Result<()> {
+    conn.execute("DELETE FROM settings WHERE name = $1", params![name])?;
+    Ok(())
+}
+
+/// Returns the names of the settings that start with `prefix`, in lexicographic order.
+pub fn list_settings_names(conn: &Connection, prefix: &str) -> Result<Vec<String>> {
+    // `substr` is used instead of `LIKE` so that `%` and `_` in the prefix aren't wildcards.
+    let mut stmt = conn.prepare(
+        "SELECT name FROM settings WHERE substr(name, 1, length($1)) = $1 ORDER BY name",
+    )?;
+    stmt.query_map(params![prefix], |row| row.get(0))?.collect()
+}
+
 /// Checks if a table exists in the database.
 pub fn table_exists(transaction: &Transaction, table_name: &str) -> rusqlite::Result<bool> {
     Ok(transaction
@@ -8,10 +8,16 @@ use alloc::boxed::Box;
 use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::vec::Vec;
 use std::path::PathBuf;
-use std::string::ToString;
+use std::string::{String, ToString};
 
 use db_management::pool_manager::{Pool, SqlitePoolManager};
-use db_management::utils::apply_migrations;
+use db_management::utils::{
+    apply_migrations,
+    get_settings_value,
+    list_settings_names,
+    remove_settings_value,
+    set_settings_value,
+};
 use miden_objects::Word;
 use miden_objects::account::{Account, AccountCode, AccountHeader, AccountId};
 use miden_objects::block::{BlockHeader, BlockNumber};
@@ -137,6 +143,26 @@ impl Store for SqliteStore {
         .await
     }
 
+    async fn get_setting(&self, name: String) -> Result<Option<Vec<u8>>, StoreError> {
+        self.interact_with_connection(move |conn| Ok(get_settings_value(conn, &name)?))
+            .await
+    }
+
+    async fn set_setting(&self, name: String, value: Vec<u8>) -> Result<(), StoreError> {
+        self.interact_with_connection(move |conn| Ok(set_settings_value(conn, &name, &value)?))
+            .await
+    }
+
+    async fn remove_setting(&self, name: String) -> Result<(), StoreError> {
+        self.interact_with_connection(move |conn| Ok(remove_settings_value(conn, &name)?))
+            .await
+    }
+
+    async fn list_setting_names(&self, prefix: String) -> Result<Vec<String>, StoreError> {
+        self.interact_with_connection(move |conn| Ok(list_settings_names(conn, &prefix)?))
+            .await
+    }
+
     async fn get_transactions(
         &self,
         transaction_filter: TransactionFilter,
@@ -364,6 +390,7 @@ pub mod tests {
 
     use super::SqliteStore;
     use crate::store::Store;
+    use crate::store::memory_store::MemoryStore;
     use crate::testing::common::create_test_store_path;
 
     fn assert_send_sync<T: Send + Sync>() {}
@@ -371,6 +398,7 @@ pub mod tests {
     #[test]
     fn is_send_sync() {
         assert_send_sync::<SqliteStore>();
+        assert_send_sync::<MemoryStore>();
         assert_send_sync::<Box<dyn Store>>();
     }
 
@@ -386,6 +414,9 @@ pub mod tests {
         let client = SqliteStore::new(create_test_store_path()).await.unwrap();
         let client: Box<SqliteStore> = client.into();
         tokio::task::spawn(async move { dyn_trait_send_fut(client).await });
+
+        let client: Box<MemoryStore> = MemoryStore::new().into();
+        tokio::task::spawn(async move { dyn_trait_send_fut(client).await });
     }
 
     pub(crate) async fn create_test_store() -> SqliteStore {
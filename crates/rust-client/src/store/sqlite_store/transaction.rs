@@ -17,6 +17,7 @@ use crate::store::{StoreError, TransactionFilter};
 use crate::transaction::{
     TransactionDetails,
     TransactionRecord,
+    TransactionRequest,
     TransactionStatus,
     TransactionStatusVariant,
     TransactionStoreUpdate,
@@ -30,7 +31,9 @@ pub(crate) const UPSERT_TRANSACTION_QUERY: &str = insert_sql!(
         script_root,
         block_num,
         status_variant,
-        status
+        status,
+        request,
+        retry_of
     } | REPLACE
 );
 
@@ -43,7 +46,7 @@ pub(crate) const INSERT_TRANSACTION_SCRIPT_QUERY: &str =
 impl TransactionFilter {
     /// Returns a [String] containing the query for this Filter.
     pub fn to_query(&self) -> String {
-        const QUERY: &str = "SELECT tx.id, script.script, tx.details, tx.status \
+        const QUERY: &str = "SELECT tx.id, script.script, tx.details, tx.status, tx.request, tx.retry_of \
             FROM transactions AS tx LEFT JOIN transaction_scripts AS script ON tx.script_root = script.script_root";
         match self {
             TransactionFilter::All => QUERY.to_string(),
@@ -86,6 +89,10 @@ struct SerializedTransactionData {
     status_variant: u8,
     /// Serialized transaction status
     status: Vec<u8>,
+    /// Serialized request the transaction was executed from
+    request: Option<Vec<u8>>,
+    /// ID of the transaction retried by this one
+    retry_of: Option<String>,
 }
 
 struct SerializedTransactionParts {
@@ -97,6 +104,10 @@ struct SerializedTransactionParts {
     details: Vec<u8>,
     /// Serialized transaction status
     status: Vec<u8>,
+    /// Serialized request the transaction was executed from
+    request: Option<Vec<u8>>,
+    /// ID of the transaction retried by this one
+    retry_of: Option<String>,
 }
 
 impl SqliteStore {
@@ -175,7 +186,8 @@ impl SqliteStore {
             details,
             executed_transaction.tx_args().tx_script().cloned(),
             TransactionStatus::Pending,
-        );
+        )
+        .with_request(tx_update.request().cloned(), tx_update.retry_of());
 
         // Insert transaction data
         upsert_transaction_record(&tx, &transaction_record)?;
@@ -216,6 +228,8 @@ pub(crate) fn upsert_transaction_record(
         block_num,
         status_variant,
         status,
+        request,
+        retry_of,
     } = serialize_transaction_data(transaction);
 
     if let Some(root) = script_root.clone() {
@@ -224,7 +238,7 @@ pub(crate) fn upsert_transaction_record(
 
     tx.execute(
         UPSERT_TRANSACTION_QUERY,
-        params![id, details, script_root, block_num, status_variant, status],
+        params![id, details, script_root, block_num, status_variant, status, request, retry_of],
     )?;
 
     Ok(())
@@ -245,6 +259,8 @@ fn serialize_transaction_data(transaction_record: &TransactionRecord) -> Seriali
         block_num: transaction_record.details.block_num.as_u32(),
         status_variant: transaction_record.status.variant() as u8,
         status: transaction_record.status.to_bytes(),
+        request: transaction_record.request.as_ref().map(TransactionRequest::to_bytes),
+        retry_of: transaction_record.retry_of.map(|id| id.to_hex()),
     }
 }
 
@@ -255,15 +271,31 @@ fn parse_transaction_columns(
     let tx_script: Option<Vec<u8>> = row.get(1)?;
     let details: Vec<u8> = row.get(2)?;
     let status: Vec<u8> = row.get(3)?;
+    let request: Option<Vec<u8>> = row.get(4)?;
+    let retry_of: Option<String> = row.get(5)?;
 
-    Ok(SerializedTransactionParts { id, tx_script, details, status })
+    Ok(SerializedTransactionParts {
+        id,
+        tx_script,
+        details,
+        status,
+        request,
+        retry_of,
+    })
 }
 
 /// Parse a transaction from the provided parts.
 fn parse_transaction(
     serialized_transaction: SerializedTransactionParts,
 ) -> Result<TransactionRecord, StoreError> {
-    let SerializedTransactionParts { id, tx_script, details, status } = serialized_transaction;
+    let SerializedTransactionParts {
+        id,
+        tx_script,
+        details,
+        status,
+        request,
+        retry_of,
+    } = serialized_transaction;
 
     let id: Word = id.as_str().try_into()?;
 
@@ -271,10 +303,19 @@ fn parse_transaction(
         .map(|script| TransactionScript::read_from_bytes(&script))
         .transpose()?;
 
+    let request: Option<TransactionRequest> = request
+        .map(|request| TransactionRequest::read_from_bytes(&request))
+        .transpose()?;
+
+    let retry_of: Option<Word> =
+        retry_of.map(|retry_of| retry_of.as_str().try_into()).transpose()?;
+
     Ok(TransactionRecord {
         id: id.into(),
         details: TransactionDetails::read_from_bytes(&details)?,
         script,
         status: TransactionStatus::read_from_bytes(&status)?,
+        request,
+        retry_of: retry_of.map(Into::into),
     })
 }
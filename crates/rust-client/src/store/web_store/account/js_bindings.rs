@@ -33,6 +33,9 @@ extern "C" {
     #[wasm_bindgen(js_name = getAccountAuthByPubKey)]
     pub fn idxdb_get_account_auth_by_pub_key(pub_key: String) -> js_sys::Promise;
 
+    #[wasm_bindgen(js_name = getAccountAuthPubKeys)]
+    pub fn idxdb_get_account_auth_pub_keys() -> js_sys::Promise;
+
     // INSERTS
     // ================================================================================================
 
@@ -85,4 +88,7 @@ extern "C" {
 
     #[wasm_bindgen(js_name = undoAccountStates)]
     pub fn idxdb_undo_account_states(account_hashes: Vec<String>) -> js_sys::Promise;
+
+    #[wasm_bindgen(js_name = removeAccountAuth)]
+    pub fn idxdb_remove_account_auth(pub_key: String) -> js_sys::Promise;
 }
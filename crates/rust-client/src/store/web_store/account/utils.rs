@@ -12,11 +12,13 @@ use wasm_bindgen_futures::JsFuture;
 
 use super::js_bindings::{
     idxdb_get_account_auth_by_pub_key,
+    idxdb_get_account_auth_pub_keys,
     idxdb_insert_account_asset_vault,
     idxdb_insert_account_auth,
     idxdb_insert_account_code,
     idxdb_insert_account_record,
     idxdb_insert_account_storage,
+    idxdb_remove_account_auth,
 };
 use super::models::{AccountAuthIdxdbObject, AccountRecordIdxdbObject};
 use crate::store::{AccountStatus, StoreError};
@@ -74,6 +76,24 @@ pub async fn get_account_auth_by_pub_key(pub_key: String) -> Result<String, JsVa
     }
 }
 
+pub async fn get_account_auth_pub_keys() -> Result<Vec<String>, JsValue> {
+    let promise = idxdb_get_account_auth_pub_keys();
+    let js_pub_keys = JsFuture::from(promise).await?;
+
+    let pub_keys: Option<Vec<String>> = from_value(js_pub_keys).map_err(|err| {
+        JsValue::from_str(&format!("error: failed to deserialize public keys: {err}"))
+    })?;
+
+    Ok(pub_keys.unwrap_or_default())
+}
+
+pub async fn remove_account_auth(pub_key: String) -> Result<(), JsValue> {
+    let promise = idxdb_remove_account_auth(pub_key);
+    JsFuture::from(promise).await?;
+
+    Ok(())
+}
+
 pub async fn insert_account_record(
     account: &Account,
     account_seed: Option<Word>,
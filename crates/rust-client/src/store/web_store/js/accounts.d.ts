@@ -47,6 +47,7 @@ export declare function getAccountAssetVault(vaultRoot: string): Promise<{
 export declare function getAccountAuthByPubKey(pubKey: string): Promise<{
   secretKey: string;
 }>;
+export declare function getAccountAuthPubKeys(): Promise<string[] | undefined>;
 export declare function insertAccountCode(
   codeRoot: string,
   code: Uint8Array
@@ -92,3 +93,4 @@ export declare function lockAccount(accountId: string): Promise<void>;
 export declare function undoAccountStates(
   accountCommitments: string[]
 ): Promise<void>;
+export declare function removeAccountAuth(pubKey: string): Promise<void>;
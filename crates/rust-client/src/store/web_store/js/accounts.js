@@ -224,6 +224,15 @@ export async function getAccountAuthByPubKey(pubKey) {
     };
     return data;
 }
+export async function getAccountAuthPubKeys() {
+    try {
+        const allAccountAuths = await accountAuths.toArray();
+        return allAccountAuths.map((accountAuth) => accountAuth.pubKey);
+    }
+    catch (error) {
+        logWebStoreError(error, "Error fetching account auth public keys");
+    }
+}
 // INSERT FUNCTIONS
 export async function insertAccountCode(codeRoot, code) {
     try {
@@ -369,4 +378,12 @@ export async function undoAccountStates(accountCommitments) {
         logWebStoreError(error, `Error undoing account states: ${accountCommitments.join(",")}`);
     }
 }
+export async function removeAccountAuth(pubKey) {
+    try {
+        await accountAuths.where("pubKey").equals(pubKey).delete();
+    }
+    catch (error) {
+        logWebStoreError(error, `Error removing account auth for pubKey: ${pubKey}`);
+    }
+}
 //# sourceMappingURL=accounts.js.map
\ No newline at end of file
@@ -30,6 +30,7 @@ var Table;
     Table["PartialBlockchainNodes"] = "partialBlockchainNodes";
     Table["Tags"] = "tags";
     Table["ForeignAccountCode"] = "foreignAccountCode";
+    Table["Settings"] = "settings";
 })(Table || (Table = {}));
 const db = new Dexie(DATABASE_NAME);
 db.version(1).stores({
@@ -49,6 +50,9 @@ db.version(1).stores({
     [Table.Tags]: indexes("id++", "tag", "source_note_id", "source_account_id"),
     [Table.ForeignAccountCode]: indexes("accountId"),
 });
+db.version(2).stores({
+    [Table.Settings]: indexes("name"),
+});
 function indexes(...items) {
     return items.join(",");
 }
@@ -73,5 +77,6 @@ const blockHeaders = db.table(Table.BlockHeaders);
 const partialBlockchainNodes = db.table(Table.PartialBlockchainNodes);
 const tags = db.table(Table.Tags);
 const foreignAccountCode = db.table(Table.ForeignAccountCode);
-export { db, accountCodes, accountStorages, accountVaults, accountAuths, accounts, transactions, transactionScripts, inputNotes, outputNotes, notesScripts, stateSync, blockHeaders, partialBlockchainNodes, tags, foreignAccountCode, };
+const settings = db.table(Table.Settings);
+export { db, accountCodes, accountStorages, accountVaults, accountAuths, accounts, transactions, transactionScripts, inputNotes, outputNotes, notesScripts, stateSync, blockHeaders, partialBlockchainNodes, tags, foreignAccountCode, settings, };
 //# sourceMappingURL=schema.js.map
\ No newline at end of file
@@ -1,4 +1,4 @@
-import { db, stateSync, inputNotes, outputNotes, transactions, blockHeaders, partialBlockchainNodes, tags, } from "./schema.js";
+import { db, stateSync, inputNotes, outputNotes, transactions, blockHeaders, partialBlockchainNodes, tags, settings, } from "./schema.js";
 import { upsertTransactionRecord, insertTransactionScript, } from "./transactions.js";
 import { upsertInputNote, upsertOutputNote } from "./notes.js";
 import { insertAccountStorage, insertAccountAssetVault, insertAccountRecord, } from "./accounts.js";
@@ -66,6 +66,43 @@ export async function removeNoteTag(tag, sourceNoteId, sourceAccountId) {
         logWebStoreError(error, "Failed to remove note tag");
     }
 }
+export async function getSetting(name) {
+    try {
+        const record = await settings.get(name);
+        if (!record) {
+            return null;
+        }
+        const valueArrayBuffer = await record.value.arrayBuffer();
+        return { value: uint8ArrayToBase64(new Uint8Array(valueArrayBuffer)) };
+    }
+    catch (error) {
+        logWebStoreError(error, `Error fetching setting ${name}`);
+    }
+}
+export async function setSetting(name, value) {
+    try {
+        await settings.put({ name, value: new Blob([new Uint8Array(value)]) });
+    }
+    catch (error) {
+        logWebStoreError(error, `Failed to set setting ${name}`);
+    }
+}
+export async function removeSetting(name) {
+    try {
+        await settings.delete(name);
+    }
+    catch (error) {
+        logWebStoreError(error, `Failed to remove setting ${name}`);
+    }
+}
+export async function listSettingNames(prefix) {
+    try {
+        return await settings.where("name").startsWith(prefix).primaryKeys();
+    }
+    catch (error) {
+        logWebStoreError(error, `Error listing settings with prefix ${prefix}`);
+    }
+}
 /*
  * Takes a `JsStateSyncUpdate` object and writes the state update into the store.
  * @param {JsStateSyncUpdate}
@@ -72,6 +72,11 @@ export async function getTransactions(filter) {
             const statusArrayBuffer = await transactionRecord.status.arrayBuffer();
             const statusArray = new Uint8Array(statusArrayBuffer);
             const statusBase64 = uint8ArrayToBase64(statusArray);
+            let requestBase64 = undefined;
+            if (transactionRecord.request) {
+                const requestArrayBuffer = await transactionRecord.request.arrayBuffer();
+                requestBase64 = uint8ArrayToBase64(new Uint8Array(requestArrayBuffer));
+            }
             const data = {
                 id: transactionRecord.id,
                 details: detailsBase64,
@@ -80,6 +85,8 @@ export async function getTransactions(filter) {
                 blockNum: transactionRecord.blockNum.toString(),
                 statusVariant: transactionRecord.statusVariant,
                 status: statusBase64,
+                request: requestBase64,
+                retryOf: transactionRecord.retryOf,
             };
             return data;
         }));
@@ -103,7 +110,7 @@ export async function insertTransactionScript(scriptRoot, txScript) {
         logWebStoreError(error, "Failed to insert transaction script");
     }
 }
-export async function upsertTransactionRecord(transactionId, details, blockNum, statusVariant, status, scriptRoot) {
+export async function upsertTransactionRecord(transactionId, details, blockNum, statusVariant, status, scriptRoot, request, retryOf) {
     try {
         const detailsBlob = new Blob([new Uint8Array(details)]);
         const statusBlob = new Blob([new Uint8Array(status)]);
@@ -114,6 +121,8 @@ export async function upsertTransactionRecord(transactionId, details, blockNum,
             blockNum: parseInt(blockNum, 10),
             statusVariant,
             status: statusBlob,
+            request: mapOption(request, (request) => new Blob([new Uint8Array(request)])),
+            retryOf,
         };
         await transactions.put(data);
     }
@@ -9,6 +9,7 @@
 
 use alloc::boxed::Box;
 use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
 use alloc::vec::Vec;
 
 use miden_objects::Word;
@@ -91,6 +92,22 @@ impl Store for WebStore {
         self.apply_state_sync(state_sync_update).await
     }
 
+    async fn get_setting(&self, name: String) -> Result<Option<Vec<u8>>, StoreError> {
+        self.get_setting(name).await
+    }
+
+    async fn set_setting(&self, name: String, value: Vec<u8>) -> Result<(), StoreError> {
+        self.set_setting(name, value).await
+    }
+
+    async fn remove_setting(&self, name: String) -> Result<(), StoreError> {
+        self.remove_setting(name).await
+    }
+
+    async fn list_setting_names(&self, prefix: String) -> Result<Vec<String>, StoreError> {
+        self.list_setting_names(prefix).await
+    }
+
     // TRANSACTIONS
     // --------------------------------------------------------------------------------------------
 
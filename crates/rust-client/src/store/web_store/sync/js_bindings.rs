@@ -23,6 +23,12 @@ extern "C" {
     #[wasm_bindgen(js_name = getNoteTags)]
     pub fn idxdb_get_note_tags() -> js_sys::Promise;
 
+    #[wasm_bindgen(js_name = getSetting)]
+    pub fn idxdb_get_setting(name: String) -> js_sys::Promise;
+
+    #[wasm_bindgen(js_name = listSettingNames)]
+    pub fn idxdb_list_setting_names(prefix: String) -> js_sys::Promise;
+
     // INSERTS
     // ================================================================================================
 
@@ -33,6 +39,9 @@ extern "C" {
         source_account_id: Option<String>,
     ) -> js_sys::Promise;
 
+    #[wasm_bindgen(js_name = setSetting)]
+    pub fn idxdb_set_setting(name: String, value: Vec<u8>) -> js_sys::Promise;
+
     #[wasm_bindgen(js_name = applyStateSync)]
     pub fn idxdb_apply_state_sync(state_update: JsStateSyncUpdate) -> js_sys::Promise;
 
@@ -45,6 +54,9 @@ extern "C" {
         source_account_id: Option<String>,
     ) -> js_sys::Promise;
 
+    #[wasm_bindgen(js_name = removeSetting)]
+    pub fn idxdb_remove_setting(name: String) -> js_sys::Promise;
+
     #[wasm_bindgen(js_name = discardTransactions)]
     pub fn idxdb_discard_transactions(transactions: Vec<String>) -> js_sys::Promise;
 }
@@ -25,12 +25,16 @@ use js_bindings::{
     idxdb_add_note_tag,
     idxdb_apply_state_sync,
     idxdb_get_note_tags,
+    idxdb_get_setting,
     idxdb_get_sync_height,
+    idxdb_list_setting_names,
     idxdb_remove_note_tag,
+    idxdb_remove_setting,
+    idxdb_set_setting,
 };
 
 mod models;
-use models::{NoteTagIdxdbObject, SyncHeightIdxdbObject};
+use models::{NoteTagIdxdbObject, SettingIdxdbObject, SyncHeightIdxdbObject};
 
 mod flattened_vec;
 use flattened_vec::flatten_nested_u8_vec;
@@ -80,6 +84,48 @@ impl WebStore {
         Ok(block_num_as_u32.into())
     }
 
+    pub(super) async fn get_setting(&self, name: String) -> Result<Option<Vec<u8>>, StoreError> {
+        let promise = idxdb_get_setting(name);
+        let js_value = JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to get setting: {js_error:?}"))
+        })?;
+        let setting_idxdb: Option<SettingIdxdbObject> = from_value(js_value)
+            .map_err(|err| StoreError::DatabaseError(format!("failed to deserialize {err:?}")))?;
+
+        Ok(setting_idxdb.map(|setting| setting.value))
+    }
+
+    pub(super) async fn set_setting(&self, name: String, value: Vec<u8>) -> Result<(), StoreError> {
+        let promise = idxdb_set_setting(name, value);
+        JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to set setting: {js_error:?}"))
+        })?;
+
+        Ok(())
+    }
+
+    pub(super) async fn remove_setting(&self, name: String) -> Result<(), StoreError> {
+        let promise = idxdb_remove_setting(name);
+        JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to remove setting: {js_error:?}"))
+        })?;
+
+        Ok(())
+    }
+
+    pub(super) async fn list_setting_names(
+        &self,
+        prefix: String,
+    ) -> Result<Vec<String>, StoreError> {
+        let promise = idxdb_list_setting_names(prefix);
+        let js_value = JsFuture::from(promise).await.map_err(|js_error| {
+            StoreError::DatabaseError(format!("failed to list settings: {js_error:?}"))
+        })?;
+
+        from_value(js_value)
+            .map_err(|err| StoreError::DatabaseError(format!("failed to deserialize {err:?}")))
+    }
+
     pub(super) async fn add_note_tag(&self, tag: NoteTagRecord) -> Result<bool, StoreError> {
         if self.get_note_tags().await?.contains(&tag) {
             return Ok(false);
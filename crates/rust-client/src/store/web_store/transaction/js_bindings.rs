@@ -28,5 +28,7 @@ extern "C" {
         statusVariant: u8,
         status: Vec<u8>,
         scriptRoot: Option<Vec<u8>>,
+        request: Option<Vec<u8>>,
+        retryOf: Option<String>,
     ) -> js_sys::Promise;
 }
@@ -15,6 +15,7 @@ use crate::store::{StoreError, TransactionFilter};
 use crate::transaction::{
     TransactionDetails,
     TransactionRecord,
+    TransactionRequest,
     TransactionStatus,
     TransactionStoreUpdate,
 };
@@ -74,7 +75,22 @@ impl WebStore {
 
                 let status = TransactionStatus::read_from_bytes(&tx_idxdb.status)?;
 
-                Ok(TransactionRecord { id: id.into(), details, script, status })
+                let request = tx_idxdb
+                    .request
+                    .map(|request| TransactionRequest::read_from_bytes(&request))
+                    .transpose()?;
+
+                let retry_of: Option<Word> =
+                    tx_idxdb.retry_of.map(TryInto::try_into).transpose()?;
+
+                Ok(TransactionRecord {
+                    id: id.into(),
+                    details,
+                    script,
+                    status,
+                    request,
+                    retry_of: retry_of.map(Into::into),
+                })
             })
             .collect();
 
@@ -86,11 +102,7 @@ impl WebStore {
         tx_update: TransactionStoreUpdate,
     ) -> Result<(), StoreError> {
         // Transaction Data
-        insert_proven_transaction_data(
-            tx_update.executed_transaction(),
-            tx_update.submission_height(),
-        )
-        .await?;
+        insert_proven_transaction_data(&tx_update).await?;
 
         // Account Data
         // TODO: This should be refactored to avoid fetching the whole account state.
@@ -19,6 +19,10 @@ pub struct TransactionIdxdbObject {
     pub block_num: String,
     #[serde(deserialize_with = "base64_to_vec_u8_required", default)]
     pub status: Vec<u8>,
+    #[serde(deserialize_with = "base64_to_vec_u8_optional", default)]
+    pub request: Option<Vec<u8>>,
+    #[serde(default)]
+    pub retry_of: Option<String>,
 }
 
 fn base64_to_vec_u8_required<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
@@ -2,15 +2,20 @@ use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 
 use miden_objects::Word;
-use miden_objects::block::BlockNumber;
-use miden_objects::transaction::{ExecutedTransaction, ToInputNoteCommitments, TransactionScript};
+use miden_objects::transaction::{ToInputNoteCommitments, TransactionScript};
 use miden_tx::utils::Serializable;
 use wasm_bindgen::prelude::wasm_bindgen;
 use wasm_bindgen_futures::JsFuture;
 
 use super::js_bindings::{idxdb_insert_transaction_script, idxdb_upsert_transaction_record};
 use crate::store::StoreError;
-use crate::transaction::{TransactionDetails, TransactionRecord, TransactionStatus};
+use crate::transaction::{
+    TransactionDetails,
+    TransactionRecord,
+    TransactionRequest,
+    TransactionStatus,
+    TransactionStoreUpdate,
+};
 
 // TYPES
 // ================================================================================================
@@ -29,16 +34,20 @@ pub struct SerializedTransactionData {
     #[wasm_bindgen(js_name = "statusVariant")]
     pub status_variant: u8,
     pub status: Vec<u8>,
+    pub request: Option<Vec<u8>>,
+    #[wasm_bindgen(js_name = "retryOf")]
+    pub retry_of: Option<String>,
 }
 
 // ================================================================================================
 
-/// Converts an `ExecutedTransaction` into a `TransactionRecord` and inserts it into the store.
-/// `submission_height` is the block number at which the transaction was submitted to the network.
+/// Converts the transaction of a `TransactionStoreUpdate` into a `TransactionRecord` and inserts it
+/// into the store.
 pub async fn insert_proven_transaction_data(
-    executed_transaction: &ExecutedTransaction,
-    submission_height: BlockNumber,
+    tx_update: &TransactionStoreUpdate,
 ) -> Result<(), StoreError> {
+    let executed_transaction = tx_update.executed_transaction();
+
     // Build transaction record
     let nullifiers: Vec<Word> = executed_transaction
         .input_notes()
@@ -55,7 +64,7 @@ pub async fn insert_proven_transaction_data(
         input_note_nullifiers: nullifiers,
         output_notes: output_notes.clone(),
         block_num: executed_transaction.block_header().block_num(),
-        submission_height,
+        submission_height: tx_update.submission_height(),
         expiration_block_num: executed_transaction.expiration_block_num(),
         creation_timestamp: u64::try_from(chrono::Utc::now().timestamp())
             .expect("timestamp is always after epoch"),
@@ -66,7 +75,8 @@ pub async fn insert_proven_transaction_data(
         details,
         executed_transaction.tx_args().tx_script().cloned(),
         TransactionStatus::Pending,
-    );
+    )
+    .with_request(tx_update.request().cloned(), tx_update.retry_of());
 
     upsert_transaction_record(&transaction_record).await?;
 
@@ -90,6 +100,8 @@ pub(crate) fn serialize_transaction_record(
         block_num: transaction_record.details.block_num.as_u32().to_string(),
         status_variant: transaction_record.status.variant() as u8,
         status: transaction_record.status.to_bytes(),
+        request: transaction_record.request.as_ref().map(TransactionRequest::to_bytes),
+        retry_of: transaction_record.retry_of.map(|id| id.as_word().to_hex()),
     }
 }
 
@@ -113,6 +125,8 @@ pub(crate) async fn upsert_transaction_record(
         serialized_data.status_variant,
         serialized_data.status,
         serialized_data.script_root,
+        serialized_data.request,
+        serialized_data.retry_of,
     );
     JsFuture::from(promise).await.map_err(|js_error| {
         StoreError::DatabaseError(format!("failed to insert transaction data: {js_error:?}"))
@@ -280,6 +280,15 @@ export async function getAccountAuthByPubKey(pubKey: string) {
   return data;
 }
 
+export async function getAccountAuthPubKeys() {
+  try {
+    const allAccountAuths = await accountAuths.toArray();
+    return allAccountAuths.map((accountAuth) => accountAuth.pubKey);
+  } catch (error) {
+    logWebStoreError(error, "Error fetching account auth public keys");
+  }
+}
+
 // INSERT FUNCTIONS
 
 export async function insertAccountCode(codeRoot: string, code: Uint8Array) {
@@ -473,3 +482,14 @@ export async function undoAccountStates(accountCommitments: string[]) {
     );
   }
 }
+
+export async function removeAccountAuth(pubKey: string) {
+  try {
+    await accountAuths.where("pubKey").equals(pubKey).delete();
+  } catch (error) {
+    logWebStoreError(
+      error,
+      `Error removing account auth for pubKey: ${pubKey}`
+    );
+  }
+}
@@ -31,6 +31,7 @@ enum Table {
   PartialBlockchainNodes = "partialBlockchainNodes",
   Tags = "tags",
   ForeignAccountCode = "foreignAccountCode",
+  Settings = "settings",
 }
 
 export interface IAccountCode {
@@ -72,6 +73,8 @@ export interface ITransaction {
   scriptRoot?: string;
   statusVariant: number;
   status: Blob;
+  request?: Blob;
+  retryOf?: string;
 }
 
 export interface ITransactionScript {
@@ -136,6 +139,11 @@ export interface IForeignAccountCode {
   codeRoot: string;
 }
 
+export interface ISetting {
+  name: string;
+  value: Blob;
+}
+
 const db = new Dexie(DATABASE_NAME) as Dexie & {
   accountCodes: Dexie.Table<IAccountCode, string>;
   accountStorages: Dexie.Table<IAccountStorage, string>;
@@ -152,6 +160,7 @@ const db = new Dexie(DATABASE_NAME) as Dexie & {
   partialBlockchainNodes: Dexie.Table<IPartialBlockchainNode, string>;
   tags: Dexie.Table<ITag, number>;
   foreignAccountCode: Dexie.Table<IForeignAccountCode, string>;
+  settings: Dexie.Table<ISetting, string>;
 };
 
 db.version(1).stores({
@@ -183,6 +192,10 @@ db.version(1).stores({
   [Table.ForeignAccountCode]: indexes("accountId"),
 });
 
+db.version(2).stores({
+  [Table.Settings]: indexes("name"),
+});
+
 function indexes(...items: string[]): string {
   return items.join(",");
 }
@@ -215,6 +228,7 @@ const tags = db.table<ITag, number>(Table.Tags);
 const foreignAccountCode = db.table<IForeignAccountCode, string>(
   Table.ForeignAccountCode
 );
+const settings = db.table<ISetting, string>(Table.Settings);
 
 export {
   db,
@@ -233,4 +247,5 @@ export {
   partialBlockchainNodes,
   tags,
   foreignAccountCode,
+  settings,
 };
@@ -7,6 +7,7 @@ import {
   blockHeaders,
   partialBlockchainNodes,
   tags,
+  settings,
 } from "./schema.js";
 
 import {
@@ -97,6 +98,44 @@ export async function removeNoteTag(
   }
 }
 
+export async function getSetting(name: string) {
+  try {
+    const record = await settings.get(name);
+    if (!record) {
+      return null;
+    }
+
+    const valueArrayBuffer = await record.value.arrayBuffer();
+    return { value: uint8ArrayToBase64(new Uint8Array(valueArrayBuffer)) };
+  } catch (error) {
+    logWebStoreError(error, `Error fetching setting ${name}`);
+  }
+}
+
+export async function setSetting(name: string, value: Uint8Array) {
+  try {
+    await settings.put({ name, value: new Blob([new Uint8Array(value)]) });
+  } catch (error) {
+    logWebStoreError(error, `Failed to set setting ${name}`);
+  }
+}
+
+export async function removeSetting(name: string) {
+  try {
+    await settings.delete(name);
+  } catch (error) {
+    logWebStoreError(error, `Failed to remove setting ${name}`);
+  }
+}
+
+export async function listSettingNames(prefix: string) {
+  try {
+    return await settings.where("name").startsWith(prefix).primaryKeys();
+  } catch (error) {
+    logWebStoreError(error, `Error listing settings with prefix ${prefix}`);
+  }
+}
+
 // TODO: The interfaces below are already defined in Rust, we should look into something that keeps
 // types in sync between Rust and Typescript (#1083).
 interface FlattenedU8Vec {
@@ -14,6 +14,8 @@ interface ProcessedTransaction {
   blockNum: string;
   statusVariant: number;
   status?: string;
+  request?: string;
+  retryOf?: string;
 }
 
 const IDS_FILTER_PREFIX = "Ids:";
@@ -105,6 +107,14 @@ export async function getTransactions(filter: string) {
         const statusArray = new Uint8Array(statusArrayBuffer);
         const statusBase64 = uint8ArrayToBase64(statusArray);
 
+        let requestBase64: undefined | string = undefined;
+        if (transactionRecord.request) {
+          const requestArrayBuffer =
+            await transactionRecord.request.arrayBuffer();
+          const requestArray = new Uint8Array(requestArrayBuffer);
+          requestBase64 = uint8ArrayToBase64(requestArray);
+        }
+
         const data: ProcessedTransaction = {
           id: transactionRecord.id,
           details: detailsBase64,
@@ -113,6 +123,8 @@ export async function getTransactions(filter: string) {
           blockNum: transactionRecord.blockNum.toString(),
           statusVariant: transactionRecord.statusVariant,
           status: statusBase64,
+          request: requestBase64,
+          retryOf: transactionRecord.retryOf,
         };
 
         return data;
@@ -153,7 +165,9 @@ export async function upsertTransactionRecord(
   blockNum: string,
   statusVariant: number,
   status: Uint8Array,
-  scriptRoot?: Uint8Array
+  scriptRoot?: Uint8Array,
+  request?: Uint8Array,
+  retryOf?: string
 ) {
   try {
     const detailsBlob = new Blob([new Uint8Array(details)]);
@@ -166,6 +180,11 @@ export async function upsertTransactionRecord(
       blockNum: parseInt(blockNum, 10),
       statusVariant,
       status: statusBlob,
+      request: mapOption(
+        request,
+        (request) => new Blob([new Uint8Array(request)])
+      ),
+      retryOf,
     };
 
     await transactions.put(data);
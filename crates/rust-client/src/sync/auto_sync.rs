@@ -0,0 +1,334 @@
+//! Provides [`SharedClient`], a client that can be used by the application while a background
+//! task keeps it synced with the network, and [`AutoSyncConfig`], which controls how often the
+//! task syncs.
+//!
+//! The background task runs on the current thread, since the client's futures aren't `Send`. On
+//! native targets it's spawned with [`tokio::task::spawn_local`], so it must be started from
+//! within a [`tokio::task::LocalSet`], and on wasm with `wasm_bindgen_futures::spawn_local`.
+//! Each sync holds the client's lock, so transactions executed through [`SharedClient::lock`]
+//! never see the store halfway through applying a sync update.
+//!
+//! With [`SharedClient::start_auto_sync_with_metrics`], the task also exports the metrics of the
+//! RPC requests after every sync, which makes it suitable for long-running processes such as
+//! daemons.
+
+use alloc::rc::Rc;
+#[cfg(feature = "tonic")]
+use alloc::sync::Arc;
+use core::cell::Cell;
+use core::future::Future;
+use core::time::Duration;
+
+use rand::RngCore;
+use tokio::sync::{Mutex, MutexGuard};
+use tokio_util::sync::{CancellationToken, DropGuard};
+use tracing::{info, warn};
+
+use crate::Client;
+#[cfg(feature = "tonic")]
+use crate::rpc::{RpcMetrics, RpcMetricsExporter};
+use crate::transaction::TransactionAuthenticator;
+
+/// Default time between two syncs of the background task.
+pub const DEFAULT_AUTO_SYNC_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Default maximum time the background task waits before syncing again after failed syncs.
+pub const DEFAULT_AUTO_SYNC_MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+// AUTO SYNC CONFIG
+// ================================================================================================
+
+/// Configuration of the background sync task started by [`SharedClient::start_auto_sync`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AutoSyncConfig {
+    /// Time between the end of a successful sync and the start of the next one.
+    pub interval: Duration,
+    /// Maximum time to wait after failed syncs. The wait doubles with each consecutive failure,
+    /// starting from `interval`, and is randomized so that clients don't retry in lockstep.
+    pub max_backoff: Duration,
+}
+
+impl AutoSyncConfig {
+    /// Returns a configuration that syncs every `interval`, with the default maximum backoff.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            max_backoff: DEFAULT_AUTO_SYNC_MAX_BACKOFF.max(interval),
+        }
+    }
+
+    /// Sets the maximum time to wait after failed syncs.
+    #[must_use]
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Returns the time to wait after `failures` consecutive failed syncs, before jitter.
+    fn backoff(&self, failures: u32) -> Duration {
+        let factor = 1u32 << failures.saturating_sub(1).min(16);
+        self.interval.saturating_mul(factor).min(self.max_backoff)
+    }
+}
+
+impl Default for AutoSyncConfig {
+    fn default() -> Self {
+        Self::new(DEFAULT_AUTO_SYNC_INTERVAL)
+    }
+}
+
+// SHARED CLIENT
+// ================================================================================================
+
+/// A client shared between the application and the background sync task.
+///
+/// Cloning it returns another reference to the same client.
+pub struct SharedClient<AUTH> {
+    client: Rc<Mutex<Client<AUTH>>>,
+}
+
+impl<AUTH> Clone for SharedClient<AUTH> {
+    fn clone(&self) -> Self {
+        Self { client: self.client.clone() }
+    }
+}
+
+impl<AUTH> SharedClient<AUTH>
+where
+    AUTH: TransactionAuthenticator + Sync + 'static,
+{
+    /// Returns a shared client wrapping the provided client.
+    pub fn new(client: Client<AUTH>) -> Self {
+        Self { client: Rc::new(Mutex::new(client)) }
+    }
+
+    /// Locks the client, waiting for the sync in progress, if any, to be applied to the store.
+    /// The background task doesn't sync until the returned guard is dropped.
+    pub async fn lock(&self) -> MutexGuard<'_, Client<AUTH>> {
+        self.client.lock().await
+    }
+
+    /// Starts a background task on the current thread that syncs the client periodically, until
+    /// [`AutoSyncHandle::cancel`] is called. On native targets, it must be called from within a
+    /// [`tokio::task::LocalSet`].
+    ///
+    /// Failed syncs are logged and retried with an exponential, jittered backoff.
+    pub fn start_auto_sync(&self, config: AutoSyncConfig) -> AutoSyncHandle {
+        self.spawn_auto_sync(config, || {})
+    }
+
+    /// Starts a background task like [`Self::start_auto_sync`], which also exports `metrics` with
+    /// `exporter` after every sync, whether it succeeded or not. Failed exports are logged.
+    ///
+    /// The metrics are usually those of the [`MetricsRpcClient`](crate::rpc::MetricsRpcClient)
+    /// the client was built with.
+    #[cfg(feature = "tonic")]
+    pub fn start_auto_sync_with_metrics(
+        &self,
+        config: AutoSyncConfig,
+        metrics: Arc<RpcMetrics>,
+        exporter: impl RpcMetricsExporter + 'static,
+    ) -> AutoSyncHandle {
+        self.spawn_auto_sync(config, move || {
+            if let Err(err) = exporter.export(&metrics) {
+                warn!("Failed to export the RPC metrics: {err}");
+            }
+        })
+    }
+
+    /// Spawns the background task, which calls `after_sync` at the end of every sync.
+    fn spawn_auto_sync(
+        &self,
+        config: AutoSyncConfig,
+        after_sync: impl Fn() + 'static,
+    ) -> AutoSyncHandle {
+        let state = Rc::new(AutoSyncState::default());
+        let cancellation = CancellationToken::new();
+        let finished = CancellationToken::new();
+
+        spawn_local(auto_sync_loop(
+            self.clone(),
+            config,
+            after_sync,
+            state.clone(),
+            cancellation.clone(),
+            finished.clone().drop_guard(),
+        ));
+
+        AutoSyncHandle { state, cancellation, finished }
+    }
+}
+
+// AUTO SYNC HANDLE
+// ================================================================================================
+
+#[derive(Debug, Default)]
+struct AutoSyncState {
+    syncs: Cell<u64>,
+    consecutive_failures: Cell<u32>,
+}
+
+/// Handle to the background sync task started by [`SharedClient::start_auto_sync`].
+///
+/// Dropping the handle doesn't stop the task.
+#[derive(Debug)]
+pub struct AutoSyncHandle {
+    state: Rc<AutoSyncState>,
+    /// Cancelled to ask the task to stop.
+    cancellation: CancellationToken,
+    /// Cancelled by the task when it stops.
+    finished: CancellationToken,
+}
+
+impl AutoSyncHandle {
+    /// Asks the task to stop. A sync in progress is interrupted. Each sync step is applied to the
+    /// store atomically, so the store isn't left halfway through an update.
+    pub fn cancel(&self) {
+        self.cancellation.cancel();
+    }
+
+    /// Cancels the task and waits for it to stop.
+    pub async fn stop(self) {
+        self.cancel();
+        self.finished.cancelled().await;
+    }
+
+    /// Returns whether the task was asked to stop.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+
+    /// Returns whether the task is still running.
+    pub fn is_running(&self) -> bool {
+        !self.finished.is_cancelled()
+    }
+
+    /// Returns the number of successful syncs performed by the task.
+    pub fn syncs(&self) -> u64 {
+        self.state.syncs.get()
+    }
+
+    /// Returns the number of syncs that failed in a row since the last successful one.
+    pub fn consecutive_failures(&self) -> u32 {
+        self.state.consecutive_failures.get()
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Syncs the client until `cancellation` is cancelled, calling `after_sync` at the end of every
+/// sync. `_finished` cancels its token when the task stops, even if it panics.
+async fn auto_sync_loop<AUTH>(
+    client: SharedClient<AUTH>,
+    config: AutoSyncConfig,
+    after_sync: impl Fn(),
+    state: Rc<AutoSyncState>,
+    cancellation: CancellationToken,
+    _finished: DropGuard,
+) where
+    AUTH: TransactionAuthenticator + Sync + 'static,
+{
+    loop {
+        let sync = async {
+            let mut client = client.lock().await;
+            match client.sync_state().await {
+                Ok(summary) => {
+                    info!("Background sync reached block {}.", summary.block_num);
+                    state.syncs.set(state.syncs.get() + 1);
+                    state.consecutive_failures.set(0);
+                    config.interval
+                },
+                Err(err) => {
+                    let failures = state.consecutive_failures.get().saturating_add(1);
+                    state.consecutive_failures.set(failures);
+                    warn!("Background sync failed ({failures} in a row): {err}");
+                    with_jitter(config.backoff(failures), client.rng().next_u32())
+                },
+            }
+        };
+
+        let delay = tokio::select! {
+            () = cancellation.cancelled() => break,
+            delay = sync => delay,
+        };
+        after_sync();
+
+        tokio::select! {
+            () = cancellation.cancelled() => break,
+            () = sleep(delay) => {},
+        }
+    }
+}
+
+/// Returns a random duration between half of `delay` and `delay`.
+fn with_jitter(delay: Duration, random: u32) -> Duration {
+    let half = delay / 2;
+    half + half.mul_f64(f64::from(random) / f64::from(u32::MAX))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn spawn_local(task: impl Future<Output = ()> + 'static) {
+    tokio::task::spawn_local(task);
+}
+
+#[cfg(target_arch = "wasm32")]
+fn spawn_local(task: impl Future<Output = ()> + 'static) {
+    wasm_bindgen_futures::spawn_local(task);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn sleep(delay: Duration) {
+    tokio::time::sleep(delay).await;
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn sleep(delay: Duration) {
+    use wasm_bindgen_futures::JsFuture;
+    use wasm_bindgen_futures::js_sys::{Function, Promise, Reflect, global};
+
+    let millis = u32::try_from(delay.as_millis()).unwrap_or(u32::MAX);
+    let promise = Promise::new(&mut |resolve, _reject| {
+        // `setTimeout` is defined both in windows and in web workers
+        let set_timeout = Function::from(
+            Reflect::get(&global(), &"setTimeout".into()).expect("setTimeout should be defined"),
+        );
+        set_timeout
+            .call2(&global(), &resolve, &millis.into())
+            .expect("setTimeout should accept a callback and a delay");
+    });
+
+    // The promise is only ever resolved
+    let _ = JsFuture::from(promise).await;
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use super::{AutoSyncConfig, with_jitter};
+
+    #[test]
+    fn backoff_doubles_up_to_the_maximum() {
+        let config =
+            AutoSyncConfig::new(Duration::from_secs(5)).with_max_backoff(Duration::from_secs(60));
+
+        assert_eq!(config.backoff(1), Duration::from_secs(5));
+        assert_eq!(config.backoff(2), Duration::from_secs(10));
+        assert_eq!(config.backoff(4), Duration::from_secs(40));
+        assert_eq!(config.backoff(5), Duration::from_secs(60));
+        assert_eq!(config.backoff(u32::MAX), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn jitter_stays_within_half_of_the_delay() {
+        let delay = Duration::from_secs(10);
+
+        assert_eq!(with_jitter(delay, 0), Duration::from_secs(5));
+        assert_eq!(with_jitter(delay, u32::MAX), delay);
+        assert!(with_jitter(delay, u32::MAX / 2) > Duration::from_secs(5));
+    }
+}
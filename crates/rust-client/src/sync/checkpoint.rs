@@ -0,0 +1,229 @@
+//! Provides [`SyncCheckpoint`], a block trusted by the user from which a new client can start
+//! syncing instead of walking the chain from genesis.
+//!
+//! Starting from a checkpoint only requires the block header and the peaks of the chain MMR up to
+//! it. The peaks are rebuilt from the MMR proofs of the first block of each peak, and checked
+//! against the chain commitment of the trusted block header. Notes and account updates from
+//! blocks before the checkpoint aren't discovered by later syncs, so a checkpoint should be more
+//! recent than the accounts and notes the client will track.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use miden_objects::Word;
+use miden_objects::block::{BlockHeader, BlockNumber};
+use miden_objects::crypto::hash::rpo::Rpo256;
+use miden_objects::crypto::merkle::{Forest, MmrPeaks};
+use miden_tx::auth::TransactionAuthenticator;
+
+use super::{BlockUpdates, StateSyncUpdate};
+use crate::store::StoreError;
+use crate::{Client, ClientError};
+
+// SYNC CHECKPOINT
+// ================================================================================================
+
+/// A block header commitment trusted by the user.
+///
+/// Checkpoints are written as `<BLOCK NUMBER>:<COMMITMENT>`, with the commitment as a hex string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SyncCheckpoint {
+    /// Number of the block.
+    pub block_num: BlockNumber,
+    /// Commitment of the block header.
+    pub commitment: Word,
+}
+
+impl SyncCheckpoint {
+    /// Returns the checkpoint of a block header.
+    pub fn from_block_header(block_header: &BlockHeader) -> Self {
+        Self {
+            block_num: block_header.block_num(),
+            commitment: block_header.commitment(),
+        }
+    }
+}
+
+impl fmt::Display for SyncCheckpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.block_num, self.commitment.to_hex())
+    }
+}
+
+impl FromStr for SyncCheckpoint {
+    type Err = ClientError;
+
+    fn from_str(checkpoint: &str) -> Result<Self, Self::Err> {
+        let (block_num, commitment) = checkpoint.trim().split_once(':').ok_or_else(|| {
+            ClientError::InvalidSyncCheckpoint(
+                "expected the `<BLOCK NUMBER>:<COMMITMENT>` format".to_string(),
+            )
+        })?;
+
+        let block_num = block_num.parse::<u32>().map_err(|err| {
+            ClientError::InvalidSyncCheckpoint(format!("invalid block number: {err}"))
+        })?;
+        let commitment = Word::try_from(commitment).map_err(|err| {
+            ClientError::InvalidSyncCheckpoint(format!("invalid commitment: {err}"))
+        })?;
+
+        Ok(Self { block_num: block_num.into(), commitment })
+    }
+}
+
+/// Checkpoint methods.
+impl<AUTH> Client<AUTH>
+where
+    AUTH: TransactionAuthenticator + Sync + 'static,
+{
+    /// Returns the checkpoint of the block the client is synced to, which other clients can start
+    /// from with [`Client::start_from_checkpoint`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`StoreError::BlockHeaderNotFound`] if the header of the sync height block isn't
+    /// stored, which is the case for a client that never synced.
+    pub async fn sync_checkpoint(&self) -> Result<SyncCheckpoint, ClientError> {
+        let sync_height = self.store.get_sync_height().await?;
+        let (block_header, _) = self
+            .store
+            .get_block_header_by_num(sync_height)
+            .await?
+            .ok_or(StoreError::BlockHeaderNotFound(sync_height))?;
+
+        Ok(SyncCheckpoint::from_block_header(&block_header))
+    }
+
+    /// Makes a client that never synced start from the checkpoint, so that the next sync begins
+    /// right after the checkpoint block instead of genesis.
+    ///
+    /// This is done by the first sync of a client built with a start checkpoint (see
+    /// [`Client::set_start_checkpoint`]), so it only needs to be called directly otherwise.
+    ///
+    /// The header of the checkpoint block is fetched from the node and must match the checkpoint
+    /// commitment. The peaks of the chain MMR up to the block are fetched as well, and checked
+    /// against the chain commitment of the header.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ClientError::InvalidSyncCheckpoint`] if the client already synced past genesis
+    /// or the data received from the node doesn't match the checkpoint.
+    pub async fn start_from_checkpoint(
+        &mut self,
+        checkpoint: SyncCheckpoint,
+    ) -> Result<(), ClientError> {
+        self.ensure_genesis_in_place().await?;
+
+        let sync_height = self.store.get_sync_height().await?;
+        if sync_height != BlockNumber::GENESIS {
+            return Err(ClientError::InvalidSyncCheckpoint(format!(
+                "the client already synced to block {sync_height}"
+            )));
+        }
+
+        let (block_header, _) = self
+            .rpc_api
+            .get_block_header_by_number(Some(checkpoint.block_num), false)
+            .await?;
+        if block_header.block_num() != checkpoint.block_num
+            || block_header.commitment() != checkpoint.commitment
+        {
+            return Err(ClientError::InvalidSyncCheckpoint(format!(
+                "the node's header for block {} has commitment {}",
+                checkpoint.block_num,
+                block_header.commitment().to_hex()
+            )));
+        }
+
+        if checkpoint.block_num == BlockNumber::GENESIS {
+            return Ok(());
+        }
+
+        let peaks = self.fetch_chain_peaks(checkpoint.block_num).await?;
+        if peaks.hash_peaks() != block_header.chain_commitment() {
+            return Err(ClientError::InvalidSyncCheckpoint(
+                "the chain MMR peaks received from the node don't match the chain commitment of \
+                 the checkpoint block"
+                    .to_string(),
+            ));
+        }
+
+        let mut block_updates = BlockUpdates::default();
+        block_updates.insert(block_header, false, peaks, vec![]);
+
+        self.store
+            .apply_state_sync(StateSyncUpdate {
+                block_num: checkpoint.block_num,
+                block_updates,
+                ..Default::default()
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns the peaks of the chain MMR whose leaves are the blocks before `block_num`.
+    ///
+    /// Each peak is the root of a perfect subtree of the node's current chain MMR, so it's
+    /// computed from the MMR proof of the subtree's first leaf, which is its leftmost leaf at every
+    /// level.
+    async fn fetch_chain_peaks(&self, block_num: BlockNumber) -> Result<MmrPeaks, ClientError> {
+        let forest = Forest::new(block_num.as_usize());
+
+        let mut peaks = Vec::new();
+        let mut first_leaf = 0u32;
+        for height in (0..u32::BITS).rev() {
+            let num_leaves = 1u32 << height;
+            if block_num.as_u32() & num_leaves == 0 {
+                continue;
+            }
+
+            let (leaf_header, mmr_proof) =
+                self.rpc_api.get_block_header_with_proof(first_leaf.into()).await?;
+            let path: Vec<Word> = mmr_proof.merkle_path.iter().copied().collect();
+            let Some(siblings) = path.get(..height as usize) else {
+                return Err(ClientError::InvalidSyncCheckpoint(String::from(
+                    "an MMR proof received from the node is shorter than expected",
+                )));
+            };
+
+            let peak = siblings
+                .iter()
+                .fold(leaf_header.commitment(), |node, sibling| Rpo256::merge(&[node, *sibling]));
+            peaks.push(peak);
+
+            first_leaf += num_leaves;
+        }
+
+        MmrPeaks::new(forest, peaks).map_err(|err| StoreError::MmrError(err).into())
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use miden_objects::{Felt, Word};
+
+    use super::SyncCheckpoint;
+
+    #[test]
+    fn checkpoints_round_trip_through_strings() {
+        let checkpoint = SyncCheckpoint {
+            block_num: 1234.into(),
+            commitment: Word::from([Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)]),
+        };
+
+        let parsed: SyncCheckpoint = checkpoint.to_string().parse().unwrap();
+        assert_eq!(parsed, checkpoint);
+
+        assert!("1234".parse::<SyncCheckpoint>().is_err());
+        assert!("abc:0x01".parse::<SyncCheckpoint>().is_err());
+        assert!("1234:0x12".parse::<SyncCheckpoint>().is_err());
+    }
+}
@@ -0,0 +1,99 @@
+//! Provides [`SyncEvent`], the events emitted while the client syncs its state, and the
+//! [`SyncListener`] trait to receive them, for example to update a UI as notes and transactions
+//! change without waiting for the whole sync to finish.
+
+use alloc::vec::Vec;
+
+use miden_objects::account::AccountId;
+use miden_objects::block::BlockNumber;
+use miden_objects::note::NoteId;
+use miden_objects::transaction::TransactionId;
+
+use super::SyncSummary;
+
+// SYNC EVENTS
+// ================================================================================================
+
+/// An event emitted while the client syncs its state with the network.
+///
+/// [`SyncEvent::Progress`] events are emitted as the sync steps are received from the node. The
+/// rest of the events describe the changes of the sync, and are emitted once they were applied to
+/// the store, followed by [`SyncEvent::Finished`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SyncEvent {
+    /// A sync step up to `block_num` was received from the node, whose chain tip is `chain_tip`.
+    Progress {
+        block_num: BlockNumber,
+        chain_tip: BlockNumber,
+    },
+    /// A new public note relevant to the client was received.
+    NoteReceived(NoteId),
+    /// A tracked note was committed.
+    NoteCommitted(NoteId),
+    /// A tracked note was consumed.
+    NoteConsumed(NoteId),
+    /// A tracked public account was updated.
+    AccountUpdated(AccountId),
+    /// A tracked private account was locked because its state doesn't match the network.
+    AccountLocked(AccountId),
+    /// A tracked transaction was committed.
+    TransactionCommitted(TransactionId),
+    /// A tracked transaction was discarded.
+    TransactionDiscarded(TransactionId),
+    /// A SWAP note created by the client was consumed by another account.
+    SwapFilled(NoteId),
+    /// The sync finished, and the client is synced up to `block_num`.
+    Finished { block_num: BlockNumber },
+}
+
+impl SyncEvent {
+    /// Returns the events that describe the changes of a sync, ending with
+    /// [`SyncEvent::Finished`].
+    pub(crate) fn from_summary(
+        summary: &SyncSummary,
+        discarded_transactions: &[TransactionId],
+    ) -> Vec<SyncEvent> {
+        summary
+            .new_public_notes
+            .iter()
+            .copied()
+            .map(SyncEvent::NoteReceived)
+            .chain(summary.committed_notes.iter().copied().map(SyncEvent::NoteCommitted))
+            .chain(summary.consumed_notes.iter().copied().map(SyncEvent::NoteConsumed))
+            .chain(summary.updated_accounts.iter().copied().map(SyncEvent::AccountUpdated))
+            .chain(summary.locked_accounts.iter().copied().map(SyncEvent::AccountLocked))
+            .chain(
+                summary
+                    .committed_transactions
+                    .iter()
+                    .copied()
+                    .map(SyncEvent::TransactionCommitted),
+            )
+            .chain(discarded_transactions.iter().copied().map(SyncEvent::TransactionDiscarded))
+            .chain(summary.filled_swaps.iter().copied().map(SyncEvent::SwapFilled))
+            .chain([SyncEvent::Finished { block_num: summary.block_num }])
+            .collect()
+    }
+}
+
+// SYNC LISTENER
+// ================================================================================================
+
+/// Receives the [`SyncEvent`]s emitted while the client syncs its state.
+///
+/// Listeners are registered with [`crate::Client::add_sync_listener`] or with
+/// `ClientBuilder::sync_listener`. They are called synchronously from the sync, so they should
+/// return quickly. Closures taking a `&SyncEvent` can be used as listeners.
+pub trait SyncListener {
+    /// Called for each event emitted by the sync.
+    fn on_sync_event(&self, event: &SyncEvent);
+}
+
+impl<F> SyncListener for F
+where
+    F: Fn(&SyncEvent),
+{
+    fn on_sync_event(&self, event: &SyncEvent) {
+        self(event);
+    }
+}
@@ -55,6 +55,7 @@
 //! `committed_note_updates` and `consumed_note_updates`) to understand how the sync data is
 //! processed and applied to the local store.
 
+use alloc::boxed::Box;
 use alloc::collections::BTreeSet;
 use alloc::sync::Arc;
 use alloc::vec::Vec;
@@ -66,9 +67,8 @@ use miden_objects::note::{NoteId, NoteTag};
 use miden_objects::transaction::TransactionId;
 use miden_tx::auth::TransactionAuthenticator;
 use miden_tx::utils::{Deserializable, DeserializationError, Serializable};
-use tracing::info;
+use tracing::{info, instrument};
 
-use crate::note::NoteScreener;
 use crate::store::{NoteFilter, TransactionFilter};
 use crate::{Client, ClientError};
 mod block_header;
@@ -76,6 +76,23 @@ mod block_header;
 mod tag;
 pub use tag::{NoteTagRecord, NoteTagSource};
 
+#[cfg(any(feature = "tonic", feature = "web-tonic"))]
+mod auto_sync;
+#[cfg(any(feature = "tonic", feature = "web-tonic"))]
+pub use auto_sync::{
+    AutoSyncConfig,
+    AutoSyncHandle,
+    DEFAULT_AUTO_SYNC_INTERVAL,
+    DEFAULT_AUTO_SYNC_MAX_BACKOFF,
+    SharedClient,
+};
+
+mod checkpoint;
+pub use checkpoint::SyncCheckpoint;
+
+mod listener;
+pub use listener::{SyncEvent, SyncListener};
+
 mod state_sync;
 pub use state_sync::{NoteUpdateAction, OnNoteReceived, StateSync};
 
@@ -100,6 +117,11 @@ where
         self.store.get_sync_height().await.map_err(Into::into)
     }
 
+    /// Registers a listener notified of the progress and the changes of every following sync.
+    pub fn add_sync_listener(&mut self, listener: Arc<dyn SyncListener>) {
+        self.sync_listeners.push(listener);
+    }
+
     /// Syncs the client's state with the current state of the Miden network and returns a
     /// [`SyncSummary`] corresponding to the local state update.
     ///
@@ -119,12 +141,90 @@ where
     ///    state.
     /// 7. The MMR is updated with the new peaks and authentication nodes.
     /// 8. All updates are applied to the store to be persisted.
+    ///
+    /// Transactions discarded by the sync aren't retried, since that would spend funds as a side
+    /// effect of syncing. Use [`Client::retry_discarded_transactions`] to retry them.
+    ///
+    /// The registered [`SyncListener`]s are notified of each step received from the node, and of
+    /// the changes once they were applied to the store.
     pub async fn sync_state(&mut self) -> Result<SyncSummary, ClientError> {
+        self.sync_state_with_options(SyncOptions::default()).await
+    }
+
+    /// Syncs the client's state like [`Client::sync_state`], bounded by the provided options.
+    ///
+    /// With [`SyncOptions::steps_per_commit`], the updates are applied to the store every that
+    /// many steps instead of once the chain tip is reached. The sync height acts as the cursor of
+    /// the sync: if it's interrupted, only the steps received since the last commit are lost, and
+    /// the next sync resumes from the last committed block. With [`SyncOptions::to_block`], the
+    /// sync stops at the first step that reaches the block.
+    ///
+    /// The returned summary covers the changes of every commit.
+    ///
+    /// If the client never synced and a start checkpoint was set with
+    /// [`Client::set_start_checkpoint`], the sync begins right after the checkpoint block instead
+    /// of genesis.
+    #[instrument(level = "debug", skip_all)]
+    pub async fn sync_state_with_options(
+        &mut self,
+        options: SyncOptions,
+    ) -> Result<SyncSummary, ClientError> {
         _ = self.ensure_genesis_in_place().await?;
 
-        let note_screener = NoteScreener::new(self.store.clone(), self.authenticator.clone());
-        let state_sync =
-            StateSync::new(self.rpc_api.clone(), Arc::new(note_screener), self.tx_graceful_blocks);
+        if let Some(checkpoint) = self.start_checkpoint
+            && self.store.get_sync_height().await? == BlockNumber::GENESIS
+        {
+            self.start_from_checkpoint(checkpoint).await?;
+        }
+
+        let mut sync_summary = SyncSummary::new_empty(self.store.get_sync_height().await?);
+        let mut discarded_transactions = Vec::new();
+        loop {
+            let start_block = sync_summary.block_num;
+            let (commit_summary, commit_discarded_transactions) =
+                self.commit_sync_steps(options).await?;
+            let advanced = commit_summary.block_num > start_block;
+
+            sync_summary.combine_with(commit_summary);
+            discarded_transactions.extend(commit_discarded_transactions);
+
+            let reached_target_block =
+                options.to_block.is_some_and(|to_block| sync_summary.block_num >= to_block);
+            if options.steps_per_commit.is_none() || !advanced || reached_target_block {
+                break;
+            }
+        }
+
+        // Listeners only hear about the changes once they were persisted
+        if !self.sync_listeners.is_empty() {
+            for event in SyncEvent::from_summary(&sync_summary, &discarded_transactions) {
+                for listener in &self.sync_listeners {
+                    listener.on_sync_event(&event);
+                }
+            }
+        }
+
+        Ok(sync_summary)
+    }
+
+    /// Requests sync steps from the node, up to the bounds of `options`, and applies them to the
+    /// store. Returns the summary of the applied changes along with the IDs of the transactions
+    /// they discarded.
+    #[instrument(level = "debug", skip_all)]
+    async fn commit_sync_steps(
+        &mut self,
+        options: SyncOptions,
+    ) -> Result<(SyncSummary, Vec<TransactionId>), ClientError> {
+        let note_screener = self.note_screener();
+        let mut state_sync =
+            StateSync::new(self.rpc_api.clone(), Arc::new(note_screener), self.tx_graceful_blocks)
+                .with_sync_listeners(self.sync_listeners.clone());
+        if let Some(steps_per_commit) = options.steps_per_commit {
+            state_sync = state_sync.with_max_steps(steps_per_commit);
+        }
+        if let Some(to_block) = options.to_block {
+            state_sync = state_sync.with_target_block(to_block);
+        }
 
         // Get current state of the client
         let accounts = self
@@ -138,7 +238,10 @@ where
         let note_tags: BTreeSet<NoteTag> = self.store.get_unique_note_tags().await?;
 
         let unspent_input_notes = self.store.get_input_notes(NoteFilter::Unspent).await?;
-        let unspent_output_notes = self.store.get_output_notes(NoteFilter::Expected).await?;
+        // Committed SWAP notes are tracked until they are nullified, which tells when a swap
+        // created by the client was filled
+        let mut unspent_output_notes = self.store.get_output_notes(NoteFilter::Expected).await?;
+        unspent_output_notes.extend(self.pending_swap_notes().await?);
 
         let uncommitted_transactions =
             self.store.get_transactions(TransactionFilter::Uncommitted).await?;
@@ -159,6 +262,11 @@ where
             .await?;
 
         let sync_summary: SyncSummary = (&state_sync_update).into();
+        let discarded_transactions: Vec<TransactionId> = state_sync_update
+            .transaction_updates
+            .discarded_transactions()
+            .map(|tx| tx.id)
+            .collect();
         info!("Applying changes to the store.");
 
         // Apply received and computed updates to the store
@@ -171,12 +279,13 @@ where
         // Remove irrelevant block headers
         self.store.prune_irrelevant_blocks().await?;
 
-        Ok(sync_summary)
+        Ok((sync_summary, discarded_transactions))
     }
 
     /// Applies the state sync update to the store.
     ///
     /// See [`crate::Store::apply_state_sync()`] for what the update implies.
+    #[instrument(level = "debug", skip_all, fields(block_num = %update.block_num))]
     pub async fn apply_state_sync(&mut self, update: StateSyncUpdate) -> Result<(), ClientError> {
         self.store.apply_state_sync(update).await.map_err(ClientError::StoreError)?;
 
@@ -185,6 +294,37 @@ where
     }
 }
 
+// SYNC OPTIONS
+// ================================================================================================
+
+/// Bounds of a sync started with [`Client::sync_state_with_options`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SyncOptions {
+    /// Number of steps received from the node after which the updates are applied to the store.
+    /// If `None`, they're applied once the client reached the chain tip.
+    pub steps_per_commit: Option<usize>,
+    /// Block at which the sync stops. Steps end at blocks with changes relevant to the client or
+    /// at the chain tip, so the client may end up synced past it. If `None`, the client syncs up
+    /// to the chain tip.
+    pub to_block: Option<BlockNumber>,
+}
+
+impl SyncOptions {
+    /// Applies the updates to the store every `steps_per_commit` steps. Zero is treated as one.
+    #[must_use]
+    pub fn with_steps_per_commit(mut self, steps_per_commit: usize) -> Self {
+        self.steps_per_commit = Some(steps_per_commit.max(1));
+        self
+    }
+
+    /// Stops the sync at the first step that reaches `to_block`.
+    #[must_use]
+    pub fn with_to_block(mut self, to_block: BlockNumber) -> Self {
+        self.to_block = Some(to_block);
+        self
+    }
+}
+
 // SYNC SUMMARY
 // ================================================================================================
 
@@ -205,6 +345,8 @@ pub struct SyncSummary {
     pub locked_accounts: Vec<AccountId>,
     /// IDs of committed transactions.
     pub committed_transactions: Vec<TransactionId>,
+    /// IDs of SWAP notes created by the client that were consumed by other accounts.
+    pub filled_swaps: Vec<NoteId>,
 }
 
 impl SyncSummary {
@@ -216,6 +358,7 @@ impl SyncSummary {
         updated_accounts: Vec<AccountId>,
         locked_accounts: Vec<AccountId>,
         committed_transactions: Vec<TransactionId>,
+        filled_swaps: Vec<NoteId>,
     ) -> Self {
         Self {
             block_num,
@@ -225,6 +368,7 @@ impl SyncSummary {
             updated_accounts,
             locked_accounts,
             committed_transactions,
+            filled_swaps,
         }
     }
 
@@ -237,6 +381,7 @@ impl SyncSummary {
             updated_accounts: vec![],
             locked_accounts: vec![],
             committed_transactions: vec![],
+            filled_swaps: vec![],
         }
     }
 
@@ -247,6 +392,7 @@ impl SyncSummary {
             && self.updated_accounts.is_empty()
             && self.locked_accounts.is_empty()
             && self.committed_transactions.is_empty()
+            && self.filled_swaps.is_empty()
     }
 
     pub fn combine_with(&mut self, mut other: Self) {
@@ -257,6 +403,7 @@ impl SyncSummary {
         self.updated_accounts.append(&mut other.updated_accounts);
         self.locked_accounts.append(&mut other.locked_accounts);
         self.committed_transactions.append(&mut other.committed_transactions);
+        self.filled_swaps.append(&mut other.filled_swaps);
     }
 }
 
@@ -269,6 +416,7 @@ impl Serializable for SyncSummary {
         self.updated_accounts.write_into(target);
         self.locked_accounts.write_into(target);
         self.committed_transactions.write_into(target);
+        self.filled_swaps.write_into(target);
     }
 }
 
@@ -283,6 +431,7 @@ impl Deserializable for SyncSummary {
         let updated_accounts = Vec::<AccountId>::read_from(source)?;
         let locked_accounts = Vec::<AccountId>::read_from(source)?;
         let committed_transactions = Vec::<TransactionId>::read_from(source)?;
+        let filled_swaps = Vec::<NoteId>::read_from(source)?;
 
         Ok(Self {
             block_num,
@@ -292,6 +441,7 @@ impl Deserializable for SyncSummary {
             updated_accounts,
             locked_accounts,
             committed_transactions,
+            filled_swaps,
         })
     }
 }
@@ -9,10 +9,10 @@ use miden_objects::block::{BlockHeader, BlockNumber};
 use miden_objects::crypto::merkle::{InOrderIndex, MmrDelta, MmrPeaks, PartialMmr};
 use miden_objects::note::{NoteId, NoteTag};
 use tonic::async_trait;
-use tracing::info;
+use tracing::{info, instrument};
 
 use super::state_sync_update::TransactionUpdateTracker;
-use super::{AccountUpdates, StateSyncUpdate};
+use super::{AccountUpdates, StateSyncUpdate, SyncEvent, SyncListener};
 use crate::ClientError;
 use crate::note::NoteUpdateTracker;
 use crate::rpc::NodeRpcClient;
@@ -54,6 +54,23 @@ pub trait OnNoteReceived {
         committed_note: CommittedNote,
         public_note: Option<InputNoteRecord>,
     ) -> Result<NoteUpdateAction, ClientError>;
+
+    /// Callback that gets executed with the notes received in each step of the sync, along with
+    /// their optional public note records. It returns the action to be taken for each note, in
+    /// the same order.
+    ///
+    /// By default, it calls [`OnNoteReceived::on_note_received`] for each note. Implementations
+    /// can override it to check the relevance of the notes together.
+    async fn on_notes_received(
+        &self,
+        notes: Vec<(CommittedNote, Option<InputNoteRecord>)>,
+    ) -> Result<Vec<NoteUpdateAction>, ClientError> {
+        let mut actions = Vec::with_capacity(notes.len());
+        for (committed_note, public_note) in notes {
+            actions.push(self.on_note_received(committed_note, public_note).await?);
+        }
+        Ok(actions)
+    }
 }
 
 // STATE SYNC
@@ -74,6 +91,14 @@ pub struct StateSync {
     /// The number of blocks that are considered old enough to discard pending transactions. If
     /// `None`, there is no limit and transactions will be kept indefinitely.
     tx_graceful_blocks: Option<u32>,
+    /// Listeners notified of the progress of the sync as its steps are received.
+    sync_listeners: Vec<Arc<dyn SyncListener>>,
+    /// Maximum number of steps requested from the node. If `None`, steps are requested until the
+    /// chain tip is reached.
+    max_steps: Option<usize>,
+    /// Block after which no more steps are requested. If `None`, steps are requested until the
+    /// chain tip is reached.
+    target_block: Option<BlockNumber>,
 }
 
 impl StateSync {
@@ -94,12 +119,43 @@ impl StateSync {
             rpc_api,
             note_screener,
             tx_graceful_blocks,
+            sync_listeners: Vec::new(),
+            max_steps: None,
+            target_block: None,
         }
     }
 
+    /// Sets the listeners that get a [`SyncEvent::Progress`] event for each sync step received
+    /// from the node.
+    #[must_use]
+    pub fn with_sync_listeners(mut self, sync_listeners: Vec<Arc<dyn SyncListener>>) -> Self {
+        self.sync_listeners = sync_listeners;
+        self
+    }
+
+    /// Limits the sync to `max_steps` steps, so that the returned update can be applied to the
+    /// store before the client reached the chain tip. A limit of zero is treated as one step.
+    #[must_use]
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = Some(max_steps.max(1));
+        self
+    }
+
+    /// Stops the sync at the first step that reaches `target_block`. Steps end at blocks with
+    /// changes relevant to the client or at the chain tip, so the sync may end past the block.
+    #[must_use]
+    pub fn with_target_block(mut self, target_block: BlockNumber) -> Self {
+        self.target_block = Some(target_block);
+        self
+    }
+
     /// Syncs the state of the client with the chain tip of the node, returning the updates that
     /// should be applied to the store.
     ///
+    /// If the sync is bounded with [`StateSync::with_max_steps`] or
+    /// [`StateSync::with_target_block`], the update ends at the last step received, and its
+    /// block is stored so that a following sync can resume from it.
+    ///
     /// During the sync process, the client will go through the following steps:
     /// 1. A request is sent to the node to get the state updates. This request includes tracked
     ///    account IDs and the tags of notes that might have changed or that might be of interest to
@@ -149,6 +205,15 @@ impl StateSync {
         let mut state_sync_steps = Vec::new();
 
         loop {
+            let reached_max_steps =
+                self.max_steps.is_some_and(|max_steps| state_sync_steps.len() >= max_steps);
+            let reached_target_block = self
+                .target_block
+                .is_some_and(|target_block| state_sync_update.block_num >= target_block);
+            if reached_max_steps || reached_target_block {
+                break;
+            }
+
             info!("Performing sync state step.");
 
             let step = self
@@ -158,6 +223,14 @@ impl StateSync {
                 break;
             };
             state_sync_update.block_num = step.block_header.block_num();
+
+            let progress = SyncEvent::Progress {
+                block_num: state_sync_update.block_num,
+                chain_tip: step.chain_tip,
+            };
+            for listener in &self.sync_listeners {
+                listener.on_sync_event(&progress);
+            }
             state_sync_steps.push(step);
         }
 
@@ -176,9 +249,10 @@ impl StateSync {
         // to notes based on the received information.
         info!("Applying state transitions locally.");
 
-        for sync_step in state_sync_steps {
+        let last_step = state_sync_steps.len().saturating_sub(1);
+        for (step_index, sync_step) in state_sync_steps.into_iter().enumerate() {
             let StateSyncInfo {
-                chain_tip,
+                chain_tip: _,
                 block_header,
                 mmr_delta,
                 account_commitment_updates,
@@ -215,7 +289,9 @@ impl StateSync {
                 mmr_delta,
             )?;
 
-            let include_block = found_relevant_note || chain_tip == block_header.block_num();
+            // The block of the last step becomes the sync height, which a following sync resumes
+            // from, so it's stored even if it has no relevant notes
+            let include_block = found_relevant_note || step_index == last_step;
             if include_block {
                 state_sync_update.block_updates.insert(
                     block_header,
@@ -238,6 +314,7 @@ impl StateSync {
     /// reports the same block number, `None` is returned, signalling that the client is already at
     /// the requested height. Otherwise the full [`StateSyncInfo`] is returned for deferred
     /// processing by the caller.
+    #[instrument(level = "debug", skip_all, fields(block_num = %current_block_num))]
     async fn sync_state_step(
         &self,
         current_block_num: BlockNumber,
@@ -270,6 +347,7 @@ impl StateSync {
     /// * Private accounts that have been marked as mismatched because the current commitment
     ///   doesn't match the one received from the node. The client will need to handle these cases
     ///   as they could be a stale account state or a reason to lock the account.
+    #[instrument(level = "debug", skip_all)]
     async fn account_state_sync(
         &self,
         account_updates: &mut AccountUpdates,
@@ -338,6 +416,7 @@ impl StateSync {
     ///
     /// The `public_notes` parameter provides cached public note details for the current sync
     /// iteration so the node is only queried once per batch.
+    #[instrument(level = "debug", skip_all, fields(notes = note_inclusions.len()))]
     async fn note_state_sync(
         &self,
         note_updates: &mut NoteUpdateTracker,
@@ -348,13 +427,19 @@ impl StateSync {
         // `found_relevant_note` tracks whether we want to persist the block header in the end
         let mut found_relevant_note = false;
 
-        for committed_note in note_inclusions {
-            let public_note = (!committed_note.metadata().is_private())
-                .then(|| public_notes.get(committed_note.note_id()))
-                .flatten()
-                .cloned();
+        let received_notes = note_inclusions
+            .into_iter()
+            .map(|committed_note| {
+                let public_note = (!committed_note.metadata().is_private())
+                    .then(|| public_notes.get(committed_note.note_id()))
+                    .flatten()
+                    .cloned();
+                (committed_note, public_note)
+            })
+            .collect();
 
-            match self.note_screener.on_note_received(committed_note, public_note).await? {
+        for action in self.note_screener.on_notes_received(received_notes).await? {
+            match action {
                 NoteUpdateAction::Commit(committed_note) => {
                     // Only mark the downloaded block header as relevant if we are talking about
                     // an input note (output notes get marked as committed but we don't need the
@@ -398,6 +483,7 @@ impl StateSync {
     /// notes. It then processes the nullifiers to apply the state transitions on the note updates.
     ///
     /// The `state_sync_update` parameter will be updated to track the new discarded transactions.
+    #[instrument(level = "debug", skip_all)]
     async fn sync_nullifiers(
         &self,
         state_sync_update: &mut StateSyncUpdate,
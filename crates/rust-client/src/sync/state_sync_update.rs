@@ -10,8 +10,15 @@ use miden_objects::transaction::TransactionId;
 
 use super::SyncSummary;
 use crate::account::Account;
-use crate::note::{NoteUpdateTracker, NoteUpdateType};
+use crate::note::{
+    NoteUpdateTracker,
+    NoteUpdateType,
+    OutputNoteUpdate,
+    decode_swap_inputs,
+    is_reclaimed,
+};
 use crate::rpc::domain::transaction::TransactionInclusion;
+use crate::store::OutputNoteRecord;
 use crate::transaction::{DiscardCause, TransactionRecord, TransactionStatus};
 
 // STATE SYNC UPDATE
@@ -74,6 +81,19 @@ impl From<&StateSyncUpdate> for SyncSummary {
             .filter_map(|note| note.inner().is_consumed().then_some(note.inner().id()))
             .collect();
 
+        // SWAP notes consumed by a transaction of the account that created them were reclaimed
+        let filled_swap_ids = value
+            .note_updates
+            .updated_output_notes()
+            .map(OutputNoteUpdate::inner)
+            .filter(|note| {
+                note.is_consumed()
+                    && note.recipient().and_then(decode_swap_inputs).is_some()
+                    && !is_reclaimed(note, value.transaction_updates.committed_transactions())
+            })
+            .map(OutputNoteRecord::id)
+            .collect();
+
         SyncSummary::new(
             value.block_num,
             new_public_note_ids,
@@ -92,6 +112,7 @@ impl From<&StateSyncUpdate> for SyncSummary {
                 .map(|(id, _)| *id)
                 .collect(),
             value.transaction_updates.committed_transactions().map(|t| t.id).collect(),
+            filled_swap_ids,
         )
     }
 }
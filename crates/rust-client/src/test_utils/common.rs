@@ -75,6 +75,19 @@ pub async fn insert_new_wallet_with_seed(
     init_seed: [u8; 32],
 ) -> Result<(Account, Word, SecretKey), ClientError> {
     let key_pair = SecretKey::with_rng(client.rng());
+
+    insert_new_wallet_with_secrets(client, storage_mode, keystore, init_seed, key_pair).await
+}
+
+/// Inserts a new wallet account built with the provided seed and auth key into the client and into
+/// the keystore.
+pub async fn insert_new_wallet_with_secrets(
+    client: &mut TestClient,
+    storage_mode: AccountStorageMode,
+    keystore: &TestClientKeyStore,
+    init_seed: [u8; 32],
+    key_pair: SecretKey,
+) -> Result<(Account, Word, SecretKey), ClientError> {
     let pub_key = key_pair.public_key();
 
     keystore.add_key(&AuthSecretKey::RpoFalcon512(key_pair.clone())).unwrap();
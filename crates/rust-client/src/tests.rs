@@ -4,13 +4,14 @@ use std::collections::BTreeSet;
 use std::env::temp_dir;
 use std::println;
 use std::string::ToString;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 // TESTS
 // ================================================================================================
 use miden_lib::{
     account::{
-        auth::AuthRpoFalcon512,
+        auth::{AuthRpoFalcon512, AuthRpoFalcon512Multisig},
+        components::basic_wallet_library,
         faucets::BasicFungibleFaucet,
         interface::AccountInterfaceError,
         wallets::BasicWallet,
@@ -24,16 +25,27 @@ use miden_objects::account::{
     AccountBuilder,
     AccountCode,
     AccountComponent,
+    AccountDelta,
     AccountHeader,
     AccountId,
+    AccountStorageDelta,
     AccountStorageMode,
     AccountType,
+    AccountVaultDelta,
     AuthSecretKey,
     StorageMap,
     StorageSlot,
 };
-use miden_objects::asset::{Asset, FungibleAsset, TokenSymbol};
+use miden_objects::asset::{
+    Asset,
+    FungibleAsset,
+    NonFungibleAsset,
+    NonFungibleAssetDetails,
+    TokenSymbol,
+};
+use miden_objects::block::BlockHeader;
 use miden_objects::crypto::dsa::rpo_falcon512::{PublicKey, SecretKey};
+use miden_objects::crypto::merkle::{Forest, MmrPeaks};
 use miden_objects::crypto::rand::{FeltRng, RpoRandomCoin};
 use miden_objects::note::{
     Note,
@@ -41,6 +53,7 @@ use miden_objects::note::{
     NoteExecutionHint,
     NoteExecutionMode,
     NoteFile,
+    NoteId,
     NoteInputs,
     NoteMetadata,
     NoteRecipient,
@@ -49,12 +62,15 @@ use miden_objects::note::{
 };
 use miden_objects::testing::account_id::{
     ACCOUNT_ID_PRIVATE_SENDER,
+    ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET,
     ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_1,
     ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_2,
+    ACCOUNT_ID_PUBLIC_NON_FUNGIBLE_FAUCET,
     ACCOUNT_ID_REGULAR_PRIVATE_ACCOUNT_UPDATABLE_CODE,
     ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE,
     ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_UPDATABLE_CODE,
 };
+use miden_objects::testing::constants::NON_FUNGIBLE_ASSET_DATA;
 use miden_objects::transaction::{InputNote, OutputNote};
 use miden_objects::vm::AdviceInputs;
 use miden_objects::{EMPTY_WORD, Felt, ONE, Word, ZERO};
@@ -63,15 +79,39 @@ use miden_tx::TransactionExecutorError;
 use miden_tx::utils::{Deserializable, Serializable};
 use rand::rngs::StdRng;
 use rand::{Rng, RngCore};
+use uuid::Uuid;
 
 use crate::builder::ClientBuilder;
 use crate::keystore::FilesystemKeyStore;
-use crate::note::NoteRelevance;
-use crate::rpc::NodeRpcClient;
+use crate::note::{
+    NoteRelevance,
+    NoteRelevanceCheck,
+    NoteRelevanceChecker,
+    NoteScreenerError,
+    NoteScreeningCache,
+    SwapStatus,
+};
+use crate::rpc::{
+    MetricsRpcClient,
+    NodeRpcClient,
+    NodeRpcClientEndpoint,
+    PrometheusFileExporter,
+    RecordingRpcClient,
+    ReplayRpcClient,
+    RpcFixture,
+};
 use crate::store::input_note_states::ConsumedAuthenticatedLocalNoteState;
+use crate::store::memory_store::MemoryStore;
 use crate::store::sqlite_store::SqliteStore;
-use crate::store::{InputNoteRecord, InputNoteState, NoteFilter, TransactionFilter};
-use crate::sync::NoteTagSource;
+use crate::store::{
+    InputNoteRecord,
+    InputNoteState,
+    NoteFilter,
+    OutputNoteRecord,
+    Store,
+    TransactionFilter,
+};
+use crate::sync::{AutoSyncConfig, NoteTagSource, SharedClient, SyncEvent, SyncOptions};
 use crate::testing::common::{
     ACCOUNT_ID_REGULAR,
     MINT_AMOUNT,
@@ -92,12 +132,21 @@ use crate::testing::common::{
 };
 use crate::testing::mock::{MockClient, MockRpcApi};
 use crate::transaction::{
+    AssetReport,
     DiscardCause,
+    LocalTransactionProver,
+    MultisigError,
+    MultisigSigningRequest,
+    NoteScriptKind,
     PaymentNoteDescription,
+    QueuedTransactionStatus,
     SwapTransactionData,
+    TransactionFile,
+    TransactionId,
     TransactionRequestBuilder,
     TransactionRequestError,
     TransactionStatus,
+    VaultChange,
 };
 use crate::{ClientError, DebugMode};
 
@@ -108,11 +157,36 @@ const TX_GRACEFUL_BLOCKS: u32 = 20;
 // HELPERS
 // ================================================================================================
 
+/// Runs the test function, which receives the client's store, against every [`Store`]
+/// implementation. Each backend gets its own test case inside a module named after the function.
+macro_rules! store_test {
+    ($name:ident) => {
+        mod $name {
+            use super::{Arc, MemoryStore, SqliteStore, create_test_store_path};
+
+            #[tokio::test]
+            async fn sqlite_store() {
+                let store = SqliteStore::new(create_test_store_path()).await.unwrap();
+                Box::pin(super::$name(Arc::new(store))).await;
+            }
+
+            #[tokio::test]
+            async fn memory_store() {
+                Box::pin(super::$name(Arc::new(MemoryStore::new()))).await;
+            }
+        }
+    };
+}
+
 pub async fn create_test_client_builder()
 -> (ClientBuilder<TestClientKeyStore>, MockRpcApi, FilesystemKeyStore<StdRng>) {
     let store = SqliteStore::new(create_test_store_path()).await.unwrap();
-    let store = Arc::new(store);
+    Box::pin(create_test_client_builder_with_store(Arc::new(store))).await
+}
 
+pub async fn create_test_client_builder_with_store(
+    store: Arc<dyn Store>,
+) -> (ClientBuilder<TestClientKeyStore>, MockRpcApi, FilesystemKeyStore<StdRng>) {
     let mut rng = rand::rng();
     let coin_seed: [u64; 4] = rng.random();
 
@@ -137,7 +211,14 @@ pub async fn create_test_client_builder()
 
 pub async fn create_test_client()
 -> (MockClient<FilesystemKeyStore<StdRng>>, MockRpcApi, FilesystemKeyStore<StdRng>) {
-    let (builder, rpc_api, keystore) = Box::pin(create_test_client_builder()).await;
+    let store = SqliteStore::new(create_test_store_path()).await.unwrap();
+    Box::pin(create_test_client_with_store(Arc::new(store))).await
+}
+
+pub async fn create_test_client_with_store(
+    store: Arc<dyn Store>,
+) -> (MockClient<FilesystemKeyStore<StdRng>>, MockRpcApi, FilesystemKeyStore<StdRng>) {
+    let (builder, rpc_api, keystore) = Box::pin(create_test_client_builder_with_store(store)).await;
     let mut client = builder.build().await.unwrap();
     client.ensure_genesis_in_place().await.unwrap();
 
@@ -255,10 +336,9 @@ async fn insert_new_fungible_faucet(
 // TESTS
 // ================================================================================================
 
-#[tokio::test]
-async fn input_notes_round_trip() {
-    // generate test client with a random store name
-    let (mut client, rpc_api, keystore) = Box::pin(create_test_client()).await;
+store_test!(input_notes_round_trip);
+async fn input_notes_round_trip(store: Arc<dyn Store>) {
+    let (mut client, rpc_api, keystore) = Box::pin(create_test_client_with_store(store)).await;
 
     insert_new_wallet(&mut client, AccountStorageMode::Private, &keystore)
         .await
@@ -297,10 +377,9 @@ async fn input_notes_round_trip() {
     }
 }
 
-#[tokio::test]
-async fn get_input_note() {
-    // generate test client with a random store name
-    let (mut client, rpc_api, _) = Box::pin(create_test_client()).await;
+store_test!(get_input_note);
+async fn get_input_note(store: Arc<dyn Store>) {
+    let (mut client, rpc_api, _) = Box::pin(create_test_client_with_store(store)).await;
     // Get note from mocked RPC backend since any note works here
     let original_note = rpc_api.get_available_notes()[0].note().unwrap().clone();
 
@@ -322,10 +401,9 @@ async fn get_input_note() {
     assert_eq!(recorded_note.id(), retrieved_note.id());
 }
 
-#[tokio::test]
-async fn insert_basic_account() {
-    // generate test client with a random store name
-    let (mut client, _rpc_api, keystore) = Box::pin(create_test_client()).await;
+store_test!(insert_basic_account);
+async fn insert_basic_account(store: Arc<dyn Store>) {
+    let (mut client, _rpc_api, keystore) = Box::pin(create_test_client_with_store(store)).await;
 
     // Insert Account
     let account_insert_result =
@@ -353,10 +431,9 @@ async fn insert_basic_account() {
     assert_eq!(account_seed, fetched_account_seed.unwrap());
 }
 
-#[tokio::test]
-async fn insert_faucet_account() {
-    // generate test client with a random store name
-    let (mut client, _rpc_api, keystore) = Box::pin(create_test_client()).await;
+store_test!(insert_faucet_account);
+async fn insert_faucet_account(store: Arc<dyn Store>) {
+    let (mut client, _rpc_api, keystore) = Box::pin(create_test_client_with_store(store)).await;
 
     // Insert Account
     let account_insert_result =
@@ -384,10 +461,9 @@ async fn insert_faucet_account() {
     assert_eq!(account_seed, fetched_account_seed.unwrap());
 }
 
-#[tokio::test]
-async fn insert_same_account_twice_fails() {
-    // generate test client with a random store name
-    let (mut client, _rpc_api, _) = Box::pin(create_test_client()).await;
+store_test!(insert_same_account_twice_fails);
+async fn insert_same_account_twice_fails(store: Arc<dyn Store>) {
+    let (mut client, _rpc_api, _) = Box::pin(create_test_client_with_store(store)).await;
 
     let account = Account::mock(
         ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET_2,
@@ -398,10 +474,9 @@ async fn insert_same_account_twice_fails() {
     assert!(client.add_account(&account, Some(Word::default()), false).await.is_err());
 }
 
-#[tokio::test]
-async fn account_code() {
-    // generate test client with a random store name
-    let (mut client, _rpc_api, _) = Box::pin(create_test_client()).await;
+store_test!(account_code);
+async fn account_code(store: Arc<dyn Store>) {
+    let (mut client, _rpc_api, _) = Box::pin(create_test_client_with_store(store)).await;
 
     let account = Account::mock(
         ACCOUNT_ID_REGULAR_PRIVATE_ACCOUNT_UPDATABLE_CODE,
@@ -420,10 +495,9 @@ async fn account_code() {
     assert_eq!(*account.code(), *retrieved_acc.account().code());
 }
 
-#[tokio::test]
-async fn get_account_by_id() {
-    // generate test client with a random store name
-    let (mut client, _rpc_api, _) = Box::pin(create_test_client()).await;
+store_test!(get_account_by_id);
+async fn get_account_by_id(store: Arc<dyn Store>) {
+    let (mut client, _rpc_api, _) = Box::pin(create_test_client_with_store(store)).await;
 
     let account = Account::mock(
         ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_UPDATABLE_CODE,
@@ -444,10 +518,159 @@ async fn get_account_by_id() {
     assert!(client.get_account_header_by_id(invalid_id).await.unwrap().is_none());
 }
 
-#[tokio::test]
-async fn sync_state() {
-    // generate test client with a random store name
-    let (mut client, rpc_api, _) = Box::pin(create_test_client()).await;
+store_test!(update_account_with_additions);
+async fn update_account_with_additions(store: Arc<dyn Store>) {
+    let dummy_component = AccountComponent::new(
+        basic_wallet_library(),
+        vec![StorageSlot::empty_value(), StorageSlot::empty_map()],
+    )
+    .unwrap()
+    .with_supports_all_types();
+
+    let (account, seed) = AccountBuilder::new([0; 32])
+        .account_type(AccountType::RegularAccountImmutableCode)
+        .with_auth_component(AuthRpoFalcon512::new(PublicKey::new(EMPTY_WORD)))
+        .with_component(dummy_component)
+        .build()
+        .unwrap();
+    store.insert_account(&account, Some(seed)).await.unwrap();
+
+    let mut storage_delta = AccountStorageDelta::new();
+    storage_delta.set_item(1, [ZERO, ZERO, ZERO, ONE].into());
+    storage_delta.set_map_item(2, [ONE, ZERO, ZERO, ZERO].into(), [ONE, ONE, ONE, ONE].into());
+
+    let vault_delta = AccountVaultDelta::from_iters(
+        vec![
+            FungibleAsset::new(
+                AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET).unwrap(),
+                100,
+            )
+            .unwrap()
+            .into(),
+            NonFungibleAsset::new(
+                &NonFungibleAssetDetails::new(
+                    AccountId::try_from(ACCOUNT_ID_PUBLIC_NON_FUNGIBLE_FAUCET).unwrap().prefix(),
+                    NON_FUNGIBLE_ASSET_DATA.into(),
+                )
+                .unwrap(),
+            )
+            .unwrap()
+            .into(),
+        ],
+        [],
+    );
+
+    let delta = AccountDelta::new(account.id(), storage_delta, vault_delta, ONE).unwrap();
+    let mut account_after_delta = account.clone();
+    account_after_delta.apply_delta(&delta).unwrap();
+
+    store.update_account(&account_after_delta).await.unwrap();
+
+    let updated_account: Account = store.get_account(account.id()).await.unwrap().unwrap().into();
+    assert_eq!(updated_account, account_after_delta);
+}
+
+store_test!(update_account_with_removals);
+async fn update_account_with_removals(store: Arc<dyn Store>) {
+    let mut dummy_map = StorageMap::new();
+    dummy_map.insert([ONE, ZERO, ZERO, ZERO].into(), [ONE, ONE, ONE, ONE].into());
+
+    let dummy_component = AccountComponent::new(
+        basic_wallet_library(),
+        vec![StorageSlot::Value([ZERO, ZERO, ZERO, ONE].into()), StorageSlot::Map(dummy_map)],
+    )
+    .unwrap()
+    .with_supports_all_types();
+
+    let assets: Vec<Asset> = vec![
+        FungibleAsset::new(AccountId::try_from(ACCOUNT_ID_PUBLIC_FUNGIBLE_FAUCET).unwrap(), 100)
+            .unwrap()
+            .into(),
+        NonFungibleAsset::new(
+            &NonFungibleAssetDetails::new(
+                AccountId::try_from(ACCOUNT_ID_PUBLIC_NON_FUNGIBLE_FAUCET).unwrap().prefix(),
+                NON_FUNGIBLE_ASSET_DATA.into(),
+            )
+            .unwrap(),
+        )
+        .unwrap()
+        .into(),
+    ];
+    let account = AccountBuilder::new([0; 32])
+        .account_type(AccountType::RegularAccountImmutableCode)
+        .with_auth_component(AuthRpoFalcon512::new(PublicKey::new(EMPTY_WORD)))
+        .with_component(dummy_component)
+        .with_assets(assets.clone())
+        .build_existing()
+        .unwrap();
+    store.insert_account(&account, None).await.unwrap();
+
+    let mut storage_delta = AccountStorageDelta::new();
+    storage_delta.set_item(1, EMPTY_WORD);
+    storage_delta.set_map_item(2, [ONE, ZERO, ZERO, ZERO].into(), EMPTY_WORD);
+
+    let vault_delta = AccountVaultDelta::from_iters([], assets);
+
+    let delta = AccountDelta::new(account.id(), storage_delta, vault_delta, ONE).unwrap();
+    let mut account_after_delta = account.clone();
+    account_after_delta.apply_delta(&delta).unwrap();
+
+    store.update_account(&account_after_delta).await.unwrap();
+
+    let updated_account: Account = store.get_account(account.id()).await.unwrap().unwrap().into();
+    assert_eq!(updated_account, account_after_delta);
+}
+
+store_test!(block_headers);
+async fn block_headers(store: Arc<dyn Store>) {
+    let block_headers: Vec<BlockHeader> = (0..5)
+        .map(|block_num| {
+            BlockHeader::mock(block_num, None, None, &[], TransactionKernel::kernel_commitment())
+        })
+        .collect();
+    let peaks = MmrPeaks::new(Forest::empty(), Vec::new()).unwrap();
+    for block_header in &block_headers {
+        store.insert_block_header(block_header, peaks.clone(), false).await.unwrap();
+    }
+
+    let (block_header, _has_notes) =
+        store.get_block_header_by_num(3.into()).await.unwrap().unwrap();
+    assert_eq!(block_header, block_headers[3]);
+
+    let listed_headers: Vec<BlockHeader> = store
+        .get_block_headers(&[1.into(), 3.into()].into_iter().collect())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(block_header, _has_notes)| block_header)
+        .collect();
+    assert_eq!(listed_headers, [block_headers[1].clone(), block_headers[3].clone()]);
+}
+
+store_test!(settings);
+async fn settings(store: Arc<dyn Store>) {
+    for name in ["queue/b", "queue/a", "queue_other", "other"] {
+        store.set_setting(name.to_string(), name.as_bytes().to_vec()).await.unwrap();
+    }
+
+    assert_eq!(
+        store.get_setting("queue/a".to_string()).await.unwrap(),
+        Some(b"queue/a".to_vec())
+    );
+    assert_eq!(
+        store.list_setting_names("queue/".to_string()).await.unwrap(),
+        ["queue/a", "queue/b"]
+    );
+
+    store.remove_setting("queue/a".to_string()).await.unwrap();
+    store.remove_setting("queue/missing".to_string()).await.unwrap();
+    assert_eq!(store.get_setting("queue/a".to_string()).await.unwrap(), None);
+    assert_eq!(store.list_setting_names("queue/".to_string()).await.unwrap(), ["queue/b"]);
+}
+
+store_test!(sync_state);
+async fn sync_state(store: Arc<dyn Store>) {
+    let (mut client, rpc_api, _) = Box::pin(create_test_client_with_store(store)).await;
 
     // Import first mockchain note as expected
     let expected_notes = rpc_api
@@ -492,9 +715,197 @@ async fn sync_state() {
 }
 
 #[tokio::test]
-async fn sync_state_mmr() {
-    // generate test client with a random store name
-    let (mut client, rpc_api, keystore) = Box::pin(create_test_client()).await;
+async fn sync_state_with_replayed_rpc_fixture() {
+    let (builder, rpc_api, _) = Box::pin(create_test_client_builder()).await;
+    let recorder = Arc::new(RecordingRpcClient::new(Arc::new(rpc_api.clone())));
+    let mut client = builder.rpc(recorder.clone()).build().await.unwrap();
+    client.ensure_genesis_in_place().await.unwrap();
+
+    let notes = rpc_api
+        .get_available_notes()
+        .into_iter()
+        .map(|n| n.note().unwrap().clone())
+        .collect::<Vec<Note>>();
+    let note_file = |note: &Note| NoteFile::NoteDetails {
+        details: note.clone().into(),
+        after_block_num: 0.into(),
+        tag: Some(note.metadata().tag()),
+    };
+
+    for note in &notes {
+        client.import_note(note_file(note)).await.unwrap();
+    }
+    let sync_details = client.sync_state().await.unwrap();
+
+    // Write the recorded fixture and read it back, as it would be from a bug report
+    let fixture_path = temp_dir().join(format!("rpc-fixture-{}.bin", Uuid::new_v4()));
+    recorder.fixture().write_to_file(&fixture_path).unwrap();
+    let fixture = RpcFixture::read_from_file(&fixture_path).unwrap();
+    std::fs::remove_file(&fixture_path).unwrap();
+    assert_eq!(fixture, recorder.fixture());
+
+    // A client tracking the same notes gets the same results without the node
+    let (builder, ..) = Box::pin(create_test_client_builder()).await;
+    let mut replay_client =
+        builder.rpc(Arc::new(ReplayRpcClient::new(fixture))).build().await.unwrap();
+    replay_client.ensure_genesis_in_place().await.unwrap();
+
+    for note in &notes {
+        replay_client.import_note(note_file(note)).await.unwrap();
+    }
+    let replayed_details = replay_client.sync_state().await.unwrap();
+
+    assert_eq!(replayed_details.block_num, sync_details.block_num);
+    assert_eq!(replayed_details.committed_notes, sync_details.committed_notes);
+    assert_eq!(replayed_details.consumed_notes, sync_details.consumed_notes);
+    assert_eq!(
+        replay_client.get_input_notes(NoteFilter::Committed).await.unwrap().len(),
+        client.get_input_notes(NoteFilter::Committed).await.unwrap().len()
+    );
+}
+
+#[tokio::test]
+async fn auto_sync_syncs_in_the_background_until_cancelled() {
+    let (client, rpc_api, _) = Box::pin(create_test_client()).await;
+    let client = SharedClient::new(client);
+
+    tokio::task::LocalSet::new()
+        .run_until(async {
+            let handle =
+                client.start_auto_sync(AutoSyncConfig::new(core::time::Duration::from_millis(10)));
+
+            tokio::time::timeout(core::time::Duration::from_secs(10), async {
+                while handle.syncs() < 2 {
+                    tokio::time::sleep(core::time::Duration::from_millis(10)).await;
+                }
+            })
+            .await
+            .expect("the task should sync twice");
+
+            // Foreground operations wait for the sync in progress
+            assert_eq!(
+                client.lock().await.get_sync_height().await.unwrap(),
+                rpc_api.get_chain_tip_block_num()
+            );
+
+            handle.cancel();
+            assert!(handle.is_cancelled());
+            tokio::time::timeout(core::time::Duration::from_secs(10), async {
+                while handle.is_running() {
+                    tokio::time::sleep(core::time::Duration::from_millis(10)).await;
+                }
+            })
+            .await
+            .expect("the task should stop once cancelled");
+
+            // No more syncs happen once the task stopped
+            let syncs = handle.syncs();
+            tokio::time::sleep(core::time::Duration::from_millis(50)).await;
+            assert_eq!(handle.syncs(), syncs);
+            assert_eq!(handle.consecutive_failures(), 0);
+            handle.stop().await;
+        })
+        .await;
+}
+
+#[tokio::test]
+async fn auto_sync_exports_rpc_metrics_after_every_sync() {
+    let (builder, rpc_api, _) = Box::pin(create_test_client_builder()).await;
+    let metrics_client = MetricsRpcClient::new(Arc::new(rpc_api));
+    let metrics = metrics_client.metrics();
+    let mut client = builder.rpc(Arc::new(metrics_client)).build().await.unwrap();
+    client.ensure_genesis_in_place().await.unwrap();
+    let client = SharedClient::new(client);
+
+    let metrics_path = temp_dir().join(format!("rpc-metrics-{}.prom", Uuid::new_v4()));
+    tokio::task::LocalSet::new()
+        .run_until(async {
+            let handle = client.start_auto_sync_with_metrics(
+                AutoSyncConfig::new(core::time::Duration::from_millis(10)),
+                metrics.clone(),
+                PrometheusFileExporter::new(&metrics_path),
+            );
+
+            tokio::time::timeout(core::time::Duration::from_secs(10), async {
+                while handle.syncs() < 2 {
+                    tokio::time::sleep(core::time::Duration::from_millis(10)).await;
+                }
+            })
+            .await
+            .expect("the task should sync twice");
+            handle.stop().await;
+        })
+        .await;
+
+    // The file holds the metrics as of the last finished sync
+    let exported = std::fs::read_to_string(&metrics_path).unwrap();
+    std::fs::remove_file(&metrics_path).unwrap();
+    let exported_requests: u64 = exported
+        .lines()
+        .find_map(|line| line.strip_prefix("miden_rpc_requests_total{method=\"sync_state\"} "))
+        .expect("the sync requests should be exported")
+        .parse()
+        .unwrap();
+    assert!(exported_requests >= 2);
+    assert!(exported_requests <= metrics.get(NodeRpcClientEndpoint::SyncState).unwrap().requests);
+}
+
+#[tokio::test]
+async fn sync_listeners_receive_progress_and_changes() {
+    let (mut client, rpc_api, _) = Box::pin(create_test_client()).await;
+
+    let expected_notes = rpc_api
+        .get_available_notes()
+        .into_iter()
+        .map(|n| n.note().unwrap().clone())
+        .collect::<Vec<Note>>();
+
+    for note in &expected_notes {
+        client
+            .import_note(NoteFile::NoteDetails {
+                details: note.clone().into(),
+                after_block_num: 0.into(),
+                tag: Some(note.metadata().tag()),
+            })
+            .await
+            .unwrap();
+    }
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let listener_events = events.clone();
+    client.add_sync_listener(Arc::new(move |event: &SyncEvent| {
+        listener_events.lock().unwrap().push(event.clone());
+    }));
+
+    let sync_details = client.sync_state().await.unwrap();
+    let chain_tip = rpc_api.get_chain_tip_block_num();
+    let events = events.lock().unwrap();
+
+    // Progress is reported for each step, up to the chain tip
+    let progress = events
+        .iter()
+        .filter_map(|event| match event {
+            SyncEvent::Progress { block_num, chain_tip } => Some((*block_num, *chain_tip)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert!(!progress.is_empty());
+    assert!(progress.windows(2).all(|steps| steps[0].0 < steps[1].0));
+    assert_eq!(progress.last().unwrap(), &(chain_tip, chain_tip));
+
+    // The changes match the summary, and the sync ends with a finished event
+    for note_id in &sync_details.committed_notes {
+        assert!(events.contains(&SyncEvent::NoteCommitted(*note_id)));
+    }
+    for note_id in &sync_details.consumed_notes {
+        assert!(events.contains(&SyncEvent::NoteConsumed(*note_id)));
+    }
+    assert_eq!(events.last().unwrap(), &SyncEvent::Finished { block_num: chain_tip });
+}
+
+store_test!(sync_state_mmr);
+async fn sync_state_mmr(store: Arc<dyn Store>) {
+    let (mut client, rpc_api, keystore) = Box::pin(create_test_client_with_store(store)).await;
     // Import note and create wallet so that synced notes do not get discarded (due to being
     // irrelevant)
     insert_new_wallet(&mut client, AccountStorageMode::Private, &keystore)
@@ -565,9 +976,60 @@ async fn sync_state_mmr() {
 }
 
 #[tokio::test]
-async fn sync_state_tags() {
-    // generate test client with a random store name
-    let (mut client, rpc_api, _) = Box::pin(create_test_client()).await;
+async fn bounded_sync_commits_each_step_and_resumes() {
+    let (mut client, rpc_api, keystore) = Box::pin(create_test_client()).await;
+    insert_new_wallet(&mut client, AccountStorageMode::Private, &keystore)
+        .await
+        .unwrap();
+
+    let notes = rpc_api
+        .get_available_notes()
+        .into_iter()
+        .map(|n| n.note().unwrap().clone())
+        .collect::<Vec<Note>>();
+
+    for note in &notes {
+        client
+            .import_note(NoteFile::NoteDetails {
+                details: note.clone().into(),
+                after_block_num: 0.into(),
+                tag: Some(note.metadata().tag()),
+            })
+            .await
+            .unwrap();
+    }
+
+    // The first step ends at the block of the first note, where the sync stops
+    let sync_details = client
+        .sync_state_with_options(SyncOptions::default().with_to_block(1.into()))
+        .await
+        .unwrap();
+    assert_eq!(sync_details.block_num, 1.into());
+    assert_eq!(client.get_sync_height().await.unwrap(), 1.into());
+
+    // The next sync resumes from there, committing each step until the chain tip
+    let sync_details = client
+        .sync_state_with_options(SyncOptions::default().with_steps_per_commit(1))
+        .await
+        .unwrap();
+    let chain_tip = rpc_api.get_chain_tip_block_num();
+    assert_eq!(sync_details.block_num, chain_tip);
+    assert_eq!(client.get_sync_height().await.unwrap(), chain_tip);
+
+    // The partial MMR built from the committed steps tracks the blocks of both notes
+    let partial_mmr = client.build_current_partial_mmr().await.unwrap();
+    let mmr_proof = partial_mmr.open(1).unwrap().unwrap();
+    let (block_1, _) = rpc_api.get_block_header_by_number(Some(1.into()), false).await.unwrap();
+    partial_mmr.peaks().verify(block_1.commitment(), mmr_proof).unwrap();
+
+    let mmr_proof = partial_mmr.open(4).unwrap().unwrap();
+    let (block_4, _) = rpc_api.get_block_header_by_number(Some(4.into()), false).await.unwrap();
+    partial_mmr.peaks().verify(block_4.commitment(), mmr_proof).unwrap();
+}
+
+store_test!(sync_state_tags);
+async fn sync_state_tags(store: Arc<dyn Store>) {
+    let (mut client, rpc_api, _) = Box::pin(create_test_client_with_store(store)).await;
 
     // Import first mockchain note as expected
     let expected_notes = rpc_api.get_available_notes();
@@ -594,10 +1056,9 @@ async fn sync_state_tags() {
     assert_eq!(client.test_store().get_tracked_block_headers().await.unwrap().len(), 1);
 }
 
-#[tokio::test]
-async fn tags() {
-    // generate test client with a random store name
-    let (mut client, _rpc_api, _) = Box::pin(create_test_client()).await;
+store_test!(tags);
+async fn tags(store: Arc<dyn Store>) {
+    let (mut client, _rpc_api, _) = Box::pin(create_test_client_with_store(store)).await;
 
     // Assert that the store gets created with the tag 0 (used for notes consumable by any account)
     assert!(client.get_note_tags().await.unwrap().is_empty());
@@ -661,10 +1122,10 @@ async fn mint_transaction() {
     assert_eq!(transaction.executed_transaction().account_delta().nonce_delta(), ONE);
 }
 
-#[tokio::test]
-async fn import_note_validation() {
+store_test!(import_note_validation);
+async fn import_note_validation(store: Arc<dyn Store>) {
     // generate test client
-    let (mut client, rpc_api, _) = Box::pin(create_test_client()).await;
+    let (mut client, rpc_api, _) = Box::pin(create_test_client_with_store(store)).await;
 
     // generate test data
     let expected_note = rpc_api.get_available_notes()[0].clone();
@@ -733,10 +1194,9 @@ async fn transaction_request_expiration() {
     assert_eq!(tx_outputs.expiration_block_num, current_height + 5);
 }
 
-#[tokio::test]
-async fn import_processing_note_returns_error() {
-    // generate test client with a random store name
-    let (mut client, _rpc_api, keystore) = Box::pin(create_test_client()).await;
+store_test!(import_processing_note_returns_error);
+async fn import_processing_note_returns_error(store: Arc<dyn Store>) {
+    let (mut client, _rpc_api, keystore) = Box::pin(create_test_client_with_store(store)).await;
     client.sync_state().await.unwrap();
 
     let (account, _seed) = insert_new_wallet(&mut client, AccountStorageMode::Private, &keystore)
@@ -967,9 +1427,10 @@ async fn real_note_roundtrip() {
     assert!(matches!(note.state(), &InputNoteState::ConsumedAuthenticatedLocal(_)));
 }
 
-#[tokio::test]
-async fn added_notes() {
-    let (mut client, mock_rpc_api, authenticator) = Box::pin(create_test_client()).await;
+store_test!(added_notes);
+async fn added_notes(store: Arc<dyn Store>) {
+    let (mut client, mock_rpc_api, authenticator) =
+        Box::pin(create_test_client_with_store(store)).await;
 
     let faucet_account_header =
         insert_new_fungible_faucet(&mut client, AccountStorageMode::Private, &authenticator)
@@ -999,9 +1460,10 @@ async fn added_notes() {
     assert!(notes.is_empty());
 }
 
-#[tokio::test]
-async fn p2id_transfer() {
-    let (mut client, mock_rpc_api, authenticator) = Box::pin(create_test_client()).await;
+store_test!(p2id_transfer);
+async fn p2id_transfer(store: Arc<dyn Store>) {
+    let (mut client, mock_rpc_api, authenticator) =
+        Box::pin(create_test_client_with_store(store)).await;
 
     let (first_regular_account, second_regular_account, faucet_account_header) =
         setup_two_wallets_and_faucet(&mut client, AccountStorageMode::Private, &authenticator)
@@ -1448,9 +1910,10 @@ async fn p2ide_timelocked() {
     assert_eq!(target_account.vault().get_balance(faucet_account_id).unwrap(), TRANSFER_AMOUNT);
 }
 
-#[tokio::test]
-async fn get_consumable_notes() {
-    let (mut client, mock_rpc_api, authenticator) = Box::pin(create_test_client()).await;
+store_test!(get_consumable_notes);
+async fn get_consumable_notes(store: Arc<dyn Store>) {
+    let (mut client, mock_rpc_api, authenticator) =
+        Box::pin(create_test_client_with_store(store)).await;
 
     let (first_regular_account, second_regular_account, faucet_account_header) =
         setup_two_wallets_and_faucet(&mut client, AccountStorageMode::Private, &authenticator)
@@ -1548,47 +2011,196 @@ async fn get_consumable_notes() {
 }
 
 #[tokio::test]
-async fn get_output_notes() {
+async fn note_relevance_checkers_replace_execution_for_their_script() {
+    struct FixedChecker(NoteRelevanceCheck);
+
+    impl NoteRelevanceChecker for FixedChecker {
+        fn check_relevance(
+            &self,
+            _note: &Note,
+            _account: &Account,
+        ) -> Result<NoteRelevanceCheck, NoteScreenerError> {
+            Ok(self.0)
+        }
+    }
+
     let (mut client, mock_rpc_api, authenticator) = Box::pin(create_test_client()).await;
-    let _ = client.sync_state().await.unwrap();
-    let (first_regular_account, faucet_account_header) =
-        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &authenticator)
+    let (regular_account, _, faucet_account_header) =
+        setup_two_wallets_and_faucet(&mut client, AccountStorageMode::Private, &authenticator)
             .await
             .unwrap();
 
-    let from_account_id = first_regular_account.id();
-    let faucet_account_id = faucet_account_header.id();
-    let random_account_id = AccountId::try_from(ACCOUNT_ID_REGULAR).unwrap();
-
-    // No output notes initially
-    assert!(client.get_output_notes(NoteFilter::All).await.unwrap().is_empty());
-
-    // First Mint necessary token
-    let note = mint_note(&mut client, from_account_id, faucet_account_id, NoteType::Private)
-        .await
-        .1;
+    mint_note(&mut client, regular_account.id(), faucet_account_header.id(), NoteType::Private)
+        .await;
     mock_rpc_api.prove_block();
     client.sync_state().await.unwrap();
 
-    // Check that there was an output note but it wasn't consumed
-    assert!(client.get_output_notes(NoteFilter::Consumed).await.unwrap().is_empty());
-    assert!(!client.get_output_notes(NoteFilter::All).await.unwrap().is_empty());
+    // The checker registered for the P2ID script decides the relevance of the minted note
+    let p2id_root = WellKnownNote::P2ID.script_root();
+    client.register_note_relevance_checker(
+        p2id_root,
+        Arc::new(FixedChecker(NoteRelevanceCheck::Consumable(NoteRelevance::After(42)))),
+    );
+    let consumable_notes = Box::pin(client.get_consumable_notes(None)).await.unwrap();
+    let relevances = &consumable_notes.first().unwrap().1;
+    assert!(relevances.iter().all(|(_, relevance)| *relevance == NoteRelevance::After(42)));
 
-    consume_notes(&mut client, from_account_id, &[note]).await;
-    mock_rpc_api.prove_block();
-    client.sync_state().await.unwrap();
+    client.register_note_relevance_checker(
+        p2id_root,
+        Arc::new(FixedChecker(NoteRelevanceCheck::NotConsumable)),
+    );
+    assert!(Box::pin(client.get_consumable_notes(None)).await.unwrap().is_empty());
 
-    // After consuming, the note is returned when using the [NoteFilter::Consumed] filter
-    assert!(!client.get_input_notes(NoteFilter::Consumed).await.unwrap().is_empty());
+    // Undecided checkers fall back to executing the note
+    client.register_note_relevance_checker(
+        p2id_root,
+        Arc::new(FixedChecker(NoteRelevanceCheck::Unknown)),
+    );
+    let consumable_notes =
+        Box::pin(client.get_consumable_notes(Some(regular_account.id()))).await.unwrap();
+    assert_eq!(
+        consumable_notes.first().unwrap().1,
+        vec![(regular_account.id(), NoteRelevance::Now)]
+    );
+}
 
-    // Do a transfer from first account to second account
-    let asset = FungibleAsset::new(faucet_account_id, TRANSFER_AMOUNT).unwrap();
-    println!("Running P2ID tx...");
-    let tx_request = TransactionRequestBuilder::new()
-        .build_pay_to_id(
-            PaymentNoteDescription::new(
-                vec![Asset::Fungible(asset)],
-                from_account_id,
+#[tokio::test]
+async fn parallel_note_screening_matches_sequential_screening() {
+    let (mut client, _, authenticator) = Box::pin(create_test_client()).await;
+    let (first_regular_account, second_regular_account, faucet_account_header) =
+        setup_two_wallets_and_faucet(&mut client, AccountStorageMode::Private, &authenticator)
+            .await
+            .unwrap();
+
+    let asset = Asset::Fungible(FungibleAsset::new(faucet_account_header.id(), 10).unwrap());
+    let target_account_ids = [first_regular_account.id(), second_regular_account.id()];
+    let notes: Vec<Note> = (0..6)
+        .map(|index| {
+            let payment = PaymentNoteDescription::new(
+                vec![asset],
+                faucet_account_header.id(),
+                target_account_ids[index % 2],
+            );
+            TransactionRequestBuilder::new()
+                .build_pay_to_id(payment, NoteType::Public, client.rng())
+                .unwrap()
+                .expected_output_own_notes()
+                .pop()
+                .unwrap()
+        })
+        .collect();
+
+    let sequential = client
+        .note_screener()
+        .with_parallelism(1)
+        .with_cache(Arc::new(NoteScreeningCache::new()))
+        .check_relevance_batch(&notes)
+        .await
+        .unwrap();
+    let parallel = client
+        .note_screener()
+        .with_parallelism(4)
+        .with_cache(Arc::new(NoteScreeningCache::new()))
+        .check_relevance_batch(&notes)
+        .await
+        .unwrap();
+
+    assert_eq!(parallel, sequential);
+    for (index, relevances) in parallel.iter().enumerate() {
+        assert_eq!(relevances, &vec![(target_account_ids[index % 2], NoteRelevance::Now)]);
+    }
+}
+
+#[tokio::test]
+async fn cached_note_screening_is_invalidated_by_new_blocks() {
+    let (mut client, mock_rpc_api, authenticator) = Box::pin(create_test_client()).await;
+    let (first_regular_account, second_regular_account, faucet_account_header) =
+        setup_two_wallets_and_faucet(&mut client, AccountStorageMode::Private, &authenticator)
+            .await
+            .unwrap();
+    let to_account_id = second_regular_account.id();
+
+    let timelock_height = client.get_sync_height().await.unwrap() + RECALL_HEIGHT_DELTA;
+    let asset = Asset::Fungible(FungibleAsset::new(faucet_account_header.id(), 10).unwrap());
+    let payment =
+        PaymentNoteDescription::new(vec![asset], first_regular_account.id(), to_account_id)
+            .with_timelock_height(timelock_height);
+    let note = TransactionRequestBuilder::new()
+        .build_pay_to_id(payment, NoteType::Public, client.rng())
+        .unwrap()
+        .expected_output_own_notes()
+        .pop()
+        .unwrap();
+
+    let cache = Arc::new(NoteScreeningCache::new());
+    let relevance_of_target = async |client: &MockClient<FilesystemKeyStore<StdRng>>| {
+        let relevances = client
+            .note_screener()
+            .with_cache(cache.clone())
+            .check_relevance(&note)
+            .await
+            .unwrap();
+        relevances
+            .into_iter()
+            .find(|(account_id, _)| *account_id == to_account_id)
+            .unwrap()
+            .1
+    };
+
+    let timelocked_relevance = NoteRelevance::After(timelock_height.as_u32());
+    assert_eq!(relevance_of_target(&client).await, timelocked_relevance);
+    assert!(!cache.is_empty());
+    assert_eq!(relevance_of_target(&client).await, timelocked_relevance);
+
+    // Once the timelock is over, the cached results don't apply anymore
+    mock_rpc_api.advance_blocks(RECALL_HEIGHT_DELTA);
+    client.sync_state().await.unwrap();
+    assert_eq!(relevance_of_target(&client).await, NoteRelevance::Now);
+}
+
+store_test!(get_output_notes);
+async fn get_output_notes(store: Arc<dyn Store>) {
+    let (mut client, mock_rpc_api, authenticator) =
+        Box::pin(create_test_client_with_store(store)).await;
+    let _ = client.sync_state().await.unwrap();
+    let (first_regular_account, faucet_account_header) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &authenticator)
+            .await
+            .unwrap();
+
+    let from_account_id = first_regular_account.id();
+    let faucet_account_id = faucet_account_header.id();
+    let random_account_id = AccountId::try_from(ACCOUNT_ID_REGULAR).unwrap();
+
+    // No output notes initially
+    assert!(client.get_output_notes(NoteFilter::All).await.unwrap().is_empty());
+
+    // First Mint necessary token
+    let note = mint_note(&mut client, from_account_id, faucet_account_id, NoteType::Private)
+        .await
+        .1;
+    mock_rpc_api.prove_block();
+    client.sync_state().await.unwrap();
+
+    // Check that there was an output note but it wasn't consumed
+    assert!(client.get_output_notes(NoteFilter::Consumed).await.unwrap().is_empty());
+    assert!(!client.get_output_notes(NoteFilter::All).await.unwrap().is_empty());
+
+    consume_notes(&mut client, from_account_id, &[note]).await;
+    mock_rpc_api.prove_block();
+    client.sync_state().await.unwrap();
+
+    // After consuming, the note is returned when using the [NoteFilter::Consumed] filter
+    assert!(!client.get_input_notes(NoteFilter::Consumed).await.unwrap().is_empty());
+
+    // Do a transfer from first account to second account
+    let asset = FungibleAsset::new(faucet_account_id, TRANSFER_AMOUNT).unwrap();
+    println!("Running P2ID tx...");
+    let tx_request = TransactionRequestBuilder::new()
+        .build_pay_to_id(
+            PaymentNoteDescription::new(
+                vec![Asset::Fungible(asset)],
+                from_account_id,
                 random_account_id,
             ),
             NoteType::Private,
@@ -1610,9 +2222,10 @@ async fn get_output_notes() {
     assert!(client.get_input_note(output_note_id).await.unwrap().is_none());
 }
 
-#[tokio::test]
-async fn account_rollback() {
-    let (builder, mock_rpc_api, authenticator) = Box::pin(create_test_client_builder()).await;
+store_test!(account_rollback);
+async fn account_rollback(store: Arc<dyn Store>) {
+    let (builder, mock_rpc_api, authenticator) =
+        Box::pin(create_test_client_builder_with_store(store)).await;
 
     let mut client = builder.tx_graceful_blocks(Some(TX_GRACEFUL_BLOCKS)).build().await.unwrap();
 
@@ -1706,9 +2319,9 @@ async fn account_rollback() {
     );
 }
 
-#[tokio::test]
-async fn subsequent_discarded_transactions() {
-    let (mut client, mock_rpc_api, keystore) = create_test_client().await;
+store_test!(subsequent_discarded_transactions);
+async fn subsequent_discarded_transactions(store: Arc<dyn Store>) {
+    let (mut client, mock_rpc_api, keystore) = Box::pin(create_test_client_with_store(store)).await;
 
     let (regular_account, faucet_account_header) =
         setup_wallet_and_faucet(&mut client, AccountStorageMode::Public, &keystore)
@@ -1803,6 +2416,373 @@ async fn subsequent_discarded_transactions() {
     );
 }
 
+#[tokio::test]
+async fn discarded_transaction_is_retried() {
+    let (mut client, mock_rpc_api, keystore) = create_test_client().await;
+
+    let (regular_account, faucet_account_header) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Public, &keystore)
+            .await
+            .unwrap();
+
+    let account_id = regular_account.id();
+    let faucet_account_id = faucet_account_header.id();
+
+    let note = mint_note(&mut client, account_id, faucet_account_id, NoteType::Private).await.1;
+    mock_rpc_api.prove_block();
+    client.sync_state().await.unwrap();
+
+    consume_notes(&mut client, account_id, &[note]).await;
+    mock_rpc_api.prove_block();
+    client.sync_state().await.unwrap();
+
+    // Create a transaction that will expire in 2 blocks and don't submit it to the node
+    let asset = FungibleAsset::new(faucet_account_id, TRANSFER_AMOUNT).unwrap();
+    let tx_request = TransactionRequestBuilder::new()
+        .expiration_delta(2)
+        .build_pay_to_id(
+            PaymentNoteDescription::new(vec![Asset::Fungible(asset)], account_id, account_id),
+            NoteType::Public,
+            client.rng(),
+        )
+        .unwrap();
+
+    let tx_result = Box::pin(client.new_transaction(account_id, tx_request)).await.unwrap();
+    let discarded_tx_id = tx_result.executed_transaction().id();
+    Box::pin(client.testing_apply_transaction(tx_result)).await.unwrap();
+
+    // Pending transactions can't be retried
+    assert!(matches!(
+        Box::pin(client.retry_transaction(discarded_tx_id)).await,
+        Err(ClientError::TransactionNotRetryable(..))
+    ));
+
+    mock_rpc_api.advance_blocks(3);
+    client.sync_state().await.unwrap();
+
+    // Retry the expired transaction and check that the retry gets committed
+    let tx_result = Box::pin(client.retry_transaction(discarded_tx_id)).await.unwrap();
+    assert_eq!(tx_result.retry_of(), Some(discarded_tx_id));
+    let retry_tx_id = tx_result.executed_transaction().id();
+    Box::pin(client.submit_transaction(tx_result)).await.unwrap();
+
+    mock_rpc_api.prove_block();
+    client.sync_state().await.unwrap();
+
+    let retry_record = client
+        .get_transactions(TransactionFilter::Ids(vec![retry_tx_id]))
+        .await
+        .unwrap()
+        .pop()
+        .unwrap();
+
+    assert!(matches!(retry_record.status, TransactionStatus::Committed { .. }));
+    assert_eq!(retry_record.retry_of, Some(discarded_tx_id));
+    assert!(retry_record.request.is_some());
+
+    // A transaction can only be retried once
+    assert!(matches!(
+        Box::pin(client.retry_transaction(discarded_tx_id)).await,
+        Err(ClientError::TransactionNotRetryable(..))
+    ));
+}
+
+#[tokio::test]
+async fn queued_transactions_build_on_each_other() {
+    let (mut client, mock_rpc_api, keystore) = create_test_client().await;
+
+    let (regular_account, faucet_account_header) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Public, &keystore)
+            .await
+            .unwrap();
+
+    let account_id = regular_account.id();
+    let faucet_account_id = faucet_account_header.id();
+
+    let note = mint_note(&mut client, account_id, faucet_account_id, NoteType::Private).await.1;
+    mock_rpc_api.prove_block();
+    client.sync_state().await.unwrap();
+
+    consume_notes(&mut client, account_id, &[note]).await;
+    mock_rpc_api.prove_block();
+    client.sync_state().await.unwrap();
+
+    // Enqueue two payments from the same account
+    let mut queued_ids = Vec::new();
+    for _ in 0..2 {
+        let asset = FungibleAsset::new(faucet_account_id, TRANSFER_AMOUNT).unwrap();
+        let tx_request = TransactionRequestBuilder::new()
+            .build_pay_to_id(
+                PaymentNoteDescription::new(vec![Asset::Fungible(asset)], account_id, account_id),
+                NoteType::Public,
+                client.rng(),
+            )
+            .unwrap();
+
+        queued_ids.push(client.enqueue_transaction(account_id, tx_request).await.unwrap());
+    }
+
+    let status = client.transaction_queue_status().await.unwrap();
+    assert_eq!(status.len(), 2);
+    assert_eq!(status[1].status, QueuedTransactionStatus::Queued { position: 1 });
+
+    // The queue is persisted, so another client on the same store sees it
+    let (builder, ..) = Box::pin(create_test_client_builder_with_store(client.store.clone())).await;
+    let other_client = builder.build().await.unwrap();
+    assert_eq!(other_client.transaction_queue_status().await.unwrap(), status);
+
+    let processed = Box::pin(client.process_transaction_queue()).await.unwrap();
+    let tx_ids: Vec<TransactionId> = processed
+        .iter()
+        .map(|queued| match queued.status {
+            QueuedTransactionStatus::Submitted(tx_id) => tx_id,
+            ref status => panic!("queued transaction wasn't submitted: {status}"),
+        })
+        .collect();
+    assert_eq!(processed.iter().map(|queued| queued.id).collect::<Vec<_>>(), queued_ids);
+
+    // The second transaction was executed against the state left by the first one
+    let records = client.get_transactions(TransactionFilter::Ids(tx_ids.clone())).await.unwrap();
+    let first = records.iter().find(|tx| tx.id == tx_ids[0]).unwrap();
+    let second = records.iter().find(|tx| tx.id == tx_ids[1]).unwrap();
+    assert_eq!(second.details.init_account_state, first.details.final_account_state);
+    assert!(matches!(second.status, TransactionStatus::Pending));
+
+    assert_eq!(
+        client.get_queued_transaction(queued_ids[1]).await.unwrap().unwrap().status,
+        QueuedTransactionStatus::Submitted(tx_ids[1])
+    );
+}
+
+#[tokio::test]
+async fn simulated_transaction_reports_effects() {
+    let (mut client, mock_rpc_api, keystore) = create_test_client().await;
+
+    let (regular_account, faucet_account_header) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Public, &keystore)
+            .await
+            .unwrap();
+
+    let account_id = regular_account.id();
+    let faucet_account_id = faucet_account_header.id();
+
+    let note = mint_note(&mut client, account_id, faucet_account_id, NoteType::Private).await.1;
+    mock_rpc_api.prove_block();
+    client.sync_state().await.unwrap();
+
+    consume_notes(&mut client, account_id, &[note]).await;
+    mock_rpc_api.prove_block();
+    client.sync_state().await.unwrap();
+
+    let target_account_id =
+        AccountId::try_from(ACCOUNT_ID_REGULAR_PUBLIC_ACCOUNT_IMMUTABLE_CODE).unwrap();
+    let asset = FungibleAsset::new(faucet_account_id, TRANSFER_AMOUNT).unwrap();
+    let tx_request = TransactionRequestBuilder::new()
+        .build_pay_to_id(
+            PaymentNoteDescription::new(
+                vec![Asset::Fungible(asset)],
+                account_id,
+                target_account_id,
+            ),
+            NoteType::Public,
+            client.rng(),
+        )
+        .unwrap();
+
+    let account_before = client.get_account(account_id).await.unwrap().unwrap();
+    let report = Box::pin(client.simulate_transaction(account_id, tx_request)).await.unwrap();
+
+    assert_eq!(report.account_id, account_id);
+    assert!(report.consumed_notes.is_empty());
+    assert_eq!(report.created_notes.len(), 1);
+    assert_eq!(
+        report.created_notes[0].script,
+        NoteScriptKind::P2id { target: target_account_id }
+    );
+
+    let expected_asset = AssetReport::Fungible {
+        faucet_id: faucet_account_id,
+        amount: TRANSFER_AMOUNT,
+        symbol: Some("TEST".to_string()),
+        decimals: Some(10),
+    };
+    assert_eq!(report.created_notes[0].assets, vec![expected_asset.clone()]);
+    assert_eq!(report.vault_changes, vec![VaultChange { asset: expected_asset, added: false }]);
+    assert_eq!(report.nonce_delta, 1);
+    assert!(report.cycles.total > 0);
+
+    // Simulating doesn't change the account or track the transaction
+    let account_after = client.get_account(account_id).await.unwrap().unwrap();
+    assert_eq!(account_after.account().commitment(), account_before.account().commitment());
+    assert!(
+        client
+            .get_transactions(TransactionFilter::Ids(vec![report.transaction_id]))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // Simulating the consumption of an unauthenticated note doesn't track the note
+    let serial_num = client.rng().draw_word();
+    let recipient = utils::build_p2id_recipient(account_id, serial_num).unwrap();
+    let tag = NoteTag::from_account_id(account_id);
+    let metadata = NoteMetadata::new(
+        faucet_account_id,
+        NoteType::Private,
+        tag,
+        NoteExecutionHint::always(),
+        ZERO,
+    )
+    .unwrap();
+    let note = Note::new(NoteAssets::new(vec![asset.into()]).unwrap(), metadata, recipient);
+
+    let tx_request = TransactionRequestBuilder::new()
+        .unauthenticated_input_notes([(note.clone(), None)])
+        .build()
+        .unwrap();
+    let report = Box::pin(client.simulate_transaction(account_id, tx_request)).await.unwrap();
+
+    assert_eq!(report.consumed_notes.len(), 1);
+    assert_eq!(report.consumed_notes[0].id, note.id());
+    assert!(client.get_input_note(note.id()).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn multisig_transaction_requires_threshold_signatures() {
+    let (mut client, mock_rpc_api, keystore) = create_test_client().await;
+
+    let (_, faucet_account_header) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+    let faucet_account_id = faucet_account_header.id();
+
+    // 2-of-3 wallet with one approver key in the client's keystore and another one held by a
+    // co-signer
+    let local_key = SecretKey::with_rng(&mut client.rng);
+    let co_signer_key = SecretKey::with_rng(&mut client.rng);
+    let approvers = vec![
+        local_key.public_key(),
+        co_signer_key.public_key(),
+        SecretKey::with_rng(&mut client.rng).public_key(),
+    ];
+
+    keystore.add_key(&AuthSecretKey::RpoFalcon512(local_key)).unwrap();
+    let co_signer_keystore =
+        FilesystemKeyStore::new(temp_dir().join(Uuid::new_v4().to_string())).unwrap();
+    co_signer_keystore.add_key(&AuthSecretKey::RpoFalcon512(co_signer_key)).unwrap();
+
+    let mut init_seed = [0u8; 32];
+    client.rng.fill_bytes(&mut init_seed);
+    let (account, seed) = AccountBuilder::new(init_seed)
+        .account_type(AccountType::RegularAccountImmutableCode)
+        .storage_mode(AccountStorageMode::Private)
+        .with_auth_component(AuthRpoFalcon512Multisig::new(2, approvers.clone()).unwrap())
+        .with_component(BasicWallet)
+        .build()
+        .unwrap();
+    client.add_account(&account, Some(seed), false).await.unwrap();
+    let account_id = account.id();
+
+    let note = mint_note(&mut client, account_id, faucet_account_id, NoteType::Private).await.1;
+    mock_rpc_api.prove_block();
+    client.sync_state().await.unwrap();
+
+    let tx_request = TransactionRequestBuilder::new().build_consume_notes(vec![note.id()]).unwrap();
+    let mut signing_request = Box::pin(client.propose_multisig_transaction(account_id, tx_request))
+        .await
+        .unwrap();
+
+    assert_eq!(signing_request.threshold(), 2);
+    assert_eq!(
+        signing_request.signers().copied().collect::<Vec<_>>(),
+        vec![Word::from(approvers[0])]
+    );
+
+    // A single signature isn't enough
+    let result = Box::pin(client.finalize_multisig_transaction(signing_request.clone())).await;
+    assert!(matches!(
+        result,
+        Err(ClientError::MultisigError(MultisigError::NotEnoughSignatures {
+            threshold: 2,
+            signatures: 1
+        }))
+    ));
+
+    // The co-signer signs the request it received serialized
+    let mut co_signed =
+        MultisigSigningRequest::read_from_bytes(&signing_request.to_bytes()).unwrap();
+    let signed = co_signed.sign(&co_signer_keystore).await.unwrap();
+    assert_eq!(signed, vec![Word::from(approvers[1])]);
+
+    signing_request.merge(co_signed).unwrap();
+    assert!(signing_request.is_complete());
+
+    let tx_result = Box::pin(client.finalize_multisig_transaction(signing_request)).await.unwrap();
+    Box::pin(client.submit_transaction(tx_result)).await.unwrap();
+
+    assert_account_has_single_asset(&client, account_id, faucet_account_id, MINT_AMOUNT).await;
+}
+
+#[tokio::test]
+async fn transaction_file_is_proven_and_submitted_later() {
+    let (mut client, mock_rpc_api, keystore) = create_test_client().await;
+
+    let (regular_account, faucet_account_header) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+
+    let account_id = regular_account.id();
+    let faucet_account_id = faucet_account_header.id();
+
+    let note = mint_note(&mut client, account_id, faucet_account_id, NoteType::Private).await.1;
+    mock_rpc_api.prove_block();
+    client.sync_state().await.unwrap();
+
+    // Execute the transaction and export it without proving it
+    let tx_request = TransactionRequestBuilder::new().build_consume_notes(vec![note.id()]).unwrap();
+    let tx_result = Box::pin(client.new_transaction(account_id, tx_request)).await.unwrap();
+    let tx_id = tx_result.executed_transaction().id();
+
+    let unproven_file = TransactionFile::Unproven(tx_result);
+    let unproven_file = TransactionFile::read_from_bytes(&unproven_file.to_bytes()).unwrap();
+    assert_eq!(unproven_file.transaction_id(), tx_id);
+
+    // Prove it as a different machine would, and export the proven transaction
+    let TransactionFile::Unproven(tx_result) = unproven_file else {
+        panic!("expected an unproven transaction file");
+    };
+    let proven_transaction = client
+        .prove_transaction(&tx_result, Arc::new(LocalTransactionProver::default()))
+        .await
+        .unwrap();
+    let proven_file = TransactionFile::Proven { tx_result, proven_transaction };
+    let proven_file = TransactionFile::read_from_bytes(&proven_file.to_bytes()).unwrap();
+
+    // Submit it and track it in the store
+    let TransactionFile::Proven { tx_result, proven_transaction } = proven_file else {
+        panic!("expected a proven transaction file");
+    };
+    let submission_height = client.submit_proven_transaction(proven_transaction).await.unwrap();
+    Box::pin(client.apply_transaction(submission_height, tx_result)).await.unwrap();
+
+    mock_rpc_api.prove_block();
+    client.sync_state().await.unwrap();
+
+    let tx_record = client
+        .get_transactions(TransactionFilter::Ids(vec![tx_id]))
+        .await
+        .unwrap()
+        .pop()
+        .unwrap();
+    assert!(matches!(tx_record.status, TransactionStatus::Committed { .. }));
+    assert_account_has_single_asset(&client, account_id, faucet_account_id, MINT_AMOUNT).await;
+
+    // Files that aren't transaction files are rejected
+    assert!(TransactionFile::read_from_bytes(&note.to_bytes()).is_err());
+}
+
 #[tokio::test]
 async fn missing_recipient_digest() {
     let (mut client, _, keystore) = create_test_client().await;
@@ -2002,8 +2982,98 @@ async fn swap_chain_test() {
 }
 
 #[tokio::test]
-async fn empty_storage_map() {
-    let (mut client, _, keystore) = create_test_client().await;
+async fn swaps_are_tracked_until_filled_or_reclaimed() {
+    let (mut client, mock_rpc_api, keystore) = create_test_client().await;
+
+    let (creator, offered_faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+    let (filler, requested_faucet) =
+        setup_wallet_and_faucet(&mut client, AccountStorageMode::Private, &keystore)
+            .await
+            .unwrap();
+
+    // The creator also holds the requested asset, which is needed to cancel a swap
+    for (wallet, faucet) in [
+        (&creator, &offered_faucet),
+        (&filler, &requested_faucet),
+        (&creator, &requested_faucet),
+    ] {
+        mint_and_consume(&mut client, wallet.id(), faucet.id(), NoteType::Private).await;
+        mock_rpc_api.prove_block();
+        client.sync_state().await.unwrap();
+    }
+
+    let requested_asset = Asset::Fungible(FungibleAsset::new(requested_faucet.id(), 1).unwrap());
+    let swap_data = SwapTransactionData::new(
+        creator.id(),
+        Asset::Fungible(FungibleAsset::new(offered_faucet.id(), 1).unwrap()),
+        requested_asset,
+    );
+
+    let mut swap_notes = vec![];
+    for _ in 0..2 {
+        let tx_request = TransactionRequestBuilder::new()
+            .build_swap(&swap_data, NoteType::Private, NoteType::Private, client.rng())
+            .unwrap();
+        swap_notes.push(tx_request.expected_output_own_notes()[0].id());
+        execute_tx(&mut client, creator.id(), tx_request).await;
+        mock_rpc_api.prove_block();
+        client.sync_state().await.unwrap();
+    }
+    let (filled_swap, cancelled_swap) = (swap_notes[0], swap_notes[1]);
+
+    let swaps = client.get_swaps().await.unwrap();
+    assert_eq!(swaps.len(), 2);
+    for swap in &swaps {
+        assert_eq!(swap.status, SwapStatus::Open);
+        assert_eq!(swap.account_id, creator.id());
+        assert_eq!(swap.requested_asset, requested_asset);
+    }
+
+    // Only the SWAP notes are followed during sync, not the notes minted by the faucets
+    let mut pending_swaps: Vec<NoteId> = client
+        .pending_swap_notes()
+        .await
+        .unwrap()
+        .iter()
+        .map(OutputNoteRecord::id)
+        .collect();
+    pending_swaps.sort();
+    swap_notes.sort();
+    assert_eq!(pending_swaps, swap_notes);
+
+    // Another account fills the first swap
+    let tx_request =
+        TransactionRequestBuilder::new().build_consume_notes(vec![filled_swap]).unwrap();
+    execute_tx(&mut client, filler.id(), tx_request).await;
+    mock_rpc_api.prove_block();
+    let sync_summary = client.sync_state().await.unwrap();
+    assert_eq!(sync_summary.filled_swaps, vec![filled_swap]);
+
+    // The creator cancels the second one
+    let tx_request = client.cancel_swap_request(cancelled_swap).await.unwrap();
+    execute_tx(&mut client, creator.id(), tx_request).await;
+    mock_rpc_api.prove_block();
+    let sync_summary = client.sync_state().await.unwrap();
+    assert!(sync_summary.filled_swaps.is_empty());
+
+    let filled = client.get_swap(filled_swap).await.unwrap().unwrap();
+    assert_eq!(filled.status, SwapStatus::Filled);
+    let cancelled = client.get_swap(cancelled_swap).await.unwrap().unwrap();
+    assert_eq!(cancelled.status, SwapStatus::Reclaimed);
+    assert!(client.pending_swap_notes().await.unwrap().is_empty());
+
+    assert!(matches!(
+        client.cancel_swap_request(filled_swap).await,
+        Err(ClientError::SwapNotCancellable(..))
+    ));
+}
+
+store_test!(empty_storage_map);
+async fn empty_storage_map(store: Arc<dyn Store>) {
+    let (mut client, _, keystore) = Box::pin(create_test_client_with_store(store)).await;
 
     let storage_map = StorageMap::new();
 
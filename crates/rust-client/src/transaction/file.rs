@@ -0,0 +1,125 @@
+use alloc::string::ToString;
+
+use miden_objects::transaction::{ProvenTransaction, TransactionId};
+use miden_tx::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+use super::{TransactionRequest, TransactionResult};
+
+/// Magic bytes that identify a serialized [`TransactionFile`].
+const MAGIC: &[u8; 4] = b"mtxf";
+
+/// Version of the [`TransactionFile`] format, written after the magic bytes.
+const FILE_VERSION: u8 = 1;
+
+// TRANSACTION FILE
+// ================================================================================================
+
+/// A transaction that was exported to be proven or submitted later, possibly by a different
+/// client.
+///
+/// This allows splitting the transaction pipeline across machines: a transaction can be executed
+/// (and signed) on a machine that never connects to the network, proven elsewhere, and finally
+/// submitted by an online client.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TransactionFile {
+    /// An executed transaction that still has to be proven.
+    Unproven(TransactionResult),
+    /// A proven transaction that is ready to be submitted, along with the result it was proven
+    /// from, which is needed to track the transaction after submitting it.
+    Proven {
+        tx_result: TransactionResult,
+        proven_transaction: ProvenTransaction,
+    },
+}
+
+impl TransactionFile {
+    /// Returns the ID of the transaction.
+    pub fn transaction_id(&self) -> TransactionId {
+        self.tx_result().executed_transaction().id()
+    }
+
+    /// Returns the result of executing the transaction.
+    pub fn tx_result(&self) -> &TransactionResult {
+        match self {
+            TransactionFile::Unproven(tx_result) | TransactionFile::Proven { tx_result, .. } => {
+                tx_result
+            },
+        }
+    }
+}
+
+impl Serializable for TransactionFile {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(MAGIC);
+        target.write_u8(FILE_VERSION);
+        match self {
+            TransactionFile::Unproven(tx_result) => {
+                target.write_u8(0);
+                write_tx_result(tx_result, target);
+            },
+            TransactionFile::Proven { tx_result, proven_transaction } => {
+                target.write_u8(1);
+                write_tx_result(tx_result, target);
+                proven_transaction.write_into(target);
+            },
+        }
+    }
+}
+
+impl Deserializable for TransactionFile {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let magic = source.read_array::<4>()?;
+        if &magic != MAGIC {
+            return Err(DeserializationError::InvalidValue("not a transaction file".to_string()));
+        }
+
+        let version = source.read_u8()?;
+        if version != FILE_VERSION {
+            return Err(DeserializationError::InvalidValue(format!(
+                "unsupported transaction file version {version}"
+            )));
+        }
+
+        match source.read_u8()? {
+            0 => Ok(TransactionFile::Unproven(read_tx_result(source)?)),
+            1 => {
+                let tx_result = read_tx_result(source)?;
+                let proven_transaction = ProvenTransaction::read_from(source)?;
+
+                if proven_transaction.id() != tx_result.executed_transaction().id() {
+                    return Err(DeserializationError::InvalidValue(
+                        "proven transaction doesn't match the transaction result".to_string(),
+                    ));
+                }
+
+                Ok(TransactionFile::Proven { tx_result, proven_transaction })
+            },
+            variant => Err(DeserializationError::InvalidValue(format!(
+                "unknown transaction file variant {variant}"
+            ))),
+        }
+    }
+}
+
+/// Writes the transaction result along with the request it was executed from and the transaction
+/// it retries, which aren't part of its own serialization.
+fn write_tx_result<W: ByteWriter>(tx_result: &TransactionResult, target: &mut W) {
+    tx_result.write_into(target);
+    tx_result.request().cloned().write_into(target);
+    tx_result.retry_of().write_into(target);
+}
+
+/// Reads a transaction result written by [`write_tx_result`].
+fn read_tx_result<R: ByteReader>(
+    source: &mut R,
+) -> Result<TransactionResult, DeserializationError> {
+    let mut tx_result = TransactionResult::read_from(source)?;
+    if let Some(request) = Option::<TransactionRequest>::read_from(source)? {
+        tx_result = tx_result.with_request(request);
+    }
+    if let Some(retry_of) = Option::<TransactionId>::read_from(source)? {
+        tx_result = tx_result.with_retry_of(retry_of);
+    }
+
+    Ok(tx_result)
+}
@@ -85,11 +85,11 @@ use miden_objects::{AssetError, Felt, Word};
 use miden_remote_prover_client::remote_prover::tx_prover::RemoteTransactionProver;
 use miden_tx::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
 use miden_tx::{DataStore, NoteConsumptionChecker, TransactionExecutor};
-use tracing::info;
+use tracing::{info, instrument};
 
 use super::Client;
 use crate::ClientError;
-use crate::note::{NoteScreener, NoteUpdateTracker};
+use crate::note::NoteUpdateTracker;
 use crate::rpc::domain::account::AccountProof;
 use crate::store::data_store::ClientDataStore;
 use crate::store::input_note_states::ExpectedNoteState;
@@ -103,11 +103,19 @@ use crate::store::{
 };
 use crate::sync::NoteTagRecord;
 
+mod file;
+mod multisig;
+#[cfg(feature = "tonic")]
+mod prover_pool;
+mod queue;
+mod report;
 mod request;
+mod retry;
 
 // RE-EXPORTS
 // ================================================================================================
 
+pub use file::TransactionFile;
 pub use miden_lib::account::interface::{AccountComponentInterface, AccountInterface};
 pub use miden_lib::transaction::TransactionKernel;
 pub use miden_objects::transaction::{
@@ -119,6 +127,7 @@ pub use miden_objects::transaction::{
     ProvenTransaction,
     TransactionId,
     TransactionScript,
+    TransactionSummary,
     TransactionWitness,
 };
 pub use miden_objects::vm::{AdviceInputs, AdviceMap};
@@ -130,6 +139,32 @@ pub use miden_tx::{
     TransactionExecutorError,
     TransactionProverError,
 };
+pub use multisig::{MultisigAuthenticator, MultisigError, MultisigSigningRequest};
+#[cfg(feature = "tonic")]
+pub use prover_pool::{
+    DEFAULT_PROVER_RETRY_AFTER,
+    DEFAULT_PROVER_TIMEOUT,
+    ProverPool,
+    ProverSelection,
+    ProverStatus,
+};
+pub use queue::{
+    MAX_PROCESSED_TRANSACTIONS,
+    QueuedTransaction,
+    QueuedTransactionId,
+    QueuedTransactionStatus,
+};
+pub use report::{
+    AssetReport,
+    CycleCounts,
+    NoteInputsDecoder,
+    NoteReport,
+    NoteScriptKind,
+    StorageChange,
+    TokenInfo,
+    TransactionReport,
+    VaultChange,
+};
 pub use request::{
     ForeignAccount,
     NoteArgs,
@@ -140,6 +175,7 @@ pub use request::{
     TransactionRequestError,
     TransactionScriptTemplate,
 };
+pub use retry::{DEFAULT_MAX_TRANSACTION_RETRIES, TransactionRetryPolicy};
 
 // TRANSACTION RESULT
 // ================================================================================================
@@ -179,11 +215,18 @@ impl TransactionProver for RemoteTransactionProver {
 /// Represents the result of executing a transaction by the client.
 ///
 /// It contains an [`ExecutedTransaction`], and a list of `future_notes` that we expect to receive
-/// in the future (you can check at swap notes for an example of this).
+/// in the future (you can check at swap notes for an example of this). Results created by
+/// [`Client::new_transaction`] also keep the request they were executed from, so that the
+/// transaction can be retried if it gets discarded.
+///
+/// The serialized result only has the executed transaction and the future notes. The request and
+/// the transaction it retries are kept by the store and by [`TransactionFile`].
 #[derive(Clone, Debug, PartialEq)]
 pub struct TransactionResult {
     transaction: ExecutedTransaction,
     future_notes: Vec<(NoteDetails, NoteTag)>,
+    request: Option<TransactionRequest>,
+    retry_of: Option<TransactionId>,
 }
 
 impl TransactionResult {
@@ -193,7 +236,36 @@ impl TransactionResult {
         transaction: ExecutedTransaction,
         future_notes: Vec<(NoteDetails, NoteTag)>,
     ) -> Result<Self, ClientError> {
-        Ok(Self { transaction, future_notes })
+        Ok(Self {
+            transaction,
+            future_notes,
+            request: None,
+            retry_of: None,
+        })
+    }
+
+    /// Sets the request the transaction was executed from.
+    #[must_use]
+    pub fn with_request(mut self, request: TransactionRequest) -> Self {
+        self.request = Some(request);
+        self
+    }
+
+    /// Sets the discarded transaction that this transaction retries.
+    #[must_use]
+    pub fn with_retry_of(mut self, retry_of: TransactionId) -> Self {
+        self.retry_of = Some(retry_of);
+        self
+    }
+
+    /// Returns the request the transaction was executed from, if it's known.
+    pub fn request(&self) -> Option<&TransactionRequest> {
+        self.request.as_ref()
+    }
+
+    /// Returns the ID of the discarded transaction that this transaction retries, if any.
+    pub fn retry_of(&self) -> Option<TransactionId> {
+        self.retry_of
     }
 
     /// Returns the [`ExecutedTransaction`].
@@ -251,7 +323,12 @@ impl Deserializable for TransactionResult {
         let transaction = ExecutedTransaction::read_from(source)?;
         let future_notes = Vec::<(NoteDetails, NoteTag)>::read_from(source)?;
 
-        Ok(Self { transaction, future_notes })
+        Ok(Self {
+            transaction,
+            future_notes,
+            request: None,
+            retry_of: None,
+        })
     }
 }
 
@@ -270,6 +347,12 @@ pub struct TransactionRecord {
     pub script: Option<TransactionScript>,
     /// Current status of the transaction.
     pub status: TransactionStatus,
+    /// Request the transaction was executed from, used to retry the transaction if it gets
+    /// discarded. It's unknown for transactions that weren't created through
+    /// [`Client::new_transaction`].
+    pub request: Option<TransactionRequest>,
+    /// ID of the discarded transaction that this transaction retries, if any.
+    pub retry_of: Option<TransactionId>,
 }
 
 impl TransactionRecord {
@@ -280,7 +363,26 @@ impl TransactionRecord {
         script: Option<TransactionScript>,
         status: TransactionStatus,
     ) -> TransactionRecord {
-        TransactionRecord { id, details, script, status }
+        TransactionRecord {
+            id,
+            details,
+            script,
+            status,
+            request: None,
+            retry_of: None,
+        }
+    }
+
+    /// Sets the request the transaction was executed from and the transaction it retries.
+    #[must_use]
+    pub fn with_request(
+        mut self,
+        request: Option<TransactionRequest>,
+        retry_of: Option<TransactionId>,
+    ) -> TransactionRecord {
+        self.request = request;
+        self.retry_of = retry_of;
+        self
     }
 
     /// Updates (if necessary) the transaction status to signify that the transaction was
@@ -528,6 +630,10 @@ pub struct TransactionStoreUpdate {
     note_updates: NoteUpdateTracker,
     /// New note tags to be tracked.
     new_tags: Vec<NoteTagRecord>,
+    /// Request the transaction was executed from, if it's known.
+    request: Option<TransactionRequest>,
+    /// ID of the discarded transaction that this transaction retries, if any.
+    retry_of: Option<TransactionId>,
 }
 
 impl TransactionStoreUpdate {
@@ -550,9 +656,23 @@ impl TransactionStoreUpdate {
             submission_height,
             note_updates,
             new_tags,
+            request: None,
+            retry_of: None,
         }
     }
 
+    /// Sets the request the transaction was executed from and the transaction it retries.
+    #[must_use]
+    pub fn with_request(
+        mut self,
+        request: Option<TransactionRequest>,
+        retry_of: Option<TransactionId>,
+    ) -> Self {
+        self.request = request;
+        self.retry_of = retry_of;
+        self
+    }
+
     /// Returns the executed transaction.
     pub fn executed_transaction(&self) -> &ExecutedTransaction {
         &self.executed_transaction
@@ -572,6 +692,16 @@ impl TransactionStoreUpdate {
     pub fn new_tags(&self) -> &[NoteTagRecord] {
         &self.new_tags
     }
+
+    /// Returns the request the transaction was executed from, if it's known.
+    pub fn request(&self) -> Option<&TransactionRequest> {
+        self.request.as_ref()
+    }
+
+    /// Returns the ID of the discarded transaction that this transaction retries, if any.
+    pub fn retry_of(&self) -> Option<TransactionId> {
+        self.retry_of
+    }
 }
 
 /// Transaction management methods
@@ -610,10 +740,27 @@ where
         &mut self,
         account_id: AccountId,
         transaction_request: TransactionRequest,
+    ) -> Result<TransactionResult, ClientError> {
+        let authenticator = self.authenticator.clone();
+        self.execute_transaction_request(account_id, transaction_request, authenticator.as_deref())
+            .await
+    }
+
+    /// Executes the transaction request like [`Client::new_transaction`], requesting the
+    /// signatures from the given authenticator instead of the client's one.
+    #[instrument(level = "debug", skip_all, fields(account_id = %account_id))]
+    pub(crate) async fn execute_transaction_request<A: TransactionAuthenticator + Sync>(
+        &mut self,
+        account_id: AccountId,
+        transaction_request: TransactionRequest,
+        authenticator: Option<&A>,
     ) -> Result<TransactionResult, ClientError> {
         // Validates the transaction request before executing
         self.validate_request(account_id, &transaction_request).await?;
 
+        // Kept in the result so the transaction can be retried if it gets discarded
+        let request = transaction_request.clone();
+
         // Ensure authenticated notes have their inclusion proofs (a.k.a they're in a committed
         // state)
         let authenticated_input_note_ids: Vec<NoteId> =
@@ -624,16 +771,6 @@ where
             .get_input_notes(NoteFilter::List(authenticated_input_note_ids))
             .await?;
 
-        // If tx request contains unauthenticated_input_notes we should insert them
-        let unauthenticated_input_notes = transaction_request
-            .unauthenticated_input_notes()
-            .iter()
-            .cloned()
-            .map(Into::into)
-            .collect::<Vec<_>>();
-
-        self.store.upsert_input_notes(&unauthenticated_input_notes).await?;
-
         let mut notes = transaction_request.build_input_notes(authenticated_note_records)?;
 
         let output_recipients =
@@ -683,14 +820,16 @@ where
         }
 
         // Execute the transaction and get the witness
-        let executed_transaction = self
-            .build_executor(&data_store)?
-            .execute_transaction(account_id, block_num, notes, tx_args)
-            .await?;
+        let mut executor = TransactionExecutor::new(&data_store).with_options(self.exec_options)?;
+        if let Some(authenticator) = authenticator {
+            executor = executor.with_authenticator(authenticator);
+        }
+        let executed_transaction =
+            executor.execute_transaction(account_id, block_num, notes, tx_args).await?;
 
         validate_executed_transaction(&executed_transaction, &output_recipients)?;
 
-        TransactionResult::new(executed_transaction, future_notes)
+        Ok(TransactionResult::new(executed_transaction, future_notes)?.with_request(request))
     }
 
     /// Proves the specified transaction using a local prover, submits it to the network, and saves
@@ -715,8 +854,12 @@ where
     }
 
     /// Proves the specified transaction result using the provided prover.
-    async fn prove_transaction(
-        &mut self,
+    ///
+    /// The proven transaction can then be submitted with [`Client::submit_proven_transaction`],
+    /// possibly by a different client.
+    #[instrument(level = "debug", skip_all)]
+    pub async fn prove_transaction(
+        &self,
         tx_result: &TransactionResult,
         tx_prover: Arc<dyn TransactionProver>,
     ) -> Result<ProvenTransaction, ClientError> {
@@ -730,7 +873,13 @@ where
         Ok(proven_transaction)
     }
 
-    async fn submit_proven_transaction(
+    /// Submits a proven transaction to the network and returns the block height at which it was
+    /// submitted.
+    ///
+    /// This doesn't update the local store. To track the transaction, pass its result and the
+    /// returned height to [`Client::apply_transaction`].
+    #[instrument(level = "debug", skip_all)]
+    pub async fn submit_proven_transaction(
         &mut self,
         proven_transaction: ProvenTransaction,
     ) -> Result<BlockNumber, ClientError> {
@@ -741,7 +890,13 @@ where
         Ok(block_num)
     }
 
-    async fn apply_transaction(
+    /// Saves a submitted transaction into the local store for tracking, updating the executing
+    /// account and the notes consumed and created by the transaction.
+    ///
+    /// The executing account must be tracked by the client. `submission_height` is the height
+    /// returned by [`Client::submit_proven_transaction`].
+    #[instrument(level = "debug", skip_all)]
+    pub async fn apply_transaction(
         &self,
         submission_height: BlockNumber,
         tx_result: TransactionResult,
@@ -781,12 +936,15 @@ where
             })
             .collect();
 
+        let request = tx_result.request().cloned();
+        let retry_of = tx_result.retry_of();
         let tx_update = TransactionStoreUpdate::new(
             tx_result.into(),
             submission_height,
             note_updates,
             new_tags,
-        );
+        )
+        .with_request(request, retry_of);
 
         self.store.apply_transaction(tx_update).await?;
         info!("Transaction stored.");
@@ -875,7 +1033,7 @@ where
 
         // New relevant input notes
         let mut new_input_notes = vec![];
-        let note_screener = NoteScreener::new(self.store.clone(), self.authenticator.clone());
+        let note_screener = self.note_screener();
 
         for note in notes_from_output(executed_tx.output_notes()) {
             // TODO: check_relevance() should have the option to take multiple notes
@@ -914,7 +1072,17 @@ where
         let consumed_note_ids =
             executed_tx.tx_inputs().input_notes().iter().map(InputNote::id).collect();
 
-        let consumed_notes = self.get_input_notes(NoteFilter::List(consumed_note_ids)).await?;
+        let mut consumed_notes = self.get_input_notes(NoteFilter::List(consumed_note_ids)).await?;
+
+        // Unauthenticated input notes are only tracked once the transaction is applied, so that
+        // executing a transaction doesn't change the store
+        for input_note in executed_tx.tx_inputs().input_notes().iter() {
+            if let InputNote::Unauthenticated { note } = input_note
+                && !consumed_notes.iter().any(|record| record.id() == note.id())
+            {
+                consumed_notes.push(note.clone().into());
+            }
+        }
 
         let mut updated_input_notes = vec![];
 
@@ -1362,7 +1530,7 @@ mod test {
 
     use super::PaymentNoteDescription;
     use crate::tests::create_test_client;
-    use crate::transaction::{TransactionRequestBuilder, TransactionResult};
+    use crate::transaction::{TransactionFile, TransactionRequestBuilder, TransactionResult};
 
     #[tokio::test]
     async fn transaction_creates_two_notes() {
@@ -1423,10 +1591,17 @@ mod test {
         // Prove and apply transaction
         Box::pin(client.testing_apply_transaction(tx_result.clone())).await.unwrap();
 
-        // Test serialization
+        // Test serialization. The result's own encoding doesn't include the request
         let bytes: std::vec::Vec<u8> = tx_result.to_bytes();
         let decoded = TransactionResult::read_from_bytes(&bytes).unwrap();
 
-        assert_eq!(tx_result, decoded);
+        assert_eq!(decoded.executed_transaction(), tx_result.executed_transaction());
+        assert_eq!(decoded.future_notes(), tx_result.future_notes());
+        assert!(decoded.request().is_none());
+
+        // Transaction files keep it, so that the transaction can be retried
+        assert!(tx_result.request().is_some());
+        let tx_file = TransactionFile::Unproven(tx_result.clone());
+        assert_eq!(TransactionFile::read_from_bytes(&tx_file.to_bytes()).unwrap(), tx_file);
     }
 }
@@ -0,0 +1,516 @@
+//! Provides the APIs to execute transactions of threshold multisig accounts, whose auth component
+//! requires the signatures of at least `threshold` of its approvers.
+//!
+//! Since the keys of the approvers are usually held by different parties, the signatures are
+//! collected in steps:
+//!
+//! 1. The proposer executes the transaction with [`Client::propose_multisig_transaction`], which
+//!    returns a [`MultisigSigningRequest`] with the summary of the transaction to be signed. The
+//!    request is signed with the approver keys the proposer has.
+//! 2. The request is serialized and handed to the other approvers, who sign it with
+//!    [`MultisigSigningRequest::sign`] using their own [`TransactionAuthenticator`] (for example, a
+//!    keystore or an external signer).
+//! 3. The signed requests are combined with [`MultisigSigningRequest::merge`], and once enough
+//!    signatures were collected the transaction is executed again with
+//!    [`Client::finalize_multisig_transaction`], which uses a [`MultisigAuthenticator`] to provide
+//!    them. The result is proven and submitted like any other transaction.
+//!
+//! ```ignore
+//! let mut signing_request = client.propose_multisig_transaction(account_id, request).await?;
+//!
+//! // On each co-signer's machine
+//! co_signer_request.sign(&co_signer_keystore).await?;
+//!
+//! signing_request.merge(co_signer_request)?;
+//! let tx_result = client.finalize_multisig_transaction(signing_request).await?;
+//! client.submit_transaction(tx_result).await?;
+//! ```
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use miden_lib::AuthScheme;
+use miden_lib::account::interface::AccountInterface;
+use miden_objects::account::{Account, AccountId};
+use miden_objects::transaction::TransactionSummary;
+use miden_objects::{Felt, Hasher, Word};
+use miden_tx::auth::SigningInputs;
+use miden_tx::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+use miden_tx::{AuthenticationError, TransactionExecutorError};
+use thiserror::Error;
+use tracing::info;
+
+use super::{TransactionAuthenticator, TransactionRequest, TransactionResult};
+use crate::{Client, ClientError};
+
+/// Magic bytes that identify a serialized [`MultisigSigningRequest`].
+const MAGIC: &[u8; 4] = b"msig";
+
+// MULTISIG ERROR
+// ================================================================================================
+
+/// Errors generated while collecting the signatures of a multisig transaction.
+#[derive(Debug, Error)]
+pub enum MultisigError {
+    #[error("account {0} doesn't have a multisig auth component")]
+    NotMultisigAccount(AccountId),
+    #[error("transaction of account {0} was authorized without signatures")]
+    SignaturesNotRequired(AccountId),
+    #[error("public key {0} isn't an approver of the account")]
+    UnknownApprover(Word),
+    #[error("signing requests are for different transactions")]
+    TransactionMismatch,
+    #[error("signing requests have different thresholds or approvers")]
+    ApproversMismatch,
+    #[error("transaction has {signatures} signatures but {threshold} are required")]
+    NotEnoughSignatures { threshold: u32, signatures: usize },
+    #[error("transaction changed since it was proposed, so it has to be proposed and signed again")]
+    TransactionChanged,
+    #[error("collected signatures weren't accepted by the account")]
+    InvalidSignatures,
+    #[error("failed to sign the transaction")]
+    SigningFailed(#[source] AuthenticationError),
+}
+
+// MULTISIG SIGNING REQUEST
+// ================================================================================================
+
+/// A transaction of a multisig account along with the signatures collected for it so far.
+///
+/// The signed message is the commitment to the [`TransactionSummary`], which covers the account
+/// delta, the input and output notes and the salt of the transaction. Executing the same request
+/// against the same account state produces the same summary, so the signatures remain valid until
+/// the account changes.
+#[derive(Clone, Debug)]
+pub struct MultisigSigningRequest {
+    account_id: AccountId,
+    transaction_request: TransactionRequest,
+    tx_summary: TransactionSummary,
+    threshold: u32,
+    approvers: Vec<Word>,
+    signatures: BTreeMap<Word, Vec<Felt>>,
+}
+
+impl MultisigSigningRequest {
+    /// Returns the ID of the account that executes the transaction.
+    pub fn account_id(&self) -> AccountId {
+        self.account_id
+    }
+
+    /// Returns the request the transaction is executed from.
+    pub fn transaction_request(&self) -> &TransactionRequest {
+        &self.transaction_request
+    }
+
+    /// Returns the summary of the transaction that the approvers sign.
+    pub fn tx_summary(&self) -> &TransactionSummary {
+        &self.tx_summary
+    }
+
+    /// Returns the message signed by the approvers.
+    pub fn message(&self) -> Word {
+        self.tx_summary.to_commitment()
+    }
+
+    /// Returns the number of signatures required to authorize the transaction.
+    pub fn threshold(&self) -> u32 {
+        self.threshold
+    }
+
+    /// Returns the public keys of the account's approvers.
+    pub fn approvers(&self) -> &[Word] {
+        &self.approvers
+    }
+
+    /// Returns the public keys of the approvers that already signed the transaction.
+    pub fn signers(&self) -> impl Iterator<Item = &Word> {
+        self.signatures.keys()
+    }
+
+    /// Returns whether enough signatures were collected to authorize the transaction.
+    pub fn is_complete(&self) -> bool {
+        self.signatures.len() >= self.threshold as usize
+    }
+
+    /// Signs the transaction with every approver key known to the authenticator that didn't sign
+    /// it yet, and returns the public keys that signed.
+    ///
+    /// Approvers whose keys the authenticator doesn't have are skipped.
+    pub async fn sign<A: TransactionAuthenticator>(
+        &mut self,
+        authenticator: &A,
+    ) -> Result<Vec<Word>, MultisigError> {
+        let signing_inputs = SigningInputs::TransactionSummary(Box::new(self.tx_summary.clone()));
+        let mut signed = Vec::new();
+
+        for pub_key in &self.approvers {
+            if self.signatures.contains_key(pub_key) {
+                continue;
+            }
+
+            match authenticator.get_signature(*pub_key, &signing_inputs).await {
+                Ok(signature) => {
+                    self.signatures.insert(*pub_key, signature);
+                    signed.push(*pub_key);
+                },
+                Err(AuthenticationError::UnknownPublicKey(_)) => {},
+                Err(err) => return Err(MultisigError::SigningFailed(err)),
+            }
+        }
+
+        Ok(signed)
+    }
+
+    /// Adds a signature produced by an approver outside of the client.
+    ///
+    /// The signature isn't verified here. Invalid signatures make the transaction fail when it's
+    /// finalized.
+    pub fn add_signature(
+        &mut self,
+        pub_key: Word,
+        signature: Vec<Felt>,
+    ) -> Result<(), MultisigError> {
+        if !self.approvers.contains(&pub_key) {
+            return Err(MultisigError::UnknownApprover(pub_key));
+        }
+
+        self.signatures.insert(pub_key, signature);
+        Ok(())
+    }
+
+    /// Adds the signatures collected in another request for the same transaction.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`MultisigError::TransactionMismatch`] if the requests are for different
+    ///   transactions.
+    /// - Returns [`MultisigError::ApproversMismatch`] if the requests have different thresholds or
+    ///   approvers.
+    /// - Returns [`MultisigError::UnknownApprover`] if the other request has a signature of a key
+    ///   that isn't an approver. No signature is added in that case.
+    pub fn merge(&mut self, other: MultisigSigningRequest) -> Result<(), MultisigError> {
+        if other.account_id != self.account_id || other.message() != self.message() {
+            return Err(MultisigError::TransactionMismatch);
+        }
+
+        if other.threshold != self.threshold || other.approvers != self.approvers {
+            return Err(MultisigError::ApproversMismatch);
+        }
+
+        if let Some(pub_key) = other.signatures.keys().find(|key| !self.approvers.contains(key)) {
+            return Err(MultisigError::UnknownApprover(*pub_key));
+        }
+
+        self.signatures.extend(other.signatures);
+        Ok(())
+    }
+
+    /// Returns the advice map entries from which the multisig auth procedure reads the
+    /// signatures, keyed by the hash of the public key and the message.
+    fn advice_map_entries(&self) -> impl Iterator<Item = (Word, Vec<Felt>)> + '_ {
+        let message = self.message();
+        self.signatures.iter().map(move |(pub_key, signature)| {
+            (Hasher::merge(&[*pub_key, message]), signature.clone())
+        })
+    }
+}
+
+impl Serializable for MultisigSigningRequest {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(MAGIC);
+        self.account_id.write_into(target);
+        self.transaction_request.write_into(target);
+        self.tx_summary.write_into(target);
+        self.message().write_into(target);
+        self.threshold.write_into(target);
+        self.approvers.write_into(target);
+        self.signatures.write_into(target);
+    }
+}
+
+impl Deserializable for MultisigSigningRequest {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let magic = source.read_array::<4>()?;
+        if &magic != MAGIC {
+            return Err(DeserializationError::InvalidValue(
+                "not a multisig signing request".to_string(),
+            ));
+        }
+
+        let account_id = AccountId::read_from(source)?;
+        let transaction_request = TransactionRequest::read_from(source)?;
+
+        // The summary is what approvers review, so it must match the message they sign
+        let tx_summary = TransactionSummary::read_from(source)?;
+        let message = Word::read_from(source)?;
+        if tx_summary.to_commitment() != message {
+            return Err(DeserializationError::InvalidValue(
+                "transaction summary doesn't match the signed message".to_string(),
+            ));
+        }
+        if tx_summary.account_delta().id() != account_id {
+            return Err(DeserializationError::InvalidValue(
+                "transaction summary is for a different account".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            account_id,
+            transaction_request,
+            tx_summary,
+            threshold: u32::read_from(source)?,
+            approvers: Vec::<Word>::read_from(source)?,
+            signatures: BTreeMap::<Word, Vec<Felt>>::read_from(source)?,
+        })
+    }
+}
+
+// MULTISIG AUTHENTICATOR
+// ================================================================================================
+
+/// A [`TransactionAuthenticator`] that provides the signatures collected in a
+/// [`MultisigSigningRequest`], and forwards the requests for any other message or key to an
+/// inner authenticator, if there's one.
+pub struct MultisigAuthenticator<'a, A> {
+    message: Word,
+    signatures: &'a BTreeMap<Word, Vec<Felt>>,
+    inner: Option<&'a A>,
+}
+
+impl<'a, A: TransactionAuthenticator + Sync> MultisigAuthenticator<'a, A> {
+    pub fn new(signing_request: &'a MultisigSigningRequest, inner: Option<&'a A>) -> Self {
+        Self {
+            message: signing_request.message(),
+            signatures: &signing_request.signatures,
+            inner,
+        }
+    }
+}
+
+impl<A: TransactionAuthenticator + Sync> TransactionAuthenticator for MultisigAuthenticator<'_, A> {
+    async fn get_signature(
+        &self,
+        pub_key: Word,
+        signing_inputs: &SigningInputs,
+    ) -> Result<Vec<Felt>, AuthenticationError> {
+        if signing_inputs.to_commitment() == self.message
+            && let Some(signature) = self.signatures.get(&pub_key)
+        {
+            return Ok(signature.clone());
+        }
+
+        match self.inner {
+            Some(inner) => inner.get_signature(pub_key, signing_inputs).await,
+            None => Err(AuthenticationError::UnknownPublicKey(pub_key.to_hex())),
+        }
+    }
+}
+
+// CLIENT METHODS
+// ================================================================================================
+
+impl<AUTH> Client<AUTH>
+where
+    AUTH: TransactionAuthenticator + Sync + 'static,
+{
+    /// Executes the transaction request against a multisig account to get the summary its
+    /// approvers have to sign, and returns it as a [`MultisigSigningRequest`] signed with the
+    /// approver keys known to the client's authenticator.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`MultisigError::NotMultisigAccount`] if the account doesn't have a multisig auth
+    ///   component.
+    /// - Returns [`MultisigError::SignaturesNotRequired`] if the transaction didn't need any
+    ///   signature to be authorized.
+    /// - Returns a [`ClientError::TransactionExecutorError`] if the execution fails for any other
+    ///   reason.
+    pub async fn propose_multisig_transaction(
+        &mut self,
+        account_id: AccountId,
+        transaction_request: TransactionRequest,
+    ) -> Result<MultisigSigningRequest, ClientError> {
+        let account: Account = self
+            .store
+            .get_account(account_id)
+            .await?
+            .ok_or(ClientError::AccountDataNotFound(account_id))?
+            .into();
+        let (threshold, approvers) =
+            multisig_approvers(&account).ok_or(MultisigError::NotMultisigAccount(account_id))?;
+
+        // Without signatures, the auth procedure rejects the transaction with its summary
+        let no_signatures: Option<&AUTH> = None;
+        let tx_summary = match self
+            .execute_transaction_request(account_id, transaction_request.clone(), no_signatures)
+            .await
+        {
+            Err(ClientError::TransactionExecutorError(TransactionExecutorError::Unauthorized(
+                tx_summary,
+            ))) => *tx_summary,
+            Ok(_) => return Err(MultisigError::SignaturesNotRequired(account_id).into()),
+            Err(err) => return Err(err),
+        };
+
+        let mut signing_request = MultisigSigningRequest {
+            account_id,
+            transaction_request,
+            tx_summary,
+            threshold,
+            approvers,
+            signatures: BTreeMap::new(),
+        };
+
+        if let Some(authenticator) = self.authenticator.as_deref() {
+            let signed = signing_request.sign(authenticator).await?;
+            info!("Proposed multisig transaction signed by {} local keys", signed.len());
+        }
+
+        Ok(signing_request)
+    }
+
+    /// Executes the transaction of a signing request that collected enough signatures, and returns
+    /// the result. As with [`Client::new_transaction`], the result still has to be submitted.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`MultisigError::NotEnoughSignatures`] if the request has less signatures than the
+    ///   threshold of the account.
+    /// - Returns [`MultisigError::TransactionChanged`] if executing the request no longer produces
+    ///   the signed summary, for example because the account state changed.
+    /// - Returns [`MultisigError::InvalidSignatures`] if the account rejected the signatures.
+    pub async fn finalize_multisig_transaction(
+        &mut self,
+        signing_request: MultisigSigningRequest,
+    ) -> Result<TransactionResult, ClientError> {
+        if !signing_request.is_complete() {
+            return Err(MultisigError::NotEnoughSignatures {
+                threshold: signing_request.threshold,
+                signatures: signing_request.signatures.len(),
+            }
+            .into());
+        }
+
+        let mut transaction_request = signing_request.transaction_request.clone();
+        transaction_request
+            .advice_map_mut()
+            .extend(signing_request.advice_map_entries());
+
+        let inner = self.authenticator.clone();
+        let authenticator = MultisigAuthenticator::new(&signing_request, inner.as_deref());
+
+        match self
+            .execute_transaction_request(
+                signing_request.account_id,
+                transaction_request,
+                Some(&authenticator),
+            )
+            .await
+        {
+            Err(ClientError::TransactionExecutorError(TransactionExecutorError::Unauthorized(
+                tx_summary,
+            ))) => {
+                if tx_summary.to_commitment() == signing_request.message() {
+                    Err(MultisigError::InvalidSignatures.into())
+                } else {
+                    Err(MultisigError::TransactionChanged.into())
+                }
+            },
+            result => result,
+        }
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns the threshold and the public keys of the approvers of the account, if it has a
+/// multisig auth component.
+pub(crate) fn multisig_approvers(account: &Account) -> Option<(u32, Vec<Word>)> {
+    let interface: AccountInterface = account.into();
+
+    interface.auth().iter().find_map(|auth| match auth {
+        AuthScheme::RpoFalcon512Multisig { threshold, pub_keys } => {
+            Some((*threshold, pub_keys.iter().map(|key| Word::from(*key)).collect()))
+        },
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeMap;
+    use alloc::vec::Vec;
+
+    use miden_objects::account::{AccountDelta, AccountId, AccountStorageDelta, AccountVaultDelta};
+    use miden_objects::testing::account_id::ACCOUNT_ID_REGULAR_PRIVATE_ACCOUNT_UPDATABLE_CODE;
+    use miden_objects::transaction::{InputNotes, OutputNotes, TransactionSummary};
+    use miden_objects::{Felt, ONE, Word, ZERO};
+
+    use super::{MultisigError, MultisigSigningRequest};
+    use crate::transaction::TransactionRequestBuilder;
+
+    fn signing_request(approvers: Vec<Word>) -> MultisigSigningRequest {
+        let account_id =
+            AccountId::try_from(ACCOUNT_ID_REGULAR_PRIVATE_ACCOUNT_UPDATABLE_CODE).unwrap();
+        let account_delta = AccountDelta::new(
+            account_id,
+            AccountStorageDelta::new(),
+            AccountVaultDelta::default(),
+            ONE,
+        )
+        .unwrap();
+        let tx_summary = TransactionSummary::new(
+            account_delta,
+            InputNotes::new(Vec::new()).unwrap(),
+            OutputNotes::new(Vec::new()).unwrap(),
+            Word::default(),
+        );
+
+        MultisigSigningRequest {
+            account_id,
+            transaction_request: TransactionRequestBuilder::new().build().unwrap(),
+            tx_summary,
+            threshold: 2,
+            approvers,
+            signatures: BTreeMap::new(),
+        }
+    }
+
+    fn key(value: u64) -> Word {
+        [Felt::new(value), ZERO, ZERO, ZERO].into()
+    }
+
+    #[test]
+    fn merge_rejects_signatures_of_non_approvers() {
+        let mut signing_request = signing_request(vec![key(1), key(2), key(3)]);
+        signing_request.add_signature(key(1), vec![ONE]).unwrap();
+
+        let mut other = signing_request.clone();
+        other.signatures.insert(key(2), vec![ONE]);
+        other.signatures.insert(key(4), vec![ONE]);
+
+        let result = signing_request.merge(other);
+        assert!(
+            matches!(result, Err(MultisigError::UnknownApprover(pub_key)) if pub_key == key(4))
+        );
+        assert_eq!(signing_request.signers().copied().collect::<Vec<_>>(), vec![key(1)]);
+    }
+
+    #[test]
+    fn merge_rejects_different_approvers() {
+        let mut signing_request = signing_request(vec![key(1), key(2), key(3)]);
+
+        let mut other = signing_request.clone();
+        other.threshold = 1;
+        assert!(matches!(signing_request.merge(other), Err(MultisigError::ApproversMismatch)));
+
+        let mut other = signing_request.clone();
+        other.approvers.push(key(4));
+        other.signatures.insert(key(4), vec![ONE]);
+        assert!(matches!(signing_request.merge(other), Err(MultisigError::ApproversMismatch)));
+        assert_eq!(signing_request.signers().count(), 0);
+    }
+}
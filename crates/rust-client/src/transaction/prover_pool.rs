@@ -0,0 +1,556 @@
+//! Provides [`ProverPool`], a [`TransactionProver`] that spreads proving across several remote
+//! provers and falls back to proving locally when none of them is available.
+
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::time::Duration;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use miden_remote_prover_client::remote_prover::tx_prover::RemoteTransactionProver;
+use miden_tx::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+use tracing::{info, warn};
+
+use super::{
+    LocalTransactionProver,
+    ProvenTransaction,
+    TransactionProver,
+    TransactionProverError,
+    TransactionWitness,
+};
+
+/// Default time a remote prover is given to prove a transaction.
+pub const DEFAULT_PROVER_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Default time a remote prover is skipped after it fails.
+pub const DEFAULT_PROVER_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+// PROVER SELECTION
+// ================================================================================================
+
+/// Order in which the remote provers of a [`ProverPool`] are tried.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProverSelection {
+    /// Each transaction starts with the prover after the one the previous transaction started
+    /// with.
+    #[default]
+    RoundRobin,
+    /// Provers are tried from the lowest to the highest average proving latency. Provers that
+    /// haven't proven a transaction yet are tried first so their latency gets measured.
+    LeastLatency,
+}
+
+// PROVER STATUS
+// ================================================================================================
+
+/// Snapshot of the state of a remote prover in a [`ProverPool`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProverStatus {
+    /// Endpoint of the prover.
+    pub endpoint: String,
+    /// Whether the prover is currently used. A prover is unhealthy for a while after it fails or
+    /// a health check can't reach it.
+    pub healthy: bool,
+    /// Average time the prover took to prove a transaction, if it proved any.
+    pub average_latency: Option<Duration>,
+    /// Number of transactions proven by the prover.
+    pub successes: u64,
+    /// Number of failed or timed out proving attempts.
+    pub failures: u64,
+}
+
+#[derive(Debug, Default)]
+struct ProverStats {
+    average_latency: Option<Duration>,
+    last_failure: Option<Instant>,
+    successes: u64,
+    failures: u64,
+}
+
+impl ProverStats {
+    fn is_healthy(&self, retry_after: Duration) -> bool {
+        self.last_failure.is_none_or(|failure| failure.elapsed() >= retry_after)
+    }
+
+    fn record_success(&mut self, latency: Duration) {
+        // Exponential moving average, so that the latest measurements weigh the most.
+        self.average_latency = Some(match self.average_latency {
+            Some(average) => (average * 3 + latency) / 4,
+            None => latency,
+        });
+        self.last_failure = None;
+        self.successes += 1;
+    }
+
+    fn record_failure(&mut self) {
+        self.last_failure = Some(Instant::now());
+        self.failures += 1;
+    }
+}
+
+#[derive(Debug)]
+struct PooledProver {
+    endpoint: String,
+    prover: RemoteTransactionProver,
+    /// Time the prover is given to prove a transaction, instead of the pool's timeout.
+    timeout: Option<Duration>,
+    stats: Mutex<ProverStats>,
+}
+
+impl PooledProver {
+    fn stats(&self) -> std::sync::MutexGuard<'_, ProverStats> {
+        self.stats.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+// POOL STATE
+// ================================================================================================
+
+/// State of a [`ProverPool`] that is kept in its state file, so that it outlives the process.
+///
+/// Instants can't be persisted, so failure times are stored as milliseconds since the Unix epoch.
+#[derive(Debug, Default)]
+struct PoolState {
+    next_prover: u64,
+    provers: Vec<SavedProverStats>,
+}
+
+#[derive(Debug)]
+struct SavedProverStats {
+    endpoint: String,
+    average_latency_ms: Option<u64>,
+    last_failure_ms: Option<u64>,
+    successes: u64,
+    failures: u64,
+}
+
+impl SavedProverStats {
+    fn new(endpoint: &str, stats: &ProverStats) -> Self {
+        let now = SystemTime::now();
+        let last_failure_ms = stats
+            .last_failure
+            .and_then(|failure| now.checked_sub(failure.elapsed()))
+            .map(unix_millis);
+
+        Self {
+            endpoint: endpoint.to_string(),
+            average_latency_ms: stats.average_latency.map(duration_millis),
+            last_failure_ms,
+            successes: stats.successes,
+            failures: stats.failures,
+        }
+    }
+
+    fn to_stats(&self) -> ProverStats {
+        let last_failure = self.last_failure_ms.and_then(|failure_ms| {
+            let since_failure =
+                Duration::from_millis(unix_millis(SystemTime::now()).saturating_sub(failure_ms));
+            Instant::now().checked_sub(since_failure)
+        });
+
+        ProverStats {
+            average_latency: self.average_latency_ms.map(Duration::from_millis),
+            last_failure,
+            successes: self.successes,
+            failures: self.failures,
+        }
+    }
+}
+
+impl Serializable for PoolState {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.next_prover.write_into(target);
+        self.provers.write_into(target);
+    }
+}
+
+impl Deserializable for PoolState {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let next_prover = u64::read_from(source)?;
+        let provers = Vec::<SavedProverStats>::read_from(source)?;
+
+        Ok(Self { next_prover, provers })
+    }
+}
+
+impl Serializable for SavedProverStats {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.endpoint.write_into(target);
+        self.average_latency_ms.write_into(target);
+        self.last_failure_ms.write_into(target);
+        self.successes.write_into(target);
+        self.failures.write_into(target);
+    }
+}
+
+impl Deserializable for SavedProverStats {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            endpoint: String::read_from(source)?,
+            average_latency_ms: Option::<u64>::read_from(source)?,
+            last_failure_ms: Option::<u64>::read_from(source)?,
+            successes: u64::read_from(source)?,
+            failures: u64::read_from(source)?,
+        })
+    }
+}
+
+// PROVER POOL
+// ================================================================================================
+
+/// A [`TransactionProver`] that holds several remote provers.
+///
+/// Each transaction is sent to the remote provers in the order given by the pool's
+/// [`ProverSelection`] until one of them proves it. Provers that fail or don't answer within the
+/// pool's timeout are skipped for a while. If every remote prover fails, the transaction is
+/// proven with a [`LocalTransactionProver`] unless local fallback was disabled.
+///
+/// The round-robin position and the stats of the provers are kept in memory, so they only last
+/// as long as the pool. Processes that create a new pool for each transaction, like the CLI, can
+/// keep them in a file with [`ProverPool::with_state_file`].
+#[derive(Debug)]
+pub struct ProverPool {
+    provers: Vec<PooledProver>,
+    selection: ProverSelection,
+    timeout: Duration,
+    retry_after: Duration,
+    local_prover: Option<LocalTransactionProver>,
+    next_prover: AtomicUsize,
+    state_file: Option<PathBuf>,
+}
+
+impl ProverPool {
+    /// Returns a new pool with a remote prover for each of the endpoints, round-robin selection,
+    /// default timeouts and local fallback enabled.
+    pub fn new<I, S>(endpoints: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let provers = endpoints
+            .into_iter()
+            .map(Into::into)
+            .map(|endpoint: String| PooledProver {
+                prover: RemoteTransactionProver::new(endpoint.clone()),
+                endpoint,
+                timeout: None,
+                stats: Mutex::new(ProverStats::default()),
+            })
+            .collect();
+
+        Self {
+            provers,
+            selection: ProverSelection::default(),
+            timeout: DEFAULT_PROVER_TIMEOUT,
+            retry_after: DEFAULT_PROVER_RETRY_AFTER,
+            local_prover: Some(LocalTransactionProver::default()),
+            next_prover: AtomicUsize::new(0),
+            state_file: None,
+        }
+    }
+
+    /// Sets the order in which the remote provers are tried.
+    #[must_use]
+    pub fn with_selection(mut self, selection: ProverSelection) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// Sets the time each remote prover is given to prove a transaction.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the time the remote prover with the given endpoint is given to prove a transaction,
+    /// instead of the pool's timeout.
+    #[must_use]
+    pub fn with_prover_timeout(mut self, endpoint: &str, timeout: Duration) -> Self {
+        for prover in self.provers.iter_mut().filter(|prover| prover.endpoint == endpoint) {
+            prover.timeout = Some(timeout);
+        }
+        self
+    }
+
+    /// Sets the time a remote prover is skipped after it fails.
+    #[must_use]
+    pub fn with_retry_after(mut self, retry_after: Duration) -> Self {
+        self.retry_after = retry_after;
+        self
+    }
+
+    /// Sets whether transactions are proven locally when every remote prover fails.
+    #[must_use]
+    pub fn with_local_fallback(mut self, local_fallback: bool) -> Self {
+        self.local_prover = local_fallback.then(LocalTransactionProver::default);
+        self
+    }
+
+    /// Keeps the round-robin position and the stats of the provers in the file at `path`, so that
+    /// they persist across pools.
+    ///
+    /// The state is read from the file, if it exists, and written back after every transaction
+    /// and health check. Stats of endpoints that are no longer in the pool are dropped. A file
+    /// that can't be read or written is only logged, since the pool works without it.
+    #[must_use]
+    pub fn with_state_file(mut self, path: PathBuf) -> Self {
+        match std::fs::read(&path) {
+            Ok(bytes) => match PoolState::read_from_bytes(&bytes) {
+                Ok(state) => self.restore_state(&state),
+                Err(err) => warn!("Ignoring invalid prover pool state {}: {err}", path.display()),
+            },
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {},
+            Err(err) => warn!("Failed to read prover pool state {}: {err}", path.display()),
+        }
+
+        self.state_file = Some(path);
+        self
+    }
+
+    /// Returns whether transactions are proven locally when every remote prover fails.
+    pub fn has_local_fallback(&self) -> bool {
+        self.local_prover.is_some()
+    }
+
+    /// Returns the order in which the remote provers are tried.
+    pub fn selection(&self) -> ProverSelection {
+        self.selection
+    }
+
+    /// Returns the current status of each remote prover.
+    pub fn status(&self) -> Vec<ProverStatus> {
+        self.provers.iter().map(|prover| self.prover_status(prover)).collect()
+    }
+
+    /// Checks whether each remote prover can be reached and returns the updated status of the
+    /// provers. Provers that can't be reached within the pool's timeout are marked as unhealthy,
+    /// and reachable provers are marked as healthy again.
+    pub async fn check_health(&self) -> Vec<ProverStatus> {
+        for prover in &self.provers {
+            if is_reachable(&prover.endpoint, self.prover_timeout(prover)).await {
+                prover.stats().last_failure = None;
+            } else {
+                warn!("Remote prover {} is unreachable", prover.endpoint);
+                prover.stats().record_failure();
+            }
+        }
+
+        self.save_state();
+        self.status()
+    }
+
+    /// Returns the indices of the remote provers in the order they should be tried. Unhealthy
+    /// provers are left out unless there is no local prover to fall back to.
+    fn candidates(&self) -> Vec<usize> {
+        let (mut healthy, unhealthy): (Vec<usize>, Vec<usize>) = (0..self.provers.len())
+            .partition(|&index| self.provers[index].stats().is_healthy(self.retry_after));
+
+        match self.selection {
+            ProverSelection::RoundRobin => {
+                if !healthy.is_empty() {
+                    let start = self.next_prover.fetch_add(1, Ordering::Relaxed) % healthy.len();
+                    healthy.rotate_left(start);
+                }
+            },
+            ProverSelection::LeastLatency => {
+                healthy.sort_by_key(|&index| self.provers[index].stats().average_latency);
+            },
+        }
+
+        if self.local_prover.is_none() {
+            healthy.extend(unhealthy);
+        }
+
+        healthy
+    }
+
+    fn prover_timeout(&self, prover: &PooledProver) -> Duration {
+        prover.timeout.unwrap_or(self.timeout)
+    }
+
+    fn restore_state(&self, state: &PoolState) {
+        self.next_prover
+            .store(usize::try_from(state.next_prover).unwrap_or_default(), Ordering::Relaxed);
+
+        for saved in &state.provers {
+            if let Some(prover) =
+                self.provers.iter().find(|prover| prover.endpoint == saved.endpoint)
+            {
+                *prover.stats() = saved.to_stats();
+            }
+        }
+    }
+
+    /// Writes the state of the pool to its state file, if it has one.
+    fn save_state(&self) {
+        let Some(path) = &self.state_file else {
+            return;
+        };
+
+        let state = PoolState {
+            next_prover: u64::try_from(self.next_prover.load(Ordering::Relaxed))
+                .unwrap_or_default(),
+            provers: self
+                .provers
+                .iter()
+                .map(|prover| SavedProverStats::new(&prover.endpoint, &prover.stats()))
+                .collect(),
+        };
+
+        if let Err(err) = std::fs::write(path, state.to_bytes()) {
+            warn!("Failed to write prover pool state {}: {err}", path.display());
+        }
+    }
+
+    fn prover_status(&self, prover: &PooledProver) -> ProverStatus {
+        let stats = prover.stats();
+        ProverStatus {
+            endpoint: prover.endpoint.clone(),
+            healthy: stats.is_healthy(self.retry_after),
+            average_latency: stats.average_latency,
+            successes: stats.successes,
+            failures: stats.failures,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl TransactionProver for ProverPool {
+    async fn prove(
+        &self,
+        witness: TransactionWitness,
+    ) -> Result<ProvenTransaction, TransactionProverError> {
+        let mut errors = Vec::new();
+
+        for index in self.candidates() {
+            let prover = &self.provers[index];
+            let timeout = self.prover_timeout(prover);
+            let started_at = Instant::now();
+
+            match tokio::time::timeout(timeout, prover.prover.prove(witness.clone())).await {
+                Ok(Ok(proven_transaction)) => {
+                    prover.stats().record_success(started_at.elapsed());
+                    self.save_state();
+                    return Ok(proven_transaction);
+                },
+                Ok(Err(err)) => {
+                    warn!("Remote prover {} failed: {err}", prover.endpoint);
+                    prover.stats().record_failure();
+                    errors.push(format!("{}: {err}", prover.endpoint));
+                },
+                Err(_) => {
+                    warn!("Remote prover {} timed out", prover.endpoint);
+                    prover.stats().record_failure();
+                    errors.push(format!("{}: timed out after {timeout:?}", prover.endpoint));
+                },
+            }
+        }
+        self.save_state();
+
+        match &self.local_prover {
+            Some(local_prover) => {
+                info!("No remote prover proved the transaction, proving it locally");
+                local_prover.prove(witness)
+            },
+            None if errors.is_empty() => {
+                Err(TransactionProverError::other("the prover pool has no remote provers"))
+            },
+            None => Err(TransactionProverError::other(format!(
+                "every remote prover failed ({})",
+                errors.join("; ")
+            ))),
+        }
+    }
+}
+
+fn duration_millis(duration: Duration) -> u64 {
+    duration.as_millis().try_into().unwrap_or(u64::MAX)
+}
+
+fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map(duration_millis).unwrap_or_default()
+}
+
+/// Returns whether a connection to the endpoint can be established within the timeout.
+async fn is_reachable(endpoint: &str, timeout: Duration) -> bool {
+    let Ok(endpoint) = tonic::transport::Endpoint::from_shared(endpoint.to_string()) else {
+        return false;
+    };
+    let Ok(endpoint) =
+        endpoint.tls_config(tonic::transport::ClientTlsConfig::new().with_native_roots())
+    else {
+        return false;
+    };
+
+    endpoint.connect_timeout(timeout).connect().await.is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+    use std::env::temp_dir;
+
+    use uuid::Uuid;
+
+    use super::{ProverPool, ProverSelection};
+
+    #[test]
+    fn round_robin_rotates_the_starting_prover() {
+        let pool = ProverPool::new(["http://prover-a", "http://prover-b", "http://prover-c"]);
+
+        assert_eq!(pool.candidates(), [0, 1, 2]);
+        assert_eq!(pool.candidates(), [1, 2, 0]);
+        assert_eq!(pool.candidates(), [2, 0, 1]);
+    }
+
+    #[test]
+    fn least_latency_prefers_unmeasured_then_fastest_provers() {
+        let pool = ProverPool::new(["http://prover-a", "http://prover-b", "http://prover-c"])
+            .with_selection(ProverSelection::LeastLatency);
+
+        pool.provers[0].stats().record_success(Duration::from_secs(5));
+        pool.provers[2].stats().record_success(Duration::from_secs(1));
+
+        assert_eq!(pool.candidates(), [1, 2, 0]);
+    }
+
+    #[test]
+    fn failed_provers_are_skipped_until_retry() {
+        let pool = ProverPool::new(["http://prover-a", "http://prover-b"]);
+        pool.provers[0].stats().record_failure();
+
+        assert_eq!(pool.candidates(), [1]);
+        assert!(!pool.status()[0].healthy);
+
+        // Without local fallback, unhealthy provers are still tried as a last resort.
+        let pool = pool.with_local_fallback(false);
+        assert_eq!(pool.candidates().last(), Some(&0));
+
+        let pool = pool.with_retry_after(Duration::ZERO);
+        assert!(pool.status()[0].healthy);
+    }
+
+    #[test]
+    fn state_file_keeps_stats_across_pools() {
+        let endpoints = ["http://prover-a", "http://prover-b", "http://prover-c"];
+        let state_file = temp_dir().join(Uuid::new_v4().to_string());
+
+        let pool = ProverPool::new(endpoints).with_state_file(state_file.clone());
+        assert_eq!(pool.candidates(), [0, 1, 2]);
+        pool.provers[1].stats().record_failure();
+        pool.provers[2].stats().record_success(Duration::from_secs(2));
+        pool.save_state();
+
+        // A new pool starts where the previous one left off
+        let pool = ProverPool::new(endpoints).with_state_file(state_file);
+        assert_eq!(pool.candidates(), [2, 0]);
+        let status = pool.status();
+        assert!(!status[1].healthy);
+        assert_eq!(status[2].average_latency, Some(Duration::from_secs(2)));
+        assert_eq!(status[2].successes, 1);
+    }
+}
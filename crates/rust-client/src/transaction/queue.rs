@@ -0,0 +1,483 @@
+//! Provides a per-account queue of transactions, so that several transactions can be submitted
+//! for the same account without waiting for each of them to be committed.
+//!
+//! Submitting a transaction updates the account in the local store as soon as the node accepts
+//! the transaction. Executing two transactions for the same account before submitting either of
+//! them makes both start from the same state, and the one that's included later gets discarded.
+//! The queue avoids that by executing each transaction only after the previous transaction of the
+//! same account was submitted, so that it builds on top of the state of the pending one.
+//!
+//! The queue is persisted in the client's store, along with the outcome of the most recently
+//! processed transactions.
+//!
+//! ```ignore
+//! for payout in payouts {
+//!     client.enqueue_transaction(account_id, payout).await?;
+//! }
+//!
+//! for queued in client.process_transaction_queue().await? {
+//!     println!("{}: {}", queued.id, queued.status);
+//! }
+//! ```
+
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt;
+
+use miden_objects::account::AccountId;
+use miden_objects::transaction::TransactionId;
+use miden_objects::utils::SliceReader;
+use miden_tx::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+use tracing::{info, warn};
+
+use super::{TransactionAuthenticator, TransactionProver, TransactionRequest};
+use crate::store::Store;
+use crate::{Client, ClientError};
+
+// QUEUED TRANSACTION
+// ================================================================================================
+
+/// Identifier assigned to a transaction when it's added to the queue.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct QueuedTransactionId(u64);
+
+impl QueuedTransactionId {
+    /// Returns the identifier as a number. Identifiers increase in the order the transactions
+    /// were enqueued.
+    pub fn as_u64(&self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Display for QueuedTransactionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+/// Status of a transaction in the queue.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum QueuedTransactionStatus {
+    /// The transaction is waiting to be executed. The position is the number of transactions of
+    /// the same account that will be executed before it.
+    Queued { position: usize },
+    /// The transaction was executed and submitted, and is now tracked as a pending transaction
+    /// with the given ID.
+    Submitted(TransactionId),
+    /// The transaction couldn't be executed, proven or submitted. The account state is left as
+    /// it was, so the following transactions of the account are still executed.
+    Failed(String),
+}
+
+impl fmt::Display for QueuedTransactionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueuedTransactionStatus::Queued { position } => write!(f, "Queued ({position})"),
+            QueuedTransactionStatus::Submitted(tx_id) => write!(f, "Submitted ({tx_id})"),
+            QueuedTransactionStatus::Failed(err) => write!(f, "Failed ({err})"),
+        }
+    }
+}
+
+/// A transaction that was added to the queue.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QueuedTransaction {
+    /// Identifier assigned to the transaction when it was enqueued.
+    pub id: QueuedTransactionId,
+    /// ID of the account that executes the transaction.
+    pub account_id: AccountId,
+    /// Current status of the transaction.
+    pub status: QueuedTransactionStatus,
+}
+
+// TRANSACTION QUEUE
+// ================================================================================================
+
+/// Name of the store setting that holds the identifier of the next enqueued transaction.
+const NEXT_ID_SETTING: &str = "transaction_queue/next_id";
+
+/// Prefix of the store settings that hold the queued transaction requests. Each request is kept
+/// under its own setting, named after the queue identifier and the account ID.
+const QUEUED_SETTING_PREFIX: &str = "transaction_queue/queued/";
+
+/// Prefix of the store settings that hold the outcomes of the processed transactions.
+const PROCESSED_SETTING_PREFIX: &str = "transaction_queue/processed/";
+
+/// Maximum number of processed transactions whose outcome is kept. Older outcomes are pruned
+/// when new transactions are processed.
+pub const MAX_PROCESSED_TRANSACTIONS: usize = 256;
+
+/// Transactions waiting to be executed, along with the outcome of the transactions that were
+/// already processed.
+///
+/// Every queued transaction and every outcome is persisted as a separate store setting, so that
+/// enqueuing or processing a transaction only writes the settings of that transaction. The
+/// identifiers in the setting names are zero-padded, so that listing the settings returns them in
+/// the order the transactions were enqueued.
+struct TransactionQueue<'store> {
+    store: &'store dyn Store,
+}
+
+impl<'store> TransactionQueue<'store> {
+    fn new(store: &'store dyn Store) -> Self {
+        Self { store }
+    }
+
+    /// Adds the request to the queue of the account and returns its queue identifier.
+    async fn push(
+        &self,
+        account_id: AccountId,
+        request: &TransactionRequest,
+    ) -> Result<QueuedTransactionId, ClientError> {
+        let id = match self.store.get_setting(NEXT_ID_SETTING.to_string()).await? {
+            Some(bytes) => QueuedTransactionId(u64::read_from_bytes(&bytes)?),
+            None => QueuedTransactionId(0),
+        };
+
+        self.store
+            .set_setting(queued_setting(id, account_id), request.to_bytes())
+            .await?;
+        self.store
+            .set_setting(NEXT_ID_SETTING.to_string(), (id.0 + 1).to_bytes())
+            .await?;
+
+        Ok(id)
+    }
+
+    /// Returns the identifiers of the queued transactions, grouped by account, in the order they
+    /// were enqueued.
+    async fn queued_ids(
+        &self,
+    ) -> Result<BTreeMap<AccountId, Vec<QueuedTransactionId>>, ClientError> {
+        let mut queued: BTreeMap<AccountId, Vec<QueuedTransactionId>> = BTreeMap::new();
+        for name in self.store.list_setting_names(QUEUED_SETTING_PREFIX.to_string()).await? {
+            let (id, account_id) = parse_queued_setting(&name)?;
+            queued.entry(account_id).or_default().push(id);
+        }
+
+        Ok(queued)
+    }
+
+    /// Returns the queued transactions, with their position in the queue of their account.
+    async fn queued(&self) -> Result<Vec<QueuedTransaction>, ClientError> {
+        Ok(self
+            .queued_ids()
+            .await?
+            .into_iter()
+            .flat_map(|(account_id, ids)| {
+                ids.into_iter().enumerate().map(move |(position, id)| QueuedTransaction {
+                    id,
+                    account_id,
+                    status: QueuedTransactionStatus::Queued { position },
+                })
+            })
+            .collect())
+    }
+
+    /// Returns the request of a queued transaction, or `None` if it's no longer queued.
+    async fn request(
+        &self,
+        id: QueuedTransactionId,
+        account_id: AccountId,
+    ) -> Result<Option<TransactionRequest>, ClientError> {
+        self.store
+            .get_setting(queued_setting(id, account_id))
+            .await?
+            .map(|bytes| TransactionRequest::read_from_bytes(&bytes))
+            .transpose()
+            .map_err(ClientError::from)
+    }
+
+    /// Removes a transaction from the queue.
+    async fn remove(
+        &self,
+        id: QueuedTransactionId,
+        account_id: AccountId,
+    ) -> Result<(), ClientError> {
+        self.store.remove_setting(queued_setting(id, account_id)).await?;
+        Ok(())
+    }
+
+    /// Returns the outcome of a processed transaction, or `None` if it wasn't processed or its
+    /// outcome was pruned.
+    async fn outcome(
+        &self,
+        id: QueuedTransactionId,
+    ) -> Result<Option<QueuedTransaction>, ClientError> {
+        self.store
+            .get_setting(processed_setting(id))
+            .await?
+            .map(|bytes| read_outcome(id, &bytes))
+            .transpose()
+    }
+
+    /// Returns the outcomes of the processed transactions, in the order they were enqueued.
+    async fn outcomes(&self) -> Result<Vec<QueuedTransaction>, ClientError> {
+        let mut outcomes = Vec::new();
+        for id in self.processed_ids().await? {
+            outcomes.extend(self.outcome(id).await?);
+        }
+
+        Ok(outcomes)
+    }
+
+    /// Records the outcome of a processed transaction, pruning the oldest outcomes past
+    /// [`MAX_PROCESSED_TRANSACTIONS`].
+    async fn record(&self, queued: &QueuedTransaction) -> Result<(), ClientError> {
+        let mut value = queued.account_id.to_bytes();
+        queued.status.write_into(&mut value);
+        self.store.set_setting(processed_setting(queued.id), value).await?;
+
+        let processed_ids = self.processed_ids().await?;
+        let excess = processed_ids.len().saturating_sub(MAX_PROCESSED_TRANSACTIONS);
+        for id in processed_ids.into_iter().take(excess) {
+            self.store.remove_setting(processed_setting(id)).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Forgets the outcomes of all processed transactions.
+    async fn clear_outcomes(&self) -> Result<(), ClientError> {
+        for id in self.processed_ids().await? {
+            self.store.remove_setting(processed_setting(id)).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn processed_ids(&self) -> Result<Vec<QueuedTransactionId>, ClientError> {
+        let names = self.store.list_setting_names(PROCESSED_SETTING_PREFIX.to_string()).await?;
+        Ok(names
+            .iter()
+            .map(|name| parse_processed_setting(name))
+            .collect::<Result<_, _>>()?)
+    }
+}
+
+fn queued_setting(id: QueuedTransactionId, account_id: AccountId) -> String {
+    format!("{QUEUED_SETTING_PREFIX}{:020}/{}", id.0, account_id.to_hex())
+}
+
+fn processed_setting(id: QueuedTransactionId) -> String {
+    format!("{PROCESSED_SETTING_PREFIX}{:020}", id.0)
+}
+
+fn parse_queued_setting(
+    name: &str,
+) -> Result<(QueuedTransactionId, AccountId), DeserializationError> {
+    let invalid_name =
+        || DeserializationError::InvalidValue(format!("invalid queued transaction setting {name}"));
+
+    let (id, account_id) = name
+        .strip_prefix(QUEUED_SETTING_PREFIX)
+        .and_then(|suffix| suffix.split_once('/'))
+        .ok_or_else(invalid_name)?;
+    let id = id.parse().map_err(|_| invalid_name())?;
+    let account_id = AccountId::from_hex(account_id).map_err(|_| invalid_name())?;
+
+    Ok((QueuedTransactionId(id), account_id))
+}
+
+fn parse_processed_setting(name: &str) -> Result<QueuedTransactionId, DeserializationError> {
+    name.strip_prefix(PROCESSED_SETTING_PREFIX)
+        .and_then(|id| id.parse().ok())
+        .map(QueuedTransactionId)
+        .ok_or_else(|| {
+            DeserializationError::InvalidValue(format!(
+                "invalid processed transaction setting {name}"
+            ))
+        })
+}
+
+fn read_outcome(id: QueuedTransactionId, bytes: &[u8]) -> Result<QueuedTransaction, ClientError> {
+    let mut source = SliceReader::new(bytes);
+    Ok(QueuedTransaction {
+        id,
+        account_id: AccountId::read_from(&mut source)?,
+        status: QueuedTransactionStatus::read_from(&mut source)?,
+    })
+}
+
+// CLIENT METHODS
+// ================================================================================================
+
+impl<AUTH> Client<AUTH>
+where
+    AUTH: TransactionAuthenticator + Sync + 'static,
+{
+    /// Adds a transaction to the queue of the account. The transaction isn't executed until
+    /// [`Client::process_transaction_queue`] is called.
+    ///
+    /// The queue is persisted in the store, so queued transactions survive restarts of the
+    /// client.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClientError::AccountDataNotFound`] if the account isn't tracked by the client.
+    pub async fn enqueue_transaction(
+        &mut self,
+        account_id: AccountId,
+        transaction_request: TransactionRequest,
+    ) -> Result<QueuedTransactionId, ClientError> {
+        if self.store.get_account_header(account_id).await?.is_none() {
+            return Err(ClientError::AccountDataNotFound(account_id));
+        }
+
+        let id = TransactionQueue::new(self.store.as_ref())
+            .push(account_id, &transaction_request)
+            .await?;
+        info!("Enqueued transaction {id} for account {account_id}");
+
+        Ok(id)
+    }
+
+    /// Executes, proves with the client's local prover and submits every queued transaction, and
+    /// returns the outcome of each of them.
+    ///
+    /// See [`Client::process_transaction_queue_with_prover`].
+    pub async fn process_transaction_queue(
+        &mut self,
+    ) -> Result<Vec<QueuedTransaction>, ClientError> {
+        self.process_transaction_queue_with_prover(self.tx_prover.clone()).await
+    }
+
+    /// Executes, proves with the provided prover and submits every queued transaction, and
+    /// returns the outcome of each of them.
+    ///
+    /// The transactions of each account are processed in the order they were enqueued, each one
+    /// against the account state left by the previous one. A transaction that fails doesn't stop
+    /// the following ones.
+    ///
+    /// Errors of individual transactions don't make this method fail. They're reported through
+    /// [`QueuedTransactionStatus::Failed`] instead.
+    ///
+    /// The queue is saved before each transaction is executed. If processing is interrupted, the
+    /// transaction that was being processed is reported as failed instead of being executed
+    /// again, since it may have been submitted already.
+    pub async fn process_transaction_queue_with_prover(
+        &mut self,
+        tx_prover: Arc<dyn TransactionProver>,
+    ) -> Result<Vec<QueuedTransaction>, ClientError> {
+        let store = self.store.clone();
+        let queue = TransactionQueue::new(store.as_ref());
+        let mut processed = Vec::new();
+
+        for (account_id, ids) in queue.queued_ids().await? {
+            for id in ids {
+                let Some(request) = queue.request(id, account_id).await? else {
+                    continue;
+                };
+
+                queue
+                    .record(&QueuedTransaction {
+                        id,
+                        account_id,
+                        status: QueuedTransactionStatus::Failed(
+                            "processing was interrupted".to_string(),
+                        ),
+                    })
+                    .await?;
+                queue.remove(id, account_id).await?;
+
+                let status =
+                    match self.execute_and_submit(account_id, request, tx_prover.clone()).await {
+                        Ok(transaction_id) => QueuedTransactionStatus::Submitted(transaction_id),
+                        Err(err) => {
+                            warn!("Queued transaction {id} failed: {err}");
+                            QueuedTransactionStatus::Failed(err.to_string())
+                        },
+                    };
+
+                let queued = QueuedTransaction { id, account_id, status };
+                queue.record(&queued).await?;
+                processed.push(queued);
+            }
+        }
+
+        Ok(processed)
+    }
+
+    /// Returns the queued transactions followed by the transactions that were already processed,
+    /// in the order they were enqueued.
+    ///
+    /// Only the outcomes of the last [`MAX_PROCESSED_TRANSACTIONS`] processed transactions are
+    /// kept.
+    pub async fn transaction_queue_status(&self) -> Result<Vec<QueuedTransaction>, ClientError> {
+        let queue = TransactionQueue::new(self.store.as_ref());
+        let mut transactions = queue.queued().await?;
+        transactions.extend(queue.outcomes().await?);
+        transactions.sort_by_key(|queued| queued.id);
+        Ok(transactions)
+    }
+
+    /// Returns the transaction with the given queue identifier, if it's still queued or its
+    /// outcome wasn't pruned yet.
+    pub async fn get_queued_transaction(
+        &self,
+        id: QueuedTransactionId,
+    ) -> Result<Option<QueuedTransaction>, ClientError> {
+        let queue = TransactionQueue::new(self.store.as_ref());
+        if let Some(outcome) = queue.outcome(id).await? {
+            return Ok(Some(outcome));
+        }
+
+        Ok(queue.queued().await?.into_iter().find(|queued| queued.id == id))
+    }
+
+    /// Forgets the outcome of the transactions that were already processed. Transactions that
+    /// are still queued are kept.
+    pub async fn clear_processed_transactions(&mut self) -> Result<(), ClientError> {
+        TransactionQueue::new(self.store.as_ref()).clear_outcomes().await
+    }
+
+    async fn execute_and_submit(
+        &mut self,
+        account_id: AccountId,
+        transaction_request: TransactionRequest,
+        tx_prover: Arc<dyn TransactionProver>,
+    ) -> Result<TransactionId, ClientError> {
+        let tx_result = self.new_transaction(account_id, transaction_request).await?;
+        let transaction_id = tx_result.executed_transaction().id();
+        self.submit_transaction_with_prover(tx_result, tx_prover).await?;
+
+        Ok(transaction_id)
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for QueuedTransactionStatus {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            QueuedTransactionStatus::Queued { position } => {
+                target.write_u8(0);
+                target.write_usize(*position);
+            },
+            QueuedTransactionStatus::Submitted(tx_id) => {
+                target.write_u8(1);
+                tx_id.write_into(target);
+            },
+            QueuedTransactionStatus::Failed(err) => {
+                target.write_u8(2);
+                err.write_into(target);
+            },
+        }
+    }
+}
+
+impl Deserializable for QueuedTransactionStatus {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(QueuedTransactionStatus::Queued { position: source.read_usize()? }),
+            1 => Ok(QueuedTransactionStatus::Submitted(TransactionId::read_from(source)?)),
+            2 => Ok(QueuedTransactionStatus::Failed(String::read_from(source)?)),
+            variant => Err(DeserializationError::InvalidValue(format!(
+                "invalid queued transaction status {variant}"
+            ))),
+        }
+    }
+}
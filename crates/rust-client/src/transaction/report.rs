@@ -0,0 +1,569 @@
+//! Provides [`TransactionReport`], a structured description of the effects of an executed
+//! transaction, and [`Client::simulate_transaction`], which executes a transaction only to
+//! describe it.
+//!
+//! With the `serde` feature, reports can be serialized. Identifiers and words are serialized as
+//! hex strings and block numbers as integers.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use miden_lib::account::faucets::BasicFungibleFaucet;
+use miden_lib::note::well_known_note::WellKnownNote;
+use miden_objects::account::{
+    Account,
+    AccountId,
+    AccountIdPrefix,
+    AccountStorageDelta,
+    AccountVaultDelta,
+};
+use miden_objects::asset::{Asset, FungibleAsset, NonFungibleDeltaAction};
+use miden_objects::block::BlockNumber;
+use miden_objects::note::{Note, NoteId, NoteTag, NoteType};
+use miden_objects::transaction::{OutputNote, TransactionId};
+use miden_objects::{Felt, Word};
+
+use super::{TransactionAuthenticator, TransactionRequest, TransactionResult};
+use crate::{Client, ClientError};
+
+// TRANSACTION REPORT
+// ================================================================================================
+
+/// Effects of an executed transaction.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TransactionReport {
+    /// ID of the transaction.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serde_impl::display"))]
+    pub transaction_id: TransactionId,
+    /// ID of the account that executes the transaction.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serde_impl::display"))]
+    pub account_id: AccountId,
+    /// Block against which the transaction was executed.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serde_impl::block_number"))]
+    pub block_num: BlockNumber,
+    /// Block after which the transaction can no longer be included in a block.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serde_impl::block_number"))]
+    pub expiration_block_num: BlockNumber,
+    /// Notes consumed by the transaction.
+    pub consumed_notes: Vec<NoteReport>,
+    /// Notes created by the transaction.
+    pub created_notes: Vec<NoteReport>,
+    /// Assets added to or removed from the account vault.
+    pub vault_changes: Vec<VaultChange>,
+    /// Storage slots and storage map entries updated by the transaction.
+    pub storage_changes: Vec<StorageChange>,
+    /// Amount the account nonce is incremented by.
+    pub nonce_delta: u64,
+    /// Number of VM cycles spent in each stage of the transaction.
+    pub cycles: CycleCounts,
+}
+
+impl TransactionReport {
+    /// Returns the report of an executed transaction. Token symbols and decimals are left
+    /// empty, see [`TransactionReport::set_token_info`].
+    pub fn new(tx_result: &TransactionResult) -> Self {
+        let executed_transaction = tx_result.executed_transaction();
+        let account_delta = executed_transaction.account_delta();
+        let consumed_notes = executed_transaction
+            .input_notes()
+            .iter()
+            .map(|input_note| NoteReport::from_note(input_note.note()))
+            .collect();
+
+        let created_notes =
+            tx_result.created_notes().iter().map(NoteReport::from_output_note).collect();
+
+        let vault_changes = VaultChange::from_delta(account_delta.vault());
+        let storage_changes = StorageChange::from_delta(account_delta.storage());
+
+        let measurements = executed_transaction.measurements();
+        let cycles = CycleCounts {
+            prologue: measurements.prologue,
+            notes_processing: measurements.notes_processing,
+            tx_script_processing: measurements.tx_script_processing,
+            epilogue: measurements.epilogue,
+            auth_procedure: measurements.auth_procedure,
+            total: measurements.total_cycles(),
+        };
+
+        Self {
+            transaction_id: executed_transaction.id(),
+            account_id: executed_transaction.account_id(),
+            block_num: executed_transaction.block_header().block_num(),
+            expiration_block_num: executed_transaction.expiration_block_num(),
+            consumed_notes,
+            created_notes,
+            vault_changes,
+            storage_changes,
+            nonce_delta: account_delta.nonce_delta().as_int(),
+            cycles,
+        }
+    }
+
+    /// Fills in the token symbol and decimals of the fungible assets issued by the given
+    /// faucets.
+    pub fn set_token_info(&mut self, faucets: &BTreeMap<AccountId, TokenInfo>) {
+        for asset in self.assets_mut() {
+            if let AssetReport::Fungible { faucet_id, symbol, decimals, .. } = asset
+                && let Some(token) = faucets.get(faucet_id)
+            {
+                *symbol = Some(token.symbol.clone());
+                *decimals = Some(token.decimals);
+            }
+        }
+    }
+
+    /// Returns the IDs of the faucets that issued the fungible assets in the report.
+    pub fn fungible_faucet_ids(&self) -> Vec<AccountId> {
+        let requested_assets =
+            self.consumed_notes.iter().chain(&self.created_notes).filter_map(|note| {
+                match &note.script {
+                    NoteScriptKind::Swap { requested_asset } => requested_asset.as_ref(),
+                    _ => None,
+                }
+            });
+
+        let mut faucet_ids: Vec<AccountId> = self
+            .consumed_notes
+            .iter()
+            .chain(&self.created_notes)
+            .flat_map(|note| note.assets.iter())
+            .chain(requested_assets)
+            .chain(self.vault_changes.iter().map(|change| &change.asset))
+            .filter_map(|asset| match asset {
+                AssetReport::Fungible { faucet_id, .. } => Some(*faucet_id),
+                AssetReport::NonFungible { .. } => None,
+            })
+            .collect();
+        faucet_ids.sort();
+        faucet_ids.dedup();
+        faucet_ids
+    }
+
+    /// Names the notes with the given script roots, for scripts that aren't well known to the
+    /// client. Notes whose script is already decoded are left as they are.
+    pub fn name_note_scripts<'a, I>(&mut self, scripts: I)
+    where
+        I: IntoIterator<Item = (Word, &'a str)>,
+    {
+        let no_inputs: NoteInputsDecoder = |_| None;
+        self.decode_note_scripts(scripts.into_iter().map(|(root, name)| (root, name, no_inputs)));
+    }
+
+    /// Names the notes with the given script roots and decodes their inputs with the given
+    /// decoders, for scripts that aren't well known to the client. Notes whose script is already
+    /// decoded are left as they are, and notes whose inputs can't be decoded are only named.
+    pub fn decode_note_scripts<'a, I>(&mut self, scripts: I)
+    where
+        I: IntoIterator<Item = (Word, &'a str, NoteInputsDecoder)>,
+    {
+        let scripts: Vec<(Word, &str, NoteInputsDecoder)> = scripts.into_iter().collect();
+
+        for note in self.consumed_notes.iter_mut().chain(self.created_notes.iter_mut()) {
+            if let (NoteScriptKind::Unknown, Some(root)) = (&note.script, note.script_root)
+                && let Some((_, name, decode)) =
+                    scripts.iter().find(|(script_root, ..)| *script_root == root)
+            {
+                let decoded_inputs = note.inputs.as_deref().and_then(decode).unwrap_or_default();
+                note.script = NoteScriptKind::Named { name: (*name).into(), decoded_inputs };
+            }
+        }
+    }
+
+    fn assets_mut(&mut self) -> impl Iterator<Item = &mut AssetReport> {
+        let note_assets = self
+            .consumed_notes
+            .iter_mut()
+            .chain(self.created_notes.iter_mut())
+            .flat_map(|note| {
+                let requested_asset = match &mut note.script {
+                    NoteScriptKind::Swap { requested_asset } => requested_asset.as_mut(),
+                    _ => None,
+                };
+                note.assets.iter_mut().chain(requested_asset)
+            });
+
+        note_assets.chain(self.vault_changes.iter_mut().map(|change| &mut change.asset))
+    }
+}
+
+/// Decodes the inputs of a note script that isn't well known to the client into named values.
+/// Returns `None` if the inputs don't have the layout the script expects.
+pub type NoteInputsDecoder = fn(&[Felt]) -> Option<BTreeMap<String, String>>;
+
+/// Token symbol and decimals of a fungible faucet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TokenInfo {
+    /// Symbol of the token.
+    pub symbol: String,
+    /// Number of decimals of the token.
+    pub decimals: u8,
+}
+
+impl TokenInfo {
+    /// Returns the token information of a basic fungible faucet account, or `None` if the
+    /// account isn't one.
+    pub fn from_faucet(account: Account) -> Option<Self> {
+        let faucet = BasicFungibleFaucet::try_from(account).ok()?;
+        let symbol = faucet.symbol().to_string().ok()?;
+
+        Some(Self { symbol, decimals: faucet.decimals() })
+    }
+}
+
+// NOTE REPORT
+// ================================================================================================
+
+/// A note consumed or created by a transaction.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct NoteReport {
+    /// ID of the note.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serde_impl::display"))]
+    pub id: NoteId,
+    /// Whether the note is public or private.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serde_impl::display"))]
+    pub note_type: NoteType,
+    /// Tag of the note.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serde_impl::note_tag"))]
+    pub tag: NoteTag,
+    /// Root of the note script. Unknown for created notes whose details aren't known.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serde_impl::option_word"))]
+    pub script_root: Option<Word>,
+    /// Inputs of the note. Unknown for created notes whose details aren't known.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serde_impl::option_felts"))]
+    pub inputs: Option<Vec<Felt>>,
+    /// Decoded note script.
+    pub script: NoteScriptKind,
+    /// Assets held by the note. Empty for created notes whose assets aren't known.
+    pub assets: Vec<AssetReport>,
+}
+
+impl NoteReport {
+    fn from_note(note: &Note) -> Self {
+        Self {
+            id: note.id(),
+            note_type: note.metadata().note_type(),
+            tag: note.metadata().tag(),
+            script_root: Some(note.script().root()),
+            inputs: Some(note.inputs().values().to_vec()),
+            script: NoteScriptKind::decode(note),
+            assets: note.assets().iter().map(AssetReport::from).collect(),
+        }
+    }
+
+    /// Returns the report of a note created by a transaction. The script, inputs and assets are
+    /// only known for notes whose details are included in the output note.
+    pub fn from_output_note(output_note: &OutputNote) -> Self {
+        if let OutputNote::Full(note) = output_note {
+            return Self::from_note(note);
+        }
+
+        Self {
+            id: output_note.id(),
+            note_type: output_note.metadata().note_type(),
+            tag: output_note.metadata().tag(),
+            script_root: None,
+            inputs: None,
+            script: NoteScriptKind::Unknown,
+            assets: output_note
+                .assets()
+                .map(|assets| assets.iter().map(AssetReport::from).collect())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Script of a note, decoded for the scripts that are well known to the client.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum NoteScriptKind {
+    /// Pay-to-ID note that can only be consumed by the target account.
+    P2id {
+        #[cfg_attr(feature = "serde", serde(serialize_with = "serde_impl::display"))]
+        target: AccountId,
+    },
+    /// Pay-to-ID note that can be reclaimed by the sender after the reclaim height, and that
+    /// can't be consumed before the timelock height. Heights of 0 mean the note has no reclaim
+    /// height or timelock.
+    P2ide {
+        #[cfg_attr(feature = "serde", serde(serialize_with = "serde_impl::display"))]
+        target: AccountId,
+        reclaim_height: u32,
+        timelock_height: u32,
+    },
+    /// Swap note that gives its assets in exchange for the requested asset.
+    Swap { requested_asset: Option<AssetReport> },
+    /// Note whose script was named with [`TransactionReport::name_note_scripts`] or
+    /// [`TransactionReport::decode_note_scripts`], along with its decoded inputs, if any.
+    Named {
+        name: String,
+        decoded_inputs: BTreeMap<String, String>,
+    },
+    /// Note whose script isn't known.
+    Unknown,
+}
+
+impl NoteScriptKind {
+    fn decode(note: &Note) -> Self {
+        let root = note.script().root();
+        let inputs = note.inputs().values();
+
+        if root == WellKnownNote::P2ID.script_root() && inputs.len() == 2 {
+            if let Ok(target) = AccountId::try_from([inputs[1], inputs[0]]) {
+                return NoteScriptKind::P2id { target };
+            }
+        } else if root == WellKnownNote::P2IDE.script_root() && inputs.len() == 4 {
+            if let (Ok(target), Ok(reclaim_height), Ok(timelock_height)) = (
+                AccountId::try_from([inputs[1], inputs[0]]),
+                u32::try_from(inputs[2].as_int()),
+                u32::try_from(inputs[3].as_int()),
+            ) {
+                return NoteScriptKind::P2ide { target, reclaim_height, timelock_height };
+            }
+        } else if root == WellKnownNote::SWAP.script_root() {
+            // The requested asset follows the payback note's recipient in the note inputs
+            let requested_asset = inputs
+                .get(4..8)
+                .and_then(|elements| Word::try_from(elements).ok())
+                .and_then(|word| Asset::try_from(word).ok())
+                .map(|asset| AssetReport::from(&asset));
+            return NoteScriptKind::Swap { requested_asset };
+        }
+
+        NoteScriptKind::Unknown
+    }
+}
+
+// ASSET AND STORAGE CHANGES
+// ================================================================================================
+
+/// An asset held by a note or moved in or out of the account vault.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum AssetReport {
+    /// A fungible asset. The token symbol and decimals are known for faucets tracked by the
+    /// client.
+    Fungible {
+        #[cfg_attr(feature = "serde", serde(serialize_with = "serde_impl::display"))]
+        faucet_id: AccountId,
+        amount: u64,
+        symbol: Option<String>,
+        decimals: Option<u8>,
+    },
+    /// A non-fungible asset.
+    NonFungible {
+        #[cfg_attr(feature = "serde", serde(serialize_with = "serde_impl::display"))]
+        faucet_id_prefix: AccountIdPrefix,
+        #[cfg_attr(feature = "serde", serde(serialize_with = "serde_impl::word"))]
+        asset: Word,
+    },
+}
+
+impl From<&Asset> for AssetReport {
+    fn from(asset: &Asset) -> Self {
+        match asset {
+            Asset::Fungible(fungible_asset) => AssetReport::Fungible {
+                faucet_id: fungible_asset.faucet_id(),
+                amount: fungible_asset.amount(),
+                symbol: None,
+                decimals: None,
+            },
+            Asset::NonFungible(non_fungible_asset) => AssetReport::NonFungible {
+                faucet_id_prefix: non_fungible_asset.faucet_id_prefix(),
+                asset: (*asset).into(),
+            },
+        }
+    }
+}
+
+/// An asset added to or removed from the account vault.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct VaultChange {
+    /// The asset that was moved.
+    pub asset: AssetReport,
+    /// Whether the asset was added to the vault, or removed from it.
+    pub added: bool,
+}
+
+impl VaultChange {
+    /// Returns the changes described by the vault delta of an account.
+    pub fn from_delta(delta: &AccountVaultDelta) -> Vec<Self> {
+        let mut changes: Vec<Self> = delta
+            .fungible()
+            .iter()
+            .filter_map(|(faucet_id, amount)| {
+                let asset = FungibleAsset::new(*faucet_id, amount.unsigned_abs()).ok()?;
+                Some(Self {
+                    asset: AssetReport::from(&Asset::from(asset)),
+                    added: amount.is_positive(),
+                })
+            })
+            .collect();
+        changes.extend(delta.non_fungible().iter().map(|(asset, action)| Self {
+            asset: AssetReport::from(&Asset::from(*asset)),
+            added: matches!(action, NonFungibleDeltaAction::Add),
+        }));
+
+        changes
+    }
+}
+
+/// An update to the account storage.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum StorageChange {
+    /// A value slot was set to a new value.
+    Value {
+        slot: u8,
+        #[cfg_attr(feature = "serde", serde(serialize_with = "serde_impl::word"))]
+        value: Word,
+    },
+    /// An entry of a map slot was set to a new value.
+    MapEntry {
+        slot: u8,
+        #[cfg_attr(feature = "serde", serde(serialize_with = "serde_impl::word"))]
+        key: Word,
+        #[cfg_attr(feature = "serde", serde(serialize_with = "serde_impl::word"))]
+        value: Word,
+    },
+}
+
+impl StorageChange {
+    /// Returns the changes described by the storage delta of an account.
+    pub fn from_delta(delta: &AccountStorageDelta) -> Vec<Self> {
+        let mut changes: Vec<Self> = delta
+            .values()
+            .iter()
+            .map(|(slot, value)| Self::Value { slot: *slot, value: *value })
+            .collect();
+        for (slot, map_delta) in delta.maps() {
+            changes.extend(map_delta.entries().iter().map(|(key, value)| Self::MapEntry {
+                slot: *slot,
+                key: (*key).into(),
+                value: *value,
+            }));
+        }
+
+        changes
+    }
+}
+
+/// Number of VM cycles spent in each stage of a transaction.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CycleCounts {
+    /// Cycles spent in the transaction prologue.
+    pub prologue: usize,
+    /// Cycles spent processing the input notes.
+    pub notes_processing: usize,
+    /// Cycles spent executing the transaction script.
+    pub tx_script_processing: usize,
+    /// Cycles spent in the transaction epilogue.
+    pub epilogue: usize,
+    /// Cycles spent in the account's authentication procedure, as part of the epilogue.
+    pub auth_procedure: usize,
+    /// Total number of cycles of the transaction.
+    pub total: usize,
+}
+
+// CLIENT METHODS
+// ================================================================================================
+
+impl<AUTH> Client<AUTH>
+where
+    AUTH: TransactionAuthenticator + Sync + 'static,
+{
+    /// Executes a transaction without proving or submitting it, and returns a report of its
+    /// effects.
+    ///
+    /// As with [`Client::new_transaction`], the client state isn't changed. In particular, the
+    /// request's unauthenticated input notes aren't tracked.
+    pub async fn simulate_transaction(
+        &mut self,
+        account_id: AccountId,
+        transaction_request: TransactionRequest,
+    ) -> Result<TransactionReport, ClientError> {
+        let tx_result = self.new_transaction(account_id, transaction_request).await?;
+        self.transaction_report(&tx_result).await
+    }
+
+    /// Returns a report of the effects of an executed transaction. The token symbol and
+    /// decimals of the assets are filled in for faucets tracked by the client.
+    pub async fn transaction_report(
+        &self,
+        tx_result: &TransactionResult,
+    ) -> Result<TransactionReport, ClientError> {
+        let mut report = TransactionReport::new(tx_result);
+
+        let mut faucets = BTreeMap::new();
+        for faucet_id in report.fungible_faucet_ids() {
+            if let Some(record) = self.store.get_account(faucet_id).await?
+                && let Some(token) = TokenInfo::from_faucet(record.into())
+            {
+                faucets.insert(faucet_id, token);
+            }
+        }
+        report.set_token_info(&faucets);
+
+        Ok(report)
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use core::fmt::Display;
+
+    use miden_objects::block::BlockNumber;
+    use miden_objects::note::NoteTag;
+    use miden_objects::{Felt, Word};
+    use serde::Serializer;
+
+    pub fn display<T: Display, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn block_number<S: Serializer>(
+        block_num: &BlockNumber,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(block_num.as_u32())
+    }
+
+    pub fn word<S: Serializer>(word: &Word, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&word.to_hex())
+    }
+
+    pub fn option_word<S: Serializer>(
+        word: &Option<Word>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match word {
+            Some(word) => serializer.serialize_str(&word.to_hex()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn note_tag<S: Serializer>(tag: &NoteTag, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(tag.as_u32())
+    }
+
+    pub fn option_felts<S: Serializer>(
+        felts: &Option<Vec<Felt>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match felts {
+            Some(felts) => serializer.collect_seq(felts.iter().map(Felt::as_int)),
+            None => serializer.serialize_none(),
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! Provides the APIs to retry transactions that were discarded by the network.
+//!
+//! Every transaction created through [`Client::new_transaction`] keeps the
+//! [`TransactionRequest`](super::TransactionRequest) it was executed from. When a sync discards
+//! such a transaction because it went stale, expired or was built on top of another discarded
+//! transaction, the request can be executed again against the current state of the account. The
+//! new transaction is linked to the discarded one through [`TransactionRecord::retry_of`].
+//!
+//! Transactions discarded because one of their input notes was consumed by another transaction
+//! are never retried, as executing them again could spend funds twice.
+
+use alloc::collections::BTreeMap;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use miden_objects::transaction::TransactionId;
+use tracing::{info, warn};
+
+use super::{
+    DiscardCause,
+    TransactionAuthenticator,
+    TransactionRecord,
+    TransactionResult,
+    TransactionStatus,
+};
+use crate::store::TransactionFilter;
+use crate::{Client, ClientError};
+
+/// Default maximum number of times a discarded transaction is retried.
+pub const DEFAULT_MAX_TRANSACTION_RETRIES: u32 = 3;
+
+// RETRY POLICY
+// ================================================================================================
+
+/// Policy used by [`Client::retry_discarded_transactions`] to retry discarded transactions.
+///
+/// It's set with [`Client::set_transaction_retry_policy`]. Syncing never retries transactions on
+/// its own, so that funds are only spent when asked to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransactionRetryPolicy {
+    /// Maximum number of times a transaction is retried. A retry that gets discarded counts
+    /// towards the limit of the transaction it retries.
+    pub max_retries: u32,
+}
+
+impl Default for TransactionRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: DEFAULT_MAX_TRANSACTION_RETRIES,
+        }
+    }
+}
+
+impl DiscardCause {
+    /// Returns whether a transaction discarded for this cause can be safely executed again.
+    ///
+    /// Transactions whose input notes were consumed by other transactions can't be retried.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            DiscardCause::Expired | DiscardCause::Stale | DiscardCause::DiscardedInitialState => {
+                true
+            },
+            DiscardCause::InputConsumed => false,
+        }
+    }
+}
+
+// CLIENT METHODS
+// ================================================================================================
+
+impl<AUTH> Client<AUTH>
+where
+    AUTH: TransactionAuthenticator + Sync + 'static,
+{
+    /// Executes the request of a discarded transaction again against the current state of the
+    /// client and returns the result, linked to the discarded transaction.
+    ///
+    /// As with [`Client::new_transaction`], the result still has to be submitted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClientError::TransactionNotRetryable`] if the transaction isn't tracked, wasn't
+    /// discarded for a retryable [`DiscardCause`], has no stored request or was already retried.
+    pub async fn retry_transaction(
+        &mut self,
+        transaction_id: TransactionId,
+    ) -> Result<TransactionResult, ClientError> {
+        let transactions = self.store.get_transactions(TransactionFilter::All).await?;
+        let record = check_retryable(&transactions, transaction_id)?;
+
+        let account_id = record.details.account_id;
+        let request = record.request.clone().ok_or_else(|| {
+            ClientError::TransactionNotRetryable(
+                transaction_id,
+                "the request it was executed from is unknown".to_string(),
+            )
+        })?;
+
+        info!("Retrying discarded transaction {transaction_id}");
+        Ok(self.new_transaction(account_id, request).await?.with_retry_of(transaction_id))
+    }
+
+    /// Executes every retryable discarded transaction that wasn't retried yet again, proves it
+    /// with the client's local prover and submits it. Returns the IDs of the submitted retries.
+    ///
+    /// Transactions are retried up to the maximum number of retries of the client's
+    /// [`TransactionRetryPolicy`], or of the default policy if none is set. Failed retries are
+    /// logged and skipped.
+    pub async fn retry_discarded_transactions(
+        &mut self,
+    ) -> Result<Vec<TransactionId>, ClientError> {
+        let policy = self.tx_retry_policy.unwrap_or_default();
+
+        let transactions = self.store.get_transactions(TransactionFilter::All).await?;
+        let retries_by_id: BTreeMap<TransactionId, Option<TransactionId>> =
+            transactions.iter().map(|tx| (tx.id, tx.retry_of)).collect();
+
+        // Retry older transactions first, so that transactions built on top of them can be
+        // retried on top of their retries.
+        let mut candidates: Vec<&TransactionRecord> = transactions
+            .iter()
+            .filter(|tx| check_retryable(&transactions, tx.id).is_ok() && tx.request.is_some())
+            .collect();
+        candidates.sort_by_key(|tx| tx.details.creation_timestamp);
+
+        let mut submitted = Vec::new();
+        for record in candidates {
+            if retry_count(&retries_by_id, record.id) >= policy.max_retries {
+                info!("Transaction {} reached the maximum number of retries", record.id);
+                continue;
+            }
+
+            let tx_result = match self.retry_transaction(record.id).await {
+                Ok(tx_result) => tx_result,
+                Err(err) => {
+                    warn!("Failed to retry transaction {}: {err}", record.id);
+                    continue;
+                },
+            };
+
+            let retry_id = tx_result.executed_transaction().id();
+            match self.submit_transaction(tx_result).await {
+                Ok(()) => submitted.push(retry_id),
+                Err(err) => warn!("Failed to submit retry of transaction {}: {err}", record.id),
+            }
+        }
+
+        Ok(submitted)
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns the record of the transaction if it can be retried.
+fn check_retryable(
+    transactions: &[TransactionRecord],
+    transaction_id: TransactionId,
+) -> Result<&TransactionRecord, ClientError> {
+    let not_retryable =
+        |reason: &str| ClientError::TransactionNotRetryable(transaction_id, reason.to_string());
+
+    let record = transactions
+        .iter()
+        .find(|tx| tx.id == transaction_id)
+        .ok_or_else(|| not_retryable("it isn't tracked by the client"))?;
+
+    match &record.status {
+        TransactionStatus::Discarded(cause) if cause.is_retryable() => {},
+        TransactionStatus::Discarded(cause) => {
+            return Err(not_retryable(&format!("it was discarded with cause {cause}")));
+        },
+        _ => return Err(not_retryable("it wasn't discarded")),
+    }
+
+    if let Some(retry) = transactions.iter().find(|tx| tx.retry_of == Some(transaction_id)) {
+        return Err(not_retryable(&format!("it was already retried by transaction {}", retry.id)));
+    }
+
+    Ok(record)
+}
+
+/// Returns how many times the transaction is a retry of an earlier transaction.
+fn retry_count(
+    retries_by_id: &BTreeMap<TransactionId, Option<TransactionId>>,
+    transaction_id: TransactionId,
+) -> u32 {
+    let mut count = 0;
+    let mut current = transaction_id;
+    while let Some(Some(retry_of)) = retries_by_id.get(&current) {
+        count += 1;
+        current = *retry_of;
+    }
+    count
+}
@@ -1,5 +1,7 @@
 extern crate alloc;
+use alloc::rc::Rc;
 use alloc::sync::Arc;
+use core::cell::RefCell;
 use std::fmt::Write;
 
 use miden_client::consts::MIXER_DEFAULT_URL;
@@ -37,6 +39,8 @@ pub struct WebClient {
     keystore: Option<WebKeyStore<RpoRandomCoin>>,
     inner: Option<Client<WebKeyStore<RpoRandomCoin>>>,
     mock_rpc_api: Option<Arc<MockRpcApi>>,
+    /// First error thrown by a sync listener callback during the current sync.
+    sync_listener_error: Rc<RefCell<Option<JsValue>>>,
 }
 
 impl Default for WebClient {
@@ -54,6 +58,7 @@ impl WebClient {
             store: None,
             keystore: None,
             mock_rpc_api: None,
+            sync_listener_error: Rc::new(RefCell::new(None)),
         }
     }
 
@@ -1,6 +1,12 @@
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+use core::cell::RefCell;
+
 use miden_client::note::build_swap_tag as native_build_swap_tag;
+use miden_client::sync::{SyncEvent as NativeSyncEvent, SyncListener};
 use miden_objects::asset::{Asset as NativeAsset, FungibleAsset as NativeFungibleAsset};
 use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::js_sys::{Function, Object, Reflect};
 
 use crate::models::account_id::AccountId;
 use crate::models::note_tag::NoteTag;
@@ -10,20 +16,46 @@ use crate::{WebClient, js_error_with_context};
 
 #[wasm_bindgen]
 impl WebClient {
+    /// Syncs the client with the network. If a sync listener callback throws, the sync still
+    /// completes, and the returned promise is rejected with the first error thrown.
     #[wasm_bindgen(js_name = "syncState")]
     pub async fn sync_state(&mut self) -> Result<SyncSummary, JsValue> {
+        let sync_listener_error = self.sync_listener_error.clone();
+        sync_listener_error.borrow_mut().take();
+
         if let Some(client) = self.get_mut_inner() {
             let sync_summary = client
                 .sync_state()
                 .await
                 .map_err(|err| js_error_with_context(err, "failed to sync state"))?;
 
+            if let Some(err) = sync_listener_error.borrow_mut().take() {
+                return Err(err);
+            }
+
             Ok(sync_summary.into())
         } else {
             Err(JsValue::from_str("Client not initialized"))
         }
     }
 
+    /// Registers a callback called with an object describing each event of the following syncs.
+    /// The object has a `type` field (`progress`, `noteReceived`, `noteCommitted`,
+    /// `noteConsumed`, `accountUpdated`, `accountLocked`, `transactionCommitted`,
+    /// `transactionDiscarded`, `swapFilled` or `finished`) and either the `id` of the note,
+    /// account or transaction, or the `blockNum` (and `chainTip` for progress events). Errors
+    /// thrown by the callback are returned by `syncState`.
+    #[wasm_bindgen(js_name = "addSyncListener")]
+    pub fn add_sync_listener(&mut self, callback: Function) -> Result<(), JsValue> {
+        let error = self.sync_listener_error.clone();
+        if let Some(client) = self.get_mut_inner() {
+            client.add_sync_listener(Arc::new(JsSyncListener { callback, error }));
+            Ok(())
+        } else {
+            Err(JsValue::from_str("Client not initialized"))
+        }
+    }
+
     #[wasm_bindgen(js_name = "getSyncHeight")]
     pub async fn get_sync_height(&mut self) -> Result<u32, JsValue> {
         if let Some(client) = self.get_mut_inner() {
@@ -70,3 +102,65 @@ impl WebClient {
         Ok(native_note_tag.into())
     }
 }
+
+// SYNC LISTENER
+// ================================================================================================
+
+/// Forwards the sync events to a JS callback, and keeps the first error it throws so that it's
+/// returned once the sync finishes.
+struct JsSyncListener {
+    callback: Function,
+    error: Rc<RefCell<Option<JsValue>>>,
+}
+
+impl SyncListener for JsSyncListener {
+    fn on_sync_event(&self, event: &NativeSyncEvent) {
+        let (event_type, fields): (&str, Vec<(&str, JsValue)>) = match event {
+            NativeSyncEvent::Progress { block_num, chain_tip } => (
+                "progress",
+                vec![
+                    ("blockNum", block_num.as_u32().into()),
+                    ("chainTip", chain_tip.as_u32().into()),
+                ],
+            ),
+            NativeSyncEvent::NoteReceived(note_id) => {
+                ("noteReceived", vec![("id", note_id.to_hex().into())])
+            },
+            NativeSyncEvent::NoteCommitted(note_id) => {
+                ("noteCommitted", vec![("id", note_id.to_hex().into())])
+            },
+            NativeSyncEvent::NoteConsumed(note_id) => {
+                ("noteConsumed", vec![("id", note_id.to_hex().into())])
+            },
+            NativeSyncEvent::AccountUpdated(account_id) => {
+                ("accountUpdated", vec![("id", account_id.to_hex().into())])
+            },
+            NativeSyncEvent::AccountLocked(account_id) => {
+                ("accountLocked", vec![("id", account_id.to_hex().into())])
+            },
+            NativeSyncEvent::TransactionCommitted(transaction_id) => {
+                ("transactionCommitted", vec![("id", transaction_id.to_hex().into())])
+            },
+            NativeSyncEvent::TransactionDiscarded(transaction_id) => {
+                ("transactionDiscarded", vec![("id", transaction_id.to_hex().into())])
+            },
+            NativeSyncEvent::SwapFilled(note_id) => {
+                ("swapFilled", vec![("id", note_id.to_hex().into())])
+            },
+            NativeSyncEvent::Finished { block_num } => {
+                ("finished", vec![("blockNum", block_num.as_u32().into())])
+            },
+        };
+
+        let object = Object::new();
+        let _ = Reflect::set(&object, &"type".into(), &event_type.into());
+        for (key, value) in fields {
+            let _ = Reflect::set(&object, &key.into(), &value);
+        }
+
+        // The sync can't be interrupted from a listener, so errors are reported after it finishes
+        if let Err(err) = self.callback.call1(&JsValue::NULL, &object) {
+            self.error.borrow_mut().get_or_insert(err);
+        }
+    }
+}